@@ -7,6 +7,25 @@ mod m20250818_233038_task;
 mod m20250902_023308_create_metas;
 mod m20250902_024553_create_reminder;
 mod m20251024_061500_alter_timestamps_to_timestamptz;
+mod m20251101_120000_create_session;
+mod m20251102_090000_add_user_to_notes;
+mod m20251103_100000_create_login_attempt;
+mod m20251104_090000_add_two_factor;
+mod m20251105_080000_create_personal_access_token;
+mod m20251106_090000_add_user_profile;
+mod m20251107_090000_create_password_reset_token;
+mod m20251108_090000_add_task_recurrence;
+mod m20251109_090000_create_checklist_item;
+mod m20251110_090000_create_task_dependency;
+mod m20251111_090000_create_category;
+mod m20251112_090000_add_search_vector;
+mod m20251113_090000_add_soft_delete;
+mod m20251114_090000_add_task_status_tracking;
+mod m20251115_090000_add_task_overdue;
+mod m20251116_090000_create_time_entry;
+mod m20251117_090000_create_pomodoro_session;
+mod m20251118_090000_add_session_previous_token;
+mod m20251119_090000_add_occurrence_status_tracking;
 
 pub struct Migrator;
 
@@ -20,6 +39,25 @@ impl MigratorTrait for Migrator {
             Box::new(m20250902_023308_create_metas::Migration),
             Box::new(m20250902_024553_create_reminder::Migration),
             Box::new(m20251024_061500_alter_timestamps_to_timestamptz::Migration),
+            Box::new(m20251101_120000_create_session::Migration),
+            Box::new(m20251102_090000_add_user_to_notes::Migration),
+            Box::new(m20251103_100000_create_login_attempt::Migration),
+            Box::new(m20251104_090000_add_two_factor::Migration),
+            Box::new(m20251105_080000_create_personal_access_token::Migration),
+            Box::new(m20251106_090000_add_user_profile::Migration),
+            Box::new(m20251107_090000_create_password_reset_token::Migration),
+            Box::new(m20251108_090000_add_task_recurrence::Migration),
+            Box::new(m20251109_090000_create_checklist_item::Migration),
+            Box::new(m20251110_090000_create_task_dependency::Migration),
+            Box::new(m20251111_090000_create_category::Migration),
+            Box::new(m20251112_090000_add_search_vector::Migration),
+            Box::new(m20251113_090000_add_soft_delete::Migration),
+            Box::new(m20251114_090000_add_task_status_tracking::Migration),
+            Box::new(m20251115_090000_add_task_overdue::Migration),
+            Box::new(m20251116_090000_create_time_entry::Migration),
+            Box::new(m20251117_090000_create_pomodoro_session::Migration),
+            Box::new(m20251118_090000_add_session_previous_token::Migration),
+            Box::new(m20251119_090000_add_occurrence_status_tracking::Migration),
         ]
     }
 }
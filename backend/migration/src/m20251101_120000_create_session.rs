@@ -0,0 +1,53 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use crate::m20250817_224457_user::User;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Session::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Session::Id))
+                    .col(integer(Session::UserId).not_null())
+                    .col(string(Session::RefreshTokenHash).not_null().unique_key())
+                    .col(timestamp_with_time_zone(Session::CreatedAt).default(Expr::current_timestamp()))
+                    .col(timestamp_with_time_zone(Session::LastUsedAt).default(Expr::current_timestamp()))
+                    .col(timestamp_with_time_zone(Session::ExpiresAt).not_null())
+                    .col(timestamp_with_time_zone_null(Session::RevokedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-session-user_id")
+                            .from(Session::Table, Session::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Session::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum Session {
+    Table,
+    Id,
+    UserId,
+    RefreshTokenHash,
+    CreatedAt,
+    LastUsedAt,
+    ExpiresAt,
+    RevokedAt,
+}
@@ -0,0 +1,49 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use crate::m20250817_224457_user::User;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Notas antigas não têm dono e ficam inacessíveis pela API.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Notes::Table)
+                    .add_column(integer_null(Notes::UserId))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk-notes-user_id")
+                            .from_tbl(Notes::Table)
+                            .from_col(Notes::UserId)
+                            .to_tbl(User::Table)
+                            .to_col(User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Notes::Table)
+                    .drop_foreign_key(Alias::new("fk-notes-user_id"))
+                    .drop_column(Notes::UserId)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Notes {
+    Table,
+    UserId,
+}
@@ -0,0 +1,63 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(LoginAttempt::Table)
+                    .if_not_exists()
+                    .col(pk_auto(LoginAttempt::Id))
+                    .col(string(LoginAttempt::Action).not_null())
+                    .col(string(LoginAttempt::Username).not_null())
+                    .col(string_null(LoginAttempt::Ip))
+                    .col(boolean(LoginAttempt::Succeeded).not_null())
+                    .col(timestamp_with_time_zone(LoginAttempt::CreatedAt).default(Expr::current_timestamp()))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-login_attempt-username-created_at")
+                    .table(LoginAttempt::Table)
+                    .col(LoginAttempt::Username)
+                    .col(LoginAttempt::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-login_attempt-ip-created_at")
+                    .table(LoginAttempt::Table)
+                    .col(LoginAttempt::Ip)
+                    .col(LoginAttempt::CreatedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(LoginAttempt::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum LoginAttempt {
+    Table,
+    Id,
+    Action,
+    Username,
+    Ip,
+    Succeeded,
+    CreatedAt,
+}
@@ -0,0 +1,81 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use crate::m20250817_224457_user::User;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // O segredo fica salvo já na inscrição, mas o 2FA só é exigido
+        // depois que o usuário confirma um código (`totp_enabled`).
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(User::Table)
+                    .add_column(string_null(Totp::Secret))
+                    .add_column(boolean(Totp::Enabled).default(false))
+                    .add_column(big_integer_null(Totp::LastStep))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(RecoveryCode::Table)
+                    .if_not_exists()
+                    .col(pk_auto(RecoveryCode::Id))
+                    .col(integer(RecoveryCode::UserId).not_null())
+                    .col(string(RecoveryCode::CodeHash).not_null())
+                    .col(timestamp_with_time_zone_null(RecoveryCode::UsedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-recovery_code-user_id")
+                            .from(RecoveryCode::Table, RecoveryCode::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(RecoveryCode::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(User::Table)
+                    .drop_column(Totp::Secret)
+                    .drop_column(Totp::Enabled)
+                    .drop_column(Totp::LastStep)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Totp {
+    #[sea_orm(iden = "totp_secret")]
+    Secret,
+    #[sea_orm(iden = "totp_enabled")]
+    Enabled,
+    #[sea_orm(iden = "totp_last_step")]
+    LastStep,
+}
+
+#[derive(DeriveIden)]
+pub enum RecoveryCode {
+    Table,
+    Id,
+    UserId,
+    CodeHash,
+    UsedAt,
+}
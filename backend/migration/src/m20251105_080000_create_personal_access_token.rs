@@ -0,0 +1,57 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use crate::m20250817_224457_user::User;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PersonalAccessToken::Table)
+                    .if_not_exists()
+                    .col(pk_auto(PersonalAccessToken::Id))
+                    .col(integer(PersonalAccessToken::UserId).not_null())
+                    .col(string(PersonalAccessToken::Name).not_null())
+                    .col(string(PersonalAccessToken::TokenHash).not_null().unique_key())
+                    .col(string(PersonalAccessToken::Scopes).not_null())
+                    .col(timestamp_with_time_zone(PersonalAccessToken::CreatedAt).default(Expr::current_timestamp()))
+                    .col(timestamp_with_time_zone_null(PersonalAccessToken::LastUsedAt))
+                    .col(timestamp_with_time_zone_null(PersonalAccessToken::ExpiresAt))
+                    .col(timestamp_with_time_zone_null(PersonalAccessToken::RevokedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-personal_access_token-user_id")
+                            .from(PersonalAccessToken::Table, PersonalAccessToken::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PersonalAccessToken::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum PersonalAccessToken {
+    Table,
+    Id,
+    UserId,
+    Name,
+    TokenHash,
+    Scopes,
+    CreatedAt,
+    LastUsedAt,
+    ExpiresAt,
+    RevokedAt,
+}
@@ -0,0 +1,53 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use crate::m20250817_224457_user::User;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(User::Table)
+                    .add_column(string_null(Profile::DisplayName))
+                    .add_column(string_null(Profile::Email).unique_key())
+                    .add_column(string(Profile::Timezone).default("UTC"))
+                    .add_column(string(Profile::Locale).default("pt-BR"))
+                    .add_column(small_integer(Profile::WeekStartDay).default(1))
+                    .add_column(boolean(Profile::Use24hClock).default(false))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(User::Table)
+                    .drop_column(Profile::DisplayName)
+                    .drop_column(Profile::Email)
+                    .drop_column(Profile::Timezone)
+                    .drop_column(Profile::Locale)
+                    .drop_column(Profile::WeekStartDay)
+                    .drop_column(Profile::Use24hClock)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Profile {
+    DisplayName,
+    Email,
+    Timezone,
+    Locale,
+    /// 0 = domingo, 1 = segunda-feira, ... 6 = sábado.
+    WeekStartDay,
+    #[sea_orm(iden = "use_24h_clock")]
+    Use24hClock,
+}
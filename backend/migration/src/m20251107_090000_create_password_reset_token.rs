@@ -0,0 +1,51 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use crate::m20250817_224457_user::User;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PasswordResetToken::Table)
+                    .if_not_exists()
+                    .col(pk_auto(PasswordResetToken::Id))
+                    .col(integer(PasswordResetToken::UserId).not_null())
+                    .col(string(PasswordResetToken::TokenHash).not_null().unique_key())
+                    .col(timestamp_with_time_zone(PasswordResetToken::CreatedAt).default(Expr::current_timestamp()))
+                    .col(timestamp_with_time_zone(PasswordResetToken::ExpiresAt).not_null())
+                    .col(timestamp_with_time_zone_null(PasswordResetToken::UsedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-password_reset_token-user_id")
+                            .from(PasswordResetToken::Table, PasswordResetToken::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PasswordResetToken::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum PasswordResetToken {
+    Table,
+    Id,
+    UserId,
+    TokenHash,
+    CreatedAt,
+    ExpiresAt,
+    UsedAt,
+}
@@ -0,0 +1,111 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TaskRecurrence::Table)
+                    .if_not_exists()
+                    .col(pk_auto(TaskRecurrence::Id))
+                    .col(integer(TaskRecurrence::TaskId).not_null().unique_key())
+                    .col(string(TaskRecurrence::Frequency).not_null())
+                    .col(integer(TaskRecurrence::Interval).not_null().default(1))
+                    .col(string_null(TaskRecurrence::Weekdays))
+                    .col(timestamp_with_time_zone_null(TaskRecurrence::Until))
+                    .col(integer_null(TaskRecurrence::Count))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-task_recurrence-task_id")
+                            .from(TaskRecurrence::Table, TaskRecurrence::TaskId)
+                            .to(Task::Table, Task::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TaskOccurrence::Table)
+                    .if_not_exists()
+                    .col(pk_auto(TaskOccurrence::Id))
+                    .col(integer(TaskOccurrence::TaskId).not_null())
+                    .col(timestamp_with_time_zone(TaskOccurrence::OccurrenceStart).not_null())
+                    .col(boolean(TaskOccurrence::Skipped).not_null().default(false))
+                    .col(string_null(TaskOccurrence::Status))
+                    .col(string_null(TaskOccurrence::Title))
+                    .col(string_null(TaskOccurrence::Description))
+                    .col(string_null(TaskOccurrence::Category))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-task_occurrence-task_id")
+                            .from(TaskOccurrence::Table, TaskOccurrence::TaskId)
+                            .to(Task::Table, Task::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-task_occurrence-task_id-occurrence_start")
+                    .table(TaskOccurrence::Table)
+                    .col(TaskOccurrence::TaskId)
+                    .col(TaskOccurrence::OccurrenceStart)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TaskOccurrence::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(TaskRecurrence::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum TaskRecurrence {
+    Table,
+    Id,
+    TaskId,
+    Frequency,
+    Interval,
+    Weekdays,
+    Until,
+    Count,
+}
+
+#[derive(DeriveIden)]
+pub enum TaskOccurrence {
+    Table,
+    Id,
+    TaskId,
+    OccurrenceStart,
+    Skipped,
+    Status,
+    Title,
+    Description,
+    Category,
+}
+
+#[derive(DeriveIden)]
+enum Task {
+    Table,
+    Id,
+}
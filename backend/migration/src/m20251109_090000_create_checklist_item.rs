@@ -0,0 +1,88 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ChecklistItem::Table)
+                    .if_not_exists()
+                    .col(pk_auto(ChecklistItem::Id))
+                    .col(integer(ChecklistItem::TaskId).not_null())
+                    .col(string(ChecklistItem::Title).not_null())
+                    .col(integer(ChecklistItem::Position).not_null())
+                    .col(boolean(ChecklistItem::Done).not_null().default(false))
+                    .col(timestamp_with_time_zone_null(ChecklistItem::CompletedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-checklist_item-task_id")
+                            .from(ChecklistItem::Table, ChecklistItem::TaskId)
+                            .to(Task::Table, Task::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-checklist_item-task_id")
+                    .table(ChecklistItem::Table)
+                    .col(ChecklistItem::TaskId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Task::Table)
+                    .add_column(boolean(TaskChecklist::ChecklistAutoComplete).default(false))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Task::Table)
+                    .drop_column(TaskChecklist::ChecklistAutoComplete)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(ChecklistItem::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ChecklistItem {
+    Table,
+    Id,
+    TaskId,
+    Title,
+    Position,
+    Done,
+    CompletedAt,
+}
+
+#[derive(DeriveIden)]
+enum TaskChecklist {
+    ChecklistAutoComplete,
+}
+
+#[derive(DeriveIden)]
+enum Task {
+    Table,
+    Id,
+}
@@ -0,0 +1,69 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TaskDependency::Table)
+                    .if_not_exists()
+                    .col(pk_auto(TaskDependency::Id))
+                    .col(integer(TaskDependency::TaskId).not_null())
+                    .col(integer(TaskDependency::BlockedById).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-task_dependency-task_id")
+                            .from(TaskDependency::Table, TaskDependency::TaskId)
+                            .to(Task::Table, Task::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-task_dependency-blocked_by_id")
+                            .from(TaskDependency::Table, TaskDependency::BlockedById)
+                            .to(Task::Table, Task::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-task_dependency-task_id-blocked_by_id")
+                    .table(TaskDependency::Table)
+                    .col(TaskDependency::TaskId)
+                    .col(TaskDependency::BlockedById)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TaskDependency::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TaskDependency {
+    Table,
+    Id,
+    TaskId,
+    BlockedById,
+}
+
+#[derive(DeriveIden)]
+enum Task {
+    Table,
+    Id,
+}
@@ -0,0 +1,136 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use crate::m20250817_224457_user::User;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Tabelas que passam a apontar para `category`.
+const CATEGORIZED: [&str; 3] = ["task", "goal", "reminder"];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Category::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Category::Id))
+                    .col(integer(Category::UserId).not_null())
+                    .col(string(Category::Name).not_null())
+                    .col(string(Category::Color).not_null().default("#000080"))
+                    .col(string_null(Category::Icon))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-category-user_id")
+                            .from(Category::Table, Category::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // "Trabalho" e "trabalho" são a mesma categoria.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"CREATE UNIQUE INDEX "idx-category-user_id-name" ON "category" (user_id, LOWER(name));"#,
+            )
+            .await?;
+
+        for table in CATEGORIZED {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(table))
+                        .add_column(integer_null(Categorized::CategoryId))
+                        .add_foreign_key(
+                            TableForeignKey::new()
+                                .name(format!("fk-{}-category_id", table))
+                                .from_tbl(Alias::new(table))
+                                .from_col(Categorized::CategoryId)
+                                .to_tbl(Category::Table)
+                                .to_col(Category::Id)
+                                .on_delete(ForeignKeyAction::SetNull)
+                                .on_update(ForeignKeyAction::Cascade),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        // Cria uma categoria para cada nome já usado (sem diferenciar
+        // maiúsculas), com a grafia mais frequente, e liga os registros a ela.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                INSERT INTO "category" (user_id, name)
+                SELECT DISTINCT ON (user_id, LOWER(name)) user_id, name
+                FROM (
+                    SELECT user_id, TRIM(category) AS name, COUNT(*) AS uses
+                    FROM (
+                        SELECT user_id, category FROM "task"
+                        UNION ALL SELECT user_id, category FROM "goal"
+                        UNION ALL SELECT user_id, category FROM "reminder"
+                    ) AS used
+                    WHERE category IS NOT NULL AND TRIM(category) <> ''
+                    GROUP BY user_id, TRIM(category)
+                ) AS spellings
+                ORDER BY user_id, LOWER(name), uses DESC, name;
+                "#,
+            )
+            .await?;
+
+        for table in CATEGORIZED {
+            manager
+                .get_connection()
+                .execute_unprepared(&format!(
+                    r#"
+                    UPDATE "{table}" AS t
+                    SET category_id = c.id, category = c.name
+                    FROM "category" AS c
+                    WHERE c.user_id = t.user_id AND LOWER(c.name) = LOWER(TRIM(t.category));
+                    "#
+                ))
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in CATEGORIZED {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(table))
+                        .drop_foreign_key(Alias::new(format!("fk-{}-category_id", table)))
+                        .drop_column(Categorized::CategoryId)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .drop_table(Table::drop().table(Category::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Category {
+    Table,
+    Id,
+    UserId,
+    Name,
+    Color,
+    Icon,
+}
+
+#[derive(DeriveIden)]
+enum Categorized {
+    CategoryId,
+}
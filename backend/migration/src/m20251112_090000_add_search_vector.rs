@@ -0,0 +1,60 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Tabelas pesquisáveis e as colunas indexadas, com o peso de cada uma
+/// (`A` para títulos e nomes, `B` para descrições).
+const SEARCHABLE: [(&str, &[(&str, char)]); 4] = [
+    ("task", &[("title", 'A'), ("description", 'B')]),
+    ("goal", &[("name", 'A'), ("description", 'B')]),
+    ("reminder", &[("name", 'A')]),
+    ("notes", &[("text", 'A')]),
+];
+
+/// Configurações de idioma combinadas no vetor, para que buscas em português
+/// e em inglês encontrem as variações das palavras.
+const CONFIGS: [&str; 2] = ["portuguese", "english"];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for (table, columns) in SEARCHABLE {
+            let vector = columns
+                .iter()
+                .flat_map(|(column, weight)| {
+                    CONFIGS.iter().map(move |config| {
+                        format!("setweight(to_tsvector('{config}', coalesce(\"{column}\", '')), '{weight}')")
+                    })
+                })
+                .collect::<Vec<_>>()
+                .join(" || ");
+
+            manager
+                .get_connection()
+                .execute_unprepared(&format!(
+                    r#"
+                    ALTER TABLE "{table}"
+                    ADD COLUMN search_vector tsvector GENERATED ALWAYS AS ({vector}) STORED;
+                    CREATE INDEX "idx-{table}-search_vector" ON "{table}" USING GIN (search_vector);
+                    "#
+                ))
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for (table, _) in SEARCHABLE {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(table))
+                        .drop_column(Alias::new("search_vector"))
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Tabelas cujos registros vão para a lixeira antes de serem removidos.
+const TRASHABLE: [&str; 4] = ["task", "goal", "reminder", "notes"];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in TRASHABLE {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(table))
+                        .add_column(timestamp_with_time_zone_null(Trashable::DeletedAt))
+                        .to_owned(),
+                )
+                .await?;
+
+            // A lixeira e a limpeza só consultam os registros excluídos.
+            manager
+                .get_connection()
+                .execute_unprepared(&format!(
+                    r#"CREATE INDEX "idx-{table}-deleted_at" ON "{table}" (deleted_at) WHERE deleted_at IS NOT NULL;"#
+                ))
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in TRASHABLE {
+            // Registros na lixeira não voltam: com a coluna removida, seriam restaurados.
+            manager
+                .get_connection()
+                .execute_unprepared(&format!(r#"DELETE FROM "{table}" WHERE deleted_at IS NOT NULL;"#))
+                .await?;
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(table))
+                        .drop_column(Trashable::DeletedAt)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Trashable {
+    DeletedAt,
+}
@@ -0,0 +1,98 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Task::Table)
+                    .add_column(timestamp_with_time_zone_null(TaskStatusTracking::CompletedAt))
+                    .add_column(timestamp_with_time_zone_null(TaskStatusTracking::PostponedAt))
+                    .add_column(timestamp_with_time_zone_null(TaskStatusTracking::StatusChangedAt))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Sem o horário real, as tarefas já concluídas ficam com o fim planejado.
+        manager
+            .get_connection()
+            .execute_unprepared(r#"UPDATE "task" SET completed_at = complete_date WHERE status = 'Concluída';"#)
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TaskStatusHistory::Table)
+                    .if_not_exists()
+                    .col(pk_auto(TaskStatusHistory::Id))
+                    .col(integer(TaskStatusHistory::TaskId).not_null())
+                    .col(string_null(TaskStatusHistory::FromStatus))
+                    .col(string(TaskStatusHistory::ToStatus).not_null())
+                    .col(timestamp_with_time_zone(TaskStatusHistory::ChangedAt).default(Expr::current_timestamp()))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-task_status_history-task_id")
+                            .from(TaskStatusHistory::Table, TaskStatusHistory::TaskId)
+                            .to(Task::Table, Task::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-task_status_history-task_id")
+                    .table(TaskStatusHistory::Table)
+                    .col(TaskStatusHistory::TaskId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TaskStatusHistory::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Task::Table)
+                    .drop_column(TaskStatusTracking::CompletedAt)
+                    .drop_column(TaskStatusTracking::PostponedAt)
+                    .drop_column(TaskStatusTracking::StatusChangedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TaskStatusTracking {
+    CompletedAt,
+    PostponedAt,
+    StatusChangedAt,
+}
+
+#[derive(DeriveIden)]
+enum TaskStatusHistory {
+    Table,
+    Id,
+    TaskId,
+    FromStatus,
+    ToStatus,
+    ChangedAt,
+}
+
+#[derive(DeriveIden)]
+enum Task {
+    Table,
+    Id,
+}
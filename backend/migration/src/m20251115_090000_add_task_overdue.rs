@@ -0,0 +1,48 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Task::Table)
+                    .add_column(timestamp_with_time_zone_null(TaskOverdue::OverdueAt))
+                    .to_owned(),
+            )
+            .await?;
+
+        // A verificação periódica procura as tarefas pendentes que já terminaram.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"CREATE INDEX "idx-task-pending-complete_date" ON "task" (complete_date) WHERE status = 'Pendente' AND overdue_at IS NULL AND deleted_at IS NULL;"#,
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Task::Table)
+                    .drop_column(TaskOverdue::OverdueAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TaskOverdue {
+    OverdueAt,
+}
+
+#[derive(DeriveIden)]
+enum Task {
+    Table,
+}
@@ -0,0 +1,95 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use crate::m20250817_224457_user::User;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TimeEntry::Table)
+                    .if_not_exists()
+                    .col(pk_auto(TimeEntry::Id))
+                    .col(integer(TimeEntry::UserId).not_null())
+                    .col(integer(TimeEntry::TaskId).not_null())
+                    .col(timestamp_with_time_zone(TimeEntry::StartedAt).not_null())
+                    .col(timestamp_with_time_zone_null(TimeEntry::StoppedAt))
+                    .col(text_null(TimeEntry::Note))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-time_entry-user_id")
+                            .from(TimeEntry::Table, TimeEntry::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-time_entry-task_id")
+                            .from(TimeEntry::Table, TimeEntry::TaskId)
+                            .to(Task::Table, Task::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-time_entry-task_id")
+                    .table(TimeEntry::Table)
+                    .col(TimeEntry::TaskId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-time_entry-user_id-started_at")
+                    .table(TimeEntry::Table)
+                    .col(TimeEntry::UserId)
+                    .col(TimeEntry::StartedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // No máximo um cronômetro ligado por usuário.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"CREATE UNIQUE INDEX "idx-time_entry-user_id-running" ON "time_entry" (user_id) WHERE stopped_at IS NULL;"#,
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TimeEntry::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TimeEntry {
+    Table,
+    Id,
+    UserId,
+    TaskId,
+    StartedAt,
+    StoppedAt,
+    Note,
+}
+
+#[derive(DeriveIden)]
+enum Task {
+    Table,
+    Id,
+}
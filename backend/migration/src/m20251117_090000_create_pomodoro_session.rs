@@ -0,0 +1,118 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use crate::m20250817_224457_user::User;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(User::Table)
+                    .add_column(integer(PomodoroSettings::PomodoroWorkMinutes).default(25))
+                    .add_column(integer(PomodoroSettings::PomodoroShortBreakMinutes).default(5))
+                    .add_column(integer(PomodoroSettings::PomodoroLongBreakMinutes).default(15))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(PomodoroSession::Table)
+                    .if_not_exists()
+                    .col(pk_auto(PomodoroSession::Id))
+                    .col(integer(PomodoroSession::UserId).not_null())
+                    .col(integer_null(PomodoroSession::TaskId))
+                    .col(string(PomodoroSession::Kind).not_null())
+                    .col(string(PomodoroSession::Status).not_null())
+                    .col(timestamp_with_time_zone(PomodoroSession::StartedAt).not_null())
+                    .col(timestamp_with_time_zone(PomodoroSession::EndsAt).not_null())
+                    .col(timestamp_with_time_zone_null(PomodoroSession::FinishedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-pomodoro_session-user_id")
+                            .from(PomodoroSession::Table, PomodoroSession::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-pomodoro_session-task_id")
+                            .from(PomodoroSession::Table, PomodoroSession::TaskId)
+                            .to(Task::Table, Task::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-pomodoro_session-user_id-started_at")
+                    .table(PomodoroSession::Table)
+                    .col(PomodoroSession::UserId)
+                    .col(PomodoroSession::StartedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // No máximo uma sessão em andamento por usuário.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"CREATE UNIQUE INDEX "idx-pomodoro_session-user_id-running" ON "pomodoro_session" (user_id) WHERE status = 'running';"#,
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PomodoroSession::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(User::Table)
+                    .drop_column(PomodoroSettings::PomodoroWorkMinutes)
+                    .drop_column(PomodoroSettings::PomodoroShortBreakMinutes)
+                    .drop_column(PomodoroSettings::PomodoroLongBreakMinutes)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PomodoroSettings {
+    PomodoroWorkMinutes,
+    PomodoroShortBreakMinutes,
+    PomodoroLongBreakMinutes,
+}
+
+#[derive(DeriveIden)]
+enum PomodoroSession {
+    Table,
+    Id,
+    UserId,
+    TaskId,
+    Kind,
+    Status,
+    StartedAt,
+    EndsAt,
+    FinishedAt,
+}
+
+#[derive(DeriveIden)]
+enum Task {
+    Table,
+    Id,
+}
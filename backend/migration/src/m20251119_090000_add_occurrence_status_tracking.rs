@@ -0,0 +1,127 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TaskOccurrence::Table)
+                    .add_column(integer_null(TaskOccurrence::CategoryId))
+                    .add_column(timestamp_with_time_zone_null(TaskOccurrence::CompletedAt))
+                    .add_column(timestamp_with_time_zone_null(TaskOccurrence::PostponedAt))
+                    .add_column(timestamp_with_time_zone_null(TaskOccurrence::StatusChangedAt))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk-task_occurrence-category_id")
+                            .from_tbl(TaskOccurrence::Table)
+                            .from_col(TaskOccurrence::CategoryId)
+                            .to_tbl(Category::Table)
+                            .to_col(Category::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Liga as exceções às categorias do dono da série, criando as que só
+        // existiam como texto nelas.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                INSERT INTO "category" (user_id, name)
+                SELECT DISTINCT ON (t.user_id, LOWER(TRIM(o.category))) t.user_id, TRIM(o.category)
+                FROM "task_occurrence" AS o
+                JOIN "task" AS t ON t.id = o.task_id
+                WHERE o.category IS NOT NULL AND TRIM(o.category) <> ''
+                ORDER BY t.user_id, LOWER(TRIM(o.category)), TRIM(o.category)
+                ON CONFLICT (user_id, LOWER(name)) DO NOTHING;
+                "#,
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                UPDATE "task_occurrence" AS o
+                SET category_id = c.id, category = c.name
+                FROM "task" AS t, "category" AS c
+                WHERE t.id = o.task_id AND c.user_id = t.user_id AND LOWER(c.name) = LOWER(TRIM(o.category));
+                "#,
+            )
+            .await?;
+
+        // Como nas tarefas, as ocorrências já concluídas ficam com o fim planejado.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                UPDATE "task_occurrence" AS o
+                SET completed_at = o.occurrence_start + (t.complete_date - t.begin_date)
+                FROM "task" AS t
+                WHERE t.id = o.task_id AND o.status = 'Concluída';
+                "#,
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TaskStatusHistory::Table)
+                    .add_column(timestamp_with_time_zone_null(TaskStatusHistory::OccurrenceStart))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TaskStatusHistory::Table)
+                    .drop_column(TaskStatusHistory::OccurrenceStart)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TaskOccurrence::Table)
+                    .drop_foreign_key(Alias::new("fk-task_occurrence-category_id"))
+                    .drop_column(TaskOccurrence::CategoryId)
+                    .drop_column(TaskOccurrence::CompletedAt)
+                    .drop_column(TaskOccurrence::PostponedAt)
+                    .drop_column(TaskOccurrence::StatusChangedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TaskOccurrence {
+    Table,
+    CategoryId,
+    CompletedAt,
+    PostponedAt,
+    StatusChangedAt,
+}
+
+#[derive(DeriveIden)]
+enum TaskStatusHistory {
+    Table,
+    OccurrenceStart,
+}
+
+#[derive(DeriveIden)]
+enum Category {
+    Table,
+    Id,
+}
@@ -0,0 +1,158 @@
+//! Camada de autorização por dono do recurso.
+//!
+//! Toda rota que recebe `/<id>` como primeiro segmento após o ponto de montagem
+//! deve usar o guard [`Owned`] para carregar o registro, garantindo que ele
+//! pertence ao usuário do token. A semântica é a mesma para todos os recursos:
+//!
+//! - `401 Unauthorized`: token ausente, inválido ou sessão revogada;
+//! - `404 Not Found`: o id não existe (ou não é um número válido);
+//! - `403 Forbidden`: o registro existe, mas pertence a outro usuário.
+//!
+//! Registros na lixeira contam como inexistentes; só a lixeira os acessa.
+
+use crate::controller::auth::UserClaim;
+use crate::db::Pool;
+use crate::errors::AppError;
+use crate::entity::{category, goal, notes, personal_access_token, reminder, task};
+use rocket::request::{FromRequest, Outcome, Request};
+use rocket::State;
+use sea_orm::{EntityTrait, FromQueryResult, PrimaryKeyTrait};
+
+/// Entidade que pertence a um usuário.
+pub trait OwnedResource: FromQueryResult + Sized + Send {
+    type Entity: EntityTrait<Model = Self>;
+
+    /// Nome usado nas mensagens de erro.
+    const NAME: &'static str;
+
+    fn owner_id(&self) -> Option<i32>;
+
+    /// Se o registro está na lixeira.
+    fn deleted(&self) -> bool {
+        false
+    }
+}
+
+impl OwnedResource for task::Model {
+    type Entity = task::Entity;
+    const NAME: &'static str = "Task";
+
+    fn owner_id(&self) -> Option<i32> {
+        Some(self.user_id)
+    }
+
+    fn deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+}
+
+impl OwnedResource for goal::Model {
+    type Entity = goal::Entity;
+    const NAME: &'static str = "Goal";
+
+    fn owner_id(&self) -> Option<i32> {
+        Some(self.user_id)
+    }
+
+    fn deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+}
+
+impl OwnedResource for reminder::Model {
+    type Entity = reminder::Entity;
+    const NAME: &'static str = "Reminder";
+
+    fn owner_id(&self) -> Option<i32> {
+        Some(self.user_id)
+    }
+
+    fn deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+}
+
+impl OwnedResource for notes::Model {
+    type Entity = notes::Entity;
+    const NAME: &'static str = "Note";
+
+    fn owner_id(&self) -> Option<i32> {
+        self.user_id
+    }
+
+    fn deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+}
+
+impl OwnedResource for personal_access_token::Model {
+    type Entity = personal_access_token::Entity;
+    const NAME: &'static str = "Access token";
+
+    fn owner_id(&self) -> Option<i32> {
+        Some(self.user_id)
+    }
+}
+
+impl OwnedResource for category::Model {
+    type Entity = category::Entity;
+    const NAME: &'static str = "Category";
+
+    fn owner_id(&self) -> Option<i32> {
+        Some(self.user_id)
+    }
+}
+
+/// Registro carregado a partir do `<id>` da rota e já verificado como
+/// pertencente ao usuário autenticado.
+pub struct Owned<M> {
+    pub model: M,
+}
+
+impl<M> Owned<M> {
+    pub fn into_inner(self) -> M {
+        self.model
+    }
+}
+
+#[rocket::async_trait]
+impl<'r, M> FromRequest<'r> for Owned<M>
+where
+    M: OwnedResource,
+    <<M::Entity as EntityTrait>::PrimaryKey as PrimaryKeyTrait>::ValueType: From<i32>,
+{
+    type Error = AppError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let claim = match request.guard::<UserClaim>().await {
+            Outcome::Success(claim) => claim,
+            Outcome::Error(e) => return Outcome::Error(e),
+            Outcome::Forward(status) => return Outcome::Forward(status),
+        };
+
+        let user_id = match claim.user_id() {
+            Ok(user_id) => user_id,
+            Err(e) => return e.fail(request),
+        };
+
+        let not_found = AppError::NotFound(format!("{} not found", M::NAME));
+
+        let id = match request.param::<i32>(0) {
+            Some(Ok(id)) => id,
+            _ => return not_found.fail(request),
+        };
+
+        let db = match request.guard::<&State<Pool>>().await {
+            Outcome::Success(db) => db,
+            _ => return AppError::Internal("Database unavailable".to_string()).fail(request),
+        };
+
+        match M::Entity::find_by_id(id).one(db.inner()).await {
+            Ok(Some(model)) if model.deleted() => not_found.fail(request),
+            Ok(Some(model)) if model.owner_id() == Some(user_id) => Outcome::Success(Owned { model }),
+            Ok(Some(_)) => AppError::Forbidden(format!("{} belongs to another user", M::NAME)).fail(request),
+            Ok(None) => not_found.fail(request),
+            Err(e) => AppError::from(e).fail(request),
+        }
+    }
+}
@@ -1,103 +1,391 @@
 use crate::db::Pool;
-use crate::dto::auth_dto::AuthDto;
-use crate::repository::auth_repository::UserError;
-use rocket::http::Status;
+use crate::dto::auth_dto::{
+    AuthDto, ChangePasswordDto, ChangeUsernameDto, DeleteAccountDto, DisableTwoFactorDto,
+    PasswordResetConfirmDto, PasswordResetRequestDto, RefreshDto, TwoFactorCodeDto, TwoFactorLoginDto,
+};
+use crate::errors::AppError;
+use crate::mailer::Mailer;
+use rocket::http::{Method, Status};
+use rocket::request::{FromRequest, Outcome, Request};
 use rocket::{serde::json::Json, State};
 use rocket::post;
 use rocket::get;
-use rocket_jwt::jwt;
+use rocket::{delete, put};
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
 use std::env::{var, VarError};
-use crate::service::auth_service;
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::service::personal_access_token_service::{self, Access};
+use crate::service::{auth_service, password_reset_service, two_factor_service};
+use crate::throttle::{AuthAction, AuthThrottle};
+
+/// Validade do access token, em segundos. Tokens curtos são renovados via `/refresh`.
+const ACCESS_TOKEN_EXP: u64 = 900;
+const ACCESS_TOKEN_LEEWAY: u64 = 60;
+/// Validade do token de desafio emitido quando o login exige o segundo fator.
+const CHALLENGE_TOKEN_EXP: u64 = 300;
 
 #[derive(serde::Serialize)]
 pub struct TokenResponse {
     token: String,
+    refresh_token: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct ChallengeResponse {
+    two_factor_required: bool,
+    challenge_token: String,
 }
 
+/// O login devolve os tokens diretamente ou, com 2FA ativo, um desafio
+/// a ser trocado em `/login/2fa`.
 #[derive(serde::Serialize)]
-pub struct ErrorResponse {
-    error: String,
+#[serde(untagged)]
+pub enum LoginResponse {
+    Tokens(TokenResponse),
+    Challenge(ChallengeResponse),
+}
+
+#[derive(serde::Serialize)]
+pub struct RecoveryCodesResponse {
+    recovery_codes: Vec<String>,
 }
 
 fn get_secret_key() -> Result<String, VarError> {
     var("JWT_SECRET_KEY")
 }
 
-#[jwt(get_secret_key(), exp = 36000)]
+fn secret_key() -> String {
+    get_secret_key().expect("JWT_SECRET_KEY must be set.")
+}
+
+/// Usuário autenticado. `sid` identifica a sessão do JWT; é `None` quando a
+/// requisição usa um token de acesso pessoal.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct UserClaim {
     id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sid: Option<i32>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct UserClaimJwtClaim {
+    exp: u64,
+    iat: u64,
+    user: UserClaim,
 }
 
 impl UserClaim {
-    pub fn get_id(&self) -> &str {
-        &self.id
+    pub fn user_id(&self) -> Result<i32, AppError> {
+        self.id
+            .parse::<i32>()
+            .map_err(|_| AppError::Unauthorized("Invalid token: user_id is not valid".to_string()))
+    }
+
+    /// Sessão do token; rotas que gerenciam a sessão não aceitam tokens pessoais.
+    pub fn session_id(&self) -> Result<i32, AppError> {
+        self.sid
+            .ok_or_else(|| AppError::Forbidden("This endpoint requires a login session".to_string()))
+    }
+
+    pub fn sign(user: UserClaim) -> String {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        let payload = UserClaimJwtClaim {
+            exp: now + ACCESS_TOKEN_EXP,
+            iat: now,
+            user,
+        };
+
+        encode(&Header::default(), &payload, &EncodingKey::from_secret(secret_key().as_bytes())).unwrap()
+    }
+
+    pub fn decode(token: &str) -> jsonwebtoken::errors::Result<UserClaimJwtClaim> {
+        let mut validation = Validation::default();
+        validation.leeway = ACCESS_TOKEN_LEEWAY;
+
+        decode::<UserClaimJwtClaim>(token, &DecodingKey::from_secret(secret_key().as_bytes()), &validation)
+            .map(|token_data| token_data.claims)
+    }
+}
+
+/// Token curto que prova que a senha já foi verificada. Não contém o campo
+/// `user`, portanto não é aceito como access token.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct ChallengeClaim {
+    exp: u64,
+    iat: u64,
+    challenge_user_id: i32,
+}
+
+impl ChallengeClaim {
+    fn sign(user_id: i32) -> String {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        let payload = ChallengeClaim {
+            exp: now + CHALLENGE_TOKEN_EXP,
+            iat: now,
+            challenge_user_id: user_id,
+        };
+
+        encode(&Header::default(), &payload, &EncodingKey::from_secret(secret_key().as_bytes())).unwrap()
+    }
+
+    fn decode(token: &str) -> Result<i32, AppError> {
+        decode::<ChallengeClaim>(token, &DecodingKey::from_secret(secret_key().as_bytes()), &Validation::default())
+            .map(|token_data| token_data.claims.challenge_user_id)
+            .map_err(|_| AppError::Unauthorized("Invalid or expired challenge token".to_string()))
+    }
+}
+
+/// Além da assinatura e da expiração do JWT, exige que a sessão do token
+/// continue ativa, permitindo revogar tokens pelo logout. Também aceita tokens
+/// de acesso pessoal, restritos aos escopos concedidos.
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for UserClaim {
+    type Error = AppError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let token = match request
+            .headers()
+            .get_one("Authorization")
+            .and_then(|auth| auth.strip_prefix("Bearer"))
+        {
+            Some(token) => token.trim(),
+            None => return AppError::Unauthorized("EmptySignature".to_string()).fail(request),
+        };
+
+        let db = match request.guard::<&State<Pool>>().await {
+            Outcome::Success(db) => db,
+            _ => return AppError::Internal("Database unavailable".to_string()).fail(request),
+        };
+
+        if personal_access_token_service::is_personal_access_token(token) {
+            return match personal_access_token_claim(request, db, token).await {
+                Ok(claim) => Outcome::Success(claim),
+                Err(e) => e.fail(request),
+            };
+        }
+
+        let claim = match UserClaim::decode(token) {
+            Ok(token_data) => token_data.user,
+            Err(err) => return AppError::Unauthorized(err.to_string()).fail(request),
+        };
+
+        let (user_id, session_id) = match (claim.id.parse::<i32>(), claim.sid) {
+            (Ok(user_id), Some(session_id)) => (user_id, session_id),
+            _ => return AppError::Unauthorized("Invalid token".to_string()).fail(request),
+        };
+
+        match auth_service::is_session_active(db, session_id, user_id).await {
+            Ok(true) => Outcome::Success(claim),
+            Ok(false) => AppError::Unauthorized("Session revoked or expired".to_string()).fail(request),
+            Err(e) => e.fail(request),
+        }
+    }
+}
+
+/// Autentica um token de acesso pessoal. O recurso é o primeiro segmento após
+/// `/api`; `GET` exige leitura e os demais métodos, escrita.
+async fn personal_access_token_claim(
+    request: &Request<'_>,
+    db: &Pool,
+    token: &str,
+) -> Result<UserClaim, AppError> {
+    let access_token = personal_access_token_service::authenticate(db, token).await?;
+
+    let resource = request
+        .uri()
+        .path()
+        .segments()
+        .nth(1)
+        .filter(|resource| personal_access_token_service::RESOURCES.contains(resource))
+        .ok_or_else(|| AppError::Forbidden("Personal access tokens cannot access this endpoint".to_string()))?;
+
+    let access = match request.method() {
+        Method::Get | Method::Head => Access::Read,
+        _ => Access::Write,
+    };
+
+    if !personal_access_token_service::allows(&access_token, resource, access) {
+        let access_name = if access == Access::Read { "read" } else { "write" };
+        return Err(AppError::Forbidden(format!("Token lacks the {}:{} scope", resource, access_name)));
     }
+
+    Ok(UserClaim {
+        id: access_token.user_id.to_string(),
+        sid: None,
+    })
+}
+
+/// Cria a sessão do usuário e monta o par access/refresh token.
+async fn issue_tokens(db: &Pool, user_id: i32) -> Result<TokenResponse, AppError> {
+    let (session, refresh_token) = auth_service::start_session(db, user_id).await?;
+    let token = UserClaim::sign(UserClaim {
+        id: user_id.to_string(),
+        sid: Some(session.id),
+    });
+    Ok(TokenResponse { token, refresh_token })
 }
 
 #[post("/register", data = "<auth_dto>")]
 pub async fn register(
     auth_dto: Json<AuthDto>,
     db: &State<Pool>,
-) -> Result<(Status, Json<TokenResponse>), (Status, Json<ErrorResponse>)> {
-    let result: Result<crate::entity::user::Model, UserError> =
-        auth_service::register_user(db, &auth_dto).await;
-    match result {
-        Ok(user) => {
-            let user_claim = UserClaim {
-                id: user.id.to_string(),
-            };
-            let token = UserClaim::sign(user_claim);
-            Ok((Status::Created, Json(TokenResponse { token })))
-        }
-        Err(err) => match err {
-            UserError::InvalidUser(msg) => {
-                Err((Status::BadRequest, Json(ErrorResponse { error: msg })))
-            }
-            UserError::UserAlreadyExists(msg) => {
-                Err((Status::Conflict, Json(ErrorResponse { error: msg })))
-            }
-            UserError::DatabaseError(msg) => Err((
-                Status::InternalServerError,
-                Json(ErrorResponse { error: msg }),
-            )),
-        },
-    }
+    throttle: AuthThrottle<'_>,
+) -> Result<(Status, Json<TokenResponse>), AppError> {
+    throttle.check(AuthAction::Register, &auth_dto.username).await?;
+    let result = auth_service::register_user(db, &auth_dto).await;
+    throttle.record(AuthAction::Register, &auth_dto.username, &result).await?;
+    let user = result?;
+    let tokens = issue_tokens(db, user.id).await?;
+    Ok((Status::Created, Json(tokens)))
 }
 
 #[post("/login", data = "<auth_dto>")]
 pub async fn login(
     db: &State<Pool>,
     auth_dto: Json<AuthDto>,
-) -> Result<Json<TokenResponse>, (Status, Json<ErrorResponse>)> {
+    throttle: AuthThrottle<'_>,
+) -> Result<Json<LoginResponse>, AppError> {
+    throttle.check(AuthAction::Login, &auth_dto.username).await?;
     let result = auth_service::login_user(db, &auth_dto).await;
+    throttle.record(AuthAction::Login, &auth_dto.username, &result).await?;
+    let user = result?;
 
-    println!(
-        "secret key {}",
-        get_secret_key().unwrap_or_else(|_| "secret".into())
-    );
-    match result {
-        Ok(user) => {
-            let user_claim = UserClaim {
-                id: user.id.to_string(),
-            };
-            let token = UserClaim::sign(user_claim);
-            Ok(Json(TokenResponse { token }))
-        }
-        Err(err) => match err {
-            UserError::InvalidUser(msg) | UserError::UserAlreadyExists(msg) => {
-                Err((Status::BadRequest, Json(ErrorResponse { error: msg })))
-            }
-            UserError::DatabaseError(msg) => Err((
-                Status::InternalServerError,
-                Json(ErrorResponse { error: msg }),
-            )),
-        },
+    if user.totp_enabled {
+        return Ok(Json(LoginResponse::Challenge(ChallengeResponse {
+            two_factor_required: true,
+            challenge_token: ChallengeClaim::sign(user.id),
+        })));
+    }
+
+    let tokens = issue_tokens(db, user.id).await?;
+    Ok(Json(LoginResponse::Tokens(tokens)))
+}
+
+#[post("/login/2fa", data = "<login_dto>")]
+pub async fn login_two_factor(
+    db: &State<Pool>,
+    login_dto: Json<TwoFactorLoginDto>,
+    throttle: AuthThrottle<'_>,
+) -> Result<Json<TokenResponse>, AppError> {
+    let user_id = ChallengeClaim::decode(&login_dto.challenge_token)?;
+    let user = auth_service::find_user(db, user_id).await?;
+    let username = user.username.clone();
+
+    throttle.check(AuthAction::SecondFactor, &username).await?;
+    let result = two_factor_service::verify_login_code(db, user, &login_dto.code).await;
+    throttle.record(AuthAction::SecondFactor, &username, &result).await?;
+    let user = result?;
+
+    let tokens = issue_tokens(db, user.id).await?;
+    Ok(Json(tokens))
+}
+
+#[post("/refresh", data = "<refresh_dto>")]
+pub async fn refresh(
+    db: &State<Pool>,
+    refresh_dto: Json<RefreshDto>,
+) -> Result<Json<TokenResponse>, AppError> {
+    let (session, refresh_token) = auth_service::refresh_session(db, &refresh_dto.refresh_token).await?;
+    let token = UserClaim::sign(UserClaim {
+        id: session.user_id.to_string(),
+        sid: Some(session.id),
+    });
+    Ok(Json(TokenResponse { token, refresh_token }))
+}
+
+#[post("/logout")]
+pub async fn logout(
+    db: &State<Pool>,
+    user_claim: UserClaim,
+) -> Result<Status, AppError> {
+    auth_service::end_session(db, user_claim.session_id()?).await?;
+    Ok(Status::NoContent)
+}
+
+#[put("/account/password", data = "<password_dto>")]
+pub async fn change_password(
+    db: &State<Pool>,
+    password_dto: Json<ChangePasswordDto>,
+    user_claim: UserClaim,
+) -> Result<Status, AppError> {
+    let user_id = user_claim.user_id()?;
+    auth_service::change_password(db, user_id, user_claim.session_id()?, &password_dto).await?;
+    Ok(Status::NoContent)
+}
+
+#[put("/account/username", data = "<username_dto>")]
+pub async fn change_username(
+    db: &State<Pool>,
+    username_dto: Json<ChangeUsernameDto>,
+    user_claim: UserClaim,
+) -> Result<Status, AppError> {
+    let user_id = user_claim.user_id()?;
+    auth_service::change_username(db, user_id, &username_dto).await?;
+    Ok(Status::NoContent)
+}
+
+#[delete("/account", data = "<delete_dto>")]
+pub async fn delete_account(
+    db: &State<Pool>,
+    delete_dto: Json<DeleteAccountDto>,
+    user_claim: UserClaim,
+) -> Result<Status, AppError> {
+    let user_id = user_claim.user_id()?;
+    auth_service::delete_account(db, user_id, &delete_dto).await?;
+    Ok(Status::NoContent)
+}
+
+#[post("/2fa/enroll")]
+pub async fn enroll_two_factor(
+    db: &State<Pool>,
+    user_claim: UserClaim,
+) -> Result<Json<two_factor_service::Enrollment>, AppError> {
+    let user_id = user_claim.user_id()?;
+    Ok(Json(two_factor_service::enroll(db, user_id).await?))
+}
+
+#[post("/2fa/confirm", data = "<code_dto>")]
+pub async fn confirm_two_factor(
+    db: &State<Pool>,
+    code_dto: Json<TwoFactorCodeDto>,
+    user_claim: UserClaim,
+) -> Result<Json<RecoveryCodesResponse>, AppError> {
+    let user_id = user_claim.user_id()?;
+    let recovery_codes = two_factor_service::confirm(db, user_id, &code_dto.code).await?;
+    Ok(Json(RecoveryCodesResponse { recovery_codes }))
+}
+
+#[post("/2fa/disable", data = "<disable_dto>")]
+pub async fn disable_two_factor(
+    db: &State<Pool>,
+    disable_dto: Json<DisableTwoFactorDto>,
+    user_claim: UserClaim,
+) -> Result<Status, AppError> {
+    let user_id = user_claim.user_id()?;
+    two_factor_service::disable(db, user_id, &disable_dto.password).await?;
+    Ok(Status::NoContent)
+}
+
+/// Responde sempre `202`, para não revelar quais e-mails têm conta cadastrada.
+#[post("/password-reset/request", data = "<reset_dto>")]
+pub async fn request_password_reset(
+    db: &State<Pool>,
+    mailer: &State<Box<dyn Mailer>>,
+    reset_dto: Json<PasswordResetRequestDto>,
+) -> Status {
+    if let Err(e) = password_reset_service::request_reset(db, mailer.inner().as_ref(), &reset_dto.email).await {
+        log::error!("password reset: failed to process request: {}", e);
     }
+    Status::Accepted
 }
 
-#[get("/user_info")]
-pub async fn user_info(user_claim: UserClaim) -> String {
-    format!("User id: {}", user_claim.id)
+#[post("/password-reset/confirm", data = "<confirm_dto>")]
+pub async fn confirm_password_reset(
+    db: &State<Pool>,
+    confirm_dto: Json<PasswordResetConfirmDto>,
+) -> Result<Status, AppError> {
+    password_reset_service::confirm_reset(db, &confirm_dto).await?;
+    Ok(Status::NoContent)
 }
 
 #[get("/")]
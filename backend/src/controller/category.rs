@@ -0,0 +1,55 @@
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::{delete, get, post, put, State};
+use crate::authorization::Owned;
+use crate::controller::auth::UserClaim;
+use crate::db::Pool;
+use crate::dto::category_dto::{CategoryDto, CategoryUpdateDto};
+use crate::entity::category;
+use crate::errors::AppError;
+use crate::service::category_service;
+
+#[get("/")]
+pub async fn list_categories(
+    db: &State<Pool>,
+    token: UserClaim,
+) -> Result<Json<Vec<category::Model>>, AppError> {
+    let user_id = token.user_id()?;
+    Ok(Json(category_service::list_categories_db(db, user_id).await?))
+}
+
+#[get("/<_id>")]
+pub async fn get_category(_id: i32, category: Owned<category::Model>) -> Json<category::Model> {
+    Json(category.into_inner())
+}
+
+#[post("/", data = "<category_dto>")]
+pub async fn create_category(
+    category_dto: Json<CategoryDto>,
+    db: &State<Pool>,
+    token: UserClaim,
+) -> Result<Json<category::Model>, AppError> {
+    let user_id = token.user_id()?;
+    Ok(Json(category_service::create_category_db(db, user_id, &category_dto).await?))
+}
+
+#[put("/<_id>", data = "<category_dto>")]
+pub async fn update_category(
+    _id: i32,
+    category_dto: Json<CategoryUpdateDto>,
+    db: &State<Pool>,
+    category: Owned<category::Model>,
+) -> Result<Json<category::Model>, AppError> {
+    Ok(Json(category_service::update_category_db(db, category.into_inner(), &category_dto).await?))
+}
+
+/// Remove a categoria; tarefas, metas e lembretes mantêm apenas o nome.
+#[delete("/<_id>")]
+pub async fn delete_category(
+    _id: i32,
+    db: &State<Pool>,
+    category: Owned<category::Model>,
+) -> Result<Status, AppError> {
+    category_service::delete_category_db(db, category.into_inner()).await?;
+    Ok(Status::NoContent)
+}
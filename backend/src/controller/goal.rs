@@ -1,10 +1,12 @@
 use crate::db::Pool;
 use crate::dto::goal_dto::{GoalDto, GoalResponseDto};
+use crate::dto::list_dto::{ListQuery, PageDto};
 use crate::entity::goal;
 use crate::service::goal_service;
-use rocket::http::Status;
+use crate::errors::AppError;
 use rocket::{serde::json::Json, State};
 use crate::controller::auth::UserClaim;
+use crate::authorization::Owned;
 
 use rocket::post;
 use rocket::put;
@@ -16,75 +18,64 @@ pub async fn create_goal(
     db: &State<Pool>,
     goal_dto: Json<GoalDto>,
     token: UserClaim,
-) -> Result<Json<GoalResponseDto>, (Status, String)> {
-    let user_id = token.get_id().parse::<i32>().map_err(|_| {
-        (Status::BadRequest, "Invalid token: user_id is not valid".to_string())
-    })?;
+) -> Result<Json<GoalResponseDto>, AppError> {
+    let user_id = token.user_id()?;
     match goal_service::create_goal_db(db, &goal_dto, user_id).await {
         Ok(goal_response) => Ok(Json(goal_response)),
         Err(e) => Err(e),
     }
 }
 
-#[put("/<id>", data = "<goal_dto>")]
+#[put("/<_id>", data = "<goal_dto>")]
 pub async fn update_goal(
     db: &State<Pool>,
-    id: i32,
+    _id: i32,
     goal_dto: Json<GoalDto>,
-    token: UserClaim,
-) -> Result<Json<GoalResponseDto>, (Status, String)> {
-    let user_id = token.get_id().parse::<i32>().map_err(|_| {
-        (Status::BadRequest, "Invalid token: user_id is not valid".to_string())
-    })?;
-    match goal_service::update_goal_db(db, id, &goal_dto, user_id).await {
+    goal: Owned<goal::Model>,
+) -> Result<Json<GoalResponseDto>, AppError> {
+    match goal_service::update_goal_db(db, goal.into_inner(), &goal_dto).await {
         Ok(goal_response) => Ok(Json(goal_response)),
         Err(e) => Err(e),
     }
 }
 
-#[delete("/<id>")]
+#[delete("/<_id>")]
 pub async fn delete_goal(
     db: &State<Pool>,
-    id: i32,
-    _token: UserClaim, //verify if is the same user to delete
-) -> Result<Json<goal::Model>, (Status, String)> {
-    match goal_service::delete_goal_db(db, id).await {
+    _id: i32,
+    goal: Owned<goal::Model>,
+) -> Result<Json<goal::Model>, AppError> {
+    match goal_service::delete_goal_db(db, goal.into_inner()).await {
         Ok(goal) => Ok(Json(goal)),
         Err(e) => Err(e),
     }
 }
 
-#[get("/")]
+/// Metas filtradas, ordenadas e paginadas por cursor.
+#[get("/?<query..>")]
 pub async fn list_goals(
+    query: ListQuery,
     db: &State<Pool>,
-    _token: UserClaim, //verify if is the same user to list
-) -> Result<Json<Vec<GoalResponseDto>>, (Status, String)> {
-    match goal_service::list_goals_db(db).await {
-        Ok(goals) => Ok(Json(goals)),
-        Err(e) => Err(e),
-    }
+    token: UserClaim,
+) -> Result<Json<PageDto<GoalResponseDto>>, AppError> {
+    let user_id = token.user_id()?;
+    Ok(Json(goal_service::list_goals_db(db, user_id, &query).await?))
 }
 
-#[get("/<id>")]
+#[get("/<_id>")]
 pub async fn get_goal(
-    db: &State<Pool>,
-    id: i32,
-    _token: UserClaim, //verify if is the same user to get
-) -> Result<Json<GoalResponseDto>, (Status, String)> {
-    match goal_service::get_goal_db(db, id).await {
-        Ok(goal) => Ok(Json(goal)),
-        Err(e) => Err(e),
-    }
+    _id: i32,
+    goal: Owned<goal::Model>,
+) -> Json<GoalResponseDto> {
+    Json(goal_service::convert_to_response_dto(goal.into_inner()))
 }
 
 #[get("/user")]
 pub async fn get_user_goals(
     db: &State<Pool>,
     token: UserClaim,
-) -> Result<Json<Vec<GoalResponseDto>>, (Status, String)> {
-    let user_id = token.get_id().parse::<i32>().map_err(|_| {
-        (Status::BadRequest, "Invalid token: user_id is not valid".to_string())
-    })?;
+) -> Result<Json<Vec<GoalResponseDto>>, AppError> {
+    let user_id = token.user_id()?;
     match goal_service::get_goals_by_user_db(db, user_id).await {
         Ok(goals) => Ok(Json(goals)),
         Err(e) => Err(e),
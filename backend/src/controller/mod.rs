@@ -5,5 +5,11 @@ pub mod report;
 pub mod reminder;
 
 pub mod goal;
+pub mod personal_access_token;
+pub mod profile;
+pub mod category;
+pub mod search;
+pub mod trash;
+pub mod pomodoro;
 
 // use crate::dto;
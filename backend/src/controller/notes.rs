@@ -1,85 +1,91 @@
+use crate::authorization::Owned;
+use crate::controller::auth::UserClaim;
 use crate::db::Pool;
 use crate::dto::CreateNote;
 use crate::entity::notes;
-use rocket::http::Status;
+use crate::errors::AppError;
+use chrono::Utc;
 use rocket::{serde::json::Json, State};
 use rocket::{post, put, delete, get};
-use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set};
 
 #[post("/", data = "<note_dto>")]
 pub async fn create_note(
     db: &State<Pool>,
     note_dto: Json<CreateNote>,
-) -> Result<Json<notes::Model>, (Status, String)> {
+    token: UserClaim,
+) -> Result<Json<notes::Model>, AppError> {
+    let user_id = token.user_id()?;
     let conn = db.inner();
 
     let new_note = notes::ActiveModel {
         text: Set(note_dto.text.clone()),
         bolsonar: Set(note_dto.bolsonar.clone()),
+        user_id: Set(Some(user_id)),
         ..Default::default()
     };
 
     match new_note.insert(conn).await {
         Ok(saved_note) => Ok(Json(saved_note)),
-        Err(e) => Err((Status::InternalServerError, e.to_string())),
+        Err(e) => Err(e.into()),
     }
 }
 
-#[put("/<id>", data = "<note_dto>")]
+#[put("/<_id>", data = "<note_dto>")]
 pub async fn update_note(
     db: &State<Pool>,
-    id: i32,
+    _id: i32,
     note_dto: Json<CreateNote>,
-) -> Result<Json<notes::Model>, (Status, String)> {
+    note: Owned<notes::Model>,
+) -> Result<Json<notes::Model>, AppError> {
     let conn = db.inner();
 
-    match notes::Entity::find_by_id(id).one(conn).await {
-        Ok(Some(note)) => {
-            let updated_note = notes::ActiveModel {
-                id: Set(note.id),
-                text: Set(note_dto.text.clone()),
-                bolsonar: Set(note_dto.bolsonar.clone()),
-                ..Default::default()
-            };
+    let updated_note = notes::ActiveModel {
+        id: Set(note.model.id),
+        text: Set(note_dto.text.clone()),
+        bolsonar: Set(note_dto.bolsonar.clone()),
+        ..Default::default()
+    };
 
-            match updated_note.update(conn).await {
-                Ok(updated_note) => Ok(Json(updated_note)),
-                Err(e) => Err((Status::InternalServerError, e.to_string())),
-            }
-        }
-        Ok(None) => Err((Status::NotFound, "Note not found".into())),
-        Err(e) => Err((Status::InternalServerError, e.to_string())),
+    match updated_note.update(conn).await {
+        Ok(updated_note) => Ok(Json(updated_note)),
+        Err(e) => Err(e.into()),
     }
 }
 
-#[delete("/<id>")]
+#[delete("/<_id>")]
 pub async fn delete_note(
     db: &State<Pool>,
-    id: i32,
-) -> Result<Json<notes::Model>, (Status, String)> {
+    _id: i32,
+    note: Owned<notes::Model>,
+) -> Result<Json<notes::Model>, AppError> {
     let conn = db.inner();
 
-    match notes::Entity::find_by_id(id).one(conn).await {
-        Ok(Some(note)) => {
-            let deleted_note = note.clone();
-            let active_note: notes::ActiveModel = note.into();
+    // A nota vai para a lixeira, de onde pode ser restaurada.
+    let mut active_note: notes::ActiveModel = note.into_inner().into();
+    active_note.deleted_at = Set(Some(Utc::now()));
 
-            match active_note.delete(conn).await {
-                Ok(_) => Ok(Json(deleted_note)),
-                Err(e) => Err((Status::InternalServerError, e.to_string())),
-            }
-        }
-        Ok(None) => Err((Status::NotFound, "Note not found".into())),
-        Err(e) => Err((Status::InternalServerError, e.to_string())),
+    match active_note.update(conn).await {
+        Ok(deleted_note) => Ok(Json(deleted_note)),
+        Err(e) => Err(e.into()),
     }
 }
 
 #[get("/")]
-pub async fn get_all_notes(db: &State<Pool>) -> Result<Json<Vec<notes::Model>>, (Status, String)> {
+pub async fn get_all_notes(
+    db: &State<Pool>,
+    token: UserClaim,
+) -> Result<Json<Vec<notes::Model>>, AppError> {
+    let user_id = token.user_id()?;
     let conn: &sea_orm::DatabaseConnection = db.inner();
 
-    match notes::Entity::find().all(conn).await {
+    match notes::Entity::find()
+        .filter(notes::Column::UserId.eq(user_id))
+        .filter(notes::Column::DeletedAt.is_null())
+        .all(conn)
+        .await
+    {
         Ok(notes) => Ok(Json(notes)),
-        Err(e) => Err((Status::InternalServerError, e.to_string())),
+        Err(e) => Err(e.into()),
     }
 }
@@ -0,0 +1,45 @@
+use crate::authorization::Owned;
+use crate::controller::auth::UserClaim;
+use crate::db::Pool;
+use crate::dto::personal_access_token_dto::{
+    CreatePersonalAccessTokenDto, CreatedPersonalAccessTokenDto, PersonalAccessTokenResponseDto,
+};
+use crate::entity::personal_access_token;
+use crate::errors::AppError;
+use crate::service::personal_access_token_service;
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::{delete, get, post, State};
+
+#[get("/")]
+pub async fn list_tokens(
+    db: &State<Pool>,
+    token: UserClaim,
+) -> Result<Json<Vec<PersonalAccessTokenResponseDto>>, AppError> {
+    let user_id = token.user_id()?;
+    Ok(Json(personal_access_token_service::list_tokens(db, user_id).await?))
+}
+
+#[post("/", data = "<token_dto>")]
+pub async fn create_token(
+    db: &State<Pool>,
+    token_dto: Json<CreatePersonalAccessTokenDto>,
+    token: UserClaim,
+) -> Result<(Status, Json<CreatedPersonalAccessTokenDto>), AppError> {
+    let user_id = token.user_id()?;
+    let (model, token) = personal_access_token_service::create_token(db, user_id, &token_dto).await?;
+    let response = CreatedPersonalAccessTokenDto {
+        token,
+        details: personal_access_token_service::convert_to_response_dto(model),
+    };
+    Ok((Status::Created, Json(response)))
+}
+
+#[delete("/<_id>")]
+pub async fn revoke_token(
+    db: &State<Pool>,
+    _id: i32,
+    access_token: Owned<personal_access_token::Model>,
+) -> Result<Json<PersonalAccessTokenResponseDto>, AppError> {
+    Ok(Json(personal_access_token_service::revoke_token(db, access_token.into_inner()).await?))
+}
@@ -0,0 +1,62 @@
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::{get, post, put, State};
+use crate::controller::auth::UserClaim;
+use crate::db::Pool;
+use crate::dto::pomodoro_dto::{PomodoroSettingsDto, PomodoroStartDto, PomodoroStateDto};
+use crate::entity::pomodoro_session;
+use crate::errors::AppError;
+use crate::service::pomodoro_service;
+
+/// Configuração, sessão em andamento e próxima sessão sugerida.
+#[get("/")]
+pub async fn get_pomodoro_state(
+    db: &State<Pool>,
+    token: UserClaim,
+) -> Result<Json<PomodoroStateDto>, AppError> {
+    let user_id = token.user_id()?;
+    Ok(Json(pomodoro_service::get_state_db(db, user_id).await?))
+}
+
+#[put("/settings", data = "<settings_dto>")]
+pub async fn update_pomodoro_settings(
+    settings_dto: Json<PomodoroSettingsDto>,
+    db: &State<Pool>,
+    token: UserClaim,
+) -> Result<Json<PomodoroSettingsDto>, AppError> {
+    let user_id = token.user_id()?;
+    Ok(Json(pomodoro_service::update_settings_db(db, user_id, &settings_dto).await?))
+}
+
+/// Histórico das sessões, das mais recentes às mais antigas.
+#[get("/sessions?<limit>")]
+pub async fn list_pomodoro_sessions(
+    limit: Option<u64>,
+    db: &State<Pool>,
+    token: UserClaim,
+) -> Result<Json<Vec<pomodoro_session::Model>>, AppError> {
+    let user_id = token.user_id()?;
+    Ok(Json(pomodoro_service::list_sessions_db(db, user_id, limit).await?))
+}
+
+/// Inicia uma sessão de foco (com `task_id`) ou de pausa.
+#[post("/sessions", data = "<start_dto>")]
+pub async fn start_pomodoro_session(
+    start_dto: Json<PomodoroStartDto>,
+    db: &State<Pool>,
+    token: UserClaim,
+) -> Result<(Status, Json<pomodoro_session::Model>), AppError> {
+    let user_id = token.user_id()?;
+    let session = pomodoro_service::start_session_db(db, user_id, &start_dto).await?;
+    Ok((Status::Created, Json(session)))
+}
+
+/// Para a sessão em andamento.
+#[post("/sessions/stop")]
+pub async fn stop_pomodoro_session(
+    db: &State<Pool>,
+    token: UserClaim,
+) -> Result<Json<pomodoro_session::Model>, AppError> {
+    let user_id = token.user_id()?;
+    Ok(Json(pomodoro_service::stop_session_db(db, user_id).await?))
+}
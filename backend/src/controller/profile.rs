@@ -0,0 +1,26 @@
+use crate::controller::auth::UserClaim;
+use crate::db::Pool;
+use crate::dto::profile_dto::{ProfileResponseDto, UpdateProfileDto};
+use crate::errors::AppError;
+use crate::service::profile_service;
+use rocket::serde::json::Json;
+use rocket::{get, put, State};
+
+#[get("/")]
+pub async fn get_profile(
+    db: &State<Pool>,
+    token: UserClaim,
+) -> Result<Json<ProfileResponseDto>, AppError> {
+    let user_id = token.user_id()?;
+    Ok(Json(profile_service::get_profile(db, user_id).await?))
+}
+
+#[put("/", data = "<profile_dto>")]
+pub async fn update_profile(
+    db: &State<Pool>,
+    profile_dto: Json<UpdateProfileDto>,
+    token: UserClaim,
+) -> Result<Json<ProfileResponseDto>, AppError> {
+    let user_id = token.user_id()?;
+    Ok(Json(profile_service::update_profile(db, user_id, &profile_dto).await?))
+}
@@ -1,13 +1,14 @@
-use rocket::http::Status;
 use rocket::serde::json::Json;
 use rocket::State;
 use rocket::{post, get, put, delete};
+use crate::authorization::Owned;
 use crate::controller::auth::UserClaim;
 use crate::db::Pool;
+use crate::dto::list_dto::{ListQuery, PageDto};
 use crate::dto::reminder_dto::ReminderDto;
 use crate::entity::reminder;
 use crate::service::reminder_service::create_reminder_db;
-use crate::service::reminder_service::ReminderError;
+use crate::errors::AppError;
 use crate::service::reminder_service::delete_reminder_db;
 use crate::service::reminder_service;
 
@@ -18,60 +19,54 @@ pub async fn register_reminder(
     reminder_dto: Json<ReminderDto>,
     db: &State<Pool>,
     token: UserClaim,
-) -> Result<Json<reminder::Model>, (Status, String)> {
+) -> Result<Json<reminder::Model>, AppError> {
 
-    let user_id = token.get_id().parse::<i32>().map_err(|_| {
-        (
-            Status::BadRequest,
-            "Invalid token: user_id is not valid".to_string(),
-        )
-    })?;
+    let user_id = token.user_id()?;
 
-    match create_reminder_db(db, &reminder_dto, user_id).await {
-        Ok(reminder) => Ok(Json(reminder)),
-        Err(ReminderError::DatabaseError(msg)) => Err((Status::InternalServerError, msg)),
-    }
+    Ok(Json(create_reminder_db(db, &reminder_dto, user_id).await?))
 }
 
 
 
-#[delete("/<id>")]
+#[delete("/<_id>")]
 pub async fn delete_reminder(
     db: &State<Pool>,
-    id: i32,
-) -> Result<Json<reminder::Model>, (Status, String)> {
-    match delete_reminder_db(db, id).await {
+    _id: i32,
+    reminder: Owned<reminder::Model>,
+) -> Result<Json<reminder::Model>, AppError> {
+    match delete_reminder_db(db, reminder.into_inner()).await {
         Ok(reminder) => Ok(Json(reminder)),
         Err(e) => Err(e),
     }
-}  
+}
 
-#[get("/")]
-pub async fn list_reminders(db: &State<Pool>) -> Result<Json<Vec<reminder::Model>>, (Status, String)> {
-    match reminder_service::list_reminders_db(db).await {
-        Ok(reminders) => Ok(Json(reminders)),
-        Err(e) => Err(e),
-    }
+/// Lembretes filtrados, ordenados e paginados por cursor.
+#[get("/?<query..>")]
+pub async fn list_reminders(
+    query: ListQuery,
+    db: &State<Pool>,
+    token: UserClaim,
+) -> Result<Json<PageDto<reminder::Model>>, AppError> {
+    let user_id = token.user_id()?;
+    Ok(Json(reminder_service::list_reminders_db(db, user_id, &query).await?))
 }
 
-#[get("/<id>")]
+#[get("/<_id>")]
 pub async fn get_reminder(
-    db: &State<Pool>,
-    id: i32,
-) -> Result<Json<reminder::Model>, (Status, String)> {
-    match reminder_service::get_reminder_db(db, id).await {
-        Ok(reminder) => Ok(Json(reminder)),
-        Err(e) => Err(e),
-    }
+    _id: i32,
+    reminder: Owned<reminder::Model>,
+) -> Json<reminder::Model> {
+    Json(reminder.into_inner())
 }
 
-#[put("/<id>", data = "<reminder_dto>")]
+#[put("/<_id>", data = "<reminder_dto>")]
 pub async fn update_reminder(
     db: &State<Pool>,
-    id: i32,
+    _id: i32,
     reminder_dto: Json<ReminderDto>,
-) -> Result<Json<reminder::Model>, (Status, String)> {
-    match reminder_service::update_reminder_db(db, id, &reminder_dto).await {
+    reminder: Owned<reminder::Model>,
+) -> Result<Json<reminder::Model>, AppError> {
+    match reminder_service::update_reminder_db(db, reminder.into_inner(), &reminder_dto).await {
         Ok(reminder) => Ok(Json(reminder)),
         Err(e) => Err(e),
     }
@@ -81,15 +76,10 @@ pub async fn update_reminder(
 pub async fn get_reminders_by_user_id(
     db: &State<Pool>,
     token: UserClaim,
-) -> Result<Json<Vec<reminder::Model>>, (Status, String)> {
-    let user_id = token.get_id().parse::<i32>().map_err(|_| {
-        (Status::BadRequest, "Invalid token: user_id is not valid".to_string())
-    })?;
+) -> Result<Json<Vec<reminder::Model>>, AppError> {
+    let user_id = token.user_id()?;
 
-    match reminder_service::get_reminders_by_user_id_db(db, user_id).await {
-        Ok(reminders) => Ok(Json(reminders)),
-        Err(ReminderError::DatabaseError(msg)) => Err((Status::InternalServerError, msg)),
-    }
+    Ok(Json(reminder_service::get_reminders_by_user_id_db(db, user_id).await?))
 }
 
 
@@ -1,10 +1,11 @@
 use rocket::serde::json::Json;
 use rocket::{get, State};
-use rocket::http::Status;
+use crate::errors::AppError;
 use sea_orm::DatabaseConnection;
 use crate::controller::auth::UserClaim;
 use crate::dto::report_dto::*;
 use crate::service::report_service::ReportService;
+use crate::service::{auth_service, profile_service};
 
 /// Endpoint para obter estatísticas de tarefas por ano
 #[get("/stats/year/<year>")]
@@ -12,18 +13,13 @@ pub async fn get_stats_year(
     token: UserClaim,
     year: i32,
     db: &State<DatabaseConnection>,
-) -> Result<Json<StatsYearResponse>, (Status, String)>{
-    let user_id = token.get_id().parse::<i32>().map_err(|_| {
-        (Status::BadRequest, "Invalid token: user_id is not valid".to_string())
-    })?;
+) -> Result<Json<StatsYearResponse>, AppError>{
+    let user_id = token.user_id()?;
 
-    let report_service = ReportService::new(db.inner().clone());
-    let result = report_service.stats_year(user_id, year).await;
-
-    match result {
-        Ok(response) => Ok(Json(response)),
-        Err(_) => Ok(Json(StatsYearResponse::default_for_year(year)))
-    }
+    let user = auth_service::find_user(db, user_id).await?;
+    let report_service = ReportService::new(db.inner().clone(), profile_service::user_timezone(&user));
+    let response = report_service.stats_year(user_id, year).await?;
+    Ok(Json(response))
 }
 
 /// Endpoint para obter estatísticas de tarefas por mês
@@ -33,18 +29,13 @@ pub async fn get_stats_month(
     year: i32,
     month: i32,
     db: &State<DatabaseConnection>,
-) -> Result<Json<StatsMonthResponse>, (Status, String)> {
-    let user_id = token.get_id().parse::<i32>().map_err(|_| {
-        (Status::BadRequest, "Invalid token: user_id is not valid".to_string())
-    })?;
-
-    let report_service = ReportService::new(db.inner().clone());
-    let result = report_service.stats_month(user_id, year, month).await;
+) -> Result<Json<StatsMonthResponse>, AppError> {
+    let user_id = token.user_id()?;
 
-    match result {
-        Ok(response) => Ok(Json(response)),
-        Err(_) => Ok(Json(StatsMonthResponse::default_for_month(year, month)))
-    }
+    let user = auth_service::find_user(db, user_id).await?;
+    let report_service = ReportService::new(db.inner().clone(), profile_service::user_timezone(&user));
+    let response = report_service.stats_month(user_id, year, month).await?;
+    Ok(Json(response))
 }
 
 /// Endpoint para obter estatísticas de tarefas por semana
@@ -54,17 +45,12 @@ pub async fn get_stats_week(
     year: i32,
     week: i32,
     db: &State<DatabaseConnection>,
-) -> Result<Json<StatsWeekResponse>, (Status, String)> {
-    let user_id = token.get_id().parse::<i32>().map_err(|_| {
-        (Status::BadRequest, "Invalid token: user_id is not valid".to_string())
-    })?;
-
-    let report_service = ReportService::new(db.inner().clone());
-    let result = report_service.stats_week(user_id, year, week).await;
+) -> Result<Json<StatsWeekResponse>, AppError> {
+    let user_id = token.user_id()?;
 
-    match result {
-        Ok(response) => Ok(Json(response)),
-        Err(_) => Ok(Json(StatsWeekResponse::default_for_week(year, week)))
-    }
+    let user = auth_service::find_user(db, user_id).await?;
+    let report_service = ReportService::new(db.inner().clone(), profile_service::user_timezone(&user));
+    let response = report_service.stats_week(user_id, year, week).await?;
+    Ok(Json(response))
 }
 
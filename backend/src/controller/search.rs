@@ -0,0 +1,19 @@
+use rocket::serde::json::Json;
+use rocket::{get, State};
+use crate::controller::auth::UserClaim;
+use crate::db::Pool;
+use crate::dto::search_dto::SearchResultDto;
+use crate::errors::AppError;
+use crate::service::search_service;
+
+/// Busca em tarefas, metas, lembretes e notas, com os resultados misturados por relevância.
+#[get("/?<q>&<limit>")]
+pub async fn search(
+    q: &str,
+    limit: Option<u64>,
+    db: &State<Pool>,
+    token: UserClaim,
+) -> Result<Json<Vec<SearchResultDto>>, AppError> {
+    let user_id = token.user_id()?;
+    Ok(Json(search_service::search_db(db, user_id, q, limit).await?))
+}
@@ -5,97 +5,328 @@ use rocket::get;
 use rocket::post;
 use rocket::put;
 use rocket::State;
+use crate::authorization::Owned;
 use crate::controller::auth::UserClaim;
 use crate::db::Pool;
-use crate::dto::task_dto::TaskDto;
-use crate::entity::task;
-use crate::service::task_service::{delete_task_db, get_all_tasks_db, get_task_by_id_db, register_task_db, update_task_db, get_tasks_by_user_id_db, TaskError};
+use crate::dto::checklist_dto::{ChecklistItemDto, ChecklistItemUpdateDto};
+use crate::dto::list_dto::{ListQuery, PageDto};
+use crate::dto::task_dependency_dto::{DependencyGraphDto, TaskDependencyDto};
+use crate::dto::task_bulk_dto::{TaskBulkDto, TaskBulkResultDto};
+use crate::dto::task_dto::{OverdueTasksDto, TaskDto, TaskResponseDto};
+use crate::dto::task_occurrence_dto::TaskOccurrenceDto;
+use crate::dto::task_rollover_dto::{TaskRolloverDto, TaskRolloverResultDto};
+use crate::dto::time_entry_dto::{TimeEntryDto, TimeEntryUpdateDto, TimerStartDto};
+use crate::entity::{checklist_item, task, task_dependency, task_status_history, time_entry};
+use crate::errors::AppError;
+use crate::service::task_service::{delete_task_db, register_task_db, update_task_db, get_tasks_by_user_id_db, list_tasks_db};
+use crate::service::task_service::{bulk_update_tasks_db, rollover_tasks_db, list_occurrences_db, skip_occurrence_db, update_occurrence_db, to_response_dto, to_response_dtos, status_history_db};
+use crate::service::checklist_service::{create_item_db, delete_item_db, list_items_db, update_item_db};
+use crate::service::dependency_service::{add_blocker_db, dependency_graph_db, list_blockers_db, remove_blocker_db};
+use crate::service::overdue_service::list_overdue_db;
+use crate::service::time_entry_service::{create_entry_db, delete_entry_db, list_entries_db, running_timer_db, start_timer_db, stop_timer_db, update_entry_db};
 use crate::dto::task_update_dto::TaskUpdateDto;
 
+/// Mantido por compatibilidade: retorna apenas as tarefas do usuário do token.
 #[get("/all")]
-pub async fn get_all_tasks(db: &State<Pool>) -> Result<Json<Vec<task::Model>>, (Status, String)> {
-    match get_all_tasks_db(db).await {
-        Ok(tasks) => Ok(Json(tasks)),
-        Err(TaskError::DatabaseError(msg)) => Err((Status::InternalServerError, msg)),
-        _ => Err((Status::InternalServerError, "Failed to get tasks".to_string())),
-    }
+pub async fn get_all_tasks(
+    db: &State<Pool>,
+    token: UserClaim,
+) -> Result<Json<Vec<TaskResponseDto>>, AppError> {
+    let user_id = token.user_id()?;
+    let tasks = get_tasks_by_user_id_db(db, user_id).await?;
+    Ok(Json(to_response_dtos(db, tasks).await?))
+}
+
+/// Ocorrências entre `from` e `to` (`YYYY-MM-DD`, inclusive), com as tarefas
+/// repetidas expandidas.
+#[get("/occurrences?<from>&<to>")]
+pub async fn get_task_occurrences(
+    from: &str,
+    to: &str,
+    db: &State<Pool>,
+    token: UserClaim,
+) -> Result<Json<Vec<TaskOccurrenceDto>>, AppError> {
+    let user_id = token.user_id()?;
+    Ok(Json(list_occurrences_db(db, user_id, from, to).await?))
 }
 
-#[get("/<id>")]
-pub async fn get_task_by_id(id: i32, db: &State<Pool>) -> Result<Json<task::Model>, (Status, String)> {
-    match get_task_by_id_db(db, id).await {
-        Ok(task) => Ok(Json(task)),
-        Err(TaskError::TaskNotFound(msg)) => Err((Status::NotFound, msg)),
-        Err(TaskError::DatabaseError(msg)) => Err((Status::InternalServerError, msg)),
-        _ => Err((Status::InternalServerError, "Failed to get task".to_string())),
-    }
+/// Grafo de dependências entre as tarefas do usuário.
+#[get("/dependencies")]
+pub async fn get_dependency_graph(
+    db: &State<Pool>,
+    token: UserClaim,
+) -> Result<Json<DependencyGraphDto>, AppError> {
+    let user_id = token.user_id()?;
+    Ok(Json(dependency_graph_db(db, user_id).await?))
 }
 
-#[get("/")]
-pub async fn get_tasks_by_user_id(
+/// Tarefas atrasadas do usuário, com o total para o contador da agenda.
+#[get("/overdue")]
+pub async fn get_overdue_tasks(
     db: &State<Pool>,
     token: UserClaim,
-) -> Result<Json<Vec<task::Model>>, (Status, String)> {
-    let user_id = token.get_id().parse::<i32>().map_err(|_| {
-        (Status::BadRequest, "Invalid token: user_id is not valid".to_string())
-    })?;
+) -> Result<Json<OverdueTasksDto>, AppError> {
+    let user_id = token.user_id()?;
+    Ok(Json(list_overdue_db(db, user_id).await?))
+}
+
+#[get("/<_id>")]
+pub async fn get_task_by_id(
+    _id: i32,
+    db: &State<Pool>,
+    task: Owned<task::Model>,
+) -> Result<Json<TaskResponseDto>, AppError> {
+    Ok(Json(to_response_dto(db, task.into_inner()).await?))
+}
 
-    match get_tasks_by_user_id_db(db, user_id).await {
-        Ok(tasks) => Ok(Json(tasks)),
-        Err(TaskError::DatabaseError(msg)) => Err((Status::InternalServerError, msg)),
-        _ => Err((Status::InternalServerError, "Failed to get user tasks".to_string())),
-    }
+/// Tarefas filtradas, ordenadas e paginadas por cursor.
+#[get("/?<query..>")]
+pub async fn list_tasks(
+    query: ListQuery,
+    db: &State<Pool>,
+    token: UserClaim,
+) -> Result<Json<PageDto<TaskResponseDto>>, AppError> {
+    let user_id = token.user_id()?;
+    Ok(Json(list_tasks_db(db, user_id, &query).await?))
 }
+
 #[post("/", data = "<task_dto>")]
 pub async fn register_task(
     task_dto: Json<TaskDto>,
     db: &State<Pool>,
     token: UserClaim,
-) -> Result<Json<task::Model>, (Status, String)> {
-    let user_id = token.get_id().parse::<i32>().map_err(|_| {
-        (Status::BadRequest, "Invalid token: user_id is not valid".to_string())
-    })?;
+) -> Result<Json<TaskResponseDto>, AppError> {
+    let user_id = token.user_id()?;
+    let task = register_task_db(db, &task_dto, user_id).await?;
+    Ok(Json(to_response_dto(db, task).await?))
+}
 
-    match register_task_db(db, &task_dto, user_id).await {
-        Ok(task) => Ok(Json(task)),
-        Err(TaskError::DatabaseError(msg)) => Err((Status::InternalServerError, msg)),
-        _ => Err((Status::BadRequest, "Failed to create task".to_string())),
-    }
+/// Aplica a mesma ação a várias tarefas de uma vez. Responde `422` quando
+/// algum item é recusado, e nesse caso nada é gravado.
+#[post("/bulk", data = "<bulk_dto>")]
+pub async fn bulk_update_tasks(
+    bulk_dto: Json<TaskBulkDto>,
+    db: &State<Pool>,
+    token: UserClaim,
+) -> Result<(Status, Json<TaskBulkResultDto>), AppError> {
+    let user_id = token.user_id()?;
+    let result = bulk_update_tasks_db(db, user_id, &bulk_dto).await?;
+    let status = if result.applied { Status::Ok } else { Status::UnprocessableEntity };
+    Ok((status, Json(result)))
 }
 
-#[put("/<id>", data = "<task_dto>")]
-pub async fn update_task(
-    id: i32,
-    task_dto: Json<TaskUpdateDto>,
+/// Leva as tarefas pendentes de um dia para outro e informa as que não couberam.
+#[post("/rollover", data = "<rollover_dto>")]
+pub async fn rollover_tasks(
+    rollover_dto: Json<TaskRolloverDto>,
     db: &State<Pool>,
     token: UserClaim,
-) -> Result<Json<task::Model>, (Status, String)> {
-    let user_id = token.get_id().parse::<i32>().map_err(|_| {
-        (Status::BadRequest, "Invalid token: user_id is not valid".to_string())
-    })?;
+) -> Result<Json<TaskRolloverResultDto>, AppError> {
+    let user_id = token.user_id()?;
+    Ok(Json(rollover_tasks_db(db, user_id, &rollover_dto).await?))
+}
 
-    match update_task_db(db, id, &task_dto,user_id).await {
-        Ok(task) => Ok(Json(task)),
-        Err(TaskError::TaskNotFound(msg)) => Err((Status::NotFound, msg)),
-        Err(TaskError::DatabaseError(msg)) => Err((Status::InternalServerError, msg)),
-        _ => Err((Status::InternalServerError, "Failed to update task".to_string())),
-    }
+#[put("/<_id>", data = "<task_dto>")]
+pub async fn update_task(
+    _id: i32,
+    task_dto: Json<TaskUpdateDto>,
+    db: &State<Pool>,
+    task: Owned<task::Model>,
+) -> Result<Json<TaskResponseDto>, AppError> {
+    let task = update_task_db(db, task.into_inner(), &task_dto).await?;
+    Ok(Json(to_response_dto(db, task).await?))
 }
 
-#[delete("/<id>")]
+#[delete("/<_id>")]
 pub async fn delete_task(
-    id: i32,
+    _id: i32,
+    db: &State<Pool>,
+    task: Owned<task::Model>,
+) -> Result<Status, AppError> {
+    delete_task_db(db, task.into_inner()).await?;
+    Ok(Status::NoContent)
+}
+
+/// Edita só a ocorrência que começa em `start` (segundos Unix).
+#[put("/<_id>/occurrences/<start>", data = "<task_dto>")]
+pub async fn update_task_occurrence(
+    _id: i32,
+    start: i64,
+    task_dto: Json<TaskUpdateDto>,
+    db: &State<Pool>,
+    task: Owned<task::Model>,
+) -> Result<Json<TaskOccurrenceDto>, AppError> {
+    Ok(Json(update_occurrence_db(db, task.into_inner(), start, &task_dto).await?))
+}
+
+/// Pula só a ocorrência que começa em `start` (segundos Unix).
+#[delete("/<_id>/occurrences/<start>")]
+pub async fn skip_task_occurrence(
+    _id: i32,
+    start: i64,
+    db: &State<Pool>,
+    task: Owned<task::Model>,
+) -> Result<Status, AppError> {
+    skip_occurrence_db(db, task.into_inner(), start).await?;
+    Ok(Status::NoContent)
+}
+
+/// Itens do checklist da tarefa, em ordem.
+#[get("/<_id>/checklist")]
+pub async fn get_checklist(
+    _id: i32,
+    db: &State<Pool>,
+    task: Owned<task::Model>,
+) -> Result<Json<Vec<checklist_item::Model>>, AppError> {
+    Ok(Json(list_items_db(db, &task.into_inner()).await?))
+}
+
+/// Adiciona um item no fim do checklist.
+#[post("/<_id>/checklist", data = "<item_dto>")]
+pub async fn create_checklist_item(
+    _id: i32,
+    item_dto: Json<ChecklistItemDto>,
+    db: &State<Pool>,
+    task: Owned<task::Model>,
+) -> Result<Json<checklist_item::Model>, AppError> {
+    Ok(Json(create_item_db(db, &task.into_inner(), &item_dto).await?))
+}
+
+#[put("/<_id>/checklist/<item_id>", data = "<item_dto>")]
+pub async fn update_checklist_item(
+    _id: i32,
+    item_id: i32,
+    item_dto: Json<ChecklistItemUpdateDto>,
+    db: &State<Pool>,
+    task: Owned<task::Model>,
+) -> Result<Json<checklist_item::Model>, AppError> {
+    Ok(Json(update_item_db(db, task.into_inner(), item_id, &item_dto).await?))
+}
+
+#[delete("/<_id>/checklist/<item_id>")]
+pub async fn delete_checklist_item(
+    _id: i32,
+    item_id: i32,
+    db: &State<Pool>,
+    task: Owned<task::Model>,
+) -> Result<Status, AppError> {
+    delete_item_db(db, task.into_inner(), item_id).await?;
+    Ok(Status::NoContent)
+}
+
+/// Cronômetro ligado do usuário, ou `null`.
+#[get("/timer")]
+pub async fn get_running_timer(
+    db: &State<Pool>,
+    token: UserClaim,
+) -> Result<Json<Option<time_entry::Model>>, AppError> {
+    let user_id = token.user_id()?;
+    Ok(Json(running_timer_db(db, user_id).await?))
+}
+
+/// Para o cronômetro ligado do usuário.
+#[post("/timer/stop")]
+pub async fn stop_timer(
     db: &State<Pool>,
     token: UserClaim,
-) -> Result<Status, (Status, String)> {
-    let user_id = token.get_id().parse::<i32>().map_err(|_| {
-        (Status::BadRequest, "Invalid token: user_id is not valid".to_string())
-    })?;
-
-    match delete_task_db(db, id, user_id).await {
-        Ok(_) => Ok(Status::NoContent),
-        Err(TaskError::TaskNotFound(msg)) => Err((Status::NotFound, msg)),
-        Err(TaskError::DatabaseError(msg)) => Err((Status::InternalServerError, msg)),
-        _ => Err((Status::InternalServerError, "Failed to delete task".to_string())),
-    }
-}
\ No newline at end of file
+) -> Result<Json<time_entry::Model>, AppError> {
+    let user_id = token.user_id()?;
+    Ok(Json(stop_timer_db(db, user_id).await?))
+}
+
+/// Liga o cronômetro da tarefa; só pode haver um ligado por usuário.
+#[post("/<_id>/timer/start", data = "<timer_dto>")]
+pub async fn start_timer(
+    _id: i32,
+    timer_dto: Json<TimerStartDto>,
+    db: &State<Pool>,
+    task: Owned<task::Model>,
+) -> Result<(Status, Json<time_entry::Model>), AppError> {
+    let entry = start_timer_db(db, &task.into_inner(), &timer_dto).await?;
+    Ok((Status::Created, Json(entry)))
+}
+
+/// Registros de tempo da tarefa, dos mais recentes aos mais antigos.
+#[get("/<_id>/time-entries")]
+pub async fn get_time_entries(
+    _id: i32,
+    db: &State<Pool>,
+    task: Owned<task::Model>,
+) -> Result<Json<Vec<time_entry::Model>>, AppError> {
+    Ok(Json(list_entries_db(db, &task.into_inner()).await?))
+}
+
+/// Lança um registro de tempo à mão.
+#[post("/<_id>/time-entries", data = "<entry_dto>")]
+pub async fn create_time_entry(
+    _id: i32,
+    entry_dto: Json<TimeEntryDto>,
+    db: &State<Pool>,
+    task: Owned<task::Model>,
+) -> Result<(Status, Json<time_entry::Model>), AppError> {
+    let entry = create_entry_db(db, &task.into_inner(), &entry_dto).await?;
+    Ok((Status::Created, Json(entry)))
+}
+
+#[put("/<_id>/time-entries/<entry_id>", data = "<entry_dto>")]
+pub async fn update_time_entry(
+    _id: i32,
+    entry_id: i32,
+    entry_dto: Json<TimeEntryUpdateDto>,
+    db: &State<Pool>,
+    task: Owned<task::Model>,
+) -> Result<Json<time_entry::Model>, AppError> {
+    Ok(Json(update_entry_db(db, &task.into_inner(), entry_id, &entry_dto).await?))
+}
+
+#[delete("/<_id>/time-entries/<entry_id>")]
+pub async fn delete_time_entry(
+    _id: i32,
+    entry_id: i32,
+    db: &State<Pool>,
+    task: Owned<task::Model>,
+) -> Result<Status, AppError> {
+    delete_entry_db(db, &task.into_inner(), entry_id).await?;
+    Ok(Status::NoContent)
+}
+
+/// Tarefas que bloqueiam esta.
+#[get("/<_id>/dependencies")]
+pub async fn get_task_blockers(
+    _id: i32,
+    db: &State<Pool>,
+    task: Owned<task::Model>,
+) -> Result<Json<Vec<task::Model>>, AppError> {
+    Ok(Json(list_blockers_db(db, &task.into_inner()).await?))
+}
+
+#[post("/<_id>/dependencies", data = "<dependency_dto>")]
+pub async fn add_task_blocker(
+    _id: i32,
+    dependency_dto: Json<TaskDependencyDto>,
+    db: &State<Pool>,
+    task: Owned<task::Model>,
+) -> Result<Json<task_dependency::Model>, AppError> {
+    Ok(Json(add_blocker_db(db, &task.into_inner(), &dependency_dto).await?))
+}
+
+#[delete("/<_id>/dependencies/<blocked_by_id>")]
+pub async fn remove_task_blocker(
+    _id: i32,
+    blocked_by_id: i32,
+    db: &State<Pool>,
+    task: Owned<task::Model>,
+) -> Result<Status, AppError> {
+    remove_blocker_db(db, &task.into_inner(), blocked_by_id).await?;
+    Ok(Status::NoContent)
+}
+
+/// Mudanças de status da tarefa, da mais antiga à mais recente.
+#[get("/<_id>/history")]
+pub async fn get_task_status_history(
+    _id: i32,
+    db: &State<Pool>,
+    task: Owned<task::Model>,
+) -> Result<Json<Vec<task_status_history::Model>>, AppError> {
+    Ok(Json(status_history_db(db, &task.into_inner()).await?))
+}
@@ -0,0 +1,54 @@
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::{delete, get, post, State};
+use crate::controller::auth::UserClaim;
+use crate::db::Pool;
+use crate::dto::trash_dto::TrashItemDto;
+use crate::errors::AppError;
+use crate::service::trash_service;
+
+/// Itens excluídos que ainda podem ser restaurados.
+#[get("/")]
+pub async fn list_trash(
+    db: &State<Pool>,
+    token: UserClaim,
+) -> Result<Json<Vec<TrashItemDto>>, AppError> {
+    let user_id = token.user_id()?;
+    Ok(Json(trash_service::list_trash_db(db, user_id).await?))
+}
+
+/// Restaura um item; `kind` é `task`, `goal`, `reminder` ou `note`.
+#[post("/<kind>/<id>/restore")]
+pub async fn restore_item(
+    kind: &str,
+    id: i32,
+    db: &State<Pool>,
+    token: UserClaim,
+) -> Result<Status, AppError> {
+    let user_id = token.user_id()?;
+    trash_service::restore_db(db, user_id, kind, id).await?;
+    Ok(Status::NoContent)
+}
+
+/// Remove um item de vez.
+#[delete("/<kind>/<id>")]
+pub async fn purge_item(
+    kind: &str,
+    id: i32,
+    db: &State<Pool>,
+    token: UserClaim,
+) -> Result<Status, AppError> {
+    let user_id = token.user_id()?;
+    trash_service::purge_db(db, user_id, kind, id).await?;
+    Ok(Status::NoContent)
+}
+
+#[delete("/")]
+pub async fn empty_trash(
+    db: &State<Pool>,
+    token: UserClaim,
+) -> Result<Status, AppError> {
+    let user_id = token.user_id()?;
+    trash_service::empty_trash_db(db, user_id).await?;
+    Ok(Status::NoContent)
+}
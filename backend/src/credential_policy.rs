@@ -0,0 +1,221 @@
+//! Política de credenciais aplicada no cadastro, na troca do nome de usuário e
+//! sempre que uma senha é definida (troca e redefinição).
+//!
+//! Os padrões são os mesmos do frontend (`utils/validation.rs`) e as mensagens,
+//! em português, podem ser exibidas diretamente na tela de cadastro.
+//!
+//! Configuração por variáveis de ambiente:
+//!
+//! - `AUTH_USERNAME_MIN_LENGTH` (padrão 6) e `AUTH_USERNAME_MAX_LENGTH` (padrão 32);
+//! - `AUTH_PASSWORD_MIN_LENGTH` (padrão 8);
+//! - `AUTH_PASSWORD_REQUIRE_LOWERCASE`, `AUTH_PASSWORD_REQUIRE_UPPERCASE` e
+//!   `AUTH_PASSWORD_REQUIRE_DIGIT` (padrão `true`);
+//! - `AUTH_PASSWORD_REQUIRE_SYMBOL` (padrão `false`);
+//! - `AUTH_PASSWORD_BLOCKLIST_FILE`: arquivo com senhas proibidas, uma por
+//!   linha, somadas à lista embutida de senhas comuns.
+
+use std::collections::HashSet;
+use std::env::var;
+use std::sync::OnceLock;
+
+/// Senhas comuns recusadas independentemente das classes de caracteres.
+/// A comparação ignora maiúsculas e minúsculas.
+const COMMON_PASSWORDS: &[&str] = &[
+    "123456", "12345678", "123456789", "1234567890", "password", "password1", "password123",
+    "passw0rd", "qwerty", "qwerty123", "qwertyuiop", "abc123", "abc12345", "abcd1234", "111111",
+    "000000", "iloveyou", "admin", "admin123", "administrator", "welcome", "welcome1", "welcome123",
+    "letmein", "letmein1", "monkey", "dragon", "football", "baseball", "sunshine", "princess",
+    "trustno1", "master", "superman", "starwars", "changeme", "secret", "test1234", "senha",
+    "senha123", "senha1234", "mudar123", "mudar@123", "brasil", "brasil123", "flamengo",
+    "corinthians", "palmeiras", "gremio", "123mudar", "agenda", "agenda123",
+];
+
+const USERNAME_SYMBOLS: [char; 3] = ['.', '_', '-'];
+
+#[derive(Clone, Debug)]
+pub struct CredentialPolicy {
+    pub username_min_length: usize,
+    pub username_max_length: usize,
+    pub password_min_length: usize,
+    pub require_lowercase: bool,
+    pub require_uppercase: bool,
+    pub require_digit: bool,
+    pub require_symbol: bool,
+    pub blocklist: HashSet<String>,
+}
+
+impl Default for CredentialPolicy {
+    fn default() -> Self {
+        CredentialPolicy {
+            username_min_length: 6,
+            username_max_length: 32,
+            password_min_length: 8,
+            require_lowercase: true,
+            require_uppercase: true,
+            require_digit: true,
+            require_symbol: false,
+            blocklist: COMMON_PASSWORDS.iter().map(|password| password.to_string()).collect(),
+        }
+    }
+}
+
+impl CredentialPolicy {
+    pub fn from_env() -> Self {
+        let default = CredentialPolicy::default();
+        let mut blocklist = default.blocklist;
+        if let Ok(path) = var("AUTH_PASSWORD_BLOCKLIST_FILE") {
+            match std::fs::read_to_string(&path) {
+                Ok(contents) => blocklist.extend(
+                    contents
+                        .lines()
+                        .map(|line| line.trim().to_lowercase())
+                        .filter(|line| !line.is_empty()),
+                ),
+                Err(e) => log::warn!("credential policy: could not read blocklist '{}': {}", path, e),
+            }
+        }
+
+        CredentialPolicy {
+            username_min_length: env_or("AUTH_USERNAME_MIN_LENGTH", default.username_min_length),
+            username_max_length: env_or("AUTH_USERNAME_MAX_LENGTH", default.username_max_length),
+            password_min_length: env_or("AUTH_PASSWORD_MIN_LENGTH", default.password_min_length),
+            require_lowercase: env_or("AUTH_PASSWORD_REQUIRE_LOWERCASE", default.require_lowercase),
+            require_uppercase: env_or("AUTH_PASSWORD_REQUIRE_UPPERCASE", default.require_uppercase),
+            require_digit: env_or("AUTH_PASSWORD_REQUIRE_DIGIT", default.require_digit),
+            require_symbol: env_or("AUTH_PASSWORD_REQUIRE_SYMBOL", default.require_symbol),
+            blocklist,
+        }
+    }
+
+    /// Política lida do ambiente na primeira utilização.
+    pub fn global() -> &'static CredentialPolicy {
+        static POLICY: OnceLock<CredentialPolicy> = OnceLock::new();
+        POLICY.get_or_init(CredentialPolicy::from_env)
+    }
+
+    pub fn username_errors(&self, username: &str) -> Vec<String> {
+        let mut errors = Vec::new();
+        let length = username.chars().count();
+
+        if length < self.username_min_length {
+            errors.push(format!(
+                "O nome de usuário deve ter pelo menos {} caracteres",
+                self.username_min_length
+            ));
+        }
+        if length > self.username_max_length {
+            errors.push(format!(
+                "O nome de usuário deve ter no máximo {} caracteres",
+                self.username_max_length
+            ));
+        }
+        if !username.chars().all(|c| c.is_ascii_alphanumeric() || USERNAME_SYMBOLS.contains(&c)) {
+            errors.push("Use apenas letras sem acento, números, '.', '_' ou '-'".to_string());
+        }
+
+        errors
+    }
+
+    /// Erros da senha; com `username`, também recusa senhas que contenham o nome de usuário.
+    pub fn password_errors(&self, password: &str, username: Option<&str>) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        if password.chars().count() < self.password_min_length {
+            errors.push(format!("Senha curta, no mínimo {} dígitos", self.password_min_length));
+        }
+        if self.require_lowercase && !password.chars().any(|c| c.is_lowercase()) {
+            errors.push("Pelo menos uma letra minúscula".to_string());
+        }
+        if self.require_uppercase && !password.chars().any(|c| c.is_uppercase()) {
+            errors.push("Pelo menos uma letra maiúscula".to_string());
+        }
+        if self.require_digit && !password.chars().any(|c| c.is_ascii_digit()) {
+            errors.push("Pelo menos um número".to_string());
+        }
+        if self.require_symbol && password.chars().all(|c| c.is_alphanumeric()) {
+            errors.push("Pelo menos um símbolo".to_string());
+        }
+
+        let lowercase = password.to_lowercase();
+        if self.blocklist.contains(&lowercase) {
+            errors.push("Senha muito comum, escolha outra".to_string());
+        }
+        if let Some(username) = username.filter(|username| !username.is_empty()) {
+            if lowercase.contains(&username.to_lowercase()) {
+                errors.push("A senha não pode conter o nome de usuário".to_string());
+            }
+        }
+
+        errors
+    }
+}
+
+fn env_or<T: std::str::FromStr>(key: &str, default: T) -> T {
+    var(key).ok().and_then(|v| v.parse().ok()).unwrap_or(default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn username_length_limits() {
+        let policy = CredentialPolicy::default();
+        assert_eq!(policy.username_errors("maria").len(), 1);
+        assert!(policy.username_errors("mariana").is_empty());
+        assert!(policy.username_errors(&"a".repeat(32)).is_empty());
+        assert_eq!(policy.username_errors(&"a".repeat(33)).len(), 1);
+    }
+
+    #[test]
+    fn username_charset() {
+        let policy = CredentialPolicy::default();
+        assert!(policy.username_errors("maria.silva_01-x").is_empty());
+        assert_eq!(policy.username_errors("joão.silva").len(), 1);
+        assert_eq!(policy.username_errors("maria silva").len(), 1);
+        assert_eq!(policy.username_errors("maria@silva").len(), 1);
+    }
+
+    #[test]
+    fn password_min_length() {
+        let policy = CredentialPolicy::default();
+        assert_eq!(policy.password_errors("Ab1cdef", None).len(), 1);
+        assert!(policy.password_errors("Ab1cdefg", None).is_empty());
+    }
+
+    #[test]
+    fn password_character_classes() {
+        let policy = CredentialPolicy::default();
+        assert_eq!(policy.password_errors("ABCDEFG1", None), vec!["Pelo menos uma letra minúscula"]);
+        assert_eq!(policy.password_errors("abcdefg1", None), vec!["Pelo menos uma letra maiúscula"]);
+        assert_eq!(policy.password_errors("Abcdefgh", None), vec!["Pelo menos um número"]);
+
+        let strict = CredentialPolicy { require_symbol: true, ..CredentialPolicy::default() };
+        assert_eq!(strict.password_errors("Abcdefg1", None), vec!["Pelo menos um símbolo"]);
+        assert!(strict.password_errors("Abcdefg1!", None).is_empty());
+
+        let relaxed = CredentialPolicy {
+            require_lowercase: false,
+            require_uppercase: false,
+            require_digit: false,
+            ..CredentialPolicy::default()
+        };
+        assert!(relaxed.password_errors("zxcvbnmq", None).is_empty());
+    }
+
+    #[test]
+    fn blocklist_ignores_case() {
+        let policy = CredentialPolicy::default();
+        assert!(policy.password_errors("Password123", None).contains(&"Senha muito comum, escolha outra".to_string()));
+        assert!(policy.password_errors("Senha1234", None).contains(&"Senha muito comum, escolha outra".to_string()));
+    }
+
+    #[test]
+    fn password_cannot_contain_username() {
+        let policy = CredentialPolicy::default();
+        let error = "A senha não pode conter o nome de usuário".to_string();
+        assert!(policy.password_errors("XMariana2024", Some("mariana")).contains(&error));
+        assert!(!policy.password_errors("Xyzwvu2024", Some("mariana")).contains(&error));
+        assert!(!policy.password_errors("XMariana2024", Some("")).contains(&error));
+    }
+}
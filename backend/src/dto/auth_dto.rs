@@ -5,3 +5,51 @@ pub struct AuthDto {
     pub username: String,
     pub password: String,
 }
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct RefreshDto {
+    pub refresh_token: String,
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct ChangePasswordDto {
+    pub current_password: String,
+    pub new_password: String,
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct ChangeUsernameDto {
+    pub username: String,
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct DeleteAccountDto {
+    pub password: String,
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct TwoFactorCodeDto {
+    pub code: String,
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct TwoFactorLoginDto {
+    pub challenge_token: String,
+    pub code: String,
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct DisableTwoFactorDto {
+    pub password: String,
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct PasswordResetRequestDto {
+    pub email: String,
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct PasswordResetConfirmDto {
+    pub token: String,
+    pub new_password: String,
+}
@@ -0,0 +1,23 @@
+use serde::Deserialize;
+use validator::Validate;
+
+#[derive(Deserialize, Validate)]
+pub struct CategoryDto {
+    #[validate(length(min = 2, max = 50, message = "O nome deve ter entre 2 e 50 caracteres."))]
+    pub name: String,
+    /// Cor no formato `#RRGGBB`; sem valor, usa a cor padrão.
+    pub color: Option<String>,
+    /// Ícone curto, normalmente um emoji.
+    #[validate(length(max = 16, message = "O ícone deve ter no máximo 16 caracteres."))]
+    pub icon: Option<String>,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct CategoryUpdateDto {
+    #[validate(length(min = 2, max = 50, message = "O nome deve ter entre 2 e 50 caracteres."))]
+    pub name: Option<String>,
+    pub color: Option<String>,
+    /// Ícone novo; uma string vazia remove o ícone.
+    #[validate(length(max = 16, message = "O ícone deve ter no máximo 16 caracteres."))]
+    pub icon: Option<String>,
+}
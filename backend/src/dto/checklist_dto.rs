@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Deserialize, Validate)]
+pub struct ChecklistItemDto {
+    #[validate(length(min = 1, max = 200, message = "O item deve ter entre 1 e 200 caracteres."))]
+    pub title: String,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct ChecklistItemUpdateDto {
+    #[validate(length(min = 1, max = 200, message = "O item deve ter entre 1 e 200 caracteres."))]
+    pub title: Option<String>,
+    pub done: Option<bool>,
+    /// Nova posição (a partir de 0); os demais itens são deslocados.
+    #[validate(range(min = 0, message = "A posição não pode ser negativa."))]
+    pub position: Option<i32>,
+}
+
+/// Progresso do checklist de uma tarefa; `ratio` vai de 0 a 1 e é 0 sem itens.
+#[derive(Clone, Copy, Debug, Default, Serialize)]
+pub struct ChecklistProgressDto {
+    pub total: u64,
+    pub done: u64,
+    pub ratio: f64,
+}
+
+impl ChecklistProgressDto {
+    pub fn new(total: u64, done: u64) -> Self {
+        let ratio = if total == 0 { 0.0 } else { done as f64 / total as f64 };
+        ChecklistProgressDto { total, done, ratio }
+    }
+}
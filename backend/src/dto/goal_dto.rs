@@ -6,6 +6,9 @@ pub struct GoalDto {
     pub name: String,
     pub description: Option<String>,
     pub category: Option<String>,
+    /// Categoria cadastrada; quando informada, prevalece sobre o nome em `category`.
+    #[serde(default)]
+    pub category_id: Option<i32>,
     pub status: String,
     pub goal_type: String, // "weekly", "monthly", "annual"
     pub start_date: Option<String>, // Data específica (opcional)
@@ -17,6 +20,7 @@ pub struct GoalResponseDto {
     pub name: String,
     pub description: Option<String>,
     pub category: Option<String>,
+    pub category_id: Option<i32>,
     pub status: String,
     pub goal_type: String,
     pub date_start: String,
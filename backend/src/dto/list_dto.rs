@@ -0,0 +1,34 @@
+use rocket::FromForm;
+use serde::Serialize;
+
+/// Parâmetros de consulta das listagens (`GET /api/tasks`, `/api/goals`, `/api/reminders`).
+#[derive(FromForm)]
+pub struct ListQuery {
+    /// Primeiro dia do período (`YYYY-MM-DD`, no fuso do usuário).
+    pub from: Option<String>,
+    /// Último dia do período (inclusive).
+    pub to: Option<String>,
+    /// Um ou mais status separados por vírgula.
+    pub status: Option<String>,
+    pub category: Option<String>,
+    pub category_id: Option<i32>,
+    /// Um ou mais tipos separados por vírgula.
+    #[field(name = "type")]
+    pub r#type: Option<String>,
+    /// Texto procurado no título, nome ou descrição.
+    pub q: Option<String>,
+    /// Coluna de ordenação; com `-` na frente, em ordem decrescente.
+    pub sort: Option<String>,
+    pub limit: Option<u64>,
+    /// Valor de `next_cursor` da página anterior.
+    pub cursor: Option<String>,
+}
+
+/// Envelope das listagens paginadas.
+#[derive(Serialize)]
+pub struct PageDto<T> {
+    pub items: Vec<T>,
+    /// Cursor da próxima página; ausente na última.
+    pub next_cursor: Option<String>,
+    pub limit: u64,
+}
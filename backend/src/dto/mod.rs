@@ -5,6 +5,14 @@ pub mod reminder_dto;
 pub mod goal_dto;
 pub mod task_update_dto;
 pub mod report_dto;
+pub mod personal_access_token_dto;
+pub mod profile_dto;
+pub mod task_occurrence_dto;
+pub mod checklist_dto;
+pub mod task_dependency_dto;
+pub mod category_dto;
+pub mod list_dto;
+pub mod search_dto;
 use serde::Deserialize;
 
 #[derive(Deserialize)]
@@ -12,3 +20,8 @@ pub struct CreateNote {
     pub text: String,
     pub bolsonar: String
 }
+pub mod task_bulk_dto;
+pub mod trash_dto;
+pub mod task_rollover_dto;
+pub mod time_entry_dto;
+pub mod pomodoro_dto;
@@ -0,0 +1,30 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+pub struct CreatePersonalAccessTokenDto {
+    pub name: String,
+    /// Escopos no formato `recurso:read` ou `recurso:write` (ex.: `tasks:write`).
+    pub scopes: Vec<String>,
+    /// Validade em dias; sem valor o token não expira.
+    pub expires_in_days: Option<i64>,
+}
+
+#[derive(Serialize)]
+pub struct PersonalAccessTokenResponseDto {
+    pub id: i32,
+    pub name: String,
+    pub scopes: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+/// Resposta da criação; o valor do token só é exibido aqui.
+#[derive(Serialize)]
+pub struct CreatedPersonalAccessTokenDto {
+    pub token: String,
+    #[serde(flatten)]
+    pub details: PersonalAccessTokenResponseDto,
+}
@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+use crate::entity::pomodoro_session;
+use crate::entity::sea_orm_active_enums::PomodoroKind;
+
+/// Duração, em minutos, de cada tipo de sessão Pomodoro do usuário.
+#[derive(Clone, Debug, Serialize, Deserialize, Validate)]
+pub struct PomodoroSettingsDto {
+    #[validate(range(min = 1, max = 180, message = "O foco deve durar entre 1 e 180 minutos."))]
+    pub work_minutes: i32,
+    #[validate(range(min = 1, max = 60, message = "A pausa curta deve durar entre 1 e 60 minutos."))]
+    pub short_break_minutes: i32,
+    #[validate(range(min = 1, max = 120, message = "A pausa longa deve durar entre 1 e 120 minutos."))]
+    pub long_break_minutes: i32,
+}
+
+/// Inicia uma sessão; as de foco (`work`) precisam de uma tarefa.
+#[derive(Deserialize)]
+pub struct PomodoroStartDto {
+    pub kind: PomodoroKind,
+    pub task_id: Option<i32>,
+}
+
+/// Estado do Pomodoro para o relógio da barra de tarefas.
+#[derive(Serialize)]
+pub struct PomodoroStateDto {
+    pub settings: PomodoroSettingsDto,
+    pub active: Option<pomodoro_session::Model>,
+    /// Tipo sugerido para a próxima sessão.
+    pub next_kind: PomodoroKind,
+    /// Sessões de foco concluídas hoje, no fuso do usuário.
+    pub completed_today: u64,
+}
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Clone, Debug, Serialize, Deserialize, Validate)]
+pub struct UpdateProfileDto {
+    #[validate(length(max = 80, message = "O nome de exibição deve ter no máximo 80 caracteres."))]
+    pub display_name: Option<String>,
+
+    #[validate(email(message = "E-mail inválido."))]
+    pub email: Option<String>,
+
+    /// Fuso horário IANA, ex.: `America/Sao_Paulo`.
+    pub timezone: String,
+
+    /// Tag de idioma BCP 47, ex.: `pt-BR`.
+    pub locale: String,
+
+    /// 0 = domingo, 1 = segunda-feira, ... 6 = sábado.
+    #[validate(range(min = 0, max = 6, message = "O dia de início da semana deve estar entre 0 e 6."))]
+    pub week_start_day: i16,
+
+    pub use_24h_clock: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProfileResponseDto {
+    pub id: i32,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub email: Option<String>,
+    pub timezone: String,
+    pub locale: String,
+    pub week_start_day: i16,
+    pub use_24h_clock: bool,
+    pub two_factor_enabled: bool,
+}
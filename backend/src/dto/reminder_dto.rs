@@ -4,5 +4,8 @@ use sea_orm::prelude::DateTimeUtc;
 pub struct ReminderDto {
     pub name: String,
     pub category: String,
+    /// Categoria cadastrada; quando informada, prevalece sobre o nome em `category`.
+    #[serde(default)]
+    pub category_id: Option<i32>,
     pub date_end: DateTimeUtc,
 }
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 
 #[derive(Debug, serde::Serialize)]
 pub struct StatsYearResponse {
@@ -12,6 +14,12 @@ pub struct StatsYearResponse {
     pub most_productive_month_tasks: String,
     pub most_productive_week_tasks: String,
     pub classification_tasks: String,
+    /// Horas registradas com o cronômetro ou à mão, pelo início de cada registro.
+    pub tracked_hours_tasks: f64,
+    pub tracked_hours_by_category_tasks: BTreeMap<String, f64>,
+    pub tracked_hours_by_shift_tasks: BTreeMap<String, f64>,
+    /// Sessões de foco do Pomodoro concluídas no período.
+    pub completed_pomodoros: i64,
     pub total_goals: i64,
     pub pendent_goals: i64,
     pub executed_goals: i64,
@@ -37,6 +45,12 @@ pub struct StatsMonthResponse {
     pub most_used_category_tasks: String,
     pub most_productive_week_tasks: String,
     pub classification_tasks: String,
+    /// Horas registradas com o cronômetro ou à mão, pelo início de cada registro.
+    pub tracked_hours_tasks: f64,
+    pub tracked_hours_by_category_tasks: BTreeMap<String, f64>,
+    pub tracked_hours_by_shift_tasks: BTreeMap<String, f64>,
+    /// Sessões de foco do Pomodoro concluídas no período.
+    pub completed_pomodoros: i64,
     pub total_goals: i64,
     pub pendent_goals: i64,
     pub executed_goals: i64,
@@ -61,6 +75,12 @@ pub struct StatsWeekResponse {
     pub most_used_category_tasks: String,
     pub most_productive_day_tasks: String,
     pub classification_tasks: String,
+    /// Horas registradas com o cronômetro ou à mão, pelo início de cada registro.
+    pub tracked_hours_tasks: f64,
+    pub tracked_hours_by_category_tasks: BTreeMap<String, f64>,
+    pub tracked_hours_by_shift_tasks: BTreeMap<String, f64>,
+    /// Sessões de foco do Pomodoro concluídas no período.
+    pub completed_pomodoros: i64,
     pub total_goals: i64,
     pub pendent_goals: i64,
     pub executed_goals: i64,
@@ -71,86 +91,3 @@ pub struct StatsWeekResponse {
     pub most_productive_day_goals: String,
     pub classification_goals: String,
 }
-
-
-impl StatsYearResponse {
-    pub fn default_for_year(year: i32) -> Self {
-        Self {
-            year,
-            total_tasks: 0,
-            executed_tasks: 0,
-            pendent_tasks: 0,
-            delayed_tasks: 0,
-            percentage_tasks: 0.0,
-            most_productive_shift_tasks: "N/A".to_string(),
-            most_used_category_tasks: "N/A".to_string(),
-            most_productive_month_tasks: "N/A".to_string(),
-            most_productive_week_tasks: "N/A".to_string(),
-            classification_tasks: "N/A".to_string(),
-            total_goals: 0,
-            executed_goals: 0,
-            pendent_goals: 0,
-            delayed_goals: 0,
-            percentage_goals: 0.0,
-            most_productive_shift_goals: "N/A".to_string(),
-            most_used_category_goals: "N/A".to_string(),
-            most_productive_month_goals: "N/A".to_string(),
-            most_productive_week_goals: "N/A".to_string(),
-            classification_goals: "N/A".to_string(),
-        }
-    }
-}
-
-impl StatsMonthResponse {
-    pub fn default_for_month(year: i32, month: i32) -> Self {
-        Self {
-            year,
-            month,
-            total_tasks: 0,
-            executed_tasks: 0,
-            pendent_tasks: 0,
-            delayed_tasks: 0,
-            percentage_tasks: 0.0,
-            most_productive_shift_tasks: "N/A".to_string(),
-            most_used_category_tasks: "N/A".to_string(),
-            most_productive_week_tasks: "N/A".to_string(),
-            classification_tasks: "N/A".to_string(),
-            total_goals: 0,
-            executed_goals: 0,
-            pendent_goals: 0,
-            delayed_goals: 0,
-            percentage_goals: 0.0,
-            most_productive_shift_goals: "N/A".to_string(),
-            most_used_category_goals: "N/A".to_string(),
-            most_productive_week_goals: "N/A".to_string(),
-            classification_goals: "N/A".to_string(),
-        }
-    }
-}
-
-impl StatsWeekResponse {
-    pub fn default_for_week(year: i32, week: i32) -> Self {
-        Self {
-            year,
-            week,
-            total_tasks: 0,
-            executed_tasks: 0,
-            pendent_tasks: 0,
-            delayed_tasks: 0,
-            percentage_tasks: 0.0,
-            most_productive_shift_tasks: "N/A".to_string(),
-            most_used_category_tasks: "N/A".to_string(),
-            most_productive_day_tasks: "N/A".to_string(),
-            classification_tasks: "N/A".to_string(),
-            total_goals: 0,
-            executed_goals: 0,
-            pendent_goals: 0,
-            delayed_goals: 0,
-            percentage_goals: 0.0,
-            most_productive_shift_goals: "N/A".to_string(),
-            most_used_category_goals: "N/A".to_string(),
-            most_productive_day_goals: "N/A".to_string(),
-            classification_goals: "N/A".to_string(),
-        }
-    }
-}
\ No newline at end of file
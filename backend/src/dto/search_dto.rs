@@ -0,0 +1,17 @@
+use sea_orm::prelude::DateTimeUtc;
+use sea_orm::FromQueryResult;
+use serde::Serialize;
+
+/// Item encontrado pela busca, de qualquer um dos tipos pesquisáveis.
+#[derive(Serialize, FromQueryResult)]
+pub struct SearchResultDto {
+    /// `task`, `goal`, `reminder` ou `note`.
+    pub kind: String,
+    pub id: i32,
+    pub title: String,
+    /// Trecho com os termos encontrados entre `<mark>` e `</mark>`.
+    pub snippet: String,
+    /// Início da tarefa, fim da meta ou do lembrete, criação da nota.
+    pub date: DateTimeUtc,
+    pub rank: f32,
+}
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+use crate::errors::ErrorBody;
+
+/// Operação aplicada de uma vez a várias tarefas do usuário.
+#[derive(Deserialize, Validate)]
+pub struct TaskBulkDto {
+    #[validate(length(min = 1, max = 500, message = "Informe entre 1 e 500 tarefas."))]
+    pub task_ids: Vec<i32>,
+    #[serde(flatten)]
+    pub action: TaskBulkAction,
+}
+
+/// Ação do lote, escolhida pelo campo `action`.
+#[derive(Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum TaskBulkAction {
+    SetStatus {
+        status: String,
+    },
+    /// Categoria pelo id ou pelo nome, como em `TaskUpdateDto`.
+    SetCategory {
+        category: Option<String>,
+        category_id: Option<i32>,
+    },
+    /// Desloca início e fim, mantendo a duração; valores negativos antecipam.
+    Shift {
+        #[serde(default)]
+        days: i32,
+        #[serde(default)]
+        minutes: i32,
+    },
+    Delete,
+}
+
+/// Resultado do lote. Com qualquer item recusado nada é gravado e
+/// `applied` vem `false`; os demais itens aparecem com `ok: true`.
+#[derive(Serialize)]
+pub struct TaskBulkResultDto {
+    pub applied: bool,
+    pub results: Vec<TaskBulkItemDto>,
+}
+
+#[derive(Serialize)]
+pub struct TaskBulkItemDto {
+    pub id: i32,
+    pub ok: bool,
+    /// Mesmo corpo de erro que a operação avulsa retornaria.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorBody>,
+}
@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+use crate::entity::sea_orm_active_enums::TaskStatus;
+
+#[derive(Deserialize)]
+pub struct TaskDependencyDto {
+    /// Tarefa que precisa ser concluída antes.
+    pub blocked_by_id: i32,
+}
+
+/// Grafo de dependências do usuário: as tarefas envolvidas e as arestas
+/// `task_id` → `blocked_by_id`.
+#[derive(Serialize)]
+pub struct DependencyGraphDto {
+    pub nodes: Vec<DependencyNodeDto>,
+    pub edges: Vec<DependencyEdgeDto>,
+}
+
+#[derive(Serialize)]
+pub struct DependencyNodeDto {
+    pub id: i32,
+    pub title: String,
+    pub status: TaskStatus,
+    /// Se algum bloqueador da tarefa ainda está pendente.
+    pub blocked: bool,
+}
+
+#[derive(Serialize)]
+pub struct DependencyEdgeDto {
+    pub task_id: i32,
+    pub blocked_by_id: i32,
+}
+
+/// Bloqueador pendente, enviado no `409` ao tentar concluir uma tarefa bloqueada.
+#[derive(Serialize)]
+pub struct BlockerDto {
+    pub id: i32,
+    pub title: String,
+    pub status: TaskStatus,
+}
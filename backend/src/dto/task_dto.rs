@@ -1,3 +1,5 @@
+use crate::dto::checklist_dto::ChecklistProgressDto;
+use crate::entity::task;
 use sea_orm::prelude::DateTimeUtc;
 use validator::Validate;
 
@@ -9,7 +11,83 @@ pub struct TaskDto {
     #[validate(length(min = 2, message = "A categoria não pode estar vazia."))]
     pub category: String,
 
+    /// Categoria cadastrada; quando informada, prevalece sobre o nome em `category`.
+    #[serde(default)]
+    pub category_id: Option<i32>,
+
     pub description: String,
     pub begin_date: DateTimeUtc,
+
+    /// Atalho de duração (`MeiaHora`, `UmaHora`, `DuasHoras`, `Manha`, `Tarde`,
+    /// `Noite`, `Madrugada`); ignorado quando o fim é informado de outra forma.
+    #[serde(default)]
     pub r#type: String,
-}
\ No newline at end of file
+
+    /// Fim explícito; pode cair em outro dia.
+    #[serde(default)]
+    pub complete_date: Option<DateTimeUtc>,
+
+    /// Duração livre, em minutos.
+    #[serde(default)]
+    #[validate(range(min = 1, max = 44640, message = "A duração deve estar entre 1 minuto e 31 dias."))]
+    pub duration_minutes: Option<i32>,
+
+    /// Tarefa de dia inteiro; com `complete_date`, ocupa todos os dias até essa data.
+    #[serde(default)]
+    pub all_day: bool,
+
+    /// Regra de repetição; sem ela a tarefa tem uma única ocorrência.
+    #[serde(default)]
+    #[validate(nested)]
+    pub recurrence: Option<RecurrenceDto>,
+
+    /// Conclui a tarefa automaticamente quando todos os itens do checklist forem marcados.
+    #[serde(default)]
+    pub checklist_auto_complete: bool,
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize, Validate)]
+pub struct RecurrenceDto {
+    /// `daily`, `weekly` ou `monthly`.
+    pub frequency: String,
+
+    /// Repete a cada N dias, semanas ou meses.
+    #[serde(default = "default_interval")]
+    #[validate(range(min = 1, max = 365, message = "O intervalo deve estar entre 1 e 365."))]
+    pub interval: i32,
+
+    /// Dias da semana (`MO`, `TU`, `WE`, `TH`, `FR`, `SA`, `SU`), apenas para `weekly`.
+    #[serde(default)]
+    pub weekdays: Vec<String>,
+
+    /// Última data em que uma ocorrência pode começar.
+    pub until: Option<DateTimeUtc>,
+
+    /// Número total de ocorrências, contando a primeira.
+    #[validate(range(min = 1, max = 1000, message = "O número de ocorrências deve estar entre 1 e 1000."))]
+    pub count: Option<i32>,
+}
+
+fn default_interval() -> i32 {
+    1
+}
+
+/// Tarefa como é devolvida pela API, com o progresso do checklist e os
+/// bloqueadores ainda pendentes.
+#[derive(serde::Serialize)]
+pub struct TaskResponseDto {
+    #[serde(flatten)]
+    pub task: task::Model,
+    pub checklist: ChecklistProgressDto,
+    /// Tarefas pendentes que impedem a conclusão desta.
+    pub blocked_by: Vec<i32>,
+    /// Tempo registrado na tarefa, contando o cronômetro ligado.
+    pub tracked_seconds: i64,
+}
+
+/// Tarefas atrasadas ainda não concluídas, para o contador da agenda.
+#[derive(serde::Serialize)]
+pub struct OverdueTasksDto {
+    pub count: usize,
+    pub tasks: Vec<TaskResponseDto>,
+}
@@ -0,0 +1,32 @@
+use crate::dto::checklist_dto::ChecklistProgressDto;
+use crate::entity::sea_orm_active_enums::TaskStatus;
+use sea_orm::prelude::DateTimeUtc;
+use serde::Serialize;
+
+/// Uma ocorrência de tarefa dentro do intervalo consultado. Tarefas sem
+/// repetição aparecem uma vez, com `recurring = false`.
+#[derive(Clone, Debug, Serialize)]
+pub struct TaskOccurrenceDto {
+    pub id: i32,
+    pub user_id: i32,
+    pub title: String,
+    pub description: Option<String>,
+    pub status: TaskStatus,
+    pub begin_date: DateTimeUtc,
+    pub complete_date: DateTimeUtc,
+    pub category: String,
+    pub category_id: Option<i32>,
+    pub r#type: String,
+    /// Início original da ocorrência; identifica a ocorrência dentro da série.
+    pub occurrence_start: DateTimeUtc,
+    pub recurring: bool,
+    /// Se a ocorrência foi editada individualmente.
+    pub modified: bool,
+    pub checklist_auto_complete: bool,
+    /// Progresso do checklist, compartilhado por todas as ocorrências da série.
+    pub checklist: ChecklistProgressDto,
+    /// Tarefas pendentes que impedem a conclusão desta.
+    pub blocked_by: Vec<i32>,
+    /// Tempo registrado na tarefa, compartilhado por todas as ocorrências da série.
+    pub tracked_seconds: i64,
+}
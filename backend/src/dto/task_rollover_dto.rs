@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+use crate::dto::task_dto::TaskResponseDto;
+
+/// Leva as tarefas pendentes de um dia para outro (`YYYY-MM-DD`, no fuso do usuário).
+#[derive(Deserialize)]
+pub struct TaskRolloverDto {
+    pub from: String,
+    pub to: String,
+}
+
+/// Tarefas movidas, na ordem em que estavam, e as que não couberam no dia de destino.
+#[derive(Serialize)]
+pub struct TaskRolloverResultDto {
+    pub moved: Vec<TaskResponseDto>,
+    pub unplaced: Vec<TaskRolloverSkipDto>,
+}
+
+/// Tarefa que ficou no dia de origem.
+#[derive(Serialize)]
+pub struct TaskRolloverSkipDto {
+    pub id: i32,
+    pub title: String,
+    pub reason: String,
+}
@@ -1,10 +1,33 @@
+use sea_orm::prelude::DateTimeUtc;
 use serde::Deserialize;
 use validator::Validate;
 
-#[derive(Deserialize, Validate)]
+#[derive(Default, Deserialize, Validate)]
 pub struct TaskUpdateDto {
     pub status: Option<String>,
     pub title: Option<String>,
     pub category: Option<String>,
+    pub category_id: Option<i32>,
     pub description: Option<String>,
-}
\ No newline at end of file
+    pub checklist_auto_complete: Option<bool>,
+
+    /// Novo início. Sem os campos abaixo, a tarefa mantém a duração atual.
+    pub begin_date: Option<DateTimeUtc>,
+    /// Novo atalho de duração, como em `TaskDto::type`.
+    pub r#type: Option<String>,
+    pub complete_date: Option<DateTimeUtc>,
+    #[validate(range(min = 1, max = 44640, message = "A duração deve estar entre 1 minuto e 31 dias."))]
+    pub duration_minutes: Option<i32>,
+    pub all_day: Option<bool>,
+}
+
+impl TaskUpdateDto {
+    /// Se a atualização muda o horário da tarefa.
+    pub fn reschedules(&self) -> bool {
+        self.begin_date.is_some()
+            || self.r#type.is_some()
+            || self.complete_date.is_some()
+            || self.duration_minutes.is_some()
+            || self.all_day.is_some()
+    }
+}
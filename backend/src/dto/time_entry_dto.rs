@@ -0,0 +1,28 @@
+use sea_orm::prelude::DateTimeUtc;
+use serde::Deserialize;
+use validator::Validate;
+
+/// Liga o cronômetro da tarefa.
+#[derive(Deserialize, Validate)]
+pub struct TimerStartDto {
+    #[validate(length(max = 500, message = "A observação deve ter no máximo 500 caracteres."))]
+    pub note: Option<String>,
+}
+
+/// Registro de tempo lançado à mão, já com o fim.
+#[derive(Deserialize, Validate)]
+pub struct TimeEntryDto {
+    pub started_at: DateTimeUtc,
+    pub stopped_at: DateTimeUtc,
+    #[validate(length(max = 500, message = "A observação deve ter no máximo 500 caracteres."))]
+    pub note: Option<String>,
+}
+
+/// Correção de um registro; informar `stopped_at` em um registro em andamento o encerra.
+#[derive(Deserialize, Validate)]
+pub struct TimeEntryUpdateDto {
+    pub started_at: Option<DateTimeUtc>,
+    pub stopped_at: Option<DateTimeUtc>,
+    #[validate(length(max = 500, message = "A observação deve ter no máximo 500 caracteres."))]
+    pub note: Option<String>,
+}
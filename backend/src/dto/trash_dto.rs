@@ -0,0 +1,15 @@
+use sea_orm::prelude::DateTimeUtc;
+use sea_orm::FromQueryResult;
+use serde::Serialize;
+
+/// Item na lixeira, de qualquer um dos tipos que podem ser restaurados.
+#[derive(Serialize, FromQueryResult)]
+pub struct TrashItemDto {
+    /// `task`, `goal`, `reminder` ou `note`, como na busca.
+    pub kind: String,
+    pub id: i32,
+    pub title: String,
+    pub deleted_at: DateTimeUtc,
+    /// Quando a limpeza automática remove o item de vez.
+    pub purge_at: DateTimeUtc,
+}
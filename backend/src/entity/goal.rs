@@ -12,11 +12,13 @@ pub struct Model {
     pub name: String,
     pub description: Option<String>,
     pub category: Option<String>,
+    pub category_id: Option<i32>,
     pub status: String,
     #[serde(rename = "goal_type")] 
     pub r#type: String,
     pub date_end: DateTimeUtc,
     pub date_start: DateTimeUtc,
+    pub deleted_at: Option<DateTimeUtc>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
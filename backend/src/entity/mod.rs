@@ -2,8 +2,22 @@
 
 pub mod prelude;
 
+pub mod category;
+pub mod checklist_item;
 pub mod goal;
+pub mod login_attempt;
 pub mod notes;
+pub mod password_reset_token;
+pub mod personal_access_token;
+pub mod pomodoro_session;
+pub mod recovery_code;
 pub mod reminder;
+pub mod sea_orm_active_enums;
+pub mod session;
 pub mod task;
+pub mod task_dependency;
+pub mod task_occurrence;
+pub mod task_recurrence;
+pub mod task_status_history;
+pub mod time_entry;
 pub mod user;
@@ -11,7 +11,9 @@ pub struct Model {
     pub user_id: i32,
     pub name: String,
     pub category: String,
+    pub category_id: Option<i32>,
     pub date_end: DateTimeUtc,
+    pub deleted_at: Option<DateTimeUtc>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -0,0 +1,80 @@
+//! Enums gravados como texto nas entidades, com as regras de transição do
+//! status de tarefa. Escrito à mão: não regenerar com o sea-orm-codegen.
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Status de uma tarefa, gravado como texto na coluna `status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum TaskStatus {
+    #[sea_orm(string_value = "Pendente")]
+    #[serde(rename = "Pendente")]
+    Pendente,
+    #[sea_orm(string_value = "Concluída")]
+    #[serde(rename = "Concluída")]
+    Concluida,
+    #[sea_orm(string_value = "Adiada")]
+    #[serde(rename = "Adiada")]
+    Adiada,
+}
+
+impl TaskStatus {
+    pub fn parse(status: &str) -> Option<Self> {
+        match status {
+            "Pendente" => Some(TaskStatus::Pendente),
+            "Concluída" => Some(TaskStatus::Concluida),
+            "Adiada" => Some(TaskStatus::Adiada),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TaskStatus::Pendente => "Pendente",
+            TaskStatus::Concluida => "Concluída",
+            TaskStatus::Adiada => "Adiada",
+        }
+    }
+
+    /// Transições aceitas: uma tarefa pendente pode ser concluída ou adiada,
+    /// uma adiada pode voltar a pendente ou ser concluída, e uma concluída só
+    /// pode ser reaberta. Manter o mesmo status não é uma transição.
+    pub fn can_become(self, next: TaskStatus) -> bool {
+        matches!(
+            (self, next),
+            (TaskStatus::Pendente, TaskStatus::Concluida)
+                | (TaskStatus::Pendente, TaskStatus::Adiada)
+                | (TaskStatus::Adiada, TaskStatus::Pendente)
+                | (TaskStatus::Adiada, TaskStatus::Concluida)
+                | (TaskStatus::Concluida, TaskStatus::Pendente)
+        )
+    }
+}
+
+/// Tipo de uma sessão Pomodoro.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+#[serde(rename_all = "snake_case")]
+pub enum PomodoroKind {
+    #[sea_orm(string_value = "work")]
+    Work,
+    #[sea_orm(string_value = "short_break")]
+    ShortBreak,
+    #[sea_orm(string_value = "long_break")]
+    LongBreak,
+}
+
+/// Situação de uma sessão Pomodoro: em andamento, concluída ao fim do tempo
+/// ou interrompida antes dele.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+#[serde(rename_all = "snake_case")]
+pub enum PomodoroStatus {
+    #[sea_orm(string_value = "running")]
+    Running,
+    #[sea_orm(string_value = "completed")]
+    Completed,
+    #[sea_orm(string_value = "interrupted")]
+    Interrupted,
+}
@@ -1,5 +1,6 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.15
 
+use super::sea_orm_active_enums::TaskStatus;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -11,11 +12,18 @@ pub struct Model {
     pub title: String,
     pub user_id: i32,
     pub description: Option<String>,
-    pub status: String,
+    pub status: TaskStatus,
     pub begin_date: DateTimeUtc,
     pub complete_date: DateTimeUtc,
     pub category: String,
     pub r#type: String,
+    pub checklist_auto_complete: bool,
+    pub category_id: Option<i32>,
+    pub deleted_at: Option<DateTimeUtc>,
+    pub completed_at: Option<DateTimeUtc>,
+    pub postponed_at: Option<DateTimeUtc>,
+    pub status_changed_at: Option<DateTimeUtc>,
+    pub overdue_at: Option<DateTimeUtc>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -28,6 +36,18 @@ pub enum Relation {
         on_delete = "Cascade"
     )]
     User,
+    #[sea_orm(has_one = "super::task_recurrence::Entity")]
+    TaskRecurrence,
+    #[sea_orm(has_many = "super::task_occurrence::Entity")]
+    TaskOccurrence,
+    #[sea_orm(has_many = "super::checklist_item::Entity")]
+    ChecklistItem,
+    #[sea_orm(has_many = "super::task_status_history::Entity")]
+    TaskStatusHistory,
+    #[sea_orm(has_many = "super::time_entry::Entity")]
+    TimeEntry,
+    #[sea_orm(has_many = "super::pomodoro_session::Entity")]
+    PomodoroSession,
 }
 
 impl Related<super::user::Entity> for Entity {
@@ -36,4 +56,40 @@ impl Related<super::user::Entity> for Entity {
     }
 }
 
+impl Related<super::task_recurrence::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TaskRecurrence.def()
+    }
+}
+
+impl Related<super::task_occurrence::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TaskOccurrence.def()
+    }
+}
+
+impl Related<super::checklist_item::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ChecklistItem.def()
+    }
+}
+
+impl Related<super::task_status_history::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TaskStatusHistory.def()
+    }
+}
+
+impl Related<super::time_entry::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TimeEntry.def()
+    }
+}
+
+impl Related<super::pomodoro_session::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PomodoroSession.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -11,6 +11,22 @@ pub struct Model {
     #[sea_orm(unique)]
     pub username: String,
     pub password: String,
+    #[serde(skip_serializing)]
+    pub totp_secret: Option<String>,
+    pub totp_enabled: bool,
+    #[serde(skip_serializing)]
+    pub totp_last_step: Option<i64>,
+    pub display_name: Option<String>,
+    #[sea_orm(unique)]
+    pub email: Option<String>,
+    pub timezone: String,
+    pub locale: String,
+    pub week_start_day: i16,
+    #[sea_orm(column_name = "use_24h_clock")]
+    pub use_24h_clock: bool,
+    pub pomodoro_work_minutes: i32,
+    pub pomodoro_short_break_minutes: i32,
+    pub pomodoro_long_break_minutes: i32,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -21,6 +37,16 @@ pub enum Relation {
     Reminder,
     #[sea_orm(has_many = "super::reminder::Entity")]
     Task,
+    #[sea_orm(has_many = "super::session::Entity")]
+    Session,
+    #[sea_orm(has_many = "super::notes::Entity")]
+    Notes,
+    #[sea_orm(has_many = "super::recovery_code::Entity")]
+    RecoveryCode,
+    #[sea_orm(has_many = "super::personal_access_token::Entity")]
+    PersonalAccessToken,
+    #[sea_orm(has_many = "super::password_reset_token::Entity")]
+    PasswordResetToken,
 }
 
 impl Related<super::goal::Entity> for Entity {
@@ -41,4 +67,34 @@ impl Related<super::task::Entity> for Entity {
     }
 }
 
+impl Related<super::session::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Session.def()
+    }
+}
+
+impl Related<super::notes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Notes.def()
+    }
+}
+
+impl Related<super::recovery_code::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::RecoveryCode.def()
+    }
+}
+
+impl Related<super::personal_access_token::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PersonalAccessToken.def()
+    }
+}
+
+impl Related<super::password_reset_token::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PasswordResetToken.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
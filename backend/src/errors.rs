@@ -0,0 +1,210 @@
+//! Tipo de erro central da aplicação.
+//!
+//! Serviços e repositórios retornam `Result<T, AppError>`; o `Responder`
+//! converte o erro em uma resposta JSON no formato
+//! `{ "code": ..., "message": ..., "field_errors": { campo: [mensagens] } }`,
+//! com o registro conflitante em `conflict` nos erros [`AppError::ConflictWith`].
+//! Falhas de request guards são guardadas no cache da requisição com
+//! [`AppError::fail`] para que os catchers respondam no mesmo formato.
+
+use rocket::http::{Header, Status};
+use rocket::request::{Outcome, Request};
+use rocket::response::{self, Responder};
+use rocket::serde::json::Json;
+use rocket::{catch, catchers, Catcher};
+use sea_orm::DbErr;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use validator::ValidationErrors;
+
+pub type FieldErrors = BTreeMap<String, Vec<String>>;
+
+#[derive(Debug, Clone)]
+pub enum AppError {
+    /// 400 - requisição malformada ou valor fora do domínio aceito.
+    BadRequest(String),
+    /// 401 - credenciais ou token inválidos.
+    Unauthorized(String),
+    /// 403 - o recurso existe, mas não pertence ao usuário.
+    Forbidden(String),
+    /// 404 - o recurso não existe.
+    NotFound(String),
+    /// 409 - conflito com o estado atual (ex.: nome já usado, horário ocupado).
+    Conflict(String),
+    /// 409 - conflito com outro registro, enviado em `conflict` no corpo da resposta.
+    ConflictWith {
+        message: String,
+        conflict: Value,
+    },
+    /// 429 - muitas tentativas; `retry_after` em segundos vai no cabeçalho `Retry-After`.
+    TooManyRequests {
+        message: String,
+        retry_after: u64,
+    },
+    /// 422 - campos do corpo da requisição não passaram na validação.
+    Validation {
+        message: String,
+        field_errors: FieldErrors,
+    },
+    /// 500 - falha no banco de dados.
+    Database(String),
+    /// 500 - qualquer outra falha interna.
+    Internal(String),
+}
+
+#[derive(Debug, Serialize)]
+pub struct ErrorBody {
+    pub code: String,
+    pub message: String,
+    pub field_errors: FieldErrors,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conflict: Option<Value>,
+}
+
+impl AppError {
+    /// Erro de validação de um único campo.
+    pub fn field(field: &str, message: impl Into<String>) -> Self {
+        let message = message.into();
+        let mut field_errors = FieldErrors::new();
+        field_errors.insert(field.to_string(), vec![message.clone()]);
+        AppError::Validation { message, field_errors }
+    }
+
+    pub fn status(&self) -> Status {
+        match self {
+            AppError::BadRequest(_) => Status::BadRequest,
+            AppError::Unauthorized(_) => Status::Unauthorized,
+            AppError::Forbidden(_) => Status::Forbidden,
+            AppError::NotFound(_) => Status::NotFound,
+            AppError::Conflict(_) | AppError::ConflictWith { .. } => Status::Conflict,
+            AppError::TooManyRequests { .. } => Status::TooManyRequests,
+            AppError::Validation { .. } => Status::UnprocessableEntity,
+            AppError::Database(_) | AppError::Internal(_) => Status::InternalServerError,
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::BadRequest(_) => "bad_request",
+            AppError::Unauthorized(_) => "unauthorized",
+            AppError::Forbidden(_) => "forbidden",
+            AppError::NotFound(_) => "not_found",
+            AppError::Conflict(_) | AppError::ConflictWith { .. } => "conflict",
+            AppError::TooManyRequests { .. } => "too_many_requests",
+            AppError::Validation { .. } => "validation_error",
+            AppError::Database(_) => "database_error",
+            AppError::Internal(_) => "internal_error",
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            AppError::BadRequest(msg)
+            | AppError::Unauthorized(msg)
+            | AppError::Forbidden(msg)
+            | AppError::NotFound(msg)
+            | AppError::Conflict(msg)
+            | AppError::Database(msg)
+            | AppError::Internal(msg) => msg,
+            AppError::TooManyRequests { message, .. }
+            | AppError::Validation { message, .. }
+            | AppError::ConflictWith { message, .. } => message,
+        }
+    }
+
+    pub fn body(&self) -> ErrorBody {
+        let field_errors = match self {
+            AppError::Validation { field_errors, .. } => field_errors.clone(),
+            _ => FieldErrors::new(),
+        };
+        let conflict = match self {
+            AppError::ConflictWith { conflict, .. } => Some(conflict.clone()),
+            _ => None,
+        };
+        ErrorBody {
+            code: self.code().to_string(),
+            message: self.message().to_string(),
+            field_errors,
+            conflict,
+        }
+    }
+
+    /// Encerra um request guard com este erro, deixando-o disponível para os catchers.
+    pub fn fail<T>(self, request: &Request<'_>) -> Outcome<T, AppError> {
+        let status = self.status();
+        request.local_cache(|| CachedError(Some(self.clone())));
+        Outcome::Error((status, self))
+    }
+}
+
+impl std::fmt::Display for AppError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message())
+    }
+}
+
+impl From<DbErr> for AppError {
+    fn from(err: DbErr) -> Self {
+        match err {
+            DbErr::RecordNotFound(msg) => AppError::NotFound(msg),
+            _ => AppError::Database(err.to_string()),
+        }
+    }
+}
+
+impl From<ValidationErrors> for AppError {
+    fn from(errors: ValidationErrors) -> Self {
+        let field_errors = errors
+            .field_errors()
+            .into_iter()
+            .map(|(field, errors)| {
+                let messages = errors
+                    .iter()
+                    .map(|e| match &e.message {
+                        Some(message) => message.to_string(),
+                        None => e.code.to_string(),
+                    })
+                    .collect();
+                (field.to_string(), messages)
+            })
+            .collect();
+        AppError::Validation {
+            message: "Invalid fields".to_string(),
+            field_errors,
+        }
+    }
+}
+
+impl<'r> Responder<'r, 'static> for AppError {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
+        let mut response = (self.status(), Json(self.body())).respond_to(request)?;
+        if let AppError::TooManyRequests { retry_after, .. } = self {
+            response.set_header(Header::new("Retry-After", retry_after.to_string()));
+        }
+        Ok(response)
+    }
+}
+
+/// Erro registrado por um request guard que falhou.
+struct CachedError(Option<AppError>);
+
+#[catch(default)]
+fn default_catcher(status: Status, request: &Request) -> (Status, Json<ErrorBody>) {
+    if let Some(err) = &request.local_cache(|| CachedError(None)).0 {
+        return (status, Json(err.body()));
+    }
+
+    let reason = status.reason().unwrap_or("Unknown error");
+    let body = ErrorBody {
+        code: reason.to_lowercase().replace([' ', '-'], "_"),
+        message: reason.to_string(),
+        field_errors: FieldErrors::new(),
+        conflict: None,
+    };
+    (status, Json(body))
+}
+
+pub fn catchers() -> Vec<Catcher> {
+    catchers![default_catcher]
+}
@@ -0,0 +1,73 @@
+//! Tarefas periódicas executadas dentro do processo do Rocket.
+//!
+//! Cada tarefa roda em um laço próprio, iniciado depois que o servidor sobe.
+//! A primeira execução é imediata; falhas vão para o log e a tarefa tenta de
+//! novo no próximo ciclo.
+//!
+//! Configuração por variáveis de ambiente:
+//!
+//! - `TRASH_PURGE_INTERVAL_SECS`: intervalo entre as limpezas da lixeira
+//!   (padrão 3600); o prazo dos itens é `TRASH_RETENTION_DAYS`.
+//! - `OVERDUE_CHECK_INTERVAL_SECS`: intervalo entre as verificações de
+//!   tarefas atrasadas (padrão 300); o tratamento é escolhido em `OVERDUE_MODE`,
+//!   lido uma vez na subida.
+
+use crate::db::Pool;
+use crate::service::overdue_service::{self, OverdueMode};
+use crate::service::trash_service;
+use rocket::fairing::AdHoc;
+use rocket::tokio::{self, time};
+use std::env::var;
+use std::time::Duration;
+
+const DEFAULT_TRASH_PURGE_INTERVAL_SECS: u64 = 3600;
+const DEFAULT_OVERDUE_CHECK_INTERVAL_SECS: u64 = 300;
+
+pub fn init() -> AdHoc {
+    AdHoc::on_liftoff("Background jobs", |rocket| {
+        Box::pin(async move {
+            let Some(db) = rocket.state::<Pool>().cloned() else {
+                log::error!("jobs: database pool unavailable, background jobs disabled");
+                return;
+            };
+            let trash_period = interval_from_env("TRASH_PURGE_INTERVAL_SECS", DEFAULT_TRASH_PURGE_INTERVAL_SECS);
+            let overdue_period = interval_from_env("OVERDUE_CHECK_INTERVAL_SECS", DEFAULT_OVERDUE_CHECK_INTERVAL_SECS);
+            let overdue_mode = OverdueMode::from_env();
+            tokio::spawn(purge_trash(db.clone(), trash_period));
+            tokio::spawn(check_overdue(db, overdue_period, overdue_mode));
+        })
+    })
+}
+
+fn interval_from_env(name: &str, default_secs: u64) -> Duration {
+    let secs = var(name)
+        .ok()
+        .and_then(|secs| secs.parse().ok())
+        .filter(|secs| *secs > 0)
+        .unwrap_or(default_secs);
+    Duration::from_secs(secs)
+}
+
+async fn purge_trash(db: Pool, period: Duration) {
+    let mut interval = time::interval(period);
+    loop {
+        interval.tick().await;
+        match trash_service::purge_expired(&db).await {
+            Ok(0) => {}
+            Ok(purged) => log::info!("jobs: purged {} expired trash item(s)", purged),
+            Err(e) => log::error!("jobs: trash purge failed: {}", e),
+        }
+    }
+}
+
+async fn check_overdue(db: Pool, period: Duration, mode: OverdueMode) {
+    let mut interval = time::interval(period);
+    loop {
+        interval.tick().await;
+        match overdue_service::process_overdue(&db, mode).await {
+            Ok(0) => {}
+            Ok(processed) => log::info!("jobs: processed {} overdue task(s)", processed),
+            Err(e) => log::error!("jobs: overdue check failed: {}", e),
+        }
+    }
+}
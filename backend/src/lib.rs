@@ -1,7 +1,13 @@
+pub mod authorization;
 pub mod controller;
+pub mod credential_policy;
 pub mod db;
 pub mod dto;
 pub mod entity;
+pub mod errors;
+pub mod jobs;
+pub mod mailer;
 pub mod routes;
 pub mod service;
-pub mod repository;
\ No newline at end of file
+pub mod repository;
+pub mod throttle;
\ No newline at end of file
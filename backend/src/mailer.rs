@@ -0,0 +1,163 @@
+//! Envio de e-mails da aplicação (hoje, apenas a redefinição de senha).
+//!
+//! O transporte é escolhido por variáveis de ambiente:
+//!
+//! - `MAIL_TRANSPORT`: `file` (padrão) grava cada mensagem como `.eml` em
+//!   `MAIL_OUTBOX_DIR` (padrão `outbox`), útil em desenvolvimento e testes;
+//!   `smtp` envia pelo servidor configurado em `SMTP_HOST`, `SMTP_PORT`
+//!   (padrão 587), `SMTP_USERNAME`, `SMTP_PASSWORD` e `SMTP_TLS`
+//!   (`starttls` (padrão), `tls` ou `none`);
+//! - `MAIL_FROM`: remetente (padrão `Agenda <no-reply@localhost>`).
+
+use crate::errors::AppError;
+use lettre::message::header::ContentType;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncFileTransport, AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use std::env::var;
+use std::path::PathBuf;
+
+/// Mensagem em texto puro a ser entregue a um único destinatário.
+pub struct Mail {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+#[rocket::async_trait]
+pub trait Mailer: Send + Sync {
+    async fn send(&self, mail: &Mail) -> Result<(), AppError>;
+}
+
+/// Entrega por SMTP.
+pub struct SmtpMailer {
+    from: Mailbox,
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+}
+
+impl SmtpMailer {
+    pub fn from_env(from: Mailbox) -> Result<Self, AppError> {
+        let host = var("SMTP_HOST").map_err(|_| AppError::Internal("SMTP_HOST is not set".to_string()))?;
+        let port = var("SMTP_PORT").ok().and_then(|port| port.parse().ok()).unwrap_or(587);
+
+        let builder = match var("SMTP_TLS").as_deref() {
+            Ok("tls") => AsyncSmtpTransport::<Tokio1Executor>::relay(&host),
+            Ok("none") => Ok(AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&host)),
+            _ => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&host),
+        }
+        .map_err(|e| AppError::Internal(e.to_string()))?
+        .port(port);
+
+        let builder = match (var("SMTP_USERNAME"), var("SMTP_PASSWORD")) {
+            (Ok(username), Ok(password)) => builder.credentials(Credentials::new(username, password)),
+            _ => builder,
+        };
+
+        Ok(SmtpMailer {
+            from,
+            transport: builder.build(),
+        })
+    }
+}
+
+#[rocket::async_trait]
+impl Mailer for SmtpMailer {
+    async fn send(&self, mail: &Mail) -> Result<(), AppError> {
+        let message = build_message(&self.from, mail)?;
+        self.transport
+            .send(message)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to send email: {}", e)))?;
+        Ok(())
+    }
+}
+
+/// Grava as mensagens em um diretório em vez de enviá-las.
+pub struct FileMailer {
+    from: Mailbox,
+    outbox: PathBuf,
+}
+
+impl FileMailer {
+    pub fn new(from: Mailbox, outbox: PathBuf) -> Self {
+        FileMailer { from, outbox }
+    }
+}
+
+#[rocket::async_trait]
+impl Mailer for FileMailer {
+    async fn send(&self, mail: &Mail) -> Result<(), AppError> {
+        let message = build_message(&self.from, mail)?;
+        tokio::fs::create_dir_all(&self.outbox)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+        AsyncFileTransport::<Tokio1Executor>::new(&self.outbox)
+            .send(message)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to write email: {}", e)))?;
+        Ok(())
+    }
+}
+
+fn build_message(from: &Mailbox, mail: &Mail) -> Result<Message, AppError> {
+    let to: Mailbox = mail
+        .to
+        .parse()
+        .map_err(|_| AppError::Internal(format!("Invalid recipient address: {}", mail.to)))?;
+    Message::builder()
+        .from(from.clone())
+        .to(to)
+        .subject(mail.subject.clone())
+        .header(ContentType::TEXT_PLAIN)
+        .body(mail.body.clone())
+        .map_err(|e| AppError::Internal(e.to_string()))
+}
+
+/// Cria o `Mailer` configurado; falha na inicialização se a configuração for inválida.
+pub fn from_env() -> Box<dyn Mailer> {
+    let from: Mailbox = var("MAIL_FROM")
+        .unwrap_or_else(|_| "Agenda <no-reply@localhost>".to_string())
+        .parse()
+        .expect("MAIL_FROM must be a valid email address");
+
+    match var("MAIL_TRANSPORT").as_deref() {
+        Ok("smtp") => Box::new(SmtpMailer::from_env(from).expect("Invalid SMTP configuration")),
+        _ => {
+            let outbox = var("MAIL_OUTBOX_DIR").unwrap_or_else(|_| "outbox".to_string());
+            Box::new(FileMailer::new(from, PathBuf::from(outbox)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn file_mailer_writes_the_message_to_the_outbox() {
+        let outbox = std::env::temp_dir().join(format!("agenda-outbox-{}", std::process::id()));
+        let mailer = FileMailer::new("Agenda <no-reply@localhost>".parse().unwrap(), outbox.clone());
+        let mail = Mail {
+            to: "maria@example.com".to_string(),
+            subject: "Redefinição de senha - Agenda".to_string(),
+            body: "token=abc123".to_string(),
+        };
+        mailer.send(&mail).await.unwrap();
+
+        let messages: Vec<String> = std::fs::read_dir(&outbox)
+            .unwrap()
+            .map(|entry| std::fs::read_to_string(entry.unwrap().path()).unwrap())
+            .collect();
+        std::fs::remove_dir_all(&outbox).unwrap();
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].contains("maria@example.com"));
+        assert!(messages[0].contains("token=abc123"));
+    }
+
+    #[tokio::test]
+    async fn invalid_recipients_are_rejected() {
+        let mailer = FileMailer::new("Agenda <no-reply@localhost>".parse().unwrap(), std::env::temp_dir());
+        let mail = Mail { to: "sem-arroba".to_string(), subject: String::new(), body: String::new() };
+        assert!(mailer.send(&mail).await.is_err());
+    }
+}
@@ -1,13 +1,19 @@
 #[macro_use]
 extern crate rocket;
 
+mod authorization;
 mod controller;
+mod credential_policy;
 mod db;
 mod dto;
 mod entity;
+mod errors;
+mod jobs;
+mod mailer;
 mod routes;
 mod service;
 mod repository;
+mod throttle;
 
 use dotenvy::dotenv;
 use rocket_cors::{AllowedHeaders, AllowedOrigins, CorsOptions};
@@ -33,12 +39,22 @@ fn rocket() -> _ {
 
     let mut rocket = rocket::build()
         .attach(db::init_pool())
+        .manage(throttle::AuthThrottleState::from_env())
+        .manage(mailer::from_env())
+        .attach(jobs::init())
         .mount("/api/", routes::get_auth_routes())
         .mount("/api/notes", routes::get_note_routes())
         .mount("/api/tasks", routes::get_task_routes())
         .mount("/api/reminders", routes::get_reminder_routes())
         .mount("/api/goals", routes::get_goal_routes())
         .mount("/api/reports", routes::get_report_routes())
+        .mount("/api/tokens", routes::get_personal_access_token_routes())
+        .mount("/api/me", routes::get_profile_routes())
+        .mount("/api/categories", routes::get_category_routes())
+        .mount("/api/search", routes::get_search_routes())
+        .mount("/api/trash", routes::get_trash_routes())
+        .mount("/api/pomodoro", routes::get_pomodoro_routes())
+        .register("/", errors::catchers())
         .attach(cors.to_cors().unwrap());
 
     if Path::new("dist").exists() {
@@ -1,5 +1,6 @@
 use crate::db::Pool;
 use crate::dto::auth_dto::AuthDto;
+use crate::dto::profile_dto::UpdateProfileDto;
 use crate::entity::prelude::*;
 use crate::entity::user;
 use argon2::{
@@ -7,44 +8,30 @@ use argon2::{
     Argon2,
 };
 use rocket::State;
-use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set};
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, IntoActiveModel, QueryFilter, Set};
 use crate::entity::user::Model;
-
-#[allow(dead_code)]
-pub enum UserError {
-    InvalidUser(String),
-    UserAlreadyExists(String),
-    DatabaseError(String),
-}
+use crate::errors::AppError;
 
 pub async fn create_user(
     db: &State<Pool>,
     user_info: &AuthDto,
-) -> Result<user::Model, UserError> {
+) -> Result<user::Model, AppError> {
 
     let conn = db.inner();
-    let salt = SaltString::generate(&mut OsRng);
-    let argon2 = Argon2::default();
-    let password_hash = argon2
-        .hash_password(user_info.password.as_bytes(), &salt)
-        .map_err(|e| UserError::DatabaseError(e.to_string()))?
-        .to_string();
+    let password_hash = hash_password(&user_info.password)?;
     let new_user = user::ActiveModel {
         username: Set(user_info.username.clone()),
         password: Set(password_hash),
         ..Default::default()
     };
 
-    match new_user.insert(conn).await {
-        Ok(user) => Ok(user),
-        Err(e) => Err(UserError::DatabaseError(e.to_string())),
-    }
+    Ok(new_user.insert(conn).await?)
 }
 
 pub async fn find_user_by_credentials(
     db: &State<Pool>,
     user_info: &AuthDto,
-) -> Result<user::Model, UserError> {
+) -> Result<user::Model, AppError> {
     let conn = db.inner();
 
     let user = User::find()
@@ -53,35 +40,107 @@ pub async fn find_user_by_credentials(
         .await;
 
     let error_msg = "Invalid username or password";
-    let argon2 = Argon2::default();
 
     match user {
         Ok(Some(user)) => {
-            let parsed_hash = PasswordHash::new(&user.password)
-                .map_err(|e| UserError::DatabaseError(e.to_string()))?;
-            if argon2
-                .verify_password(user_info.password.as_bytes(), &parsed_hash)
-                .is_ok()
-            {
+            if verify_password(&user, &user_info.password)? {
                 Ok(user)
             } else {
-                Err(UserError::InvalidUser(error_msg.to_string()))
+                Err(AppError::Unauthorized(error_msg.to_string()))
             }
         }
-        Ok(None) => Err(UserError::InvalidUser(error_msg.to_string())),
-        Err(e) => Err(UserError::DatabaseError(e.to_string())),
+        Ok(None) => Err(AppError::Unauthorized(error_msg.to_string())),
+        Err(e) => Err(e.into()),
     }
 }
 
-#[allow(dead_code)]
-pub async fn find_by_username(db: &State<Pool>, username: &str) -> Result<Option<Model>, UserError> {
-    let conn = db.inner();
+pub async fn find_by_username(db: &Pool, username: &str) -> Result<Option<Model>, AppError> {
     let user = User::find()
         .filter(user::Column::Username.eq(username))
-        .one(conn)
-        .await
-        .map_err(|e| UserError::DatabaseError(e.to_string()))?;
+        .one(db)
+        .await?;
 
     Ok(user)
 }
 
+pub async fn find_by_email(db: &Pool, email: &str) -> Result<Option<Model>, AppError> {
+    let user = User::find()
+        .filter(user::Column::Email.eq(email))
+        .one(db)
+        .await?;
+
+    Ok(user)
+}
+
+pub async fn find_by_id(db: &Pool, id: i32) -> Result<Option<Model>, AppError> {
+    Ok(User::find_by_id(id).one(db).await?)
+}
+
+pub async fn update_password(db: &Pool, user: Model, new_password: &str) -> Result<Model, AppError> {
+    let password_hash = hash_password(new_password)?;
+    let mut active_user = user.into_active_model();
+    active_user.password = Set(password_hash);
+    Ok(active_user.update(db).await?)
+}
+
+pub async fn update_username(db: &Pool, user: Model, new_username: &str) -> Result<Model, AppError> {
+    let mut active_user = user.into_active_model();
+    active_user.username = Set(new_username.to_string());
+    Ok(active_user.update(db).await?)
+}
+
+/// Remove o usuário; tarefas, metas, lembretes, notas e sessões são
+/// apagados pelas chaves estrangeiras com `ON DELETE CASCADE`.
+pub async fn delete_user(db: &Pool, user: Model) -> Result<(), AppError> {
+    User::delete_by_id(user.id).exec(db).await?;
+    Ok(())
+}
+
+/// Confere a senha em texto puro contra o hash Argon2 salvo.
+pub fn verify_password(user: &Model, password: &str) -> Result<bool, AppError> {
+    let parsed_hash = PasswordHash::new(&user.password)
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+    Ok(Argon2::default()
+        .verify_password(password.as_bytes(), &parsed_hash)
+        .is_ok())
+}
+
+fn hash_password(password: &str) -> Result<String, AppError> {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| AppError::Internal(e.to_string()))
+}
+
+/// Atualiza o estado do 2FA do usuário.
+pub async fn update_two_factor(
+    db: &Pool,
+    user: Model,
+    totp_secret: Option<String>,
+    totp_enabled: bool,
+) -> Result<Model, AppError> {
+    let mut active_user = user.into_active_model();
+    active_user.totp_secret = Set(totp_secret);
+    active_user.totp_enabled = Set(totp_enabled);
+    active_user.totp_last_step = Set(None);
+    Ok(active_user.update(db).await?)
+}
+
+/// Guarda o último passo TOTP aceito, para que o mesmo código não seja reutilizado.
+pub async fn update_totp_last_step(db: &Pool, user: Model, step: i64) -> Result<Model, AppError> {
+    let mut active_user = user.into_active_model();
+    active_user.totp_last_step = Set(Some(step));
+    Ok(active_user.update(db).await?)
+}
+
+pub async fn update_profile(db: &Pool, user: Model, profile: &UpdateProfileDto) -> Result<Model, AppError> {
+    let mut active_user = user.into_active_model();
+    active_user.display_name = Set(profile.display_name.clone());
+    active_user.email = Set(profile.email.clone());
+    active_user.timezone = Set(profile.timezone.clone());
+    active_user.locale = Set(profile.locale.clone());
+    active_user.week_start_day = Set(profile.week_start_day);
+    active_user.use_24h_clock = Set(profile.use_24h_clock);
+    Ok(active_user.update(db).await?)
+}
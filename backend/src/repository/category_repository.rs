@@ -0,0 +1,100 @@
+use sea_orm::sea_query::{Expr, Func};
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, IntoActiveModel, QueryFilter, QueryOrder, Set};
+use crate::entity::{category, goal, reminder, task};
+use crate::errors::AppError;
+
+pub struct CategoryRepository<'a> {
+    db: &'a DatabaseConnection,
+}
+
+impl<'a> CategoryRepository<'a> {
+    pub fn new(db: &'a DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    pub async fn find_by_user_id(&self, user_id: i32) -> Result<Vec<category::Model>, AppError> {
+        Ok(category::Entity::find()
+            .filter(category::Column::UserId.eq(user_id))
+            .order_by_asc(category::Column::Name)
+            .all(self.db)
+            .await?)
+    }
+
+    pub async fn find_by_id(&self, user_id: i32, id: i32) -> Result<Option<category::Model>, AppError> {
+        Ok(category::Entity::find_by_id(id)
+            .filter(category::Column::UserId.eq(user_id))
+            .one(self.db)
+            .await?)
+    }
+
+    /// Busca pelo nome, sem diferenciar maiúsculas.
+    pub async fn find_by_name(&self, user_id: i32, name: &str) -> Result<Option<category::Model>, AppError> {
+        Ok(category::Entity::find()
+            .filter(category::Column::UserId.eq(user_id))
+            .filter(Expr::expr(Func::lower(Expr::col(category::Column::Name))).eq(name.to_lowercase()))
+            .one(self.db)
+            .await?)
+    }
+
+    pub async fn create(
+        &self,
+        user_id: i32,
+        name: &str,
+        color: &str,
+        icon: Option<String>,
+    ) -> Result<category::Model, AppError> {
+        let category = category::ActiveModel {
+            user_id: Set(user_id),
+            name: Set(name.to_string()),
+            color: Set(color.to_string()),
+            icon: Set(icon),
+            ..Default::default()
+        };
+        Ok(category.insert(self.db).await?)
+    }
+
+    pub async fn update(
+        &self,
+        category: category::Model,
+        name: Option<&str>,
+        color: Option<&str>,
+        icon: Option<Option<String>>,
+    ) -> Result<category::Model, AppError> {
+        let mut active = category.into_active_model();
+        if let Some(name) = name {
+            active.name = Set(name.to_string());
+        }
+        if let Some(color) = color {
+            active.color = Set(color.to_string());
+        }
+        if let Some(icon) = icon {
+            active.icon = Set(icon);
+        }
+        Ok(active.update(self.db).await?)
+    }
+
+    pub async fn delete(&self, category: category::Model) -> Result<(), AppError> {
+        category::Entity::delete_by_id(category.id).exec(self.db).await?;
+        Ok(())
+    }
+
+    /// Atualiza o nome guardado nas tarefas, metas e lembretes da categoria.
+    pub async fn rename_references(&self, category_id: i32, name: &str) -> Result<(), AppError> {
+        task::Entity::update_many()
+            .col_expr(task::Column::Category, Expr::value(name))
+            .filter(task::Column::CategoryId.eq(category_id))
+            .exec(self.db)
+            .await?;
+        goal::Entity::update_many()
+            .col_expr(goal::Column::Category, Expr::value(name))
+            .filter(goal::Column::CategoryId.eq(category_id))
+            .exec(self.db)
+            .await?;
+        reminder::Entity::update_many()
+            .col_expr(reminder::Column::Category, Expr::value(name))
+            .filter(reminder::Column::CategoryId.eq(category_id))
+            .exec(self.db)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,121 @@
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, IntoActiveModel, QueryFilter, QueryOrder, QuerySelect, Set};
+use chrono::Utc;
+use std::collections::HashMap;
+use crate::entity::checklist_item;
+use crate::errors::AppError;
+
+pub struct ChecklistRepository<'a> {
+    db: &'a DatabaseConnection,
+}
+
+impl<'a> ChecklistRepository<'a> {
+    pub fn new(db: &'a DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Itens da tarefa, na ordem do checklist.
+    pub async fn find_by_task_id(&self, task_id: i32) -> Result<Vec<checklist_item::Model>, AppError> {
+        Ok(checklist_item::Entity::find()
+            .filter(checklist_item::Column::TaskId.eq(task_id))
+            .order_by_asc(checklist_item::Column::Position)
+            .order_by_asc(checklist_item::Column::Id)
+            .all(self.db)
+            .await?)
+    }
+
+    pub async fn find_item(&self, task_id: i32, item_id: i32) -> Result<Option<checklist_item::Model>, AppError> {
+        Ok(checklist_item::Entity::find_by_id(item_id)
+            .filter(checklist_item::Column::TaskId.eq(task_id))
+            .one(self.db)
+            .await?)
+    }
+
+    /// Total de itens e de itens marcados de cada tarefa; tarefas sem itens ficam de fora.
+    pub async fn count_by_task_ids(&self, task_ids: Vec<i32>) -> Result<HashMap<i32, (u64, u64)>, AppError> {
+        let items: Vec<(i32, bool)> = checklist_item::Entity::find()
+            .select_only()
+            .column(checklist_item::Column::TaskId)
+            .column(checklist_item::Column::Done)
+            .filter(checklist_item::Column::TaskId.is_in(task_ids))
+            .into_tuple()
+            .all(self.db)
+            .await?;
+
+        let mut counts = HashMap::new();
+        for (task_id, done) in items {
+            let (total, checked) = counts.entry(task_id).or_insert((0, 0));
+            *total += 1;
+            if done {
+                *checked += 1;
+            }
+        }
+        Ok(counts)
+    }
+
+    /// Cria o item no fim do checklist.
+    pub async fn create_item(&self, task_id: i32, title: &str) -> Result<checklist_item::Model, AppError> {
+        let position = self.find_by_task_id(task_id).await?.len() as i32;
+        let item = checklist_item::ActiveModel {
+            task_id: Set(task_id),
+            title: Set(title.to_string()),
+            position: Set(position),
+            done: Set(false),
+            completed_at: Set(None),
+            ..Default::default()
+        };
+        Ok(item.insert(self.db).await?)
+    }
+
+    pub async fn update_item(
+        &self,
+        item: checklist_item::Model,
+        title: Option<&str>,
+        done: Option<bool>,
+    ) -> Result<checklist_item::Model, AppError> {
+        let was_done = item.done;
+        let mut active = item.into_active_model();
+
+        if let Some(title) = title {
+            active.title = Set(title.to_string());
+        }
+        if let Some(done) = done {
+            if done != was_done {
+                active.done = Set(done);
+                active.completed_at = Set(done.then(Utc::now));
+            }
+        }
+        Ok(active.update(self.db).await?)
+    }
+
+    /// Move o item para `position` e renumera o checklist a partir de 0.
+    pub async fn move_item(&self, item: &checklist_item::Model, position: i32) -> Result<(), AppError> {
+        let mut items: Vec<checklist_item::Model> = self
+            .find_by_task_id(item.task_id)
+            .await?
+            .into_iter()
+            .filter(|other| other.id != item.id)
+            .collect();
+        let index = (position.max(0) as usize).min(items.len());
+        items.insert(index, item.clone());
+        self.renumber(items).await
+    }
+
+    /// Remove o item e fecha o espaço deixado na numeração.
+    pub async fn delete_item(&self, item: checklist_item::Model) -> Result<(), AppError> {
+        let task_id = item.task_id;
+        checklist_item::Entity::delete_by_id(item.id).exec(self.db).await?;
+        let items = self.find_by_task_id(task_id).await?;
+        self.renumber(items).await
+    }
+
+    async fn renumber(&self, items: Vec<checklist_item::Model>) -> Result<(), AppError> {
+        for (index, item) in items.into_iter().enumerate() {
+            if item.position != index as i32 {
+                let mut active = item.into_active_model();
+                active.position = Set(index as i32);
+                active.update(self.db).await?;
+            }
+        }
+        Ok(())
+    }
+}
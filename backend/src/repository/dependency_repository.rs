@@ -0,0 +1,68 @@
+use sea_orm::{ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, JoinType, QueryFilter, QuerySelect, RelationTrait, Set};
+use crate::entity::sea_orm_active_enums::TaskStatus;
+use crate::entity::{task, task_dependency};
+use crate::errors::AppError;
+
+/// Acesso às dependências pelo pool ou, em operações em lote, por uma transação.
+pub struct DependencyRepository<'a, C: ConnectionTrait = DatabaseConnection> {
+    db: &'a C,
+}
+
+impl<'a, C: ConnectionTrait> DependencyRepository<'a, C> {
+    pub fn new(db: &'a C) -> Self {
+        Self { db }
+    }
+
+    /// Todas as dependências entre as tarefas do usuário.
+    pub async fn find_by_user_id(&self, user_id: i32) -> Result<Vec<task_dependency::Model>, AppError> {
+        Ok(task_dependency::Entity::find()
+            .join(JoinType::InnerJoin, task_dependency::Relation::Task.def())
+            .filter(task::Column::UserId.eq(user_id))
+            .all(self.db)
+            .await?)
+    }
+
+    pub async fn find(&self, task_id: i32, blocked_by_id: i32) -> Result<Option<task_dependency::Model>, AppError> {
+        Ok(task_dependency::Entity::find()
+            .filter(task_dependency::Column::TaskId.eq(task_id))
+            .filter(task_dependency::Column::BlockedById.eq(blocked_by_id))
+            .one(self.db)
+            .await?)
+    }
+
+    /// Tarefas que bloqueiam `task_id`; as que estão na lixeira não bloqueiam.
+    pub async fn find_blockers(&self, task_id: i32) -> Result<Vec<task::Model>, AppError> {
+        Ok(task::Entity::find()
+            .join(JoinType::InnerJoin, task_dependency::Relation::BlockedBy.def().rev())
+            .filter(task_dependency::Column::TaskId.eq(task_id))
+            .filter(task::Column::DeletedAt.is_null())
+            .all(self.db)
+            .await?)
+    }
+
+    /// Dependências das tarefas informadas cujo bloqueador ainda não foi
+    /// concluído nem está na lixeira.
+    pub async fn find_pending(&self, task_ids: Vec<i32>, done_status: TaskStatus) -> Result<Vec<task_dependency::Model>, AppError> {
+        Ok(task_dependency::Entity::find()
+            .join(JoinType::InnerJoin, task_dependency::Relation::BlockedBy.def())
+            .filter(task_dependency::Column::TaskId.is_in(task_ids))
+            .filter(task::Column::Status.ne(done_status))
+            .filter(task::Column::DeletedAt.is_null())
+            .all(self.db)
+            .await?)
+    }
+
+    pub async fn create(&self, task_id: i32, blocked_by_id: i32) -> Result<task_dependency::Model, AppError> {
+        let dependency = task_dependency::ActiveModel {
+            task_id: Set(task_id),
+            blocked_by_id: Set(blocked_by_id),
+            ..Default::default()
+        };
+        Ok(dependency.insert(self.db).await?)
+    }
+
+    pub async fn delete(&self, dependency: task_dependency::Model) -> Result<(), AppError> {
+        task_dependency::Entity::delete_by_id(dependency.id).exec(self.db).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+use sea_orm::{ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter};
+use crate::entity::goal;
+use crate::errors::AppError;
+use crate::repository::list_query::{self, ListFilter, Page, PageRequest};
+
+pub struct GoalRepository<'a> {
+    db: &'a DatabaseConnection,
+}
+
+impl<'a> GoalRepository<'a> {
+    pub fn new(db: &'a DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Uma página das metas do usuário cujo período se sobrepõe ao do filtro.
+    pub async fn find_page(
+        &self,
+        user_id: i32,
+        filter: &ListFilter,
+        page: PageRequest<goal::Column>,
+    ) -> Result<Page<goal::Model>, AppError> {
+        let condition = Condition::all()
+            .add(goal::Column::UserId.eq(user_id))
+            .add(goal::Column::DeletedAt.is_null())
+            .add_option(list_query::overlaps(goal::Column::DateStart, goal::Column::DateEnd, filter))
+            .add_option(list_query::one_of(goal::Column::Status, &filter.statuses))
+            .add_option(list_query::one_of(goal::Column::Type, &filter.types))
+            .add_option(list_query::category(goal::Column::CategoryId, goal::Column::Category, filter))
+            .add_option(list_query::contains_text(&[goal::Column::Name, goal::Column::Description], filter));
+        list_query::paginate(self.db, goal::Entity::find().filter(condition), goal::Column::Id, page).await
+    }
+}
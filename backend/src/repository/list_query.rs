@@ -0,0 +1,177 @@
+//! Filtros e paginação por cursor compartilhados pelas listagens de tarefas,
+//! metas e lembretes.
+//!
+//! Cada repositório traduz o [`ListFilter`] para as próprias colunas com as
+//! funções deste módulo e entrega a consulta a [`paginate`], que ordena pela
+//! coluna escolhida e usa o id como desempate. O cursor guarda a ordenação
+//! usada, o valor da coluna de ordenação e o id do último item da página.
+
+use sea_orm::prelude::DateTimeUtc;
+use sea_orm::sea_query::{Expr, Func};
+use sea_orm::{ColumnTrait, Condition, ConnectionTrait, EntityTrait, ModelTrait, Order, QueryFilter, QueryOrder, QuerySelect, Select, Value};
+use chrono::DateTime;
+use crate::errors::AppError;
+
+/// Filtros aceitos pelas listagens; os ausentes não restringem o resultado.
+pub struct ListFilter {
+    /// Início do período (inclusive).
+    pub from: Option<DateTimeUtc>,
+    /// Fim do período (exclusive).
+    pub to: Option<DateTimeUtc>,
+    pub statuses: Vec<String>,
+    pub types: Vec<String>,
+    /// Nome da categoria, sem diferenciar maiúsculas.
+    pub category: Option<String>,
+    pub category_id: Option<i32>,
+    /// Trecho procurado no título, nome ou descrição.
+    pub text: Option<String>,
+}
+
+/// Ordenação, posição e tamanho da página pedida.
+pub struct PageRequest<C> {
+    /// Ordenação como pedida pelo cliente (ex.: `-begin_date`), gravada no cursor.
+    pub key: String,
+    pub sort: C,
+    pub descending: bool,
+    pub after: Option<Cursor>,
+    pub limit: u64,
+}
+
+pub struct Page<M> {
+    pub items: Vec<M>,
+    pub next_cursor: Option<String>,
+}
+
+/// Posição do último item entregue: ordenação, valor da coluna de ordenação e id.
+pub struct Cursor {
+    pub key: String,
+    value: Value,
+    id: i32,
+}
+
+impl Cursor {
+    /// Texto opaco enviado ao cliente em `next_cursor`.
+    pub fn encode(&self) -> String {
+        let value = match &self.value {
+            Value::ChronoDateTimeUtc(Some(date)) => format!("d:{}", date.to_rfc3339()),
+            Value::String(Some(text)) => format!("s:{}", text),
+            Value::Int(Some(number)) => format!("i:{}", number),
+            _ => "n:".to_string(),
+        };
+        hex::encode(format!("{}|{}|{}", self.key, self.id, value))
+    }
+
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let decoded = String::from_utf8(hex::decode(cursor).ok()?).ok()?;
+        let (key, rest) = decoded.split_once('|')?;
+        let (id, value) = rest.split_once('|')?;
+        let value = match value.split_once(':')? {
+            ("d", date) => Value::from(DateTime::parse_from_rfc3339(date).ok()?.to_utc()),
+            ("s", text) => Value::from(text.to_string()),
+            ("i", number) => Value::from(number.parse::<i32>().ok()?),
+            _ => return None,
+        };
+        Some(Self { key: key.to_string(), value, id: id.parse().ok()? })
+    }
+}
+
+/// Registros cujo intervalo `[start, end)` se sobrepõe ao período do filtro.
+pub fn overlaps<C: ColumnTrait>(start: C, end: C, filter: &ListFilter) -> Option<Condition> {
+    if filter.from.is_none() && filter.to.is_none() {
+        return None;
+    }
+    Some(
+        Condition::all()
+            .add_option(filter.to.map(|to| start.lt(to)))
+            .add_option(filter.from.map(|from| end.gt(from))),
+    )
+}
+
+/// Registros cuja data cai dentro do período do filtro.
+pub fn within<C: ColumnTrait>(column: C, filter: &ListFilter) -> Option<Condition> {
+    if filter.from.is_none() && filter.to.is_none() {
+        return None;
+    }
+    Some(
+        Condition::all()
+            .add_option(filter.from.map(|from| column.gte(from)))
+            .add_option(filter.to.map(|to| column.lt(to))),
+    )
+}
+
+pub fn one_of<C: ColumnTrait>(column: C, values: &[String]) -> Option<Condition> {
+    if values.is_empty() {
+        return None;
+    }
+    Some(Condition::all().add(column.is_in(values.iter().cloned())))
+}
+
+/// Categoria pelo id ou pelo nome gravado no registro.
+pub fn category<C: ColumnTrait>(id_column: C, name_column: C, filter: &ListFilter) -> Option<Condition> {
+    if filter.category.is_none() && filter.category_id.is_none() {
+        return None;
+    }
+    Some(
+        Condition::all()
+            .add_option(filter.category_id.map(|id| id_column.eq(id)))
+            .add_option(filter.category.as_ref().map(|name| {
+                Expr::expr(Func::lower(Expr::col(name_column))).eq(name.to_lowercase())
+            })),
+    )
+}
+
+/// Trecho em qualquer uma das colunas, sem diferenciar maiúsculas.
+pub fn contains_text<C: ColumnTrait>(columns: &[C], filter: &ListFilter) -> Option<Condition> {
+    let text = filter.text.as_ref()?;
+    let pattern = format!("%{}%", escape_like(&text.to_lowercase()));
+    Some(columns.iter().fold(Condition::any(), |condition, column| {
+        condition.add(Expr::expr(Func::lower(Expr::col(*column))).like(pattern.as_str()))
+    }))
+}
+
+/// Escapa os curingas do `LIKE` para que o texto seja procurado literalmente.
+fn escape_like(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+/// Executa a consulta a partir do cursor e devolve uma página mais o cursor da próxima.
+pub async fn paginate<E: EntityTrait, C: ConnectionTrait>(
+    db: &C,
+    select: Select<E>,
+    id: E::Column,
+    page: PageRequest<E::Column>,
+) -> Result<Page<E::Model>, AppError> {
+    let order = if page.descending { Order::Desc } else { Order::Asc };
+    let mut select = select;
+    if let Some(after) = page.after {
+        let (beyond, beyond_id) = if page.descending {
+            (page.sort.lt(after.value.clone()), id.lt(after.id))
+        } else {
+            (page.sort.gt(after.value.clone()), id.gt(after.id))
+        };
+        select = select.filter(
+            Condition::any()
+                .add(beyond)
+                .add(Condition::all().add(page.sort.eq(after.value)).add(beyond_id)),
+        );
+    }
+
+    let mut items = select
+        .order_by(page.sort, order.clone())
+        .order_by(id, order)
+        .limit(page.limit + 1)
+        .all(db)
+        .await?;
+
+    let mut next_cursor = None;
+    if items.len() as u64 > page.limit {
+        items.truncate(page.limit as usize);
+        next_cursor = items.last().and_then(|last| match last.get(id) {
+            Value::Int(Some(last_id)) => Some(
+                Cursor { key: page.key.clone(), value: last.get(page.sort), id: last_id }.encode(),
+            ),
+            _ => None,
+        });
+    }
+    Ok(Page { items, next_cursor })
+}
@@ -0,0 +1,83 @@
+use crate::db::Pool;
+use crate::entity::login_attempt;
+use crate::errors::AppError;
+use chrono::{DateTime, Utc};
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, Select, Set};
+
+pub async fn create_attempt(
+    db: &Pool,
+    action: &str,
+    username: &str,
+    ip: Option<String>,
+    succeeded: bool,
+) -> Result<login_attempt::Model, AppError> {
+    let new_attempt = login_attempt::ActiveModel {
+        action: Set(action.to_string()),
+        username: Set(username.to_string()),
+        ip: Set(ip),
+        succeeded: Set(succeeded),
+        created_at: Set(Utc::now()),
+        ..Default::default()
+    };
+
+    Ok(new_attempt.insert(db).await?)
+}
+
+/// Data do último sucesso da ação para o usuário, se houver.
+pub async fn last_success_by_username(
+    db: &Pool,
+    action: &str,
+    username: &str,
+) -> Result<Option<DateTime<Utc>>, AppError> {
+    let attempt = login_attempt::Entity::find()
+        .filter(login_attempt::Column::Action.eq(action))
+        .filter(login_attempt::Column::Username.eq(username))
+        .filter(login_attempt::Column::Succeeded.eq(true))
+        .order_by_desc(login_attempt::Column::CreatedAt)
+        .one(db)
+        .await?;
+
+    Ok(attempt.map(|a| a.created_at))
+}
+
+/// Quantidade de falhas do usuário a partir de `since` e a data da mais recente.
+pub async fn failures_by_username(
+    db: &Pool,
+    action: &str,
+    username: &str,
+    since: DateTime<Utc>,
+) -> Result<(u64, Option<DateTime<Utc>>), AppError> {
+    let query = failures(action, since).filter(login_attempt::Column::Username.eq(username));
+    count_and_last(db, query).await
+}
+
+/// Quantidade de falhas vindas do IP a partir de `since` e a data da mais recente.
+pub async fn failures_by_ip(
+    db: &Pool,
+    action: &str,
+    ip: &str,
+    since: DateTime<Utc>,
+) -> Result<(u64, Option<DateTime<Utc>>), AppError> {
+    let query = failures(action, since).filter(login_attempt::Column::Ip.eq(ip));
+    count_and_last(db, query).await
+}
+
+fn failures(action: &str, since: DateTime<Utc>) -> Select<login_attempt::Entity> {
+    login_attempt::Entity::find()
+        .filter(login_attempt::Column::Action.eq(action))
+        .filter(login_attempt::Column::Succeeded.eq(false))
+        .filter(login_attempt::Column::CreatedAt.gte(since))
+}
+
+async fn count_and_last(
+    db: &Pool,
+    query: Select<login_attempt::Entity>,
+) -> Result<(u64, Option<DateTime<Utc>>), AppError> {
+    let count = query.clone().count(db).await?;
+    let last = query
+        .order_by_desc(login_attempt::Column::CreatedAt)
+        .one(db)
+        .await?
+        .map(|a| a.created_at);
+    Ok((count, last))
+}
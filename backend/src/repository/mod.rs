@@ -1,3 +1,18 @@
 pub mod task_repository;
 pub mod auth_repository;
+pub mod session_repository;
 
+pub mod login_attempt_repository;
+pub mod recovery_code_repository;
+pub mod personal_access_token_repository;
+pub mod password_reset_token_repository;
+pub mod checklist_repository;
+pub mod dependency_repository;
+pub mod category_repository;
+pub mod list_query;
+pub mod goal_repository;
+pub mod reminder_repository;
+pub mod search_repository;
+pub mod trash_repository;
+pub mod time_entry_repository;
+pub mod pomodoro_repository;
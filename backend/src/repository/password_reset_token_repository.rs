@@ -0,0 +1,72 @@
+use crate::db::Pool;
+use crate::entity::password_reset_token;
+use crate::errors::AppError;
+use chrono::{DateTime, Utc};
+use sea_orm::prelude::Expr;
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, Set};
+
+pub async fn create_token(
+    db: &Pool,
+    user_id: i32,
+    token_hash: String,
+    expires_at: DateTime<Utc>,
+) -> Result<password_reset_token::Model, AppError> {
+    let new_token = password_reset_token::ActiveModel {
+        user_id: Set(user_id),
+        token_hash: Set(token_hash),
+        created_at: Set(Utc::now()),
+        expires_at: Set(expires_at),
+        used_at: Set(None),
+        ..Default::default()
+    };
+
+    Ok(new_token.insert(db).await?)
+}
+
+pub async fn find_by_token_hash(
+    db: &Pool,
+    token_hash: &str,
+) -> Result<Option<password_reset_token::Model>, AppError> {
+    let token = password_reset_token::Entity::find()
+        .filter(password_reset_token::Column::TokenHash.eq(token_hash))
+        .one(db)
+        .await?;
+    Ok(token)
+}
+
+pub async fn find_latest_by_user_id(
+    db: &Pool,
+    user_id: i32,
+) -> Result<Option<password_reset_token::Model>, AppError> {
+    let token = password_reset_token::Entity::find()
+        .filter(password_reset_token::Column::UserId.eq(user_id))
+        .order_by_desc(password_reset_token::Column::CreatedAt)
+        .one(db)
+        .await?;
+    Ok(token)
+}
+
+/// Marca o token como usado se ele ainda estiver valendo. Retorna `false`
+/// se outra requisição o usou antes ou se ele expirou.
+pub async fn mark_used(db: &Pool, token_id: i32) -> Result<bool, AppError> {
+    let now = Utc::now();
+    let result = password_reset_token::Entity::update_many()
+        .col_expr(password_reset_token::Column::UsedAt, Expr::value(Some(now)))
+        .filter(password_reset_token::Column::Id.eq(token_id))
+        .filter(password_reset_token::Column::UsedAt.is_null())
+        .filter(password_reset_token::Column::ExpiresAt.gt(now))
+        .exec(db)
+        .await?;
+    Ok(result.rows_affected == 1)
+}
+
+/// Invalida os tokens ainda não utilizados do usuário, para que apenas o mais recente valha.
+pub async fn invalidate_user_tokens(db: &Pool, user_id: i32) -> Result<(), AppError> {
+    password_reset_token::Entity::update_many()
+        .col_expr(password_reset_token::Column::UsedAt, Expr::value(Some(Utc::now())))
+        .filter(password_reset_token::Column::UserId.eq(user_id))
+        .filter(password_reset_token::Column::UsedAt.is_null())
+        .exec(db)
+        .await?;
+    Ok(())
+}
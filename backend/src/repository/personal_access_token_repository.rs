@@ -0,0 +1,70 @@
+use crate::db::Pool;
+use crate::entity::personal_access_token;
+use crate::errors::AppError;
+use chrono::{DateTime, Utc};
+use sea_orm::prelude::Expr;
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, IntoActiveModel, QueryFilter, QueryOrder, Set};
+
+pub async fn create_token(
+    db: &Pool,
+    user_id: i32,
+    name: &str,
+    token_hash: String,
+    scopes: String,
+    expires_at: Option<DateTime<Utc>>,
+) -> Result<personal_access_token::Model, AppError> {
+    let new_token = personal_access_token::ActiveModel {
+        user_id: Set(user_id),
+        name: Set(name.to_string()),
+        token_hash: Set(token_hash),
+        scopes: Set(scopes),
+        created_at: Set(Utc::now()),
+        last_used_at: Set(None),
+        expires_at: Set(expires_at),
+        revoked_at: Set(None),
+        ..Default::default()
+    };
+
+    Ok(new_token.insert(db).await?)
+}
+
+pub async fn find_by_user_id(db: &Pool, user_id: i32) -> Result<Vec<personal_access_token::Model>, AppError> {
+    let tokens = personal_access_token::Entity::find()
+        .filter(personal_access_token::Column::UserId.eq(user_id))
+        .order_by_desc(personal_access_token::Column::CreatedAt)
+        .all(db)
+        .await?;
+    Ok(tokens)
+}
+
+pub async fn find_by_token_hash(
+    db: &Pool,
+    token_hash: &str,
+) -> Result<Option<personal_access_token::Model>, AppError> {
+    let token = personal_access_token::Entity::find()
+        .filter(personal_access_token::Column::TokenHash.eq(token_hash))
+        .one(db)
+        .await?;
+    Ok(token)
+}
+
+pub async fn touch_last_used(db: &Pool, id: i32) -> Result<(), AppError> {
+    personal_access_token::Entity::update_many()
+        .col_expr(personal_access_token::Column::LastUsedAt, Expr::value(Some(Utc::now())))
+        .filter(personal_access_token::Column::Id.eq(id))
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+pub async fn revoke_token(
+    db: &Pool,
+    token: personal_access_token::Model,
+) -> Result<personal_access_token::Model, AppError> {
+    if token.revoked_at.is_some() {
+        return Ok(token);
+    }
+    let mut active_token = token.into_active_model();
+    active_token.revoked_at = Set(Some(Utc::now()));
+    Ok(active_token.update(db).await?)
+}
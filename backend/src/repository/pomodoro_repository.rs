@@ -0,0 +1,88 @@
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, IntoActiveModel, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set};
+use sea_orm::prelude::DateTimeUtc;
+use crate::dto::pomodoro_dto::PomodoroSettingsDto;
+use crate::entity::sea_orm_active_enums::{PomodoroKind, PomodoroStatus};
+use crate::entity::{pomodoro_session, user};
+use crate::errors::AppError;
+
+pub struct PomodoroRepository<'a> {
+    db: &'a DatabaseConnection,
+}
+
+impl<'a> PomodoroRepository<'a> {
+    pub fn new(db: &'a DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Sessão em andamento do usuário; há no máximo uma.
+    pub async fn find_running(&self, user_id: i32) -> Result<Option<pomodoro_session::Model>, AppError> {
+        Ok(pomodoro_session::Entity::find()
+            .filter(pomodoro_session::Column::UserId.eq(user_id))
+            .filter(pomodoro_session::Column::Status.eq(PomodoroStatus::Running))
+            .one(self.db)
+            .await?)
+    }
+
+    /// Sessões do usuário, das mais recentes às mais antigas.
+    pub async fn find_recent(&self, user_id: i32, limit: u64) -> Result<Vec<pomodoro_session::Model>, AppError> {
+        Ok(pomodoro_session::Entity::find()
+            .filter(pomodoro_session::Column::UserId.eq(user_id))
+            .order_by_desc(pomodoro_session::Column::StartedAt)
+            .order_by_desc(pomodoro_session::Column::Id)
+            .limit(limit)
+            .all(self.db)
+            .await?)
+    }
+
+    /// Sessões de foco concluídas que começaram a partir de `since`.
+    pub async fn count_completed_work_since(&self, user_id: i32, since: DateTimeUtc) -> Result<u64, AppError> {
+        Ok(pomodoro_session::Entity::find()
+            .filter(pomodoro_session::Column::UserId.eq(user_id))
+            .filter(pomodoro_session::Column::Kind.eq(PomodoroKind::Work))
+            .filter(pomodoro_session::Column::Status.eq(PomodoroStatus::Completed))
+            .filter(pomodoro_session::Column::StartedAt.gte(since))
+            .count(self.db)
+            .await?)
+    }
+
+    pub async fn create_session(
+        &self,
+        user_id: i32,
+        task_id: Option<i32>,
+        kind: PomodoroKind,
+        started_at: DateTimeUtc,
+        ends_at: DateTimeUtc,
+    ) -> Result<pomodoro_session::Model, AppError> {
+        let session = pomodoro_session::ActiveModel {
+            user_id: Set(user_id),
+            task_id: Set(task_id),
+            kind: Set(kind),
+            status: Set(PomodoroStatus::Running),
+            started_at: Set(started_at),
+            ends_at: Set(ends_at),
+            finished_at: Set(None),
+            ..Default::default()
+        };
+        Ok(session.insert(self.db).await?)
+    }
+
+    pub async fn finish_session(
+        &self,
+        session: pomodoro_session::Model,
+        status: PomodoroStatus,
+        finished_at: DateTimeUtc,
+    ) -> Result<pomodoro_session::Model, AppError> {
+        let mut active = session.into_active_model();
+        active.status = Set(status);
+        active.finished_at = Set(Some(finished_at));
+        Ok(active.update(self.db).await?)
+    }
+
+    pub async fn update_settings(&self, user: user::Model, settings: &PomodoroSettingsDto) -> Result<user::Model, AppError> {
+        let mut active = user.into_active_model();
+        active.pomodoro_work_minutes = Set(settings.work_minutes);
+        active.pomodoro_short_break_minutes = Set(settings.short_break_minutes);
+        active.pomodoro_long_break_minutes = Set(settings.long_break_minutes);
+        Ok(active.update(self.db).await?)
+    }
+}
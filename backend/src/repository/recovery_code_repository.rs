@@ -0,0 +1,44 @@
+use crate::db::Pool;
+use crate::entity::recovery_code;
+use crate::errors::AppError;
+use chrono::Utc;
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, IntoActiveModel, QueryFilter, Set};
+
+/// Substitui os códigos de recuperação do usuário pelos novos hashes.
+pub async fn replace_codes(db: &Pool, user_id: i32, code_hashes: Vec<String>) -> Result<(), AppError> {
+    delete_by_user_id(db, user_id).await?;
+
+    let new_codes = code_hashes.into_iter().map(|code_hash| recovery_code::ActiveModel {
+        user_id: Set(user_id),
+        code_hash: Set(code_hash),
+        used_at: Set(None),
+        ..Default::default()
+    });
+    recovery_code::Entity::insert_many(new_codes).exec(db).await?;
+    Ok(())
+}
+
+/// Códigos ainda não utilizados do usuário.
+pub async fn find_unused(db: &Pool, user_id: i32) -> Result<Vec<recovery_code::Model>, AppError> {
+    let codes = recovery_code::Entity::find()
+        .filter(recovery_code::Column::UserId.eq(user_id))
+        .filter(recovery_code::Column::UsedAt.is_null())
+        .all(db)
+        .await?;
+    Ok(codes)
+}
+
+pub async fn mark_used(db: &Pool, code: recovery_code::Model) -> Result<(), AppError> {
+    let mut active_code = code.into_active_model();
+    active_code.used_at = Set(Some(Utc::now()));
+    active_code.update(db).await?;
+    Ok(())
+}
+
+pub async fn delete_by_user_id(db: &Pool, user_id: i32) -> Result<(), AppError> {
+    recovery_code::Entity::delete_many()
+        .filter(recovery_code::Column::UserId.eq(user_id))
+        .exec(db)
+        .await?;
+    Ok(())
+}
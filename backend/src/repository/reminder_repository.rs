@@ -0,0 +1,32 @@
+use sea_orm::{ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter};
+use crate::entity::reminder;
+use crate::errors::AppError;
+use crate::repository::list_query::{self, ListFilter, Page, PageRequest};
+
+pub struct ReminderRepository<'a> {
+    db: &'a DatabaseConnection,
+}
+
+impl<'a> ReminderRepository<'a> {
+    pub fn new(db: &'a DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Uma página dos lembretes do usuário com data dentro do período do filtro.
+    ///
+    /// Lembretes não têm status nem tipo; esses filtros são ignorados.
+    pub async fn find_page(
+        &self,
+        user_id: i32,
+        filter: &ListFilter,
+        page: PageRequest<reminder::Column>,
+    ) -> Result<Page<reminder::Model>, AppError> {
+        let condition = Condition::all()
+            .add(reminder::Column::UserId.eq(user_id))
+            .add(reminder::Column::DeletedAt.is_null())
+            .add_option(list_query::within(reminder::Column::DateEnd, filter))
+            .add_option(list_query::category(reminder::Column::CategoryId, reminder::Column::Category, filter))
+            .add_option(list_query::contains_text(&[reminder::Column::Name], filter));
+        list_query::paginate(self.db, reminder::Entity::find().filter(condition), reminder::Column::Id, page).await
+    }
+}
@@ -0,0 +1,62 @@
+use sea_orm::{DatabaseConnection, DbBackend, FromQueryResult, Statement};
+use crate::dto::search_dto::SearchResultDto;
+use crate::errors::AppError;
+
+/// Busca nas colunas `search_vector` das tarefas, metas, lembretes e notas
+/// fora da lixeira.
+/// O texto é interpretado como em buscadores (`"frase exata"`, `-excluir`, `or`)
+/// em português e em inglês; o trecho destacado só é montado para os itens
+/// que entram no resultado.
+const SEARCH_SQL: &str = r#"
+WITH query AS (
+    SELECT websearch_to_tsquery('portuguese', $1) || websearch_to_tsquery('english', $1) AS q
+),
+matches AS (
+    SELECT 'task' AS kind, t.id, t.title, concat_ws(' — ', t.title, t.description) AS document,
+           t.begin_date AS date, ts_rank_cd(t.search_vector, query.q) AS rank
+    FROM "task" AS t, query
+    WHERE t.user_id = $2 AND t.deleted_at IS NULL AND t.search_vector @@ query.q
+    UNION ALL
+    SELECT 'goal', g.id, g.name, concat_ws(' — ', g.name, g.description),
+           g.date_end, ts_rank_cd(g.search_vector, query.q)
+    FROM "goal" AS g, query
+    WHERE g.user_id = $2 AND g.deleted_at IS NULL AND g.search_vector @@ query.q
+    UNION ALL
+    SELECT 'reminder', r.id, r.name, r.name,
+           r.date_end, ts_rank_cd(r.search_vector, query.q)
+    FROM "reminder" AS r, query
+    WHERE r.user_id = $2 AND r.deleted_at IS NULL AND r.search_vector @@ query.q
+    UNION ALL
+    SELECT 'note', n.id, left(n.text, 80), n.text,
+           n.created_at, ts_rank_cd(n.search_vector, query.q)
+    FROM "notes" AS n, query
+    WHERE n.user_id = $2 AND n.deleted_at IS NULL AND n.search_vector @@ query.q
+    ORDER BY rank DESC, date DESC
+    LIMIT $3
+)
+SELECT kind, id, title,
+       ts_headline('portuguese', document, (SELECT q FROM query),
+                   'StartSel=<mark>, StopSel=</mark>, MaxWords=30, MinWords=10') AS snippet,
+       date, rank
+FROM matches
+ORDER BY rank DESC, date DESC
+"#;
+
+pub struct SearchRepository<'a> {
+    db: &'a DatabaseConnection,
+}
+
+impl<'a> SearchRepository<'a> {
+    pub fn new(db: &'a DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    pub async fn search(&self, user_id: i32, text: &str, limit: u64) -> Result<Vec<SearchResultDto>, AppError> {
+        let statement = Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            SEARCH_SQL,
+            [text.into(), user_id.into(), (limit as i64).into()],
+        );
+        Ok(SearchResultDto::find_by_statement(statement).all(self.db).await?)
+    }
+}
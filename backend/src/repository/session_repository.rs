@@ -0,0 +1,122 @@
+use crate::db::Pool;
+use crate::entity::session;
+use crate::errors::AppError;
+use chrono::{DateTime, Utc};
+use sea_orm::prelude::Expr;
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set};
+
+pub async fn create_session(
+    db: &Pool,
+    user_id: i32,
+    refresh_token_hash: String,
+    expires_at: DateTime<Utc>,
+) -> Result<session::Model, AppError> {
+    let now = Utc::now();
+    let new_session = session::ActiveModel {
+        user_id: Set(user_id),
+        refresh_token_hash: Set(refresh_token_hash),
+        created_at: Set(now),
+        last_used_at: Set(now),
+        expires_at: Set(expires_at),
+        revoked_at: Set(None),
+        ..Default::default()
+    };
+
+    new_session
+        .insert(db)
+        .await
+        .map_err(AppError::from)
+}
+
+pub async fn find_by_id(db: &Pool, id: i32) -> Result<Option<session::Model>, AppError> {
+    session::Entity::find_by_id(id)
+        .one(db)
+        .await
+        .map_err(AppError::from)
+}
+
+pub async fn find_by_refresh_token_hash(
+    db: &Pool,
+    refresh_token_hash: &str,
+) -> Result<Option<session::Model>, AppError> {
+    session::Entity::find()
+        .filter(session::Column::RefreshTokenHash.eq(refresh_token_hash))
+        .one(db)
+        .await
+        .map_err(AppError::from)
+}
+
+/// Sessão cujo refresh token anterior (já rotacionado) tem esse hash.
+pub async fn find_by_previous_refresh_token_hash(
+    db: &Pool,
+    refresh_token_hash: &str,
+) -> Result<Option<session::Model>, AppError> {
+    session::Entity::find()
+        .filter(session::Column::PreviousRefreshTokenHash.eq(refresh_token_hash))
+        .one(db)
+        .await
+        .map_err(AppError::from)
+}
+
+/// Troca o refresh token da sessão, invalidando o anterior. A troca só
+/// acontece se o token ainda for o atual; retorna `None` se outra requisição
+/// o rotacionou antes ou se a sessão foi revogada.
+pub async fn rotate_refresh_token(
+    db: &Pool,
+    session_model: session::Model,
+    refresh_token_hash: String,
+    expires_at: DateTime<Utc>,
+) -> Result<Option<session::Model>, AppError> {
+    let now = Utc::now();
+    let result = session::Entity::update_many()
+        .col_expr(session::Column::RefreshTokenHash, Expr::value(refresh_token_hash.clone()))
+        .col_expr(
+            session::Column::PreviousRefreshTokenHash,
+            Expr::value(Some(session_model.refresh_token_hash.clone())),
+        )
+        .col_expr(session::Column::LastUsedAt, Expr::value(now))
+        .col_expr(session::Column::ExpiresAt, Expr::value(expires_at))
+        .filter(session::Column::Id.eq(session_model.id))
+        .filter(session::Column::RefreshTokenHash.eq(session_model.refresh_token_hash.as_str()))
+        .filter(session::Column::RevokedAt.is_null())
+        .exec(db)
+        .await?;
+
+    if result.rows_affected != 1 {
+        return Ok(None);
+    }
+    Ok(Some(session::Model {
+        previous_refresh_token_hash: Some(session_model.refresh_token_hash),
+        refresh_token_hash,
+        last_used_at: now,
+        expires_at,
+        ..session_model
+    }))
+}
+
+pub async fn revoke_session(db: &Pool, id: i32) -> Result<(), AppError> {
+    session::Entity::update_many()
+        .col_expr(session::Column::RevokedAt, Expr::value(Some(Utc::now())))
+        .filter(session::Column::Id.eq(id))
+        .filter(session::Column::RevokedAt.is_null())
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+/// Revoga todas as sessões ativas do usuário, exceto `keep_session_id` se informado.
+pub async fn revoke_user_sessions(
+    db: &Pool,
+    user_id: i32,
+    keep_session_id: Option<i32>,
+) -> Result<(), AppError> {
+    let mut query = session::Entity::update_many()
+        .col_expr(session::Column::RevokedAt, Expr::value(Some(Utc::now())))
+        .filter(session::Column::UserId.eq(user_id))
+        .filter(session::Column::RevokedAt.is_null());
+    if let Some(keep_session_id) = keep_session_id {
+        query = query.filter(session::Column::Id.ne(keep_session_id));
+    }
+    query.exec(db).await?;
+    Ok(())
+}
@@ -1,162 +1,430 @@
-use sea_orm::{ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, DeleteResult, DbErr, EntityTrait, IntoActiveModel, QueryFilter, Set};
-use chrono::{Timelike, Duration};
+use sea_orm::{ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, DeleteResult, EntityTrait, IntoActiveModel, QueryFilter, QueryOrder, Set, TryIntoModel, UpdateResult};
+use sea_orm::sea_query::Expr;
+use sea_orm::prelude::DateTimeUtc;
+use chrono::Utc;
 use crate::dto::task_dto::TaskDto;
 use crate::dto::task_update_dto::TaskUpdateDto;
-use crate::entity::task;
+use crate::entity::sea_orm_active_enums::TaskStatus;
+use crate::entity::{category, task, task_occurrence, task_recurrence, task_status_history};
+use crate::errors::AppError;
+use crate::repository::list_query::{self, ListFilter, Page, PageRequest};
+use crate::service::recurrence_service::RecurrenceRule;
 
-pub struct TaskRepository<'a> {
-    db: &'a DatabaseConnection,
+/// Acesso às tarefas pelo pool ou, em operações em lote, por uma transação.
+pub struct TaskRepository<'a, C: ConnectionTrait = DatabaseConnection> {
+    db: &'a C,
 }
 
-impl<'a> TaskRepository<'a> {
-    pub fn new(db: &'a DatabaseConnection) -> Self {
+impl<'a, C: ConnectionTrait> TaskRepository<'a, C> {
+    pub fn new(db: &'a C) -> Self {
         Self { db }
     }
 
-    pub async fn find_all(&self) -> Result<Vec<task::Model>, DbErr> {
-        task::Entity::find().all(self.db).await
-    }
-
-    pub async fn find_by_id(&self, id: i32) -> Result<Option<task::Model>, DbErr> {
-        task::Entity::find_by_id(id).one(self.db).await
+    pub async fn find_by_user_id(&self, user_id: i32) -> Result<Vec<task::Model>, AppError> {
+        Ok(task::Entity::find()
+            .filter(task::Column::UserId.eq(user_id))
+            .filter(task::Column::DeletedAt.is_null())
+            .all(self.db)
+            .await?)
     }
 
-    pub async fn find_by_user_id(&self, user_id: i32) -> Result<Vec<task::Model>, DbErr> {
-        task::Entity::find()
+    /// Tarefas do usuário entre as informadas; as de outros usuários e as da
+    /// lixeira ficam de fora.
+    pub async fn find_by_ids(&self, user_id: i32, ids: &[i32]) -> Result<Vec<task::Model>, AppError> {
+        Ok(task::Entity::find()
             .filter(task::Column::UserId.eq(user_id))
+            .filter(task::Column::DeletedAt.is_null())
+            .filter(task::Column::Id.is_in(ids.iter().copied()))
             .all(self.db)
-            .await
+            .await?)
     }
 
-    pub async fn create_task(
+    /// Uma página das tarefas do usuário que atendem ao filtro.
+    pub async fn find_page(
         &self,
-        task_info: &TaskDto,
         user_id: i32,
-    ) -> Result<task::Model, DbErr> {
-        let (begin_date, complete_date) = match task_info.r#type.as_str() {
-            "MeiaHora" => (
-                task_info.begin_date,
-                task_info.begin_date + Duration::minutes(30),
-            ),
-            "UmaHora" => (
-                task_info.begin_date,
-                task_info.begin_date + Duration::hours(1),
-            ),
-            "DuasHoras" => (
-                task_info.begin_date,
-                task_info.begin_date + Duration::hours(1),
-            ),
-            "Manha" => (
-                task_info.begin_date.with_hour(6).unwrap().with_minute(0).unwrap().with_second(0).unwrap(),
-                task_info.begin_date.with_hour(11).unwrap().with_minute(59).unwrap().with_second(59).unwrap(),
-            ),
-            "Tarde" => (
-                task_info.begin_date.with_hour(12).unwrap().with_minute(0).unwrap().with_second(0).unwrap(),
-                task_info.begin_date.with_hour(17).unwrap().with_minute(59).unwrap().with_second(59).unwrap(),
-            ),
-            "Noite" => (
-                task_info.begin_date.with_hour(18).unwrap().with_minute(0).unwrap().with_second(0).unwrap(),
-                task_info.begin_date.with_hour(23).unwrap().with_minute(59).unwrap().with_second(59).unwrap(),
-            ),
-            "Madrugada" => (
-                task_info.begin_date.with_hour(0).unwrap().with_minute(0).unwrap().with_second(0).unwrap(),
-                task_info.begin_date.with_hour(5).unwrap().with_minute(59).unwrap().with_second(59).unwrap(),
-            ),
-            _ => {
-                return Err(DbErr::Custom(format!(
-                    "Invalid task type: {}",
-                    task_info.r#type
-                )))
-            }
-        };
-
-        // Removed validation: Tasks can now be created in the past
-        // if begin_date < Utc::now() {
-        //     return Err(DbErr::Custom(
-        //         "Task begin date cannot be in the past.".to_string(),
-        //     ));
-        // }
+        filter: &ListFilter,
+        page: PageRequest<task::Column>,
+    ) -> Result<Page<task::Model>, AppError> {
+        let condition = Condition::all()
+            .add(task::Column::UserId.eq(user_id))
+            .add(task::Column::DeletedAt.is_null())
+            .add_option(list_query::overlaps(task::Column::BeginDate, task::Column::CompleteDate, filter))
+            .add_option(list_query::one_of(task::Column::Status, &filter.statuses))
+            .add_option(list_query::one_of(task::Column::Type, &filter.types))
+            .add_option(list_query::category(task::Column::CategoryId, task::Column::Category, filter))
+            .add_option(list_query::contains_text(&[task::Column::Title, task::Column::Description], filter));
+        list_query::paginate(self.db, task::Entity::find().filter(condition), task::Column::Id, page).await
+    }
 
-        let overlapping_task = task::Entity::find()
+    /// Tarefas sem repetição que se sobrepõem a `[from, to)`.
+    pub async fn find_single_in_range(
+        &self,
+        user_id: i32,
+        from: DateTimeUtc,
+        to: DateTimeUtc,
+    ) -> Result<Vec<task::Model>, AppError> {
+        Ok(task::Entity::find()
+            .left_join(task_recurrence::Entity)
             .filter(task::Column::UserId.eq(user_id))
+            .filter(task::Column::DeletedAt.is_null())
+            .filter(task_recurrence::Column::Id.is_null())
             .filter(
                 Condition::all()
-                    .add(task::Column::BeginDate.lt(complete_date))
-                    .add(task::Column::CompleteDate.gt(begin_date)),
+                    .add(task::Column::BeginDate.lt(to))
+                    .add(task::Column::CompleteDate.gt(from)),
             )
-            .one(self.db)
+            .all(self.db)
+            .await?)
+    }
+
+    /// Tarefas com repetição cuja primeira ocorrência começa antes de `before`.
+    pub async fn find_series(
+        &self,
+        user_id: i32,
+        before: DateTimeUtc,
+    ) -> Result<Vec<(task::Model, task_recurrence::Model)>, AppError> {
+        let series = task::Entity::find()
+            .find_also_related(task_recurrence::Entity)
+            .filter(task::Column::UserId.eq(user_id))
+            .filter(task::Column::DeletedAt.is_null())
+            .filter(task::Column::BeginDate.lt(before))
+            .filter(task_recurrence::Column::Id.is_not_null())
+            .all(self.db)
             .await?;
+        Ok(series
+            .into_iter()
+            .filter_map(|(task, recurrence)| recurrence.map(|recurrence| (task, recurrence)))
+            .collect())
+    }
 
-        if overlapping_task.is_some() {
-            return Err(DbErr::Custom(
-                "Task time overlaps with an existing task.".to_string(),
-            ));
-        }
+    /// Tarefas pendentes, sem repetição, que começam em `[from, to)`,
+    /// ordenadas pelo início. As adiadas ficam onde estão.
+    pub async fn find_pending_starting_between(
+        &self,
+        user_id: i32,
+        from: DateTimeUtc,
+        to: DateTimeUtc,
+    ) -> Result<Vec<task::Model>, AppError> {
+        Ok(task::Entity::find()
+            .left_join(task_recurrence::Entity)
+            .filter(task::Column::UserId.eq(user_id))
+            .filter(task::Column::Status.eq(TaskStatus::Pendente))
+            .filter(task::Column::DeletedAt.is_null())
+            .filter(task::Column::BeginDate.gte(from))
+            .filter(task::Column::BeginDate.lt(to))
+            .filter(task_recurrence::Column::Id.is_null())
+            .order_by_asc(task::Column::BeginDate)
+            .order_by_asc(task::Column::Id)
+            .all(self.db)
+            .await?)
+    }
+
+    /// Tarefas pendentes, sem repetição, que terminaram antes de `now` e ainda
+    /// não foram marcadas como atrasadas, de todos os usuários.
+    pub async fn find_newly_overdue(&self, now: DateTimeUtc) -> Result<Vec<task::Model>, AppError> {
+        Ok(task::Entity::find()
+            .left_join(task_recurrence::Entity)
+            .filter(task::Column::Status.eq(TaskStatus::Pendente))
+            .filter(task::Column::OverdueAt.is_null())
+            .filter(task::Column::DeletedAt.is_null())
+            .filter(task::Column::CompleteDate.lt(now))
+            .filter(task_recurrence::Column::Id.is_null())
+            .order_by_asc(task::Column::UserId)
+            .order_by_asc(task::Column::BeginDate)
+            .all(self.db)
+            .await?)
+    }
+
+    /// Tarefas atrasadas do usuário que ainda não foram concluídas.
+    pub async fn find_overdue(&self, user_id: i32) -> Result<Vec<task::Model>, AppError> {
+        Ok(task::Entity::find()
+            .filter(task::Column::UserId.eq(user_id))
+            .filter(task::Column::OverdueAt.is_not_null())
+            .filter(task::Column::Status.ne(TaskStatus::Concluida))
+            .filter(task::Column::DeletedAt.is_null())
+            .order_by_asc(task::Column::CompleteDate)
+            .all(self.db)
+            .await?)
+    }
+
+    pub async fn mark_overdue(&self, task: task::Model, overdue_at: DateTimeUtc) -> Result<task::Model, AppError> {
+        let mut active_task = task.into_active_model();
+        active_task.overdue_at = Set(Some(overdue_at));
+        Ok(active_task.update(self.db).await?)
+    }
+
+    /// Tarefa do usuário que está na lixeira.
+    pub async fn find_deleted(&self, user_id: i32, id: i32) -> Result<Option<task::Model>, AppError> {
+        Ok(task::Entity::find_by_id(id)
+            .filter(task::Column::UserId.eq(user_id))
+            .filter(task::Column::DeletedAt.is_not_null())
+            .one(self.db)
+            .await?)
+    }
+
+    pub async fn find_recurrence(&self, task_id: i32) -> Result<Option<task_recurrence::Model>, AppError> {
+        Ok(task_recurrence::Entity::find()
+            .filter(task_recurrence::Column::TaskId.eq(task_id))
+            .one(self.db)
+            .await?)
+    }
+
+    /// Exceções das séries informadas que começam antes de `before`.
+    pub async fn find_exceptions(
+        &self,
+        task_ids: Vec<i32>,
+        before: DateTimeUtc,
+    ) -> Result<Vec<task_occurrence::Model>, AppError> {
+        Ok(task_occurrence::Entity::find()
+            .filter(task_occurrence::Column::TaskId.is_in(task_ids))
+            .filter(task_occurrence::Column::OccurrenceStart.lt(before))
+            .all(self.db)
+            .await?)
+    }
+
+    pub async fn find_exception(
+        &self,
+        task_id: i32,
+        occurrence_start: DateTimeUtc,
+    ) -> Result<Option<task_occurrence::Model>, AppError> {
+        Ok(task_occurrence::Entity::find()
+            .filter(task_occurrence::Column::TaskId.eq(task_id))
+            .filter(task_occurrence::Column::OccurrenceStart.eq(occurrence_start))
+            .one(self.db)
+            .await?)
+    }
 
+    pub async fn create_task(
+        &self,
+        task_info: &TaskDto,
+        user_id: i32,
+        begin_date: DateTimeUtc,
+        complete_date: DateTimeUtc,
+        task_type: &str,
+        category: Option<&category::Model>,
+    ) -> Result<task::Model, AppError> {
+        let created_at = Utc::now();
         let new_task = task::ActiveModel {
             title: Set(task_info.title.clone()),
             user_id: Set(user_id),
             description: Set(Some(task_info.description.clone())),
-            status: Set("Pendente".to_string()),
+            status: Set(TaskStatus::Pendente),
             begin_date: Set(begin_date),
             complete_date: Set(complete_date),
-            category: Set(task_info.category.clone()),
-            r#type: Set(task_info.r#type.clone()),
+            category: Set(category.map_or_else(|| task_info.category.clone(), |category| category.name.clone())),
+            category_id: Set(category.map(|category| category.id)),
+            r#type: Set(task_type.to_string()),
+            checklist_auto_complete: Set(task_info.checklist_auto_complete),
+            status_changed_at: Set(Some(created_at)),
             ..Default::default()
         };
-        new_task.insert(self.db).await
+        let task = new_task.insert(self.db).await?;
+        self.record_status_change(task.id, None, None, TaskStatus::Pendente, created_at).await?;
+        Ok(task)
     }
 
+    pub async fn create_recurrence(
+        &self,
+        task_id: i32,
+        rule: &RecurrenceRule,
+    ) -> Result<task_recurrence::Model, AppError> {
+        let recurrence = task_recurrence::ActiveModel {
+            task_id: Set(task_id),
+            frequency: Set(rule.frequency.as_str().to_string()),
+            interval: Set(rule.interval as i32),
+            weekdays: Set(rule.weekdays_value()),
+            until: Set(rule.until),
+            count: Set(rule.count.map(|count| count as i32)),
+            ..Default::default()
+        };
+        Ok(recurrence.insert(self.db).await?)
+    }
+
+    /// Atualiza a tarefa e, se `period` vier preenchido, a remarca com o novo
+    /// início, fim e tipo; a posse deve ter sido verificada pelo chamador. A
+    /// categoria já vem resolvida em `category`. Uma mudança de status precisa
+    /// ser uma transição aceita; ela grava os horários e entra no histórico.
     pub async fn update_task(
         &self,
-        id: i32,
+        task_to_update: task::Model,
         task_info: &TaskUpdateDto,
-        user_id: i32
-    ) -> Result<task::Model, DbErr> {
-        let task_to_update = self.find_by_id(id).await?
-            .ok_or(DbErr::RecordNotFound(format!("Task with id {} not found", id)))?;
-
-        if task_to_update.user_id != user_id {
-            return Err(DbErr::Custom(format!("Usuário {} não está autorizado a atualizar a tarefa {}", user_id, id)));
-        }
-
-        let status = if let Some(status) = &task_info.status {
-            match status.as_str() {
-                "Concluída" | "Adiada" | "Pendente" => Ok(status.clone()),
-                _ => Err(DbErr::Custom(format!("Status inválido: {}", status))),
-            }?
-        } else {
-            task_to_update.status.clone() // Manter o status existente
+        category: Option<&category::Model>,
+        period: Option<(DateTimeUtc, DateTimeUtc, String)>,
+    ) -> Result<task::Model, AppError> {
+        let previous_status = task_to_update.status;
+        let status = match &task_info.status {
+            Some(status) => validate_transition(previous_status, parse_status(status)?)?,
+            None => previous_status,
         };
+        let changed_at = Utc::now();
 
         let mut active_task = task_to_update.into_active_model();
 
-
         if let Some(title) = &task_info.title {
             active_task.title = Set(title.clone());
         }
 
-        match &task_info.description {
-            Some(desc) => active_task.description = Set(Some(desc.clone())),
-            None => {} // Manter a descrição existente
+        if let Some(desc) = &task_info.description {
+            active_task.description = Set(Some(desc.clone()));
         }
 
-        if let Some(category) = &task_info.category {
-            active_task.category = Set(category.clone());
+        if let Some(category) = category {
+            active_task.category = Set(category.name.clone());
+            active_task.category_id = Set(Some(category.id));
         }
 
-        active_task.status = Set(status);
-        active_task.update(self.db).await
+        if let Some(auto_complete) = task_info.checklist_auto_complete {
+            active_task.checklist_auto_complete = Set(auto_complete);
+        }
+
+        if let Some((begin_date, complete_date, task_type)) = period {
+            // Remarcada para o futuro, a tarefa deixa de estar atrasada.
+            if complete_date > changed_at {
+                active_task.overdue_at = Set(None);
+            }
+            active_task.begin_date = Set(begin_date);
+            active_task.complete_date = Set(complete_date);
+            active_task.r#type = Set(task_type);
+        }
+
+        if status != previous_status {
+            active_task.status = Set(status);
+            active_task.status_changed_at = Set(Some(changed_at));
+            match status {
+                TaskStatus::Concluida => active_task.completed_at = Set(Some(changed_at)),
+                TaskStatus::Adiada => active_task.postponed_at = Set(Some(changed_at)),
+                // Reabrir desfaz a conclusão; o último adiamento fica registrado.
+                TaskStatus::Pendente => active_task.completed_at = Set(None),
+            }
+        }
+        let task = active_task.update(self.db).await?;
+
+        if status != previous_status {
+            self.record_status_change(task.id, None, Some(previous_status), status, changed_at).await?;
+        }
+        Ok(task)
     }
 
-    pub async fn delete_task(&self, user_id: i32, id: i32) -> Result<DeleteResult, DbErr> {
-        let task_to_delete = self.find_by_id(id).await?
-            .ok_or(DbErr::RecordNotFound(format!("Task with id {} not found", id)))?;
+    /// Mudanças de status da tarefa e de suas ocorrências, da mais antiga à
+    /// mais recente.
+    pub async fn find_status_history(&self, task_id: i32) -> Result<Vec<task_status_history::Model>, AppError> {
+        Ok(task_status_history::Entity::find()
+            .filter(task_status_history::Column::TaskId.eq(task_id))
+            .order_by_asc(task_status_history::Column::ChangedAt)
+            .order_by_asc(task_status_history::Column::Id)
+            .all(self.db)
+            .await?)
+    }
 
-        if task_to_delete.user_id != user_id {
-            return Err(DbErr::Custom(format!("Usuário {} não está autorizado a excluir a tarefa {}", user_id, id)));
+    async fn record_status_change(
+        &self,
+        task_id: i32,
+        occurrence_start: Option<DateTimeUtc>,
+        from_status: Option<TaskStatus>,
+        to_status: TaskStatus,
+        changed_at: DateTimeUtc,
+    ) -> Result<task_status_history::Model, AppError> {
+        let entry = task_status_history::ActiveModel {
+            task_id: Set(task_id),
+            from_status: Set(from_status),
+            to_status: Set(to_status),
+            changed_at: Set(changed_at),
+            occurrence_start: Set(occurrence_start),
+            ..Default::default()
+        };
+        Ok(entry.insert(self.db).await?)
+    }
+
+    /// Move a tarefa para a lixeira; a posse deve ter sido verificada pelo chamador.
+    pub async fn delete_task(&self, task_to_delete: task::Model) -> Result<UpdateResult, AppError> {
+        Ok(task::Entity::update_many()
+            .col_expr(task::Column::DeletedAt, Expr::value(Utc::now()))
+            .filter(task::Column::Id.eq(task_to_delete.id))
+            .filter(task::Column::DeletedAt.is_null())
+            .exec(self.db)
+            .await?)
+    }
+
+    /// Remove as exceções de uma série, que deixam de valer quando ela é remarcada.
+    pub async fn delete_exceptions(&self, task_id: i32) -> Result<DeleteResult, AppError> {
+        Ok(task_occurrence::Entity::delete_many()
+            .filter(task_occurrence::Column::TaskId.eq(task_id))
+            .exec(self.db)
+            .await?)
+    }
+
+    /// Grava a exceção de uma ocorrência, criando-a na primeira edição. Campos
+    /// ausentes em `task_info` mantêm o valor anterior da exceção; a categoria
+    /// já vem resolvida em `category`. O status segue as mesmas regras da
+    /// tarefa, partindo do status atual da ocorrência, e a mudança entra no
+    /// histórico da série.
+    pub async fn upsert_exception(
+        &self,
+        task: &task::Model,
+        occurrence_start: DateTimeUtc,
+        task_info: &TaskUpdateDto,
+        category: Option<&category::Model>,
+        skipped: bool,
+    ) -> Result<task_occurrence::Model, AppError> {
+        let exception = self.find_exception(task.id, occurrence_start).await?;
+        let previous_status = exception.as_ref().and_then(|exception| exception.status).unwrap_or(task.status);
+        let status = match &task_info.status {
+            Some(status) => validate_transition(previous_status, parse_status(status)?)?,
+            None => previous_status,
+        };
+        let changed_at = Utc::now();
+
+        let mut active = match exception {
+            Some(exception) => exception.into_active_model(),
+            None => task_occurrence::ActiveModel {
+                task_id: Set(task.id),
+                occurrence_start: Set(occurrence_start),
+                ..Default::default()
+            },
+        };
+
+        active.skipped = Set(skipped);
+        if let Some(title) = &task_info.title {
+            active.title = Set(Some(title.clone()));
+        }
+        if let Some(desc) = &task_info.description {
+            active.description = Set(Some(desc.clone()));
+        }
+        if let Some(category) = category {
+            active.category = Set(Some(category.name.clone()));
+            active.category_id = Set(Some(category.id));
+        }
+        if status != previous_status {
+            active.status = Set(Some(status));
+            active.status_changed_at = Set(Some(changed_at));
+            match status {
+                TaskStatus::Concluida => active.completed_at = Set(Some(changed_at)),
+                TaskStatus::Adiada => active.postponed_at = Set(Some(changed_at)),
+                TaskStatus::Pendente => active.completed_at = Set(None),
+            }
+        }
+        let exception = active.save(self.db).await?.try_into_model()?;
+
+        if status != previous_status {
+            self.record_status_change(task.id, Some(occurrence_start), Some(previous_status), status, changed_at)
+                .await?;
         }
+        Ok(exception)
+    }
+}
 
-        task::Entity::delete_by_id(id).exec(self.db).await
+fn parse_status(status: &str) -> Result<TaskStatus, AppError> {
+    TaskStatus::parse(status).ok_or_else(|| AppError::field("status", format!("Status inválido: {}", status)))
+}
+
+fn validate_transition(from: TaskStatus, to: TaskStatus) -> Result<TaskStatus, AppError> {
+    if from == to || from.can_become(to) {
+        Ok(to)
+    } else {
+        Err(AppError::field(
+            "status",
+            format!("Uma tarefa {} não pode passar para {}.", from.as_str(), to.as_str()),
+        ))
     }
-}
\ No newline at end of file
+}
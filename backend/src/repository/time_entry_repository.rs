@@ -0,0 +1,104 @@
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, IntoActiveModel, QueryFilter, QueryOrder, QuerySelect, Set};
+use sea_orm::prelude::DateTimeUtc;
+use chrono::Utc;
+use std::collections::HashMap;
+use crate::entity::time_entry;
+use crate::errors::AppError;
+
+pub struct TimeEntryRepository<'a> {
+    db: &'a DatabaseConnection,
+}
+
+impl<'a> TimeEntryRepository<'a> {
+    pub fn new(db: &'a DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Registro em andamento do usuário; há no máximo um.
+    pub async fn find_running(&self, user_id: i32) -> Result<Option<time_entry::Model>, AppError> {
+        Ok(time_entry::Entity::find()
+            .filter(time_entry::Column::UserId.eq(user_id))
+            .filter(time_entry::Column::StoppedAt.is_null())
+            .one(self.db)
+            .await?)
+    }
+
+    /// Registros da tarefa, dos mais recentes aos mais antigos.
+    pub async fn find_by_task_id(&self, task_id: i32) -> Result<Vec<time_entry::Model>, AppError> {
+        Ok(time_entry::Entity::find()
+            .filter(time_entry::Column::TaskId.eq(task_id))
+            .order_by_desc(time_entry::Column::StartedAt)
+            .order_by_desc(time_entry::Column::Id)
+            .all(self.db)
+            .await?)
+    }
+
+    pub async fn find_entry(&self, task_id: i32, entry_id: i32) -> Result<Option<time_entry::Model>, AppError> {
+        Ok(time_entry::Entity::find_by_id(entry_id)
+            .filter(time_entry::Column::TaskId.eq(task_id))
+            .one(self.db)
+            .await?)
+    }
+
+    /// Segundos registrados em cada tarefa, contando os registros em andamento
+    /// até agora; tarefas sem registros ficam de fora.
+    pub async fn total_by_task_ids(&self, task_ids: Vec<i32>) -> Result<HashMap<i32, i64>, AppError> {
+        let entries: Vec<(i32, DateTimeUtc, Option<DateTimeUtc>)> = time_entry::Entity::find()
+            .select_only()
+            .column(time_entry::Column::TaskId)
+            .column(time_entry::Column::StartedAt)
+            .column(time_entry::Column::StoppedAt)
+            .filter(time_entry::Column::TaskId.is_in(task_ids))
+            .into_tuple()
+            .all(self.db)
+            .await?;
+
+        let now = Utc::now();
+        let mut totals = HashMap::new();
+        for (task_id, started_at, stopped_at) in entries {
+            let seconds = (stopped_at.unwrap_or(now) - started_at).num_seconds().max(0);
+            *totals.entry(task_id).or_insert(0) += seconds;
+        }
+        Ok(totals)
+    }
+
+    pub async fn create_entry(
+        &self,
+        user_id: i32,
+        task_id: i32,
+        started_at: DateTimeUtc,
+        stopped_at: Option<DateTimeUtc>,
+        note: Option<String>,
+    ) -> Result<time_entry::Model, AppError> {
+        let entry = time_entry::ActiveModel {
+            user_id: Set(user_id),
+            task_id: Set(task_id),
+            started_at: Set(started_at),
+            stopped_at: Set(stopped_at),
+            note: Set(note),
+            ..Default::default()
+        };
+        Ok(entry.insert(self.db).await?)
+    }
+
+    pub async fn update_entry(
+        &self,
+        entry: time_entry::Model,
+        started_at: DateTimeUtc,
+        stopped_at: Option<DateTimeUtc>,
+        note: Option<Option<String>>,
+    ) -> Result<time_entry::Model, AppError> {
+        let mut active = entry.into_active_model();
+        active.started_at = Set(started_at);
+        active.stopped_at = Set(stopped_at);
+        if let Some(note) = note {
+            active.note = Set(note);
+        }
+        Ok(active.update(self.db).await?)
+    }
+
+    pub async fn delete_entry(&self, entry: time_entry::Model) -> Result<(), AppError> {
+        time_entry::Entity::delete_by_id(entry.id).exec(self.db).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+use sea_orm::prelude::DateTimeUtc;
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbBackend, FromQueryResult, Statement, Value};
+use crate::dto::trash_dto::TrashItemDto;
+use crate::errors::AppError;
+
+/// Itens na lixeira do usuário, dos excluídos por último aos mais antigos,
+/// com a data em que serão removidos após `$2` dias.
+const TRASH_SQL: &str = r#"
+SELECT kind, id, title, deleted_at, deleted_at + make_interval(days => $2) AS purge_at
+FROM (
+    SELECT 'task' AS kind, id, title, deleted_at FROM "task"
+    WHERE user_id = $1 AND deleted_at IS NOT NULL
+    UNION ALL
+    SELECT 'goal', id, name, deleted_at FROM "goal"
+    WHERE user_id = $1 AND deleted_at IS NOT NULL
+    UNION ALL
+    SELECT 'reminder', id, name, deleted_at FROM "reminder"
+    WHERE user_id = $1 AND deleted_at IS NOT NULL
+    UNION ALL
+    SELECT 'note', id, left(text, 80), deleted_at FROM "notes"
+    WHERE user_id = $1 AND deleted_at IS NOT NULL
+) AS trash
+ORDER BY deleted_at DESC, id DESC
+"#;
+
+/// Tipos de registro que passam pela lixeira.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum TrashKind {
+    Task,
+    Goal,
+    Reminder,
+    Note,
+}
+
+impl TrashKind {
+    pub const ALL: [TrashKind; 4] = [TrashKind::Task, TrashKind::Goal, TrashKind::Reminder, TrashKind::Note];
+
+    pub fn parse(kind: &str) -> Option<Self> {
+        match kind {
+            "task" => Some(TrashKind::Task),
+            "goal" => Some(TrashKind::Goal),
+            "reminder" => Some(TrashKind::Reminder),
+            "note" => Some(TrashKind::Note),
+            _ => None,
+        }
+    }
+
+    fn table(self) -> &'static str {
+        match self {
+            TrashKind::Task => "task",
+            TrashKind::Goal => "goal",
+            TrashKind::Reminder => "reminder",
+            TrashKind::Note => "notes",
+        }
+    }
+}
+
+pub struct TrashRepository<'a> {
+    db: &'a DatabaseConnection,
+}
+
+impl<'a> TrashRepository<'a> {
+    pub fn new(db: &'a DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    pub async fn find_by_user_id(&self, user_id: i32, retention_days: i32) -> Result<Vec<TrashItemDto>, AppError> {
+        let statement = Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            TRASH_SQL,
+            [user_id.into(), retention_days.into()],
+        );
+        Ok(TrashItemDto::find_by_statement(statement).all(self.db).await?)
+    }
+
+    /// Tira o item da lixeira; retorna quantos registros voltaram (0 ou 1).
+    pub async fn restore(&self, kind: TrashKind, user_id: i32, id: i32) -> Result<u64, AppError> {
+        let sql = format!(
+            r#"UPDATE "{}" SET deleted_at = NULL WHERE id = $1 AND user_id = $2 AND deleted_at IS NOT NULL"#,
+            kind.table()
+        );
+        self.execute(&sql, [id.into(), user_id.into()]).await
+    }
+
+    /// Remove de vez um item da lixeira; retorna quantos registros saíram (0 ou 1).
+    pub async fn purge(&self, kind: TrashKind, user_id: i32, id: i32) -> Result<u64, AppError> {
+        let sql = format!(
+            r#"DELETE FROM "{}" WHERE id = $1 AND user_id = $2 AND deleted_at IS NOT NULL"#,
+            kind.table()
+        );
+        self.execute(&sql, [id.into(), user_id.into()]).await
+    }
+
+    /// Esvazia a lixeira do usuário.
+    pub async fn purge_by_user_id(&self, user_id: i32) -> Result<u64, AppError> {
+        let mut purged = 0;
+        for kind in TrashKind::ALL {
+            let sql = format!(r#"DELETE FROM "{}" WHERE user_id = $1 AND deleted_at IS NOT NULL"#, kind.table());
+            purged += self.execute(&sql, [user_id.into()]).await?;
+        }
+        Ok(purged)
+    }
+
+    /// Remove de vez, de todos os usuários, os itens excluídos antes de `cutoff`.
+    pub async fn purge_deleted_before(&self, cutoff: DateTimeUtc) -> Result<u64, AppError> {
+        let mut purged = 0;
+        for kind in TrashKind::ALL {
+            let sql = format!(r#"DELETE FROM "{}" WHERE deleted_at < $1"#, kind.table());
+            purged += self.execute(&sql, [cutoff.into()]).await?;
+        }
+        Ok(purged)
+    }
+
+    async fn execute<const N: usize>(&self, sql: &str, values: [Value; N]) -> Result<u64, AppError> {
+        let statement = Statement::from_sql_and_values(DbBackend::Postgres, sql, values);
+        Ok(self.db.execute(statement).await?.rows_affected())
+    }
+}
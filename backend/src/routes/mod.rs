@@ -5,12 +5,28 @@ use crate::controller::reminder;
 use crate::controller::goal;
 use crate::controller::auth;
 use crate::controller::report;
+use crate::controller::personal_access_token;
+use crate::controller::profile;
+use crate::controller::category;
+use crate::controller::search;
+use crate::controller::trash;
+use crate::controller::pomodoro;
 
 pub fn get_auth_routes() -> Vec<rocket::Route> {
     routes![
         auth::login,
+        auth::login_two_factor,
         auth::register,
-        auth::user_info,
+        auth::refresh,
+        auth::logout,
+        auth::change_password,
+        auth::change_username,
+        auth::delete_account,
+        auth::enroll_two_factor,
+        auth::confirm_two_factor,
+        auth::disable_two_factor,
+        auth::request_password_reset,
+        auth::confirm_password_reset,
         auth::index
     ]
 }
@@ -30,8 +46,30 @@ pub fn get_task_routes() -> Vec<rocket::Route> {
         task::get_task_by_id,
         task::register_task,
         task::update_task,
+        task::bulk_update_tasks,
+        task::rollover_tasks,
         task::delete_task,
-        task::get_tasks_by_user_id
+        task::list_tasks,
+        task::get_task_occurrences,
+        task::update_task_occurrence,
+        task::skip_task_occurrence,
+        task::get_checklist,
+        task::create_checklist_item,
+        task::update_checklist_item,
+        task::delete_checklist_item,
+        task::get_running_timer,
+        task::start_timer,
+        task::stop_timer,
+        task::get_time_entries,
+        task::create_time_entry,
+        task::update_time_entry,
+        task::delete_time_entry,
+        task::get_dependency_graph,
+        task::get_overdue_tasks,
+        task::get_task_blockers,
+        task::add_task_blocker,
+        task::remove_task_blocker,
+        task::get_task_status_history
     ]
 }
 
@@ -63,4 +101,52 @@ pub fn get_report_routes() -> Vec<rocket::Route> {
         report::get_stats_month,
         report::get_stats_week
     ]
-}
\ No newline at end of file
+}
+
+pub fn get_personal_access_token_routes() -> Vec<rocket::Route> {
+    routes![
+        personal_access_token::list_tokens,
+        personal_access_token::create_token,
+        personal_access_token::revoke_token
+    ]
+}
+
+pub fn get_profile_routes() -> Vec<rocket::Route> {
+    routes![
+        profile::get_profile,
+        profile::update_profile
+    ]
+}
+
+pub fn get_category_routes() -> Vec<rocket::Route> {
+    routes![
+        category::list_categories,
+        category::get_category,
+        category::create_category,
+        category::update_category,
+        category::delete_category
+    ]
+}
+
+pub fn get_search_routes() -> Vec<rocket::Route> {
+    routes![search::search]
+}
+
+pub fn get_trash_routes() -> Vec<rocket::Route> {
+    routes![
+        trash::list_trash,
+        trash::restore_item,
+        trash::purge_item,
+        trash::empty_trash
+    ]
+}
+
+pub fn get_pomodoro_routes() -> Vec<rocket::Route> {
+    routes![
+        pomodoro::get_pomodoro_state,
+        pomodoro::update_pomodoro_settings,
+        pomodoro::list_pomodoro_sessions,
+        pomodoro::start_pomodoro_session,
+        pomodoro::stop_pomodoro_session
+    ]
+}
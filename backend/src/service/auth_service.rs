@@ -1,20 +1,26 @@
+use crate::credential_policy::CredentialPolicy;
 use crate::db::Pool;
-use crate::dto::auth_dto::AuthDto;
-use crate::repository::auth_repository::{self, UserError};
-use crate::entity::user;
+use crate::dto::auth_dto::{AuthDto, ChangePasswordDto, ChangeUsernameDto, DeleteAccountDto};
+use crate::errors::{AppError, FieldErrors};
+use crate::repository::auth_repository;
+use crate::repository::session_repository;
+use crate::entity::{session, user};
+use chrono::{Duration, Utc};
+use rand::RngCore;
 use rocket::State;
-#[allow(dead_code)]
+use sha2::{Digest, Sha256};
+
+/// Tempo de vida de um refresh token; renovado a cada rotação.
+const REFRESH_TOKEN_TTL_DAYS: i64 = 30;
 
 pub async fn register_user(
     db: &State<Pool>,
     user_info: &AuthDto,
-) -> Result<user::Model, UserError> {
-    if !validate_user_fields(user_info) {
-        return Err(UserError::InvalidUser("Invalid Fields".to_string()));
-    }
+) -> Result<user::Model, AppError> {
+    validate_user_fields(user_info)?;
 
-    if validate_username_exists(db, user_info).await? {
-        return Err(UserError::UserAlreadyExists(
+    if validate_username_exists(db, &user_info.username).await? {
+        return Err(AppError::Conflict(
             "Username already been used".to_string(),
         ));
     }
@@ -25,12 +31,12 @@ pub async fn register_user(
 pub async fn login_user(
     db: &State<Pool>,
     user_info: &AuthDto,
-) -> Result<user::Model, UserError> {
+) -> Result<user::Model, AppError> {
     auth_repository::find_user_by_credentials(db, user_info).await
 }
 
-async fn validate_username_exists(db: &State<Pool>, user_info: &AuthDto) -> Result<bool, UserError> {
-    let exist_user = auth_repository::find_by_username(db, &user_info.username).await?;
+async fn validate_username_exists(db: &Pool, username: &str) -> Result<bool, AppError> {
+    let exist_user = auth_repository::find_by_username(db, username).await?;
 
     match exist_user {
         Some(_) => Ok(true),
@@ -38,9 +44,169 @@ async fn validate_username_exists(db: &State<Pool>, user_info: &AuthDto) -> Resu
     }
 }
 
-fn validate_user_fields(user_info: &AuthDto) -> bool {
-    if user_info.username.len() < 5 || user_info.password.len() < 5 {
-        return false;
+fn validate_user_fields(user_info: &AuthDto) -> Result<(), AppError> {
+    let mut field_errors = FieldErrors::new();
+    username_errors("username", &user_info.username, &mut field_errors);
+    password_errors("password", &user_info.password, &user_info.username, &mut field_errors);
+    into_validation_result(field_errors)
+}
+
+fn username_errors(field: &str, username: &str, field_errors: &mut FieldErrors) {
+    let errors = CredentialPolicy::global().username_errors(username);
+    if !errors.is_empty() {
+        field_errors.insert(field.to_string(), errors);
+    }
+}
+
+fn password_errors(field: &str, password: &str, username: &str, field_errors: &mut FieldErrors) {
+    let errors = CredentialPolicy::global().password_errors(password, Some(username));
+    if !errors.is_empty() {
+        field_errors.insert(field.to_string(), errors);
+    }
+}
+
+/// Valida uma nova senha do usuário com as mesmas regras do cadastro.
+pub fn validate_new_password(field: &str, password: &str, user: &user::Model) -> Result<(), AppError> {
+    let mut field_errors = FieldErrors::new();
+    password_errors(field, password, &user.username, &mut field_errors);
+    into_validation_result(field_errors)
+}
+
+fn into_validation_result(field_errors: FieldErrors) -> Result<(), AppError> {
+    if field_errors.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::Validation {
+            message: "Invalid Fields".to_string(),
+            field_errors,
+        })
+    }
+}
+
+pub async fn find_user(db: &Pool, user_id: i32) -> Result<user::Model, AppError> {
+    auth_repository::find_by_id(db, user_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User not found".to_string()))
+}
+
+/// Exige a senha atual antes de operações sensíveis na conta.
+pub fn confirm_password(user: &user::Model, password: &str, field: &str) -> Result<(), AppError> {
+    if auth_repository::verify_password(user, password)? {
+        Ok(())
+    } else {
+        Err(AppError::field(field, "Incorrect password"))
+    }
+}
+
+/// Troca a senha e revoga as demais sessões do usuário, mantendo a atual.
+pub async fn change_password(
+    db: &Pool,
+    user_id: i32,
+    session_id: i32,
+    dto: &ChangePasswordDto,
+) -> Result<(), AppError> {
+    let user = find_user(db, user_id).await?;
+    validate_new_password("new_password", &dto.new_password, &user)?;
+    confirm_password(&user, &dto.current_password, "current_password")?;
+
+    auth_repository::update_password(db, user, &dto.new_password).await?;
+    session_repository::revoke_user_sessions(db, user_id, Some(session_id)).await
+}
+
+pub async fn change_username(
+    db: &Pool,
+    user_id: i32,
+    dto: &ChangeUsernameDto,
+) -> Result<user::Model, AppError> {
+    let mut field_errors = FieldErrors::new();
+    username_errors("username", &dto.username, &mut field_errors);
+    into_validation_result(field_errors)?;
+
+    let user = find_user(db, user_id).await?;
+    if user.username == dto.username {
+        return Ok(user);
+    }
+    if validate_username_exists(db, &dto.username).await? {
+        return Err(AppError::Conflict(
+            "Username already been used".to_string(),
+        ));
+    }
+
+    auth_repository::update_username(db, user, &dto.username).await
+}
+
+/// Apaga a conta e, em cascata, todos os dados do usuário.
+pub async fn delete_account(
+    db: &Pool,
+    user_id: i32,
+    dto: &DeleteAccountDto,
+) -> Result<(), AppError> {
+    let user = find_user(db, user_id).await?;
+    confirm_password(&user, &dto.password, "password")?;
+    auth_repository::delete_user(db, user).await
+}
+
+/// Abre uma nova sessão para o usuário, retornando o refresh token em texto puro.
+/// Apenas o hash do token é persistido.
+pub async fn start_session(db: &Pool, user_id: i32) -> Result<(session::Model, String), AppError> {
+    let refresh_token = generate_token();
+    let expires_at = Utc::now() + Duration::days(REFRESH_TOKEN_TTL_DAYS);
+    let session = session_repository::create_session(db, user_id, hash_token(&refresh_token), expires_at).await?;
+    Ok((session, refresh_token))
+}
+
+/// Valida o refresh token e o rotaciona: o token apresentado deixa de ser aceito.
+/// Reapresentar um token já rotacionado (reuso ou corrida entre duas
+/// renovações) revoga a sessão inteira.
+pub async fn refresh_session(db: &Pool, refresh_token: &str) -> Result<(session::Model, String), AppError> {
+    let invalid = || AppError::Unauthorized("Invalid or expired refresh token".to_string());
+    let token_hash = hash_token(refresh_token);
+
+    let Some(session) = session_repository::find_by_refresh_token_hash(db, &token_hash).await? else {
+        if let Some(reused) = session_repository::find_by_previous_refresh_token_hash(db, &token_hash).await? {
+            log::warn!("auth: rotated refresh token reused, revoking session {}", reused.id);
+            session_repository::revoke_session(db, reused.id).await?;
+        }
+        return Err(invalid());
+    };
+
+    if !is_active(&session) {
+        return Err(invalid());
     }
-    true
-}
\ No newline at end of file
+
+    let session_id = session.id;
+    let new_refresh_token = generate_token();
+    let expires_at = Utc::now() + Duration::days(REFRESH_TOKEN_TTL_DAYS);
+    match session_repository::rotate_refresh_token(db, session, hash_token(&new_refresh_token), expires_at).await? {
+        Some(session) => Ok((session, new_refresh_token)),
+        None => {
+            log::warn!("auth: concurrent refresh token rotation, revoking session {}", session_id);
+            session_repository::revoke_session(db, session_id).await?;
+            Err(invalid())
+        }
+    }
+}
+
+pub async fn end_session(db: &Pool, session_id: i32) -> Result<(), AppError> {
+    session_repository::revoke_session(db, session_id).await
+}
+
+/// Verifica se a sessão existe, pertence ao usuário e não foi revogada nem expirou.
+pub async fn is_session_active(db: &Pool, session_id: i32, user_id: i32) -> Result<bool, AppError> {
+    let session = session_repository::find_by_id(db, session_id).await?;
+    Ok(matches!(session, Some(s) if s.user_id == user_id && is_active(&s)))
+}
+
+fn is_active(session: &session::Model) -> bool {
+    session.revoked_at.is_none() && session.expires_at > Utc::now()
+}
+
+pub fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+pub fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
@@ -0,0 +1,124 @@
+//! Categorias do usuário, compartilhadas por tarefas, metas e lembretes.
+//!
+//! Os nomes são únicos por usuário sem diferenciar maiúsculas. Tarefas, metas e
+//! lembretes guardam o `category_id` e uma cópia do nome, mantida em dia quando
+//! a categoria é renomeada. Um nome desconhecido enviado sem `category_id` cria
+//! a categoria com a cor padrão; ao excluir uma categoria, os registros mantêm
+//! apenas o nome.
+
+use rocket::State;
+use crate::db::Pool;
+use crate::dto::category_dto::{CategoryDto, CategoryUpdateDto};
+use crate::entity::category;
+use crate::errors::AppError;
+use crate::repository::category_repository::CategoryRepository;
+use validator::Validate;
+
+const DEFAULT_COLOR: &str = "#000080";
+
+pub async fn list_categories_db(db: &State<Pool>, user_id: i32) -> Result<Vec<category::Model>, AppError> {
+    CategoryRepository::new(db.inner()).find_by_user_id(user_id).await
+}
+
+pub async fn create_category_db(
+    db: &State<Pool>,
+    user_id: i32,
+    category_info: &CategoryDto,
+) -> Result<category::Model, AppError> {
+    category_info.validate()?;
+    validate_color(category_info.color.as_deref())?;
+    let repo = CategoryRepository::new(db.inner());
+    let name = category_info.name.trim();
+    ensure_name_available(&repo, user_id, name, None).await?;
+
+    repo.create(
+        user_id,
+        name,
+        category_info.color.as_deref().unwrap_or(DEFAULT_COLOR),
+        category_info.icon.clone().filter(|icon| !icon.is_empty()),
+    )
+    .await
+}
+
+/// Atualiza a categoria; um nome novo é propagado para os registros que a usam.
+pub async fn update_category_db(
+    db: &State<Pool>,
+    category: category::Model,
+    category_info: &CategoryUpdateDto,
+) -> Result<category::Model, AppError> {
+    category_info.validate()?;
+    validate_color(category_info.color.as_deref())?;
+    let repo = CategoryRepository::new(db.inner());
+    let name = category_info.name.as_deref().map(str::trim);
+    if let Some(name) = name {
+        ensure_name_available(&repo, category.user_id, name, Some(category.id)).await?;
+    }
+
+    let renamed = name.is_some_and(|name| name != category.name);
+    let icon = category_info
+        .icon
+        .clone()
+        .map(|icon| Some(icon).filter(|icon| !icon.is_empty()));
+    let category = repo
+        .update(category, name, category_info.color.as_deref(), icon)
+        .await?;
+    if renamed {
+        repo.rename_references(category.id, &category.name).await?;
+    }
+    Ok(category)
+}
+
+pub async fn delete_category_db(db: &State<Pool>, category: category::Model) -> Result<(), AppError> {
+    CategoryRepository::new(db.inner()).delete(category).await
+}
+
+/// Categoria de um registro: a de `category_id`, se informado, ou a de mesmo
+/// nome, criada se ainda não existir. Sem nenhum dos dois, não há categoria.
+pub async fn resolve_category(
+    db: &Pool,
+    user_id: i32,
+    category_id: Option<i32>,
+    name: Option<&str>,
+) -> Result<Option<category::Model>, AppError> {
+    let repo = CategoryRepository::new(db);
+
+    if let Some(category_id) = category_id {
+        return match repo.find_by_id(user_id, category_id).await? {
+            Some(category) => Ok(Some(category)),
+            None => Err(AppError::field("category_id", "Categoria não encontrada.")),
+        };
+    }
+
+    let Some(name) = name.map(str::trim).filter(|name| !name.is_empty()) else {
+        return Ok(None);
+    };
+    match repo.find_by_name(user_id, name).await? {
+        Some(category) => Ok(Some(category)),
+        None => Ok(Some(repo.create(user_id, name, DEFAULT_COLOR, None).await?)),
+    }
+}
+
+async fn ensure_name_available(
+    repo: &CategoryRepository<'_>,
+    user_id: i32,
+    name: &str,
+    ignore_id: Option<i32>,
+) -> Result<(), AppError> {
+    match repo.find_by_name(user_id, name).await? {
+        Some(existing) if Some(existing.id) != ignore_id => {
+            Err(AppError::Conflict(format!("Category '{}' already exists", existing.name)))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn validate_color(color: Option<&str>) -> Result<(), AppError> {
+    let valid = color.is_none_or(|color| {
+        color.len() == 7 && color.starts_with('#') && color[1..].chars().all(|c| c.is_ascii_hexdigit())
+    });
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::field("color", "Use o formato #RRGGBB."))
+    }
+}
@@ -0,0 +1,125 @@
+//! Checklist das tarefas: itens ordenados que podem ser marcados como feitos.
+//!
+//! Com `checklist_auto_complete` ligado, a tarefa passa para `Concluída` assim
+//! que todos os itens estiverem marcados. Desmarcar um item depois disso não
+//! reabre a tarefa. Tarefas repetidas nunca são concluídas automaticamente,
+//! já que o status vale para a série inteira, nem as bloqueadas por outras.
+
+use rocket::State;
+use crate::db::Pool;
+use crate::dto::checklist_dto::{ChecklistItemDto, ChecklistItemUpdateDto, ChecklistProgressDto};
+use crate::dto::task_update_dto::TaskUpdateDto;
+use crate::entity::sea_orm_active_enums::TaskStatus;
+use crate::entity::{checklist_item, task};
+use crate::errors::AppError;
+use crate::repository::checklist_repository::ChecklistRepository;
+use crate::repository::task_repository::TaskRepository;
+use crate::service::dependency_service;
+use std::collections::HashMap;
+use validator::Validate;
+
+pub async fn list_items_db(db: &State<Pool>, task: &task::Model) -> Result<Vec<checklist_item::Model>, AppError> {
+    ChecklistRepository::new(db.inner()).find_by_task_id(task.id).await
+}
+
+pub async fn create_item_db(
+    db: &State<Pool>,
+    task: &task::Model,
+    item_info: &ChecklistItemDto,
+) -> Result<checklist_item::Model, AppError> {
+    item_info.validate()?;
+    let title = required_title(&item_info.title)?;
+    ChecklistRepository::new(db.inner()).create_item(task.id, title).await
+}
+
+/// Renomeia, marca/desmarca ou move o item; marcar o último item pendente
+/// pode concluir a tarefa.
+pub async fn update_item_db(
+    db: &State<Pool>,
+    task: task::Model,
+    item_id: i32,
+    item_info: &ChecklistItemUpdateDto,
+) -> Result<checklist_item::Model, AppError> {
+    item_info.validate()?;
+    let title = item_info.title.as_deref().map(required_title).transpose()?;
+    let conn = db.inner();
+    let repo = ChecklistRepository::new(conn);
+    let item = find_item(&repo, &task, item_id).await?;
+
+    let mut item = repo.update_item(item, title, item_info.done).await?;
+    if let Some(position) = item_info.position {
+        repo.move_item(&item, position).await?;
+        item = find_item(&repo, &task, item_id).await?;
+    }
+
+    if item_info.done == Some(true) {
+        apply_auto_complete(conn, task).await?;
+    }
+    Ok(item)
+}
+
+/// Remove o item; se só restarem itens marcados, a tarefa pode ser concluída.
+pub async fn delete_item_db(db: &State<Pool>, task: task::Model, item_id: i32) -> Result<(), AppError> {
+    let conn = db.inner();
+    let repo = ChecklistRepository::new(conn);
+    let item = find_item(&repo, &task, item_id).await?;
+    repo.delete_item(item).await?;
+    apply_auto_complete(conn, task).await?;
+    Ok(())
+}
+
+/// Conclui a tarefa se a conclusão automática estiver ligada e todos os itens
+/// do checklist estiverem marcados.
+pub async fn apply_auto_complete(db: &Pool, task: task::Model) -> Result<task::Model, AppError> {
+    if !task.checklist_auto_complete || task.status == TaskStatus::Concluida {
+        return Ok(task);
+    }
+
+    let task_repo = TaskRepository::new(db);
+    if task_repo.find_recurrence(task.id).await?.is_some() {
+        return Ok(task);
+    }
+
+    let items = ChecklistRepository::new(db).find_by_task_id(task.id).await?;
+    if items.is_empty() || items.iter().any(|item| !item.done) {
+        return Ok(task);
+    }
+
+    // Uma tarefa bloqueada continua pendente até os bloqueadores serem concluídos.
+    if !dependency_service::pending_blockers_by_task_ids(db, vec![task.id]).await?.is_empty() {
+        return Ok(task);
+    }
+
+    let status = TaskUpdateDto {
+        status: Some(TaskStatus::Concluida.as_str().to_string()),
+        ..Default::default()
+    };
+    task_repo.update_task(task, &status, None, None).await
+}
+
+/// Progresso do checklist de cada tarefa; tarefas sem itens ficam com zero.
+pub async fn progress_by_task_ids(db: &Pool, task_ids: Vec<i32>) -> Result<HashMap<i32, ChecklistProgressDto>, AppError> {
+    let counts = ChecklistRepository::new(db).count_by_task_ids(task_ids).await?;
+    Ok(counts
+        .into_iter()
+        .map(|(task_id, (total, done))| (task_id, ChecklistProgressDto::new(total, done)))
+        .collect())
+}
+
+async fn find_item(
+    repo: &ChecklistRepository<'_>,
+    task: &task::Model,
+    item_id: i32,
+) -> Result<checklist_item::Model, AppError> {
+    repo.find_item(task.id, item_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Checklist item {} of task {} not found", item_id, task.id)))
+}
+
+fn required_title(title: &str) -> Result<&str, AppError> {
+    let title = title.trim();
+    if title.is_empty() {
+        return Err(AppError::field("title", "O item não pode estar vazio."));
+    }
+    Ok(title)
+}
@@ -0,0 +1,216 @@
+//! Dependências entre tarefas: uma tarefa pode ser bloqueada por outras do
+//! mesmo usuário e só pode ser concluída depois que todos os seus bloqueadores
+//! estiverem `Concluída`. Dependências que formariam um ciclo são recusadas.
+
+use rocket::State;
+use crate::db::Pool;
+use crate::dto::task_dependency_dto::{BlockerDto, DependencyEdgeDto, DependencyGraphDto, DependencyNodeDto, TaskDependencyDto};
+use crate::entity::sea_orm_active_enums::TaskStatus;
+use crate::entity::{task, task_dependency};
+use crate::errors::AppError;
+use crate::repository::dependency_repository::DependencyRepository;
+use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter};
+use std::collections::{HashMap, HashSet};
+
+
+pub async fn list_blockers_db(db: &State<Pool>, task: &task::Model) -> Result<Vec<task::Model>, AppError> {
+    DependencyRepository::new(db.inner()).find_blockers(task.id).await
+}
+
+/// Marca `task` como bloqueada por `blocked_by_id`.
+pub async fn add_blocker_db(
+    db: &State<Pool>,
+    task: &task::Model,
+    dependency_info: &TaskDependencyDto,
+) -> Result<task_dependency::Model, AppError> {
+    let conn = db.inner();
+    let repo = DependencyRepository::new(conn);
+    let blocked_by_id = dependency_info.blocked_by_id;
+
+    if blocked_by_id == task.id {
+        return Err(AppError::field("blocked_by_id", "Uma tarefa não pode bloquear a si mesma."));
+    }
+
+    let blocker = task::Entity::find_by_id(blocked_by_id)
+        .filter(task::Column::UserId.eq(task.user_id))
+        .filter(task::Column::DeletedAt.is_null())
+        .one(conn)
+        .await?;
+    if blocker.is_none() {
+        return Err(AppError::NotFound(format!("Task with id {} not found", blocked_by_id)));
+    }
+
+    if repo.find(task.id, blocked_by_id).await?.is_some() {
+        return Err(AppError::Conflict(format!(
+            "Task {} is already blocked by task {}",
+            task.id, blocked_by_id
+        )));
+    }
+
+    let dependencies = repo.find_by_user_id(task.user_id).await?;
+    if creates_cycle(&dependencies, task.id, blocked_by_id) {
+        return Err(AppError::field(
+            "blocked_by_id",
+            "Essa dependência criaria um ciclo entre as tarefas.",
+        ));
+    }
+
+    repo.create(task.id, blocked_by_id).await
+}
+
+pub async fn remove_blocker_db(db: &State<Pool>, task: &task::Model, blocked_by_id: i32) -> Result<(), AppError> {
+    let repo = DependencyRepository::new(db.inner());
+    let dependency = repo.find(task.id, blocked_by_id).await?.ok_or_else(|| {
+        AppError::NotFound(format!("Task {} is not blocked by task {}", task.id, blocked_by_id))
+    })?;
+    repo.delete(dependency).await
+}
+
+/// Grafo com as tarefas do usuário que têm ou são dependências.
+pub async fn dependency_graph_db(db: &State<Pool>, user_id: i32) -> Result<DependencyGraphDto, AppError> {
+    let conn = db.inner();
+    let mut dependencies = DependencyRepository::new(conn).find_by_user_id(user_id).await?;
+
+    let task_ids: HashSet<i32> = dependencies
+        .iter()
+        .flat_map(|dependency| [dependency.task_id, dependency.blocked_by_id])
+        .collect();
+    let tasks = task::Entity::find()
+        .filter(task::Column::Id.is_in(task_ids))
+        .filter(task::Column::DeletedAt.is_null())
+        .all(conn)
+        .await?;
+    // As dependências de tarefas na lixeira voltam com elas, mas não aparecem no grafo.
+    let live: HashSet<i32> = tasks.iter().map(|task| task.id).collect();
+    dependencies.retain(|dependency| live.contains(&dependency.task_id) && live.contains(&dependency.blocked_by_id));
+    let done: HashSet<i32> = tasks
+        .iter()
+        .filter(|task| task.status == TaskStatus::Concluida)
+        .map(|task| task.id)
+        .collect();
+
+    let nodes = tasks
+        .iter()
+        .map(|task| DependencyNodeDto {
+            id: task.id,
+            title: task.title.clone(),
+            status: task.status,
+            blocked: dependencies
+                .iter()
+                .any(|dependency| dependency.task_id == task.id && !done.contains(&dependency.blocked_by_id)),
+        })
+        .collect();
+    let edges = dependencies
+        .into_iter()
+        .map(|dependency| DependencyEdgeDto {
+            task_id: dependency.task_id,
+            blocked_by_id: dependency.blocked_by_id,
+        })
+        .collect();
+    Ok(DependencyGraphDto { nodes, edges })
+}
+
+/// Bloqueadores pendentes de cada tarefa; tarefas livres ficam de fora.
+pub async fn pending_blockers_by_task_ids(db: &Pool, task_ids: Vec<i32>) -> Result<HashMap<i32, Vec<i32>>, AppError> {
+    let mut blockers: HashMap<i32, Vec<i32>> = HashMap::new();
+    for dependency in DependencyRepository::new(db).find_pending(task_ids, TaskStatus::Concluida).await? {
+        blockers.entry(dependency.task_id).or_default().push(dependency.blocked_by_id);
+    }
+    for ids in blockers.values_mut() {
+        ids.sort_unstable();
+    }
+    Ok(blockers)
+}
+
+/// Recusa a conclusão de uma tarefa com bloqueadores pendentes; o `409` traz
+/// os bloqueadores em `conflict.blocked_by`. Os bloqueadores em `resolved`
+/// contam como concluídos, como os concluídos no mesmo lote; em um lote, a
+/// consulta usa a transação dele.
+pub async fn ensure_not_blocked<C: ConnectionTrait>(db: &C, task_id: i32, resolved: &[i32]) -> Result<(), AppError> {
+    let blockers: Vec<BlockerDto> = DependencyRepository::new(db)
+        .find_blockers(task_id)
+        .await?
+        .into_iter()
+        .filter(|blocker| blocker.status != TaskStatus::Concluida && !resolved.contains(&blocker.id))
+        .map(|blocker| BlockerDto {
+            id: blocker.id,
+            title: blocker.title,
+            status: blocker.status,
+        })
+        .collect();
+
+    if blockers.is_empty() {
+        return Ok(());
+    }
+    Err(AppError::ConflictWith {
+        message: "Task is blocked by pending tasks.".to_string(),
+        conflict: serde_json::json!({ "blocked_by": blockers }),
+    })
+}
+
+/// Se `task_id` passar a depender de `blocked_by_id`, existe um caminho de
+/// volta de `blocked_by_id` até `task_id`?
+fn creates_cycle(dependencies: &[task_dependency::Model], task_id: i32, blocked_by_id: i32) -> bool {
+    let mut edges: HashMap<i32, Vec<i32>> = HashMap::new();
+    for dependency in dependencies {
+        edges.entry(dependency.task_id).or_default().push(dependency.blocked_by_id);
+    }
+
+    let mut visited = HashSet::new();
+    let mut pending = vec![blocked_by_id];
+    while let Some(current) = pending.pop() {
+        if current == task_id {
+            return true;
+        }
+        if visited.insert(current) {
+            pending.extend(edges.get(&current).into_iter().flatten());
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Arestas `task_id` → `blocked_by_id`.
+    fn graph(edges: &[(i32, i32)]) -> Vec<task_dependency::Model> {
+        edges
+            .iter()
+            .enumerate()
+            .map(|(id, (task_id, blocked_by_id))| task_dependency::Model {
+                id: id as i32 + 1,
+                task_id: *task_id,
+                blocked_by_id: *blocked_by_id,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn self_dependency_is_a_cycle() {
+        assert!(creates_cycle(&[], 1, 1));
+    }
+
+    #[test]
+    fn direct_and_transitive_cycles_are_detected() {
+        // 2 depende de 1; 3 depende de 2.
+        let dependencies = graph(&[(2, 1), (3, 2)]);
+        assert!(creates_cycle(&dependencies, 1, 2));
+        assert!(creates_cycle(&dependencies, 1, 3));
+    }
+
+    #[test]
+    fn chains_and_diamonds_are_not_cycles() {
+        // 2 e 3 dependem de 1; 4 depende de 2 e de 3.
+        let dependencies = graph(&[(2, 1), (3, 1), (4, 2), (4, 3)]);
+        assert!(!creates_cycle(&dependencies, 5, 4));
+        assert!(!creates_cycle(&dependencies, 4, 1));
+        assert!(!creates_cycle(&dependencies, 3, 2));
+    }
+
+    #[test]
+    fn existing_cycles_elsewhere_do_not_loop_forever() {
+        let dependencies = graph(&[(2, 3), (3, 2)]);
+        assert!(!creates_cycle(&dependencies, 1, 2));
+    }
+}
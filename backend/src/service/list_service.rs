@@ -0,0 +1,99 @@
+//! Interpretação dos parâmetros de consulta das listagens paginadas.
+
+use crate::db::Pool;
+use crate::dto::list_dto::{ListQuery, PageDto};
+use crate::errors::AppError;
+use crate::repository::list_query::{Cursor, ListFilter, Page, PageRequest};
+use crate::service::recurrence_service::local_midnight;
+use crate::service::{auth_service, profile_service};
+use chrono::{Duration, NaiveDate};
+use chrono_tz::Tz;
+
+const DEFAULT_LIMIT: u64 = 50;
+const MAX_LIMIT: u64 = 200;
+
+/// Filtro pedido na consulta; `from` e `to` são dias inteiros no fuso do usuário.
+pub async fn parse_filter(db: &Pool, user_id: i32, query: &ListQuery) -> Result<ListFilter, AppError> {
+    let from = query.from.as_deref().map(|value| parse_day("from", value)).transpose()?;
+    let to = query.to.as_deref().map(|value| parse_day("to", value)).transpose()?;
+    if let (Some(from), Some(to)) = (from, to) {
+        if to < from {
+            return Err(AppError::field("to", "A data final deve ser igual ou posterior à inicial."));
+        }
+    }
+    let timezone = if from.is_some() || to.is_some() {
+        profile_service::user_timezone(&auth_service::find_user(db, user_id).await?)
+    } else {
+        Tz::UTC
+    };
+
+    Ok(ListFilter {
+        from: from.map(|from| local_midnight(timezone, from)),
+        to: to.map(|to| local_midnight(timezone, to + Duration::days(1))),
+        statuses: split_list(query.status.as_deref()),
+        types: split_list(query.r#type.as_deref()),
+        category: non_blank(query.category.as_deref()),
+        category_id: query.category_id,
+        text: non_blank(query.q.as_deref()),
+    })
+}
+
+/// Ordenação e posição pedidas. `sorts` lista as colunas aceitas pelo recurso;
+/// a primeira é a padrão.
+pub fn parse_page<C: Copy>(query: &ListQuery, sorts: &[(&str, C)]) -> Result<PageRequest<C>, AppError> {
+    let key = query.sort.as_deref().map(str::trim).filter(|sort| !sort.is_empty()).unwrap_or(sorts[0].0);
+    let (descending, name) = match key.strip_prefix('-') {
+        Some(name) => (true, name),
+        None => (false, key),
+    };
+    let sort = sorts
+        .iter()
+        .find(|(sort_name, _)| *sort_name == name)
+        .map(|(_, column)| *column)
+        .ok_or_else(|| {
+            let names: Vec<&str> = sorts.iter().map(|(sort_name, _)| *sort_name).collect();
+            AppError::field("sort", format!("Ordenação inválida. Use uma de: {}.", names.join(", ")))
+        })?;
+
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT);
+    if !(1..=MAX_LIMIT).contains(&limit) {
+        return Err(AppError::field("limit", format!("O limite deve estar entre 1 e {}.", MAX_LIMIT)));
+    }
+
+    let after = match query.cursor.as_deref() {
+        Some(cursor) => {
+            let cursor = Cursor::decode(cursor).ok_or_else(|| AppError::field("cursor", "Cursor inválido."))?;
+            if cursor.key != key {
+                return Err(AppError::field("cursor", "O cursor pertence a outra ordenação."));
+            }
+            Some(cursor)
+        }
+        None => None,
+    };
+
+    Ok(PageRequest { key: key.to_string(), sort, descending, after, limit })
+}
+
+/// Monta o envelope da resposta convertendo cada item.
+pub fn to_page_dto<M, T>(page: Page<M>, limit: u64, convert: impl FnMut(M) -> T) -> PageDto<T> {
+    PageDto {
+        items: page.items.into_iter().map(convert).collect(),
+        next_cursor: page.next_cursor,
+        limit,
+    }
+}
+
+fn parse_day(field: &str, value: &str) -> Result<NaiveDate, AppError> {
+    NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+        .map_err(|_| AppError::field(field, "Data inválida, use o formato YYYY-MM-DD."))
+}
+
+fn split_list(value: Option<&str>) -> Vec<String> {
+    value
+        .map(|value| value.split(',').map(str::trim).filter(|item| !item.is_empty()).map(String::from).collect())
+        .unwrap_or_default()
+}
+
+fn non_blank(value: Option<&str>) -> Option<String> {
+    value.map(str::trim).filter(|value| !value.is_empty()).map(String::from)
+}
@@ -3,3 +3,17 @@ pub mod task_service;
 pub mod reminder_service;
 pub mod report_service;
 pub mod goal_service;
+pub mod two_factor_service;
+pub mod personal_access_token_service;
+pub mod profile_service;
+pub mod password_reset_service;
+pub mod recurrence_service;
+pub mod checklist_service;
+pub mod dependency_service;
+pub mod category_service;
+pub mod list_service;
+pub mod search_service;
+pub mod trash_service;
+pub mod overdue_service;
+pub mod time_entry_service;
+pub mod pomodoro_service;
@@ -0,0 +1,98 @@
+//! Tarefas atrasadas: as pendentes, sem repetição, cujo fim já passou.
+//!
+//! A verificação periódica (`jobs.rs`) trata cada tarefa uma única vez,
+//! conforme `OVERDUE_MODE`:
+//!
+//! - `flag` (padrão): só marca a tarefa com `overdue_at`;
+//! - `postpone`: marca e passa a tarefa para `Adiada`;
+//! - `rollover`: move a tarefa para o primeiro horário livre do dia seguinte;
+//!   se ela não couber, apenas a marca.
+//!
+//! `postpone` e `rollover` só alteram tarefas que terminaram dentro de
+//! `OVERDUE_WINDOW_HOURS`; as mais antigas (por exemplo, o histórico
+//! encontrado na primeira execução) são apenas marcadas.
+//!
+//! Remarcar a tarefa para o futuro tira a marca; concluída, ela sai da lista.
+
+use chrono::{Duration, Utc};
+use std::env::var;
+use crate::db::Pool;
+use crate::dto::task_dto::OverdueTasksDto;
+use crate::dto::task_update_dto::TaskUpdateDto;
+use crate::entity::sea_orm_active_enums::TaskStatus;
+use crate::entity::task;
+use crate::errors::AppError;
+use crate::repository::task_repository::TaskRepository;
+use crate::service::task_service;
+
+/// Até quantas horas depois do fim uma tarefa ainda é adiada ou movida.
+const OVERDUE_WINDOW_HOURS: i64 = 24;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverdueMode {
+    Flag,
+    Postpone,
+    Rollover,
+}
+
+impl OverdueMode {
+    pub fn from_env() -> Self {
+        match var("OVERDUE_MODE").ok().as_deref().map(str::trim) {
+            None | Some("") | Some("flag") => OverdueMode::Flag,
+            Some("postpone") => OverdueMode::Postpone,
+            Some("rollover") => OverdueMode::Rollover,
+            Some(other) => {
+                log::warn!("OVERDUE_MODE '{}' is not recognized, using 'flag'", other);
+                OverdueMode::Flag
+            }
+        }
+    }
+}
+
+/// Tarefas atrasadas do usuário, usadas no contador da agenda.
+pub async fn list_overdue_db(db: &Pool, user_id: i32) -> Result<OverdueTasksDto, AppError> {
+    let tasks = TaskRepository::new(db).find_overdue(user_id).await?;
+    let tasks = task_service::to_response_dtos(db, tasks).await?;
+    Ok(OverdueTasksDto { count: tasks.len(), tasks })
+}
+
+/// Trata as tarefas que ficaram atrasadas desde a última verificação e
+/// retorna quantas foram tratadas. Uma tarefa com erro fica para a próxima.
+pub async fn process_overdue(db: &Pool, mode: OverdueMode) -> Result<usize, AppError> {
+    let now = Utc::now();
+    let window_start = now - Duration::hours(OVERDUE_WINDOW_HOURS);
+    let tasks = TaskRepository::new(db).find_newly_overdue(now).await?;
+    let mut processed = 0;
+    for task in tasks {
+        let id = task.id;
+        let mode = if task.complete_date < window_start { OverdueMode::Flag } else { mode };
+        match handle_overdue(db, task, mode).await {
+            Ok(()) => processed += 1,
+            Err(e) => log::warn!("overdue: task {} not processed: {}", id, e),
+        }
+    }
+    Ok(processed)
+}
+
+async fn handle_overdue(db: &Pool, task: task::Model, mode: OverdueMode) -> Result<(), AppError> {
+    let repo = TaskRepository::new(db);
+    match mode {
+        OverdueMode::Flag => {
+            repo.mark_overdue(task, Utc::now()).await?;
+        }
+        OverdueMode::Postpone => {
+            let task = repo.mark_overdue(task, Utc::now()).await?;
+            let postpone = TaskUpdateDto {
+                status: Some(TaskStatus::Adiada.as_str().to_string()),
+                ..Default::default()
+            };
+            repo.update_task(task, &postpone, None, None).await?;
+        }
+        OverdueMode::Rollover => {
+            if task_service::roll_over_overdue(db, task.clone()).await?.is_none() {
+                repo.mark_overdue(task, Utc::now()).await?;
+            }
+        }
+    }
+    Ok(())
+}
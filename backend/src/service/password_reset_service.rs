@@ -0,0 +1,162 @@
+//! Redefinição de senha por e-mail.
+//!
+//! `request_reset` gera um token de uso único, salvo apenas como hash, e envia
+//! o link de redefinição pelo `Mailer`. A resposta é sempre a mesma, exista ou
+//! não uma conta com o e-mail informado. `confirm_reset` troca a senha e
+//! encerra todas as sessões do usuário.
+//!
+//! Configuração por variáveis de ambiente:
+//!
+//! - `PASSWORD_RESET_URL`: página do frontend que recebe o token
+//!   (padrão `http://localhost:8080/reset-password`);
+//! - `PASSWORD_RESET_TOKEN_TTL_MINUTES`: validade do token (padrão 30).
+
+use crate::db::Pool;
+use crate::dto::auth_dto::PasswordResetConfirmDto;
+use crate::errors::AppError;
+use crate::mailer::{Mail, Mailer};
+use crate::repository::{auth_repository, password_reset_token_repository, session_repository};
+use crate::service::auth_service;
+use crate::entity::password_reset_token;
+use chrono::{DateTime, Duration, Utc};
+use std::env::var;
+
+const DEFAULT_RESET_URL: &str = "http://localhost:8080/reset-password";
+const DEFAULT_TOKEN_TTL_MINUTES: i64 = 30;
+/// Intervalo mínimo entre dois e-mails de redefinição para a mesma conta.
+const RESEND_INTERVAL_SECS: i64 = 60;
+
+pub async fn request_reset(db: &Pool, mailer: &dyn Mailer, email: &str) -> Result<(), AppError> {
+    let email = email.trim().to_lowercase();
+    let user = match auth_repository::find_by_email(db, &email).await? {
+        Some(user) => user,
+        None => return Ok(()),
+    };
+
+    let latest = password_reset_token_repository::find_latest_by_user_id(db, user.id).await?;
+    if latest.is_some_and(|token| token.created_at > Utc::now() - Duration::seconds(RESEND_INTERVAL_SECS)) {
+        return Ok(());
+    }
+
+    let ttl_minutes = var("PASSWORD_RESET_TOKEN_TTL_MINUTES")
+        .ok()
+        .and_then(|ttl| ttl.parse().ok())
+        .unwrap_or(DEFAULT_TOKEN_TTL_MINUTES);
+    let token = auth_service::generate_token();
+
+    password_reset_token_repository::invalidate_user_tokens(db, user.id).await?;
+    password_reset_token_repository::create_token(
+        db,
+        user.id,
+        auth_service::hash_token(&token),
+        Utc::now() + Duration::minutes(ttl_minutes),
+    )
+    .await?;
+
+    let reset_url = var("PASSWORD_RESET_URL").unwrap_or_else(|_| DEFAULT_RESET_URL.to_string());
+    let mail = Mail {
+        to: email,
+        subject: "Redefinição de senha - Agenda".to_string(),
+        body: format!(
+            "Olá, {}!\n\n\
+             Recebemos um pedido para redefinir a senha da sua conta. Para escolher uma nova senha, acesse:\n\n\
+             {}?token={}\n\n\
+             O link vale por {} minutos e só pode ser usado uma vez. Se você não fez este pedido, ignore este e-mail.\n",
+            user.display_name.as_deref().unwrap_or(&user.username),
+            reset_url,
+            token,
+            ttl_minutes
+        ),
+    };
+    mailer.send(&mail).await
+}
+
+pub async fn confirm_reset(db: &Pool, dto: &PasswordResetConfirmDto) -> Result<(), AppError> {
+    let invalid = || AppError::field("token", "Invalid or expired reset token");
+    let token = password_reset_token_repository::find_by_token_hash(db, &auth_service::hash_token(dto.token.trim()))
+        .await?
+        .ok_or_else(invalid)?;
+    ensure_usable(&token, Utc::now())?;
+
+    let user = auth_service::find_user(db, token.user_id).await?;
+    auth_service::validate_new_password("new_password", &dto.new_password, &user)?;
+    // Consome o token antes de trocar a senha: entre duas confirmações simultâneas, só uma passa.
+    if !password_reset_token_repository::mark_used(db, token.id).await? {
+        return Err(invalid());
+    }
+    let user = auth_repository::update_password(db, user, &dto.new_password).await?;
+    session_repository::revoke_user_sessions(db, user.id, None).await
+}
+
+/// Recusa tokens já usados ou expirados.
+fn ensure_usable(token: &password_reset_token::Model, now: DateTime<Utc>) -> Result<(), AppError> {
+    if token.used_at.is_some() || token.expires_at <= now {
+        return Err(AppError::field("token", "Invalid or expired reset token"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(expires_at: DateTime<Utc>, used_at: Option<DateTime<Utc>>) -> password_reset_token::Model {
+        password_reset_token::Model {
+            id: 1,
+            user_id: 1,
+            token_hash: auth_service::hash_token("token"),
+            created_at: expires_at - Duration::minutes(DEFAULT_TOKEN_TTL_MINUTES),
+            expires_at,
+            used_at,
+        }
+    }
+
+    #[test]
+    fn accepts_an_unused_token_before_expiry() {
+        let now = Utc::now();
+        assert!(ensure_usable(&token(now + Duration::minutes(1), None), now).is_ok());
+    }
+
+    #[test]
+    fn rejects_expired_tokens() {
+        let now = Utc::now();
+        assert!(ensure_usable(&token(now, None), now).is_err());
+        assert!(ensure_usable(&token(now - Duration::minutes(1), None), now).is_err());
+    }
+
+    #[test]
+    fn rejects_used_tokens() {
+        let now = Utc::now();
+        let used = token(now + Duration::minutes(10), Some(now - Duration::minutes(1)));
+        assert!(ensure_usable(&used, now).is_err());
+    }
+
+    /// A rota descarta o resultado do pedido: conta existente, inexistente ou
+    /// falha na busca produzem a mesma resposta vazia com `202`.
+    #[tokio::test]
+    async fn request_response_does_not_depend_on_the_outcome() {
+        let mut options = sea_orm::ConnectOptions::new("postgres://agenda@127.0.0.1:1/agenda");
+        options.connect_lazy(true).acquire_timeout(std::time::Duration::from_millis(200));
+        let db = sea_orm::Database::connect(options).await.unwrap();
+        let mailer: Box<dyn Mailer> = Box::new(crate::mailer::FileMailer::new(
+            "Agenda <no-reply@localhost>".parse().unwrap(),
+            std::env::temp_dir().join("agenda-reset-test"),
+        ));
+        let rocket = rocket::build()
+            .manage(db)
+            .manage(mailer)
+            .mount("/", rocket::routes![crate::controller::auth::request_password_reset]);
+        let client = rocket::local::asynchronous::Client::untracked(rocket).await.unwrap();
+
+        for email in ["ninguem@example.com", "maria@example.com"] {
+            let response = client
+                .post("/password-reset/request")
+                .header(rocket::http::ContentType::JSON)
+                .body(format!(r#"{{"email": "{}"}}"#, email))
+                .dispatch()
+                .await;
+            assert_eq!(response.status(), rocket::http::Status::Accepted);
+            assert!(response.into_string().await.unwrap_or_default().is_empty());
+        }
+    }
+}
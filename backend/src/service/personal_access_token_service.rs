@@ -0,0 +1,234 @@
+//! Tokens de acesso pessoal para scripts e integrações.
+//!
+//! Cada token tem um nome, uma lista de escopos `recurso:read`/`recurso:write`
+//! e pode ser revogado a qualquer momento. Apenas o hash SHA-256 é salvo; o
+//! valor em texto puro é devolvido uma única vez, na criação. O escopo `write`
+//! de um recurso inclui o `read`.
+
+use crate::db::Pool;
+use crate::dto::personal_access_token_dto::{CreatePersonalAccessTokenDto, PersonalAccessTokenResponseDto};
+use crate::entity::personal_access_token;
+use crate::errors::{AppError, FieldErrors};
+use crate::repository::personal_access_token_repository;
+use crate::service::auth_service;
+use chrono::{Duration, Utc};
+use rand::RngCore;
+
+/// Prefixo que distingue os tokens pessoais dos JWTs no cabeçalho `Authorization`.
+pub const TOKEN_PREFIX: &str = "agd_pat_";
+
+/// Maior validade aceita para um token (10 anos).
+const MAX_EXPIRES_IN_DAYS: i64 = 3650;
+
+/// Recursos da API acessíveis com tokens pessoais (primeiro segmento após `/api`).
+pub const RESOURCES: [&str; 6] = ["tasks", "goals", "reminders", "notes", "reports", "categories"];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    Read,
+    Write,
+}
+
+pub fn is_personal_access_token(token: &str) -> bool {
+    token.starts_with(TOKEN_PREFIX)
+}
+
+pub async fn create_token(
+    db: &Pool,
+    user_id: i32,
+    dto: &CreatePersonalAccessTokenDto,
+) -> Result<(personal_access_token::Model, String), AppError> {
+    validate(dto)?;
+
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    let token = format!("{}{}", TOKEN_PREFIX, hex::encode(bytes));
+
+    let mut scopes = dto.scopes.clone();
+    scopes.sort();
+    scopes.dedup();
+
+    let expires_at = dto
+        .expires_in_days
+        .map(|days| {
+            Utc::now()
+                .checked_add_signed(Duration::days(days))
+                .ok_or_else(|| AppError::field("expires_in_days", "Validade fora das datas aceitas"))
+        })
+        .transpose()?;
+    let model = personal_access_token_repository::create_token(
+        db,
+        user_id,
+        dto.name.trim(),
+        auth_service::hash_token(&token),
+        scopes.join(","),
+        expires_at,
+    )
+    .await?;
+    Ok((model, token))
+}
+
+pub async fn list_tokens(db: &Pool, user_id: i32) -> Result<Vec<PersonalAccessTokenResponseDto>, AppError> {
+    let tokens = personal_access_token_repository::find_by_user_id(db, user_id).await?;
+    Ok(tokens.into_iter().map(convert_to_response_dto).collect())
+}
+
+pub async fn revoke_token(
+    db: &Pool,
+    token: personal_access_token::Model,
+) -> Result<PersonalAccessTokenResponseDto, AppError> {
+    let token = personal_access_token_repository::revoke_token(db, token).await?;
+    Ok(convert_to_response_dto(token))
+}
+
+/// Valida o token apresentado e registra o uso.
+pub async fn authenticate(db: &Pool, token: &str) -> Result<personal_access_token::Model, AppError> {
+    let invalid = || AppError::Unauthorized("Invalid, expired or revoked access token".to_string());
+
+    let model = personal_access_token_repository::find_by_token_hash(db, &auth_service::hash_token(token))
+        .await?
+        .ok_or_else(invalid)?;
+
+    let expired = model.expires_at.is_some_and(|expires_at| expires_at <= Utc::now());
+    if model.revoked_at.is_some() || expired {
+        return Err(invalid());
+    }
+
+    personal_access_token_repository::touch_last_used(db, model.id).await?;
+    Ok(model)
+}
+
+/// Verifica se o token permite o acesso pedido ao recurso.
+pub fn allows(token: &personal_access_token::Model, resource: &str, access: Access) -> bool {
+    let write_scope = format!("{}:write", resource);
+    let read_scope = format!("{}:read", resource);
+    token.scopes.split(',').any(|scope| {
+        scope == write_scope || (access == Access::Read && scope == read_scope)
+    })
+}
+
+pub fn convert_to_response_dto(token: personal_access_token::Model) -> PersonalAccessTokenResponseDto {
+    PersonalAccessTokenResponseDto {
+        id: token.id,
+        name: token.name,
+        scopes: token.scopes.split(',').map(str::to_string).collect(),
+        created_at: token.created_at,
+        last_used_at: token.last_used_at,
+        expires_at: token.expires_at,
+        revoked_at: token.revoked_at,
+    }
+}
+
+fn validate(dto: &CreatePersonalAccessTokenDto) -> Result<(), AppError> {
+    let mut field_errors = FieldErrors::new();
+
+    if dto.name.trim().is_empty() {
+        field_errors.insert("name".to_string(), vec!["Name is required".to_string()]);
+    }
+
+    let invalid_scopes: Vec<String> = dto
+        .scopes
+        .iter()
+        .filter(|scope| !is_valid_scope(scope))
+        .map(|scope| format!("Invalid scope: {}", scope))
+        .collect();
+    if dto.scopes.is_empty() {
+        field_errors.insert("scopes".to_string(), vec!["At least one scope is required".to_string()]);
+    } else if !invalid_scopes.is_empty() {
+        field_errors.insert("scopes".to_string(), invalid_scopes);
+    }
+
+    if dto.expires_in_days.is_some_and(|days| days <= 0) {
+        field_errors.insert("expires_in_days".to_string(), vec!["Must be a positive number of days".to_string()]);
+    } else if dto.expires_in_days.is_some_and(|days| days > MAX_EXPIRES_IN_DAYS) {
+        field_errors.insert(
+            "expires_in_days".to_string(),
+            vec![format!("Must be at most {} days", MAX_EXPIRES_IN_DAYS)],
+        );
+    }
+
+    if field_errors.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::Validation {
+            message: "Invalid Fields".to_string(),
+            field_errors,
+        })
+    }
+}
+
+fn is_valid_scope(scope: &str) -> bool {
+    match scope.split_once(':') {
+        Some((resource, access)) => RESOURCES.contains(&resource) && (access == "read" || access == "write"),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(scopes: &str) -> personal_access_token::Model {
+        personal_access_token::Model {
+            id: 1,
+            user_id: 1,
+            name: "script".to_string(),
+            token_hash: String::new(),
+            scopes: scopes.to_string(),
+            created_at: Utc::now(),
+            last_used_at: None,
+            expires_at: None,
+            revoked_at: None,
+        }
+    }
+
+    fn dto(scopes: &[&str]) -> CreatePersonalAccessTokenDto {
+        CreatePersonalAccessTokenDto {
+            name: "script".to_string(),
+            scopes: scopes.iter().map(|scope| scope.to_string()).collect(),
+            expires_in_days: None,
+        }
+    }
+
+    #[test]
+    fn write_scope_includes_read() {
+        let token = token("tasks:write");
+        assert!(allows(&token, "tasks", Access::Read));
+        assert!(allows(&token, "tasks", Access::Write));
+    }
+
+    #[test]
+    fn read_scope_does_not_include_write() {
+        let token = token("goals:read,tasks:read");
+        assert!(allows(&token, "goals", Access::Read));
+        assert!(!allows(&token, "goals", Access::Write));
+        assert!(!allows(&token, "tasks", Access::Write));
+    }
+
+    #[test]
+    fn scopes_only_cover_their_own_resource() {
+        let token = token("tasks:write");
+        assert!(!allows(&token, "notes", Access::Read));
+        assert!(!allows(&token, "task", Access::Read));
+    }
+
+    #[test]
+    fn unknown_resources_and_access_are_rejected() {
+        assert!(is_valid_scope("reports:read"));
+        assert!(!is_valid_scope("account:write"));
+        assert!(!is_valid_scope("tasks:delete"));
+        assert!(!is_valid_scope("tasks"));
+        assert!(validate(&dto(&["tasks:read", "pomodoro:read"])).is_err());
+        assert!(validate(&dto(&[])).is_err());
+        assert!(validate(&dto(&["tasks:read", "notes:write"])).is_ok());
+    }
+
+    #[test]
+    fn expiration_is_bounded() {
+        let expiring = |days| CreatePersonalAccessTokenDto { expires_in_days: Some(days), ..dto(&["tasks:read"]) };
+        assert!(validate(&expiring(0)).is_err());
+        assert!(validate(&expiring(MAX_EXPIRES_IN_DAYS)).is_ok());
+        assert!(validate(&expiring(MAX_EXPIRES_IN_DAYS + 1)).is_err());
+        assert!(validate(&expiring(i64::MAX)).is_err());
+    }
+}
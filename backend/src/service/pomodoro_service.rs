@@ -0,0 +1,147 @@
+//! Sessões Pomodoro: foco ligado a uma tarefa, pausas curtas e longas.
+//!
+//! Cada usuário tem no máximo uma sessão em andamento. Uma sessão parada
+//! depois do fim conta como concluída e, antes dele, como interrompida; a que
+//! passou do fim sem ser parada (por exemplo, com a página fechada) é
+//! concluída na próxima consulta. As sessões de foco concluídas entram nos
+//! relatórios.
+
+use chrono::{Duration, Utc};
+use crate::db::Pool;
+use crate::dto::pomodoro_dto::{PomodoroSettingsDto, PomodoroStartDto, PomodoroStateDto};
+use crate::entity::sea_orm_active_enums::{PomodoroKind, PomodoroStatus};
+use crate::entity::{pomodoro_session, user};
+use crate::errors::AppError;
+use crate::repository::pomodoro_repository::PomodoroRepository;
+use crate::repository::task_repository::TaskRepository;
+use crate::service::recurrence_service::local_midnight;
+use crate::service::{auth_service, profile_service};
+use validator::Validate;
+
+/// Sessões de foco concluídas antes de a pausa sugerida ser a longa.
+const WORK_SESSIONS_PER_LONG_BREAK: u64 = 4;
+
+/// Maior número de sessões em `GET /api/pomodoro/sessions`.
+const MAX_HISTORY: u64 = 200;
+
+/// Configuração, sessão em andamento e a próxima sessão sugerida.
+pub async fn get_state_db(db: &Pool, user_id: i32) -> Result<PomodoroStateDto, AppError> {
+    let user = auth_service::find_user(db, user_id).await?;
+    let repo = PomodoroRepository::new(db);
+    let active = finish_expired(&repo, user_id).await?;
+
+    let timezone = profile_service::user_timezone(&user);
+    let today = Utc::now().with_timezone(&timezone).date_naive();
+    let completed_today = repo.count_completed_work_since(user_id, local_midnight(timezone, today)).await?;
+
+    let last_kind = match &active {
+        Some(session) => Some(session.kind),
+        None => repo.find_recent(user_id, 1).await?.first().map(|session| session.kind),
+    };
+    let next_kind = match last_kind {
+        Some(PomodoroKind::Work) if completed_today > 0 && completed_today % WORK_SESSIONS_PER_LONG_BREAK == 0 => {
+            PomodoroKind::LongBreak
+        }
+        Some(PomodoroKind::Work) => PomodoroKind::ShortBreak,
+        _ => PomodoroKind::Work,
+    };
+
+    Ok(PomodoroStateDto { settings: settings_of(&user), active, next_kind, completed_today })
+}
+
+pub async fn update_settings_db(
+    db: &Pool,
+    user_id: i32,
+    settings: &PomodoroSettingsDto,
+) -> Result<PomodoroSettingsDto, AppError> {
+    settings.validate()?;
+    let user = auth_service::find_user(db, user_id).await?;
+    let user = PomodoroRepository::new(db).update_settings(user, settings).await?;
+    Ok(settings_of(&user))
+}
+
+/// Inicia uma sessão com a duração configurada para o tipo. Com outra em
+/// andamento, responde `409` com ela em `conflict`.
+pub async fn start_session_db(
+    db: &Pool,
+    user_id: i32,
+    start: &PomodoroStartDto,
+) -> Result<pomodoro_session::Model, AppError> {
+    let task_id = match (start.kind, start.task_id) {
+        (PomodoroKind::Work, None) => {
+            return Err(AppError::field("task_id", "Escolha a tarefa da sessão de foco."));
+        }
+        (_, Some(task_id)) => {
+            if TaskRepository::new(db).find_by_ids(user_id, &[task_id]).await?.is_empty() {
+                return Err(AppError::NotFound(format!("Task with id {} not found", task_id)));
+            }
+            Some(task_id)
+        }
+        (_, None) => None,
+    };
+
+    let repo = PomodoroRepository::new(db);
+    if let Some(running) = finish_expired(&repo, user_id).await? {
+        return Err(AppError::ConflictWith {
+            message: "A Pomodoro session is already running; stop it first.".to_string(),
+            conflict: serde_json::to_value(running).map_err(|err| AppError::Internal(err.to_string()))?,
+        });
+    }
+
+    let settings = settings_of(&auth_service::find_user(db, user_id).await?);
+    let minutes = match start.kind {
+        PomodoroKind::Work => settings.work_minutes,
+        PomodoroKind::ShortBreak => settings.short_break_minutes,
+        PomodoroKind::LongBreak => settings.long_break_minutes,
+    };
+    let started_at = Utc::now();
+    repo.create_session(user_id, task_id, start.kind, started_at, started_at + Duration::minutes(minutes.into()))
+        .await
+}
+
+/// Para a sessão em andamento: concluída se o tempo já acabou, interrompida se não.
+pub async fn stop_session_db(db: &Pool, user_id: i32) -> Result<pomodoro_session::Model, AppError> {
+    let repo = PomodoroRepository::new(db);
+    let session = repo
+        .find_running(user_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("No Pomodoro session is running".to_string()))?;
+    let now = Utc::now();
+    if now >= session.ends_at {
+        let ends_at = session.ends_at;
+        repo.finish_session(session, PomodoroStatus::Completed, ends_at).await
+    } else {
+        repo.finish_session(session, PomodoroStatus::Interrupted, now).await
+    }
+}
+
+/// Histórico das sessões, das mais recentes às mais antigas.
+pub async fn list_sessions_db(db: &Pool, user_id: i32, limit: Option<u64>) -> Result<Vec<pomodoro_session::Model>, AppError> {
+    let repo = PomodoroRepository::new(db);
+    finish_expired(&repo, user_id).await?;
+    repo.find_recent(user_id, limit.unwrap_or(50).clamp(1, MAX_HISTORY)).await
+}
+
+/// Conclui a sessão em andamento cujo tempo já acabou e retorna a que
+/// continua em andamento, se houver.
+async fn finish_expired(
+    repo: &PomodoroRepository<'_>,
+    user_id: i32,
+) -> Result<Option<pomodoro_session::Model>, AppError> {
+    match repo.find_running(user_id).await? {
+        Some(session) if session.ends_at <= Utc::now() => {
+            let ends_at = session.ends_at;
+            repo.finish_session(session, PomodoroStatus::Completed, ends_at).await?;
+            Ok(None)
+        }
+        running => Ok(running),
+    }
+}
+
+fn settings_of(user: &user::Model) -> PomodoroSettingsDto {
+    PomodoroSettingsDto {
+        work_minutes: user.pomodoro_work_minutes,
+        short_break_minutes: user.pomodoro_short_break_minutes,
+        long_break_minutes: user.pomodoro_long_break_minutes,
+    }
+}
@@ -0,0 +1,104 @@
+use crate::db::Pool;
+use crate::dto::profile_dto::{ProfileResponseDto, UpdateProfileDto};
+use crate::entity::user;
+use crate::errors::{AppError, FieldErrors};
+use crate::repository::auth_repository;
+use crate::service::auth_service;
+use chrono_tz::Tz;
+use validator::Validate;
+
+pub async fn get_profile(db: &Pool, user_id: i32) -> Result<ProfileResponseDto, AppError> {
+    let user = auth_service::find_user(db, user_id).await?;
+    Ok(convert_to_response_dto(user))
+}
+
+pub async fn update_profile(
+    db: &Pool,
+    user_id: i32,
+    profile: &UpdateProfileDto,
+) -> Result<ProfileResponseDto, AppError> {
+    let profile = normalize(profile);
+    profile.validate()?;
+    validate_settings(&profile)?;
+
+    let user = auth_service::find_user(db, user_id).await?;
+
+    if let Some(email) = &profile.email {
+        if user.email.as_ref() != Some(email) && auth_repository::find_by_email(db, email).await?.is_some() {
+            return Err(AppError::Conflict("Email already been used".to_string()));
+        }
+    }
+
+    let user = auth_repository::update_profile(db, user, &profile).await?;
+    Ok(convert_to_response_dto(user))
+}
+
+/// Fuso horário do usuário, com UTC caso o valor salvo não seja reconhecido.
+pub fn user_timezone(user: &user::Model) -> Tz {
+    user.timezone.parse().unwrap_or(Tz::UTC)
+}
+
+pub fn convert_to_response_dto(user: user::Model) -> ProfileResponseDto {
+    ProfileResponseDto {
+        id: user.id,
+        username: user.username,
+        display_name: user.display_name,
+        email: user.email,
+        timezone: user.timezone,
+        locale: user.locale,
+        week_start_day: user.week_start_day,
+        use_24h_clock: user.use_24h_clock,
+        two_factor_enabled: user.totp_enabled,
+    }
+}
+
+/// Remove espaços e trata campos opcionais vazios como ausentes.
+fn normalize(profile: &UpdateProfileDto) -> UpdateProfileDto {
+    let optional = |value: &Option<String>| {
+        value
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+
+    UpdateProfileDto {
+        display_name: optional(&profile.display_name),
+        email: optional(&profile.email).map(|email| email.to_lowercase()),
+        timezone: profile.timezone.trim().to_string(),
+        locale: profile.locale.trim().to_string(),
+        ..profile.clone()
+    }
+}
+
+fn validate_settings(profile: &UpdateProfileDto) -> Result<(), AppError> {
+    let mut field_errors = FieldErrors::new();
+
+    if profile.timezone.parse::<Tz>().is_err() {
+        field_errors.insert("timezone".to_string(), vec!["Fuso horário IANA inválido.".to_string()]);
+    }
+    if !is_valid_locale(&profile.locale) {
+        field_errors.insert("locale".to_string(), vec!["Idioma inválido, use o formato pt-BR.".to_string()]);
+    }
+
+    if field_errors.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::Validation {
+            message: "Invalid Fields".to_string(),
+            field_errors,
+        })
+    }
+}
+
+/// Aceita tags simples no formato `idioma` ou `idioma-REGIÃO` (ex.: `pt`, `pt-BR`, `en-US`).
+fn is_valid_locale(locale: &str) -> bool {
+    let mut parts = locale.split('-');
+    let language = parts.next().unwrap_or_default();
+    let region = parts.next();
+
+    (2..=3).contains(&language.len())
+        && language.chars().all(|c| c.is_ascii_lowercase())
+        && region.is_none_or(|region| region.len() == 2 && region.chars().all(|c| c.is_ascii_uppercase()))
+        && parts.next().is_none()
+}
@@ -0,0 +1,390 @@
+//! Regras de repetição de tarefas, um subconjunto do RRULE (RFC 5545):
+//! `FREQ=DAILY|WEEKLY|MONTHLY`, `INTERVAL`, `BYDAY` (apenas semanal), `UNTIL`
+//! e `COUNT`.
+//!
+//! As ocorrências são calculadas no fuso horário do usuário: uma tarefa
+//! semanal das 23h cai sempre no mesmo dia da semana local e mantém o horário
+//! quando começa ou termina o horário de verão. Na repetição mensal, meses sem
+//! o dia da primeira ocorrência (ex.: dia 31) são pulados, como no RRULE.
+
+use crate::dto::task_dto::RecurrenceDto;
+use crate::entity::task_recurrence;
+use crate::errors::{AppError, FieldErrors};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+
+/// Limite de passos da expansão, para que regras sem fim nunca travem a requisição.
+const MAX_STEPS: u32 = 100_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+impl Frequency {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Frequency::Daily => "daily",
+            Frequency::Weekly => "weekly",
+            Frequency::Monthly => "monthly",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Frequency> {
+        match value {
+            "daily" => Some(Frequency::Daily),
+            "weekly" => Some(Frequency::Weekly),
+            "monthly" => Some(Frequency::Monthly),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RecurrenceRule {
+    pub frequency: Frequency,
+    pub interval: u32,
+    pub weekdays: Vec<Weekday>,
+    pub until: Option<DateTime<Utc>>,
+    pub count: Option<u32>,
+}
+
+impl RecurrenceRule {
+    pub fn from_dto(dto: &RecurrenceDto) -> Result<Self, AppError> {
+        let mut field_errors = FieldErrors::new();
+
+        let frequency = Frequency::parse(&dto.frequency);
+        if frequency.is_none() {
+            field_errors.insert(
+                "recurrence.frequency".to_string(),
+                vec!["Use daily, weekly ou monthly.".to_string()],
+            );
+        }
+
+        let weekdays: Vec<Option<Weekday>> = dto.weekdays.iter().map(|day| parse_weekday(day)).collect();
+        if weekdays.iter().any(Option::is_none) {
+            field_errors.insert(
+                "recurrence.weekdays".to_string(),
+                vec!["Use MO, TU, WE, TH, FR, SA ou SU.".to_string()],
+            );
+        } else if !weekdays.is_empty() && frequency != Some(Frequency::Weekly) {
+            field_errors.insert(
+                "recurrence.weekdays".to_string(),
+                vec!["Dias da semana só valem para a repetição semanal.".to_string()],
+            );
+        }
+
+        if dto.until.is_some() && dto.count.is_some() {
+            field_errors.insert(
+                "recurrence.count".to_string(),
+                vec!["Informe a data final ou o número de ocorrências, não os dois.".to_string()],
+            );
+        }
+
+        match frequency {
+            Some(frequency) if field_errors.is_empty() => {
+                let mut weekdays: Vec<Weekday> = weekdays.into_iter().flatten().collect();
+                weekdays.sort_by_key(Weekday::num_days_from_monday);
+                weekdays.dedup();
+                Ok(RecurrenceRule {
+                    frequency,
+                    interval: dto.interval.max(1) as u32,
+                    weekdays,
+                    until: dto.until,
+                    count: dto.count.map(|count| count.max(1) as u32),
+                })
+            }
+            _ => Err(AppError::Validation {
+                message: "Invalid Fields".to_string(),
+                field_errors,
+            }),
+        }
+    }
+
+    /// Lê a regra salva; valores desconhecidos caem no padrão (diária, intervalo 1).
+    pub fn from_model(model: &task_recurrence::Model) -> Self {
+        let mut weekdays: Vec<Weekday> = model
+            .weekdays
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .filter_map(parse_weekday)
+            .collect();
+        weekdays.sort_by_key(Weekday::num_days_from_monday);
+
+        RecurrenceRule {
+            frequency: Frequency::parse(&model.frequency).unwrap_or(Frequency::Daily),
+            interval: model.interval.max(1) as u32,
+            weekdays,
+            until: model.until,
+            count: model.count.map(|count| count.max(1) as u32),
+        }
+    }
+
+    /// Dias da semana no formato salvo no banco (`MO,WE,FR`), se houver.
+    pub fn weekdays_value(&self) -> Option<String> {
+        if self.weekdays.is_empty() {
+            return None;
+        }
+        let days: Vec<&str> = self.weekdays.iter().map(|day| weekday_code(*day)).collect();
+        Some(days.join(","))
+    }
+
+    /// Inícios das ocorrências da série em `[from, to)`, em ordem.
+    pub fn starts_between(
+        &self,
+        first_start: DateTime<Utc>,
+        timezone: Tz,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Vec<DateTime<Utc>> {
+        let mut starts = Vec::new();
+        self.for_each_start(first_start, timezone, |start| {
+            if start >= to {
+                return false;
+            }
+            if start >= from {
+                starts.push(start);
+            }
+            true
+        });
+        starts
+    }
+
+    /// Inícios das ocorrências que se sobrepõem a `[from, to)`, sem as que
+    /// `is_skipped` marca como puladas.
+    pub fn overlapping_starts(
+        &self,
+        first_start: DateTime<Utc>,
+        duration: Duration,
+        timezone: Tz,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        is_skipped: impl Fn(DateTime<Utc>) -> bool,
+    ) -> Vec<DateTime<Utc>> {
+        self.starts_between(first_start, timezone, from - duration, to)
+            .into_iter()
+            .filter(|start| *start + duration > from && !is_skipped(*start))
+            .collect()
+    }
+
+    /// Se `start` é o início de alguma ocorrência da série.
+    pub fn has_start(&self, first_start: DateTime<Utc>, timezone: Tz, start: DateTime<Utc>) -> bool {
+        !self
+            .starts_between(first_start, timezone, start, start + Duration::seconds(1))
+            .is_empty()
+    }
+
+    fn for_each_start(&self, first_start: DateTime<Utc>, timezone: Tz, mut visit: impl FnMut(DateTime<Utc>) -> bool) {
+        let local_first = first_start.with_timezone(&timezone);
+        let first_date = local_first.date_naive();
+        let time = local_first.time();
+        let mut emitted = 0;
+
+        for step in 0..MAX_STEPS {
+            for date in self.dates_in_step(first_date, step) {
+                if date < first_date {
+                    continue;
+                }
+                let Some(start) = local_to_utc(timezone, date.and_time(time)) else {
+                    continue;
+                };
+                if self.until.is_some_and(|until| start > until) || self.count.is_some_and(|count| emitted >= count) {
+                    return;
+                }
+                emitted += 1;
+                if !visit(start) {
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Datas candidatas do passo `step` (o N-ésimo dia, semana ou mês da série).
+    fn dates_in_step(&self, first_date: NaiveDate, step: u32) -> Vec<NaiveDate> {
+        let offset = step * self.interval;
+        match self.frequency {
+            Frequency::Daily => first_date
+                .checked_add_signed(Duration::days(offset.into()))
+                .into_iter()
+                .collect(),
+            Frequency::Weekly => {
+                let monday = first_date - Duration::days(first_date.weekday().num_days_from_monday().into());
+                let week_start = monday + Duration::weeks(offset.into());
+                if self.weekdays.is_empty() {
+                    vec![week_start + Duration::days(first_date.weekday().num_days_from_monday().into())]
+                } else {
+                    self.weekdays
+                        .iter()
+                        .map(|day| week_start + Duration::days(day.num_days_from_monday().into()))
+                        .collect()
+                }
+            }
+            Frequency::Monthly => {
+                let months = first_date.month0() + offset;
+                let year = first_date.year() + (months / 12) as i32;
+                NaiveDate::from_ymd_opt(year, months % 12 + 1, first_date.day())
+                    .into_iter()
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Converte um horário local para UTC. Em horários ambíguos usa o primeiro;
+/// em horários inexistentes (início do horário de verão), avança uma hora.
+pub fn local_to_utc(timezone: Tz, local: NaiveDateTime) -> Option<DateTime<Utc>> {
+    timezone
+        .from_local_datetime(&local)
+        .earliest()
+        .or_else(|| timezone.from_local_datetime(&(local + Duration::hours(1))).earliest())
+        .map(|start| start.with_timezone(&Utc))
+}
+
+/// Início do dia `date` no fuso informado, em UTC.
+pub fn local_midnight(timezone: Tz, date: NaiveDate) -> DateTime<Utc> {
+    local_to_utc(timezone, date.and_time(NaiveTime::MIN)).unwrap_or_else(|| date.and_time(NaiveTime::MIN).and_utc())
+}
+
+fn parse_weekday(code: &str) -> Option<Weekday> {
+    match code.trim().to_uppercase().as_str() {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+fn weekday_code(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(year: i32, month: u32, day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, 0, 0).unwrap()
+    }
+
+    fn rule(frequency: Frequency, interval: u32) -> RecurrenceRule {
+        RecurrenceRule { frequency, interval, weekdays: Vec::new(), until: None, count: None }
+    }
+
+    fn dto(frequency: &str, weekdays: &[&str]) -> RecurrenceDto {
+        RecurrenceDto {
+            frequency: frequency.to_string(),
+            interval: 1,
+            weekdays: weekdays.iter().map(|day| day.to_string()).collect(),
+            until: None,
+            count: None,
+        }
+    }
+
+    #[test]
+    fn daily_every_other_day() {
+        let starts = rule(Frequency::Daily, 2).starts_between(utc(2025, 1, 1, 10), Tz::UTC, utc(2025, 1, 1, 0), utc(2025, 1, 8, 0));
+        assert_eq!(starts, vec![utc(2025, 1, 1, 10), utc(2025, 1, 3, 10), utc(2025, 1, 5, 10), utc(2025, 1, 7, 10)]);
+    }
+
+    #[test]
+    fn until_includes_an_occurrence_starting_at_the_limit() {
+        let mut daily = rule(Frequency::Daily, 1);
+        daily.until = Some(utc(2025, 1, 3, 10));
+        let starts = daily.starts_between(utc(2025, 1, 1, 10), Tz::UTC, utc(2025, 1, 1, 0), utc(2025, 2, 1, 0));
+        assert_eq!(starts, vec![utc(2025, 1, 1, 10), utc(2025, 1, 2, 10), utc(2025, 1, 3, 10)]);
+    }
+
+    #[test]
+    fn count_is_counted_from_the_first_occurrence() {
+        let mut daily = rule(Frequency::Daily, 1);
+        daily.count = Some(3);
+        let starts = daily.starts_between(utc(2025, 1, 1, 10), Tz::UTC, utc(2025, 1, 2, 0), utc(2025, 2, 1, 0));
+        assert_eq!(starts, vec![utc(2025, 1, 2, 10), utc(2025, 1, 3, 10)]);
+    }
+
+    #[test]
+    fn weekly_byday_skips_days_before_the_first_start() {
+        let mut weekly = rule(Frequency::Weekly, 1);
+        weekly.weekdays = vec![Weekday::Mon, Weekday::Wed, Weekday::Fri];
+        // 2025-01-08 é uma quarta-feira.
+        let starts = weekly.starts_between(utc(2025, 1, 8, 9), Tz::UTC, utc(2025, 1, 1, 0), utc(2025, 1, 15, 0));
+        assert_eq!(starts, vec![utc(2025, 1, 8, 9), utc(2025, 1, 10, 9), utc(2025, 1, 13, 9)]);
+    }
+
+    #[test]
+    fn weekly_without_byday_repeats_the_first_weekday() {
+        let starts = rule(Frequency::Weekly, 2).starts_between(utc(2025, 1, 8, 9), Tz::UTC, utc(2025, 1, 1, 0), utc(2025, 2, 10, 0));
+        assert_eq!(starts, vec![utc(2025, 1, 8, 9), utc(2025, 1, 22, 9), utc(2025, 2, 5, 9)]);
+    }
+
+    #[test]
+    fn monthly_skips_months_without_the_day() {
+        let mut monthly = rule(Frequency::Monthly, 1);
+        monthly.count = Some(3);
+        let starts = monthly.starts_between(utc(2025, 1, 31, 8), Tz::UTC, utc(2025, 1, 1, 0), utc(2026, 1, 1, 0));
+        assert_eq!(starts, vec![utc(2025, 1, 31, 8), utc(2025, 3, 31, 8), utc(2025, 5, 31, 8)]);
+    }
+
+    #[test]
+    fn keeps_the_local_time_across_daylight_saving() {
+        let timezone: Tz = "America/New_York".parse().unwrap();
+        // 08:00 em Nova York: 13:00 UTC no inverno, 12:00 UTC no horário de verão (9 de março).
+        let starts = rule(Frequency::Daily, 1).starts_between(utc(2025, 3, 8, 13), timezone, utc(2025, 3, 8, 0), utc(2025, 3, 10, 0));
+        assert_eq!(starts, vec![utc(2025, 3, 8, 13), utc(2025, 3, 9, 12)]);
+    }
+
+    #[test]
+    fn expansion_stops_after_max_steps() {
+        let far = utc(2400, 1, 1, 0);
+        let starts = rule(Frequency::Daily, 1).starts_between(utc(2025, 1, 1, 10), Tz::UTC, far, far + Duration::days(30));
+        assert!(starts.is_empty());
+    }
+
+    #[test]
+    fn has_start_only_matches_occurrence_starts() {
+        let daily = rule(Frequency::Daily, 1);
+        assert!(daily.has_start(utc(2025, 1, 1, 10), Tz::UTC, utc(2025, 1, 5, 10)));
+        assert!(!daily.has_start(utc(2025, 1, 1, 10), Tz::UTC, utc(2025, 1, 5, 11)));
+        assert!(!daily.has_start(utc(2025, 1, 1, 10), Tz::UTC, utc(2024, 12, 31, 10)));
+    }
+
+    #[test]
+    fn overlapping_starts_drop_skipped_and_finished_occurrences() {
+        let daily = rule(Frequency::Daily, 1);
+        let skipped = utc(2025, 1, 3, 10);
+        let starts = daily.overlapping_starts(
+            utc(2025, 1, 1, 10),
+            Duration::hours(2),
+            Tz::UTC,
+            utc(2025, 1, 2, 11),
+            utc(2025, 1, 5, 0),
+            |start| start == skipped,
+        );
+        // A do dia 2 ainda está em andamento às 11h; a do dia 3 foi pulada.
+        assert_eq!(starts, vec![utc(2025, 1, 2, 10), utc(2025, 1, 4, 10)]);
+    }
+
+    #[test]
+    fn from_dto_rejects_weekdays_outside_weekly() {
+        assert!(RecurrenceRule::from_dto(&dto("daily", &["MO"])).is_err());
+        assert!(RecurrenceRule::from_dto(&dto("weekly", &["XX"])).is_err());
+        let weekly = RecurrenceRule::from_dto(&dto("weekly", &["fr", "MO", "FR"])).unwrap();
+        assert_eq!(weekly.weekdays, vec![Weekday::Mon, Weekday::Fri]);
+        assert_eq!(weekly.weekdays_value().as_deref(), Some("MO,FR"));
+    }
+}
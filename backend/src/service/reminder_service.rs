@@ -1,106 +1,80 @@
 use rocket::State;
-use sea_orm::{ActiveModelTrait, Set};
+use chrono::Utc;
+use sea_orm::{ActiveModelTrait, NotSet, Set};
 use crate::db::Pool;
+use crate::dto::list_dto::{ListQuery, PageDto};
 use crate::dto::reminder_dto::ReminderDto;
 use crate::entity::reminder;
-use rocket::http::Status;
+use crate::errors::AppError;
+use crate::repository::reminder_repository::ReminderRepository;
+use crate::service::{category_service, list_service};
 use sea_orm::QueryFilter;
  use sea_orm::ColumnTrait;
 
  use sea_orm::EntityTrait;
-/// Enum para erros específicos do serviço de reminders
-pub enum ReminderError {
-    DatabaseError(String),
-}
 
 /// Função que cria um novo reminder no banco
 pub async fn create_reminder_db(
     db: &State<Pool>,
     reminder_info: &ReminderDto,
     user_id: i32, // Recebe o user_id diretamente
-) -> Result<reminder::Model, ReminderError> {
+) -> Result<reminder::Model, AppError> {
     let conn = db.inner();
-    
+    let category =
+        category_service::resolve_category(conn, user_id, reminder_info.category_id, Some(&reminder_info.category)).await?;
+
     let new_reminder = reminder::ActiveModel {
         name: Set(reminder_info.name.clone()),
         user_id: Set(user_id), // Usa o user_id recebido
-        category: Set(reminder_info.category.clone()),
-        date_end: Set(reminder_info.date_end.clone()),
+        category: Set(category.as_ref().map_or_else(|| reminder_info.category.clone(), |category| category.name.clone())),
+        category_id: Set(category.map(|category| category.id)),
+        date_end: Set(reminder_info.date_end),
         ..Default::default() // id será gerado automaticamente
     };
 
     match new_reminder.insert(conn).await {
         Ok(reminder) => Ok(reminder),
-        Err(e) => Err(ReminderError::DatabaseError(e.to_string())),
-    }
-}
-
-pub async fn delete_reminder_db(db: &Pool, id: i32) -> Result<reminder::Model, (Status, String)> {
-    let conn = db;
-    match reminder::Entity::find_by_id(id).one(conn).await {
-        Ok(Some(reminder_model)) => {
-            let deleted_reminder = reminder_model.clone();
-            let active_reminder: reminder::ActiveModel = reminder_model.into();
-            match active_reminder.delete(conn).await {
-                Ok(_) => Ok(deleted_reminder),
-                Err(e) => Err((Status::InternalServerError, e.to_string())),
-            }
-        }
-        Ok(None) => Err((Status::NotFound, "Reminder not found".into())),
-        Err(e) => Err((Status::InternalServerError, e.to_string())),
-    }
-}  
-
-
-pub async fn list_reminders_db(
-    db: &Pool,
-) -> Result<Vec<reminder::Model>, (Status, String)> {
-    let conn = db;
-    match reminder::Entity::find().all(conn).await {
-        Ok(reminders) => Ok(reminders),
-        Err(e) => Err((Status::InternalServerError, e.to_string())),
+        Err(e) => Err(e.into()),
     }
 }
 
-pub async fn get_reminder_db(
-    db: &Pool,
-    id: i32,
-) -> Result<reminder::Model, (Status, String)> {
+/// Move o lembrete para a lixeira; a posse deve ter sido verificada pelo chamador.
+pub async fn delete_reminder_db(db: &Pool, reminder_model: reminder::Model) -> Result<reminder::Model, AppError> {
     let conn = db;
-    match reminder::Entity::find_by_id(id).one(conn).await {
-        Ok(Some(reminder)) => Ok(reminder),
-        Ok(None) => Err((Status::NotFound, "Reminder not found".into())),
-        Err(e) => Err((Status::InternalServerError, e.to_string())),
+    let mut active_reminder: reminder::ActiveModel = reminder_model.into();
+    active_reminder.deleted_at = Set(Some(Utc::now()));
+    match active_reminder.update(conn).await {
+        Ok(reminder) => Ok(reminder),
+        Err(e) => Err(e.into()),
     }
 }
 
-
-
+/// Atualiza o lembrete; a posse deve ter sido verificada pelo chamador.
 pub async fn update_reminder_db(
     db: &Pool,
-    id: i32,
+    reminder_model: reminder::Model,
     reminder_dto: &ReminderDto,
-) -> Result<reminder::Model, (Status, String)> {
-  
-    let date_end = reminder_dto.date_end;
-
-   let conn = db;
-    match reminder::Entity::find_by_id(id).one(conn).await {
-        Ok(Some(reminder_model)) => {
-            let updated_reminder = reminder::ActiveModel {
-                id: Set(reminder_model.id),
-                user_id: Set(reminder_model.user_id),
-                name: Set(reminder_dto.name.clone()),
-                category: Set(reminder_dto.category.clone()),
-                date_end: Set(date_end),
-            };
-            match updated_reminder.update(conn).await {
-                Ok(reminder) => Ok(reminder),
-                Err(e) => Err((Status::InternalServerError, e.to_string())),
-            }
-        }
-        Ok(None) => Err((Status::NotFound, "Reminder not found".into())),
-        Err(e) => Err((Status::InternalServerError, e.to_string())),
+) -> Result<reminder::Model, AppError> {
+    let conn = db;
+    let category = category_service::resolve_category(
+        conn,
+        reminder_model.user_id,
+        reminder_dto.category_id,
+        Some(&reminder_dto.category),
+    )
+    .await?;
+    let updated_reminder = reminder::ActiveModel {
+        id: Set(reminder_model.id),
+        user_id: Set(reminder_model.user_id),
+        name: Set(reminder_dto.name.clone()),
+        category: Set(category.as_ref().map_or_else(|| reminder_dto.category.clone(), |category| category.name.clone())),
+        category_id: Set(category.map(|category| category.id)),
+        date_end: Set(reminder_dto.date_end),
+        deleted_at: NotSet,
+    };
+    match updated_reminder.update(conn).await {
+        Ok(reminder) => Ok(reminder),
+        Err(e) => Err(e.into()),
     }
 }
 
@@ -108,15 +82,34 @@ pub async fn update_reminder_db(
 pub async fn get_reminders_by_user_id_db(
     db: &State<Pool>,
     user_id: i32,
-) -> Result<Vec<reminder::Model>, ReminderError> {
+) -> Result<Vec<reminder::Model>, AppError> {
     let conn = db.inner();
     match reminder::Entity::find()
         .filter(reminder::Column::UserId.eq(user_id))
+        .filter(reminder::Column::DeletedAt.is_null())
         .all(conn)
         .await
     {
         Ok(reminders) => Ok(reminders),
-        Err(e) => Err(ReminderError::DatabaseError(e.to_string())),
+        Err(e) => Err(e.into()),
     }
 }
 
+/// Ordenações aceitas em `GET /api/reminders`; a primeira é a padrão.
+const SORTS: [(&str, reminder::Column); 3] = [
+    ("date_end", reminder::Column::DateEnd),
+    ("name", reminder::Column::Name),
+    ("id", reminder::Column::Id),
+];
+
+pub async fn list_reminders_db(
+    db: &Pool,
+    user_id: i32,
+    query: &ListQuery,
+) -> Result<PageDto<reminder::Model>, AppError> {
+    let filter = list_service::parse_filter(db, user_id, query).await?;
+    let page = list_service::parse_page(query, &SORTS)?;
+    let limit = page.limit;
+    let reminders = ReminderRepository::new(db).find_page(user_id, &filter, page).await?;
+    Ok(list_service::to_page_dto(reminders, limit, |reminder| reminder))
+}
@@ -1,19 +1,123 @@
 use crate::dto::report_dto::*;
-use chrono::{NaiveDate, Datelike, Duration, Utc, Weekday, Timelike, TimeZone};
+use chrono::{DateTime, NaiveDate, Datelike, Duration, Months, Utc, Weekday, Timelike, TimeZone};
+use chrono_tz::Tz;
 use sea_orm::*;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use crate::entity::sea_orm_active_enums::{PomodoroKind, PomodoroStatus, TaskStatus};
 use crate::entity::task;
+use crate::entity::time_entry;
+use crate::entity::pomodoro_session;
 use crate::entity::goal;
+use crate::errors::AppError;
 
 /// Serviço para geração de relatórios e estatísticas
+///
+/// Os períodos, turnos e dias da semana são calculados no fuso horário do usuário.
 pub struct ReportService {
     db: DatabaseConnection,
+    timezone: Tz,
 }
 
 impl ReportService {
     /// Cria uma instância do ReportService
-    pub fn new(db: DatabaseConnection) -> Self {
-        Self { db }
+    pub fn new(db: DatabaseConnection, timezone: Tz) -> Self {
+        Self { db, timezone }
+    }
+
+    /// Tarefas do usuário, sem as que estão na lixeira.
+    fn tasks_of(user_id: i32) -> Select<task::Entity> {
+        task::Entity::find()
+            .filter(task::Column::UserId.eq(user_id))
+            .filter(task::Column::DeletedAt.is_null())
+    }
+
+    /// Metas do usuário, sem as que estão na lixeira.
+    fn goals_of(user_id: i32) -> Select<goal::Entity> {
+        goal::Entity::find()
+            .filter(goal::Column::UserId.eq(user_id))
+            .filter(goal::Column::DeletedAt.is_null())
+    }
+
+    /// Horas registradas nos registros encerrados que começam no período: o
+    /// total, por categoria da tarefa e por turno do início.
+    async fn tracked_hours(
+        &self,
+        user_id: i32,
+        start_date: DateTime<Utc>,
+        end_date: DateTime<Utc>,
+    ) -> Result<TrackedHours, DbErr> {
+        let entries = time_entry::Entity::find()
+            .find_also_related(task::Entity)
+            .filter(time_entry::Column::UserId.eq(user_id))
+            .filter(time_entry::Column::StoppedAt.is_not_null())
+            .filter(time_entry::Column::StartedAt.gte(start_date))
+            .filter(time_entry::Column::StartedAt.lte(end_date))
+            .filter(task::Column::DeletedAt.is_null())
+            .all(&self.db)
+            .await?;
+
+        let mut seconds = 0;
+        let mut category_seconds: HashMap<String, (String, i64)> = HashMap::new();
+        let mut shift_seconds: HashMap<&'static str, i64> = HashMap::new();
+        for (entry, task) in &entries {
+            let (Some(stopped_at), Some(task)) = (entry.stopped_at, task) else { continue };
+            let duration = (stopped_at - entry.started_at).num_seconds().max(0);
+            seconds += duration;
+
+            // Nomes que diferem só em maiúsculas ou espaços contam como a mesma categoria.
+            let name = task.category.trim().to_string();
+            category_seconds.entry(name.to_lowercase()).or_insert((name, 0)).1 += duration;
+
+            let hour = entry.started_at.with_timezone(&self.timezone).hour();
+            *shift_seconds.entry(self.shift_name(hour)).or_insert(0) += duration;
+        }
+
+        Ok(TrackedHours {
+            total: self.to_hours(seconds),
+            by_category: category_seconds
+                .into_values()
+                .map(|(category, seconds)| (category, self.to_hours(seconds)))
+                .collect(),
+            by_shift: shift_seconds
+                .into_iter()
+                .map(|(shift, seconds)| (shift.to_string(), self.to_hours(seconds)))
+                .collect(),
+        })
+    }
+
+    /// Sessões de foco concluídas que começam no período, contando as que
+    /// passaram do fim sem terem sido paradas.
+    async fn completed_pomodoros(
+        &self,
+        user_id: i32,
+        start_date: DateTime<Utc>,
+        end_date: DateTime<Utc>,
+    ) -> Result<u64, DbErr> {
+        pomodoro_session::Entity::find()
+            .filter(pomodoro_session::Column::UserId.eq(user_id))
+            .filter(pomodoro_session::Column::Kind.eq(PomodoroKind::Work))
+            .filter(pomodoro_session::Column::StartedAt.gte(start_date))
+            .filter(pomodoro_session::Column::StartedAt.lte(end_date))
+            .filter(
+                Condition::any()
+                    .add(pomodoro_session::Column::Status.eq(PomodoroStatus::Completed))
+                    .add(
+                        Condition::all()
+                            .add(pomodoro_session::Column::Status.eq(PomodoroStatus::Running))
+                            .add(pomodoro_session::Column::EndsAt.lte(Utc::now())),
+                    ),
+            )
+            .count(&self.db)
+            .await
+    }
+
+    /// Converte a meia-noite do dia informado, no fuso do usuário, para UTC.
+    fn local_midnight(&self, date: NaiveDate) -> DateTime<Utc> {
+        let midnight = date.and_hms_opt(0, 0, 0).unwrap();
+        self.timezone
+            .from_local_datetime(&midnight)
+            .earliest()
+            .map_or_else(|| midnight.and_utc(), |local| local.with_timezone(&Utc))
     }
 
     /// Obtém estatísticas de tarefas para um ano específico
@@ -21,70 +125,63 @@ impl ReportService {
         &self,
         user_id: i32,
         year: i32,
-    ) -> Result<StatsYearResponse, DbErr> {
-        let start_date = Utc.with_ymd_and_hms(year, 1, 1, 0, 0, 0).unwrap();
-        let end_date = Utc.with_ymd_and_hms(year, 12, 31, 23, 59, 59).unwrap();
+    ) -> Result<StatsYearResponse, AppError> {
+        let first_day = first_day_of_month(year, 1)?;
+        let start_date = self.local_midnight(first_day);
+        let end_date = self.local_midnight(months_later(first_day, 12)?) - Duration::seconds(1);
 
         // Total de tarefas do usuário no ano especificado
-        let total_tasks = task::Entity::find()
-            .filter(task::Column::UserId.eq(user_id))
+        let total_tasks = Self::tasks_of(user_id)
             .filter(task::Column::BeginDate.gte(start_date))
             .filter(task::Column::BeginDate.lte(end_date))
             .count(&self.db)
             .await?;
 
         // Tarefas executadas no ano
-        let executed_tasks = task::Entity::find()
-            .filter(task::Column::UserId.eq(user_id))
+        let executed_tasks = Self::tasks_of(user_id)
             .filter(task::Column::BeginDate.gte(start_date))
             .filter(task::Column::BeginDate.lte(end_date))
-            .filter(task::Column::Status.eq("Concluída"))
+            .filter(task::Column::Status.eq(TaskStatus::Concluida))
             .count(&self.db)
             .await?;
 
-        let pendent_tasks = task::Entity::find()
-            .filter(task::Column::UserId.eq(user_id))
+        let pendent_tasks = Self::tasks_of(user_id)
             .filter(task::Column::BeginDate.gte(start_date))
             .filter(task::Column::BeginDate.lte(end_date))
-            .filter(task::Column::Status.eq("Pendente"))
+            .filter(task::Column::Status.eq(TaskStatus::Pendente))
             .count(&self.db)
             .await?;
 
-        let delayed_tasks = task::Entity::find()
-            .filter(task::Column::UserId.eq(user_id))
+        let delayed_tasks = Self::tasks_of(user_id)
             .filter(task::Column::BeginDate.gte(start_date))
             .filter(task::Column::BeginDate.lte(end_date))
-            .filter(task::Column::Status.eq("Adiada"))
+            .filter(task::Column::Status.eq(TaskStatus::Adiada))
             .count(&self.db)
             .await?;
 
         // Total de metas do usuário no ano especificado
-        let total_goals = goal::Entity::find()
-            .filter(goal::Column::UserId.eq(user_id))
+        let total_goals = Self::goals_of(user_id)
             .filter(goal::Column::DateStart.gte(start_date))
             .filter(goal::Column::DateStart.lte(end_date))
             .count(&self.db)
             .await?;
 
         // metas executadas no ano
-        let executed_goals = goal::Entity::find()
-            .filter(goal::Column::UserId.eq(user_id))
+        let executed_goals = Self::goals_of(user_id)
             .filter(goal::Column::DateStart.gte(start_date))
             .filter(goal::Column::DateStart.lte(end_date))
             .filter(goal::Column::Status.eq("Concluída"))
             .count(&self.db)
             .await?;
 
-        let pendent_goals = goal::Entity::find()
-            .filter(goal::Column::UserId.eq(user_id))
+        let pendent_goals = Self::goals_of(user_id)
             .filter(goal::Column::DateStart.gte(start_date))
             .filter(goal::Column::DateStart.lte(end_date))
             .filter(goal::Column::Status.eq("Em andamento"))
             .count(&self.db)
             .await?;
 
-        let delayed_goals = goal::Entity::find()
-            .filter(goal::Column::UserId.eq(user_id))
+        let delayed_goals = Self::goals_of(user_id)
             .filter(goal::Column::DateStart.gte(start_date))
             .filter(goal::Column::DateStart.lte(end_date))
             .filter(goal::Column::Status.eq("Cancelada"))
@@ -103,18 +200,16 @@ impl ReportService {
             total_goals.try_into().unwrap()
         );
 
-        // Buscar os detalhes das tarefas executadas
-        let executed_task_details = task::Entity::find()
-            .filter(task::Column::UserId.eq(user_id))
-            .filter(task::Column::Status.eq("Concluída"))
-            .filter(task::Column::CompleteDate.gte(start_date))
-            .filter(task::Column::CompleteDate.lte(end_date))
+        // Tarefas concluídas no período, pelo horário em que foram concluídas de fato
+        let executed_task_details = Self::tasks_of(user_id)
+            .filter(task::Column::Status.eq(TaskStatus::Concluida))
+            .filter(task::Column::CompletedAt.gte(start_date))
+            .filter(task::Column::CompletedAt.lte(end_date))
             .all(&self.db)
             .await?;
 
         // metas executadas no ano
-        let executed_goals_details = goal::Entity::find()
-            .filter(goal::Column::UserId.eq(user_id))
+        let executed_goals_details = Self::goals_of(user_id)
             .filter(goal::Column::Status.eq("Concluída"))
             .filter(goal::Column::DateStart.gte(start_date))
             .filter(goal::Column::DateStart.lte(end_date))
@@ -135,33 +230,34 @@ impl ReportService {
 
         // Processar todas as tarefas
         for task in &executed_task_details {
-            self.count_shift(&mut shift_counts, task.complete_date.hour());
+            let Some(completed_at) = task.completed_at else { continue };
+            self.count_shift(&mut shift_counts, completed_at.with_timezone(&self.timezone).hour());
 
             // Contar categorias
             *category_counts.entry(task.category.clone()).or_insert(0) += 1;
 
             // Contar meses
-            let month = task.complete_date.month();
+            let month = completed_at.with_timezone(&self.timezone).month();
             *month_counts.entry(month).or_insert(0) += 1;
 
             // Contar semanas
-            let week = task.complete_date.iso_week().week();
+            let week = completed_at.with_timezone(&self.timezone).iso_week().week();
             *week_counts.entry(week).or_insert(0) += 1;
         }
 
         // Processar todas as metas
         for goal in &executed_goals_details {
-            self.count_shift(&mut shift_counts_goals, goal.date_end.hour());
+            self.count_shift(&mut shift_counts_goals, goal.date_end.with_timezone(&self.timezone).hour());
 
             // Contar categorias
-            *category_counts_goals.entry(goal.category.clone().expect("Sem Categoria")).or_insert(0) += 1;
+            *category_counts_goals.entry(goal.category.clone().unwrap_or_else(|| "Sem Categoria".to_string())).or_insert(0) += 1;
 
             // Contar meses
-            let month = goal.date_end.month();
+            let month = goal.date_end.with_timezone(&self.timezone).month();
             *month_counts_goals.entry(month).or_insert(0) += 1;
 
             // Contar semanas
-            let week = goal.date_end.iso_week().week();
+            let week = goal.date_end.with_timezone(&self.timezone).iso_week().week();
             *week_counts_goals.entry(week).or_insert(0) += 1;
         }
 
@@ -177,6 +273,9 @@ impl ReportService {
         let most_productive_week_tasks = self.find_most_productive_week(week_counts);
         let classification_tasks = self.classify_performance(percentage_tasks);
 
+        let tracked = self.tracked_hours(user_id, start_date, end_date).await?;
+        let completed_pomodoros = self.completed_pomodoros(user_id, start_date, end_date).await?;
+
         Ok(StatsYearResponse {
             year,
             total_tasks: total_tasks as i64,
@@ -189,6 +288,10 @@ impl ReportService {
             most_productive_month_tasks,
             most_productive_week_tasks,
             classification_tasks,
+            tracked_hours_tasks: tracked.total,
+            tracked_hours_by_category_tasks: tracked.by_category,
+            tracked_hours_by_shift_tasks: tracked.by_shift,
+            completed_pomodoros: completed_pomodoros as i64,
             total_goals: total_goals as i64,
             executed_goals: executed_goals as i64,
             pendent_goals: pendent_goals as i64,
@@ -208,78 +311,64 @@ impl ReportService {
         user_id: i32,
         year: i32,
         month: i32
-    ) -> Result<StatsMonthResponse, DbErr> {
-        let start_date = Utc.with_ymd_and_hms(year, month.try_into().unwrap(), 1, 0, 0, 0).unwrap();
-
-        let (next_year, next_month) = if month == 12 {
-            (year + 1, 1)
-        } else {
-            (year, month + 1)
-        };
-
-        let first_day_of_next_month = Utc.with_ymd_and_hms(next_year, next_month.try_into().unwrap(), 1, 0, 0, 0).unwrap();
+    ) -> Result<StatsMonthResponse, AppError> {
+        let first_day = first_day_of_month(year, month)?;
+        let start_date = self.local_midnight(first_day);
+        let first_day_of_next_month = self.local_midnight(months_later(first_day, 1)?);
         let end_date = first_day_of_next_month - Duration::seconds(1);
 
         // Total de tarefas do usuário no mês especificado
-        let total_tasks = task::Entity::find()
-            .filter(task::Column::UserId.eq(user_id))
+        let total_tasks = Self::tasks_of(user_id)
             .filter(task::Column::BeginDate.gte(start_date))
             .filter(task::Column::BeginDate.lte(end_date))
             .count(&self.db)
             .await?;
 
         // Tarefas executadas no mês
-        let executed_tasks = task::Entity::find()
-            .filter(task::Column::UserId.eq(user_id))
+        let executed_tasks = Self::tasks_of(user_id)
             .filter(task::Column::BeginDate.gte(start_date))
             .filter(task::Column::BeginDate.lte(end_date))
-            .filter(task::Column::Status.eq("Concluída"))
+            .filter(task::Column::Status.eq(TaskStatus::Concluida))
             .count(&self.db)
             .await?;
 
-        let pendent_tasks = task::Entity::find()
-            .filter(task::Column::UserId.eq(user_id))
+        let pendent_tasks = Self::tasks_of(user_id)
             .filter(task::Column::BeginDate.gte(start_date))
             .filter(task::Column::BeginDate.lte(end_date))
-            .filter(task::Column::Status.eq("Pendente"))
+            .filter(task::Column::Status.eq(TaskStatus::Pendente))
             .count(&self.db)
             .await?;
 
-        let delayed_tasks = task::Entity::find()
-            .filter(task::Column::UserId.eq(user_id))
+        let delayed_tasks = Self::tasks_of(user_id)
             .filter(task::Column::BeginDate.gte(start_date))
             .filter(task::Column::BeginDate.lte(end_date))
-            .filter(task::Column::Status.eq("Adiada"))
+            .filter(task::Column::Status.eq(TaskStatus::Adiada))
             .count(&self.db)
             .await?;
 
         // Total de metas do usuário no ano especificado
-        let total_goals = goal::Entity::find()
-            .filter(goal::Column::UserId.eq(user_id))
+        let total_goals = Self::goals_of(user_id)
             .filter(goal::Column::DateStart.gte(start_date))
             .filter(goal::Column::DateStart.lte(end_date))
             .count(&self.db)
             .await?;
 
         // metas executadas no ano
-        let executed_goals = goal::Entity::find()
-            .filter(goal::Column::UserId.eq(user_id))
+        let executed_goals = Self::goals_of(user_id)
             .filter(goal::Column::DateStart.gte(start_date))
             .filter(goal::Column::DateStart.lte(end_date))
             .filter(goal::Column::Status.eq("Concluída"))
             .count(&self.db)
             .await?;
 
-        let pendent_goals = goal::Entity::find()
-            .filter(goal::Column::UserId.eq(user_id))
+        let pendent_goals = Self::goals_of(user_id)
             .filter(goal::Column::DateStart.gte(start_date))
             .filter(goal::Column::DateStart.lte(end_date))
             .filter(goal::Column::Status.eq("Em andamento"))
             .count(&self.db)
             .await?;
 
-        let delayed_goals = goal::Entity::find()
-            .filter(goal::Column::UserId.eq(user_id))
+        let delayed_goals = Self::goals_of(user_id)
             .filter(goal::Column::DateStart.gte(start_date))
             .filter(goal::Column::DateStart.lte(end_date))
             .filter(goal::Column::Status.eq("Cancelada"))
@@ -298,18 +387,16 @@ impl ReportService {
             total_goals.try_into().unwrap()
         );
 
-        // Buscar os detalhes das tarefas executadas
-        let executed_task_details = task::Entity::find()
-            .filter(task::Column::UserId.eq(user_id))
-            .filter(task::Column::Status.eq("Concluída"))
-            .filter(task::Column::CompleteDate.gte(start_date))
-            .filter(task::Column::CompleteDate.lte(end_date))
+        // Tarefas concluídas no período, pelo horário em que foram concluídas de fato
+        let executed_task_details = Self::tasks_of(user_id)
+            .filter(task::Column::Status.eq(TaskStatus::Concluida))
+            .filter(task::Column::CompletedAt.gte(start_date))
+            .filter(task::Column::CompletedAt.lte(end_date))
             .all(&self.db)
             .await?;
 
         // metas executadas no ano
-        let executed_goals_details = goal::Entity::find()
-            .filter(goal::Column::UserId.eq(user_id))
+        let executed_goals_details = Self::goals_of(user_id)
             .filter(goal::Column::Status.eq("Concluída"))
             .filter(goal::Column::DateStart.gte(start_date))
             .filter(goal::Column::DateStart.lte(end_date))
@@ -329,25 +416,26 @@ impl ReportService {
 
         // Processar todas as tarefas
         for task in &executed_task_details {
-            self.count_shift(&mut shift_counts, task.complete_date.hour());
+            let Some(completed_at) = task.completed_at else { continue };
+            self.count_shift(&mut shift_counts, completed_at.with_timezone(&self.timezone).hour());
 
             // Contar categorias
             *category_counts.entry(task.category.clone()).or_insert(0) += 1;
 
             // Contar semanas
-            let week = task.complete_date.iso_week().week();
+            let week = completed_at.with_timezone(&self.timezone).iso_week().week();
             *week_counts.entry(week).or_insert(0) += 1;
         }
 
         // Processar todas as metas
         for goal in &executed_goals_details {
-            self.count_shift(&mut shift_counts_goals, goal.date_end.hour());
+            self.count_shift(&mut shift_counts_goals, goal.date_end.with_timezone(&self.timezone).hour());
 
             // Contar categorias
-            *category_counts_goals.entry(goal.category.clone().expect("Sem Categoria")).or_insert(0) += 1;
+            *category_counts_goals.entry(goal.category.clone().unwrap_or_else(|| "Sem Categoria".to_string())).or_insert(0) += 1;
 
             // Contar semanas
-            let week = goal.date_end.iso_week().week();
+            let week = goal.date_end.with_timezone(&self.timezone).iso_week().week();
             *week_counts_goals.entry(week).or_insert(0) += 1;
         }
 
@@ -362,6 +450,9 @@ impl ReportService {
         let most_productive_week_goals = self.find_most_productive_week(week_counts_goals);
         let classification_goals = self.classify_performance(percentage_goals);
 
+        let tracked = self.tracked_hours(user_id, start_date, end_date).await?;
+        let completed_pomodoros = self.completed_pomodoros(user_id, start_date, end_date).await?;
+
         Ok(StatsMonthResponse {
             year,
             month,
@@ -374,6 +465,10 @@ impl ReportService {
             most_used_category_tasks,
             most_productive_week_tasks,
             classification_tasks,
+            tracked_hours_tasks: tracked.total,
+            tracked_hours_by_category_tasks: tracked.by_category,
+            tracked_hours_by_shift_tasks: tracked.by_shift,
+            completed_pomodoros: completed_pomodoros as i64,
             total_goals: total_goals as i64,
             executed_goals: executed_goals as i64,
             pendent_goals: pendent_goals as i64,
@@ -392,75 +487,69 @@ impl ReportService {
         user_id: i32,
         year: i32,
         week_num: i32,
-    ) -> Result<StatsWeekResponse, DbErr> {
+    ) -> Result<StatsWeekResponse, AppError> {
         // Calcula o primeiro dia da semana especificada
-        let start_of_week_naive = NaiveDate::from_isoywd_opt(year, week_num.try_into().unwrap(), Weekday::Mon)
-            .expect("Ano ou número de semana inválido.");
-        let start_date = start_of_week_naive.and_hms_opt(0, 0, 0).unwrap().and_utc();
+        let start_of_week_naive = u32::try_from(week_num)
+            .ok()
+            .and_then(|week| NaiveDate::from_isoywd_opt(year, week, Weekday::Mon))
+            .ok_or_else(|| AppError::field("week", "Semana inválida para o ano informado."))?;
+        let start_date = self.local_midnight(start_of_week_naive);
 
         // O fim da semana é o início da próxima semana menos 1 segundo
-        let end_date = start_date + Duration::weeks(1) - Duration::seconds(1);
+        let end_date = self.local_midnight(start_of_week_naive + Duration::weeks(1)) - Duration::seconds(1);
 
         // Total de tarefas do usuário na semana especificada
-        let total_tasks = task::Entity::find()
-            .filter(task::Column::UserId.eq(user_id))
+        let total_tasks = Self::tasks_of(user_id)
             .filter(task::Column::BeginDate.gte(start_date))
             .filter(task::Column::BeginDate.lte(end_date))
             .count(&self.db)
             .await?;
 
         // Tarefas executadas na semana
-        let executed_tasks = task::Entity::find()
-            .filter(task::Column::UserId.eq(user_id))
+        let executed_tasks = Self::tasks_of(user_id)
             .filter(task::Column::BeginDate.gte(start_date))
             .filter(task::Column::BeginDate.lte(end_date))
-            .filter(task::Column::Status.eq("Concluída"))
+            .filter(task::Column::Status.eq(TaskStatus::Concluida))
             .count(&self.db)
             .await?;
 
-        let pendent_tasks = task::Entity::find()
-            .filter(task::Column::UserId.eq(user_id))
+        let pendent_tasks = Self::tasks_of(user_id)
             .filter(task::Column::BeginDate.gte(start_date))
             .filter(task::Column::BeginDate.lte(end_date))
-            .filter(task::Column::Status.eq("Pendente"))
+            .filter(task::Column::Status.eq(TaskStatus::Pendente))
             .count(&self.db)
             .await?;
 
-        let delayed_tasks = task::Entity::find()
-            .filter(task::Column::UserId.eq(user_id))
+        let delayed_tasks = Self::tasks_of(user_id)
             .filter(task::Column::BeginDate.gte(start_date))
             .filter(task::Column::BeginDate.lte(end_date))
-            .filter(task::Column::Status.eq("Adiada"))
+            .filter(task::Column::Status.eq(TaskStatus::Adiada))
             .count(&self.db)
             .await?;
 
         // Total de metas do usuário na semana especificada
-        let total_goals = goal::Entity::find()
-            .filter(goal::Column::UserId.eq(user_id))
+        let total_goals = Self::goals_of(user_id)
             .filter(goal::Column::DateStart.gte(start_date))
             .filter(goal::Column::DateStart.lte(end_date))
             .count(&self.db)
             .await?;
 
         // metas executadas no ano
-        let executed_goals = goal::Entity::find()
-            .filter(goal::Column::UserId.eq(user_id))
+        let executed_goals = Self::goals_of(user_id)
             .filter(goal::Column::DateStart.gte(start_date))
             .filter(goal::Column::DateStart.lte(end_date))
             .filter(goal::Column::Status.eq("Concluída"))
             .count(&self.db)
             .await?;
 
-        let pendent_goals = goal::Entity::find()
-            .filter(goal::Column::UserId.eq(user_id))
+        let pendent_goals = Self::goals_of(user_id)
             .filter(goal::Column::DateStart.gte(start_date))
             .filter(goal::Column::DateStart.lte(end_date))
             .filter(goal::Column::Status.eq("Em andamento"))
             .count(&self.db)
             .await?;
 
-        let delayed_goals = goal::Entity::find()
-            .filter(goal::Column::UserId.eq(user_id))
+        let delayed_goals = Self::goals_of(user_id)
             .filter(goal::Column::DateStart.gte(start_date))
             .filter(goal::Column::DateStart.lte(end_date))
             .filter(goal::Column::Status.eq("Cancelada"))
@@ -480,18 +569,16 @@ impl ReportService {
             total_goals.try_into().unwrap()
         );
 
-        // Buscar os detalhes das tarefas executadas
-        let executed_task_details = task::Entity::find()
-            .filter(task::Column::UserId.eq(user_id))
-            .filter(task::Column::Status.eq("Concluída"))
-            .filter(task::Column::CompleteDate.gte(start_date))
-            .filter(task::Column::CompleteDate.lte(end_date))
+        // Tarefas concluídas no período, pelo horário em que foram concluídas de fato
+        let executed_task_details = Self::tasks_of(user_id)
+            .filter(task::Column::Status.eq(TaskStatus::Concluida))
+            .filter(task::Column::CompletedAt.gte(start_date))
+            .filter(task::Column::CompletedAt.lte(end_date))
             .all(&self.db)
             .await?;
 
         // metas executadas no ano
-        let executed_goals_details = goal::Entity::find()
-            .filter(goal::Column::UserId.eq(user_id))
+        let executed_goals_details = Self::goals_of(user_id)
             .filter(goal::Column::Status.eq("Concluída"))
             .filter(goal::Column::DateStart.gte(start_date))
             .filter(goal::Column::DateStart.lte(end_date))
@@ -511,25 +598,26 @@ impl ReportService {
 
         // Processar todas as tarefas
         for task in &executed_task_details {
-            self.count_shift(&mut shift_counts, task.complete_date.hour());
+            let Some(completed_at) = task.completed_at else { continue };
+            self.count_shift(&mut shift_counts, completed_at.with_timezone(&self.timezone).hour());
 
             // Contar categorias
             *category_counts.entry(task.category.clone()).or_insert(0) += 1;
 
             // Contar dias da semana
-            let day_name = self.get_weekday_name(task.complete_date.weekday());
+            let day_name = self.get_weekday_name(completed_at.with_timezone(&self.timezone).weekday());
             *day_counts.entry(day_name).or_insert(0) += 1;
         }
 
         // Processar todas as metas
         for goal in &executed_goals_details {
-            self.count_shift(&mut shift_counts_goals, goal.date_end.hour());
+            self.count_shift(&mut shift_counts_goals, goal.date_end.with_timezone(&self.timezone).hour());
 
             // Contar categorias
-            *category_counts_goals.entry(goal.category.clone().expect("Sem Categoria")).or_insert(0) += 1;
+            *category_counts_goals.entry(goal.category.clone().unwrap_or_else(|| "Sem Categoria".to_string())).or_insert(0) += 1;
 
             // Contar dias da semana
-            let day_name = self.get_weekday_name(goal.date_end.weekday());
+            let day_name = self.get_weekday_name(goal.date_end.with_timezone(&self.timezone).weekday());
             *day_counts_goals.entry(day_name).or_insert(0) += 1;
         }
 
@@ -544,6 +632,9 @@ impl ReportService {
         let most_productive_day_goals = self.find_most_productive_day(day_counts_goals);
         let classification_goals = self.classify_performance(percentage_goals);
 
+        let tracked = self.tracked_hours(user_id, start_date, end_date).await?;
+        let completed_pomodoros = self.completed_pomodoros(user_id, start_date, end_date).await?;
+
         Ok(StatsWeekResponse {
             year,
             week: week_num,
@@ -556,6 +647,10 @@ impl ReportService {
             most_used_category_tasks,
             most_productive_day_tasks,
             classification_tasks,
+            tracked_hours_tasks: tracked.total,
+            tracked_hours_by_category_tasks: tracked.by_category,
+            tracked_hours_by_shift_tasks: tracked.by_shift,
+            completed_pomodoros: completed_pomodoros as i64,
             total_goals: total_goals as i64,
             executed_goals: executed_goals as i64,
             pendent_goals: pendent_goals as i64,
@@ -581,13 +676,22 @@ impl ReportService {
 
     /// Conta os turnos com base na hora
     fn count_shift(&self, shift_counts: &mut HashMap<&'static str, i32>, hour: u32) {
-        let shift_name = match hour {
+        *shift_counts.entry(self.shift_name(hour)).or_insert(0) += 1;
+    }
+
+    /// Turno da hora informada
+    fn shift_name(&self, hour: u32) -> &'static str {
+        match hour {
             6..=11 => "Manhã",
             12..=17 => "Tarde",
             18..=23 => "Noite",
             _ => "Madrugada", // 0..=5
-        };
-        *shift_counts.entry(shift_name).or_insert(0) += 1;
+        }
+    }
+
+    /// Converte segundos em horas, com duas casas decimais
+    fn to_hours(&self, seconds: i64) -> f64 {
+        (seconds as f64 / 36.0).round() / 100.0
     }
 
     /// Retorna o nome do dia da semana
@@ -613,9 +717,15 @@ impl ReportService {
     }
 
     /// Encontra a categoria mais usada
+    /// Nomes que diferem só em maiúsculas ou espaços contam como a mesma categoria.
     fn find_most_used_category(&self, category_counts: HashMap<String, i32>) -> String {
-        category_counts
-            .into_iter()
+        let mut merged: HashMap<String, (String, i32)> = HashMap::new();
+        for (category, count) in category_counts {
+            let name = category.trim().to_string();
+            merged.entry(name.to_lowercase()).or_insert((name, 0)).1 += count;
+        }
+        merged
+            .into_values()
             .max_by_key(|&(_, count)| count)
             .map(|(category, _)| category)
             .unwrap_or_else(|| "N/A".to_string())
@@ -676,4 +786,27 @@ impl ReportService {
             _ => "Nem tentou".to_string(),
         }
     }
-}
\ No newline at end of file
+}
+
+/// Horas registradas em um período, já agrupadas para os relatórios.
+struct TrackedHours {
+    total: f64,
+    by_category: BTreeMap<String, f64>,
+    by_shift: BTreeMap<String, f64>,
+}
+
+/// Primeiro dia do mês; ano ou mês fora do calendário viram erro de campo.
+fn first_day_of_month(year: i32, month: i32) -> Result<NaiveDate, AppError> {
+    let month = u32::try_from(month)
+        .ok()
+        .filter(|month| (1..=12).contains(month))
+        .ok_or_else(|| AppError::field("month", "O mês deve estar entre 1 e 12."))?;
+    NaiveDate::from_ymd_opt(year, month, 1).ok_or_else(|| AppError::field("year", "Ano inválido."))
+}
+
+/// Primeiro dia `months` meses depois de `first_day`.
+fn months_later(first_day: NaiveDate, months: u32) -> Result<NaiveDate, AppError> {
+    first_day
+        .checked_add_months(Months::new(months))
+        .ok_or_else(|| AppError::field("year", "Ano inválido."))
+}
@@ -0,0 +1,30 @@
+use crate::db::Pool;
+use crate::dto::search_dto::SearchResultDto;
+use crate::errors::AppError;
+use crate::repository::search_repository::SearchRepository;
+
+const DEFAULT_LIMIT: u64 = 20;
+const MAX_LIMIT: u64 = 50;
+const MAX_QUERY_CHARS: usize = 200;
+
+/// Itens do usuário que correspondem à busca, do mais para o menos relevante.
+pub async fn search_db(
+    db: &Pool,
+    user_id: i32,
+    query: &str,
+    limit: Option<u64>,
+) -> Result<Vec<SearchResultDto>, AppError> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Err(AppError::field("q", "Informe o texto da busca."));
+    }
+    if query.chars().count() > MAX_QUERY_CHARS {
+        return Err(AppError::field("q", format!("A busca deve ter no máximo {} caracteres.", MAX_QUERY_CHARS)));
+    }
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    if !(1..=MAX_LIMIT).contains(&limit) {
+        return Err(AppError::field("limit", format!("O limite deve estar entre 1 e {}.", MAX_LIMIT)));
+    }
+
+    SearchRepository::new(db).search(user_id, query, limit).await
+}
@@ -1,89 +1,989 @@
 use rocket::State;
 use crate::db::Pool;
-use crate::dto::task_dto::TaskDto;
+use crate::dto::checklist_dto::ChecklistProgressDto;
+use crate::dto::list_dto::{ListQuery, PageDto};
+use crate::dto::task_bulk_dto::{TaskBulkAction, TaskBulkDto, TaskBulkItemDto, TaskBulkResultDto};
+use crate::dto::task_dto::{TaskDto, TaskResponseDto};
+use crate::dto::task_occurrence_dto::TaskOccurrenceDto;
+use crate::dto::task_rollover_dto::{TaskRolloverDto, TaskRolloverResultDto, TaskRolloverSkipDto};
 use crate::dto::task_update_dto::TaskUpdateDto;
-use crate::entity::task;
+use crate::entity::sea_orm_active_enums::TaskStatus;
+use crate::entity::{category, task, task_occurrence, task_status_history};
+use crate::errors::AppError;
 use crate::repository::task_repository::TaskRepository;
-use sea_orm::DeleteResult;
+use crate::service::recurrence_service::{local_midnight, local_to_utc, RecurrenceRule};
+use crate::service::{auth_service, category_service, checklist_service, dependency_service, list_service, profile_service, time_entry_service};
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+use chrono_tz::Tz;
+use sea_orm::{ConnectionTrait, TransactionTrait, UpdateResult};
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
 use validator::Validate;
-/// Enum para erros específicos do serviço de tarefas.
-#[allow(dead_code)]
-pub enum TaskError {
-    TaskNotFound(String),
-    DatabaseError(String),
-    ValidationError(String),
-}
 
-pub async fn get_all_tasks_db(db: &State<Pool>) -> Result<Vec<task::Model>, TaskError> {
-    let conn = db.inner();
-    let repo = TaskRepository::new(conn);
-    repo.find_all()
-        .await
-        .map_err(|e| TaskError::DatabaseError(e.to_string()))
-}
+/// Até onde as ocorrências de uma nova série são comparadas com a agenda.
+const CONFLICT_HORIZON_DAYS: i64 = 365;
 
-pub async fn get_task_by_id_db(db: &State<Pool>, id: i32) -> Result<task::Model, TaskError> {
-    let conn = db.inner();
-    let repo = TaskRepository::new(conn);
-    repo.find_by_id(id)
-        .await
-        .map_err(|e| TaskError::DatabaseError(e.to_string()))?
-        .ok_or_else(|| TaskError::TaskNotFound(format!("Task with id {} not found", id)))
-}
+/// Maior intervalo aceito em `GET /api/tasks/occurrences`.
+const MAX_RANGE_DAYS: i64 = 400;
+
+/// Maior deslocamento aceito em `shift` nas operações em lote (10 anos).
+const MAX_SHIFT_DAYS: i32 = 3650;
+
+/// Tipo gravado nas tarefas com fim ou duração livres.
+const CUSTOM_TYPE: &str = "Personalizado";
+
+/// Tipo gravado nas tarefas de dia inteiro.
+const ALL_DAY_TYPE: &str = "DiaInteiro";
+
+/// Ordenações aceitas em `GET /api/tasks`; a primeira é a padrão.
+const SORTS: [(&str, task::Column); 5] = [
+    ("begin_date", task::Column::BeginDate),
+    ("complete_date", task::Column::CompleteDate),
+    ("title", task::Column::Title),
+    ("status", task::Column::Status),
+    ("id", task::Column::Id),
+];
 
 pub async fn get_tasks_by_user_id_db(
     db: &State<Pool>,
     user_id: i32,
-) -> Result<Vec<task::Model>, TaskError> {
+) -> Result<Vec<task::Model>, AppError> {
     let conn = db.inner();
     let repo = TaskRepository::new(conn);
-    repo.find_by_user_id(user_id)
-        .await
-        .map_err(|e| TaskError::DatabaseError(e.to_string()))
+    repo.find_by_user_id(user_id).await
 }
+
 pub async fn register_task_db(
     db: &State<Pool>,
     task_info: &TaskDto,
     user_id: i32,
-) -> Result<task::Model, TaskError> {
-    task_info.validate().map_err(|e| TaskError::ValidationError(e.to_string()))?; // validator
+) -> Result<task::Model, AppError> {
+    task_info.validate()?; // validator
     let conn = db.inner();
     let repo = TaskRepository::new(conn);
-    repo.create_task(task_info, user_id)
-        .await
-        .map_err(|e| TaskError::DatabaseError(e.to_string()))
+
+    let timezone = profile_service::user_timezone(&auth_service::find_user(conn, user_id).await?);
+    let (begin_date, complete_date, task_type) = task_period(
+        task_info.begin_date,
+        &task_info.r#type,
+        task_info.complete_date,
+        task_info.duration_minutes,
+        task_info.all_day,
+        timezone,
+    )?;
+    let rule = task_info.recurrence.as_ref().map(RecurrenceRule::from_dto).transpose()?;
+    if rule.as_ref().and_then(|rule| rule.until).is_some_and(|until| until < begin_date) {
+        return Err(AppError::field(
+            "recurrence.until",
+            "A data final deve ser posterior ao início da tarefa.",
+        ));
+    }
+
+    // Removed validation: Tasks can now be created in the past
+    // if begin_date < Utc::now() {
+    //     return Err(DbErr::Custom(
+    //         "Task begin date cannot be in the past.".to_string(),
+    //     ));
+    // }
+
+    let periods = scheduled_periods(rule.as_ref(), timezone, begin_date, complete_date);
+    ensure_no_overlap(&repo, user_id, timezone, &periods, None).await?;
+
+    let category =
+        category_service::resolve_category(conn, user_id, task_info.category_id, Some(&task_info.category)).await?;
+    let task = repo
+        .create_task(task_info, user_id, begin_date, complete_date, &task_type, category.as_ref())
+        .await?;
+    if let Some(rule) = &rule {
+        repo.create_recurrence(task.id, rule).await?;
+    }
+    Ok(task)
 }
 
+/// Atualiza a tarefa. Com `begin_date`, `type`, `complete_date`,
+/// `duration_minutes` ou `all_day`, também a remarca: o fim é recalculado e o
+/// novo horário não pode se sobrepor a outra tarefa. Remarcar uma série move
+/// todas as ocorrências e descarta as exceções.
 pub async fn update_task_db(
     db: &State<Pool>,
-    id: i32,
+    task_to_update: task::Model,
     task_info: &TaskUpdateDto,
-    user_id: i32,
-) -> Result<task::Model, TaskError> {
+) -> Result<task::Model, AppError> {
+    task_info.validate()?;
     let conn = db.inner();
     let repo = TaskRepository::new(conn);
-    repo.update_task(id, task_info, user_id)
-        .await
-        .map_err(|e| match e {
-            sea_orm::DbErr::RecordNotFound(_) => TaskError::TaskNotFound(e.to_string()),
-            _ => TaskError::DatabaseError(e.to_string()),
+    if completes(task_info) && task_to_update.status != TaskStatus::Concluida {
+        dependency_service::ensure_not_blocked(conn, task_to_update.id, &[]).await?;
+    }
+    let category = if task_info.category.is_some() || task_info.category_id.is_some() {
+        category_service::resolve_category(
+            conn,
+            task_to_update.user_id,
+            task_info.category_id,
+            task_info.category.as_deref(),
+        )
+        .await?
+    } else {
+        None
+    };
+
+    if !task_info.reschedules() {
+        let task = repo.update_task(task_to_update, task_info, category.as_ref(), None).await?;
+        return complete_if_enabled(conn, task, task_info).await;
+    }
+
+    let timezone = profile_service::user_timezone(&auth_service::find_user(conn, task_to_update.user_id).await?);
+    let task = reschedule_task(&repo, task_to_update, task_info, category.as_ref(), timezone).await?;
+    complete_if_enabled(conn, task, task_info).await
+}
+
+/// Remarca a tarefa conforme `task_info`. O novo período não pode começar
+/// depois do fim da repetição nem se sobrepor a outra tarefa; mover uma série
+/// descarta as exceções.
+async fn reschedule_task<C: ConnectionTrait>(
+    repo: &TaskRepository<'_, C>,
+    task_to_update: task::Model,
+    task_info: &TaskUpdateDto,
+    category: Option<&category::Model>,
+    timezone: Tz,
+) -> Result<task::Model, AppError> {
+    let (begin_date, complete_date, task_type) = reschedule_period(&task_to_update, task_info, timezone)?;
+    let rule = repo
+        .find_recurrence(task_to_update.id)
+        .await?
+        .map(|recurrence| RecurrenceRule::from_model(&recurrence));
+    if rule.as_ref().and_then(|rule| rule.until).is_some_and(|until| until < begin_date) {
+        return Err(AppError::field(
+            "begin_date",
+            "A repetição termina antes do novo início.",
+        ));
+    }
+
+    let periods = scheduled_periods(rule.as_ref(), timezone, begin_date, complete_date);
+    ensure_no_overlap(repo, task_to_update.user_id, timezone, &periods, Some(task_to_update.id)).await?;
+
+    let moved = begin_date != task_to_update.begin_date;
+    let task = repo
+        .update_task(task_to_update, task_info, category, Some((begin_date, complete_date, task_type)))
+        .await?;
+    if rule.is_some() && moved {
+        repo.delete_exceptions(task.id).await?;
+    }
+    Ok(task)
+}
+
+/// Aplica a ação a todas as tarefas em uma única transação, conferindo cada
+/// uma como na operação avulsa. Se alguma for recusada, a transação é desfeita
+/// e o resultado traz o erro de cada item; erros de banco encerram o lote.
+pub async fn bulk_update_tasks_db(
+    db: &Pool,
+    user_id: i32,
+    bulk: &TaskBulkDto,
+) -> Result<TaskBulkResultDto, AppError> {
+    bulk.validate()?;
+    let mut ids = bulk.task_ids.clone();
+    let mut seen = HashSet::new();
+    ids.retain(|id| seen.insert(*id));
+
+    let mut tasks = TaskRepository::new(db).find_by_ids(user_id, &ids).await?;
+    let mut errors: HashMap<i32, AppError> = ids
+        .iter()
+        .filter(|id| !tasks.iter().any(|task| task.id == **id))
+        .map(|id| (*id, AppError::NotFound(format!("Task with id {} not found", id))))
+        .collect();
+
+    let category = match &bulk.action {
+        TaskBulkAction::SetCategory { category, category_id } => {
+            if category.as_deref().is_none_or(|name| name.trim().is_empty()) && category_id.is_none() {
+                return Err(AppError::field("category", "Informe a categoria."));
+            }
+            category_service::resolve_category(db, user_id, *category_id, category.as_deref()).await?
+        }
+        _ => None,
+    };
+    let shift = match &bulk.action {
+        TaskBulkAction::Shift { days, minutes } => {
+            if days.abs() > MAX_SHIFT_DAYS {
+                return Err(AppError::field("days", format!("Desloque no máximo {} dias.", MAX_SHIFT_DAYS)));
+            }
+            if minutes.abs() > MAX_SHIFT_DAYS * 24 * 60 {
+                return Err(AppError::field("minutes", format!("Desloque no máximo {} minutos.", MAX_SHIFT_DAYS * 24 * 60)));
+            }
+            Duration::days((*days).into()) + Duration::minutes((*minutes).into())
+        }
+        _ => Duration::zero(),
+    };
+    if matches!(bulk.action, TaskBulkAction::Shift { .. }) && shift.is_zero() {
+        return Err(AppError::field("days", "Informe um deslocamento diferente de zero."));
+    }
+    // Ao adiantar, as últimas tarefas saem primeiro do caminho das anteriores; ao antecipar, o contrário.
+    if shift > Duration::zero() {
+        tasks.sort_by_key(|task| Reverse(task.begin_date));
+    } else {
+        tasks.sort_by_key(|task| task.begin_date);
+    }
+    let completing: Vec<i32> = match &bulk.action {
+        TaskBulkAction::SetStatus { status } if TaskStatus::parse(status) == Some(TaskStatus::Concluida) => tasks.iter().map(|task| task.id).collect(),
+        _ => Vec::new(),
+    };
+    let timezone = profile_service::user_timezone(&auth_service::find_user(db, user_id).await?);
+
+    let context = BulkContext { category: category.as_ref(), shift, timezone, completing: &completing };
+
+    let txn = db.begin().await?;
+    let repo = TaskRepository::new(&txn);
+    for task in tasks {
+        let id = task.id;
+        match apply_bulk_action(&txn, &repo, task, &bulk.action, &context).await {
+            Ok(()) => {}
+            Err(err @ (AppError::Database(_) | AppError::Internal(_))) => return Err(err),
+            Err(err) => {
+                errors.insert(id, err);
+            }
+        }
+    }
+
+    let applied = errors.is_empty();
+    if applied {
+        txn.commit().await?;
+    } else {
+        txn.rollback().await?;
+    }
+    let results = ids
+        .into_iter()
+        .map(|id| {
+            let error = errors.get(&id).map(AppError::body);
+            TaskBulkItemDto { id, ok: error.is_none(), error }
         })
+        .collect();
+    Ok(TaskBulkResultDto { applied, results })
+}
+
+/// Dados do lote resolvidos uma vez antes da transação.
+struct BulkContext<'a> {
+    category: Option<&'a category::Model>,
+    shift: Duration,
+    timezone: Tz,
+    /// Tarefas concluídas pelo lote, que deixam de bloquear as demais.
+    completing: &'a [i32],
+}
+
+async fn apply_bulk_action<C: ConnectionTrait>(
+    conn: &C,
+    repo: &TaskRepository<'_, C>,
+    task: task::Model,
+    action: &TaskBulkAction,
+    context: &BulkContext<'_>,
+) -> Result<(), AppError> {
+    match action {
+        TaskBulkAction::SetStatus { status } => {
+            let task_info = TaskUpdateDto { status: Some(status.clone()), ..Default::default() };
+            if completes(&task_info) && task.status != TaskStatus::Concluida {
+                dependency_service::ensure_not_blocked(conn, task.id, context.completing).await?;
+            }
+            repo.update_task(task, &task_info, None, None).await?;
+        }
+        TaskBulkAction::SetCategory { .. } => {
+            repo.update_task(task, &TaskUpdateDto::default(), context.category, None).await?;
+        }
+        TaskBulkAction::Shift { .. } => {
+            let begin_date = task
+                .begin_date
+                .checked_add_signed(context.shift)
+                .ok_or_else(|| AppError::field("days", "O deslocamento leva a tarefa para fora das datas aceitas."))?;
+            let task_info = TaskUpdateDto { begin_date: Some(begin_date), ..Default::default() };
+            reschedule_task(repo, task, &task_info, None, context.timezone).await?;
+        }
+        TaskBulkAction::Delete => {
+            repo.delete_task(task).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Ao ligar a conclusão automática, conclui a tarefa se o checklist já estiver completo.
+async fn complete_if_enabled(
+    db: &Pool,
+    task: task::Model,
+    task_info: &TaskUpdateDto,
+) -> Result<task::Model, AppError> {
+    if task_info.checklist_auto_complete == Some(true) {
+        checklist_service::apply_auto_complete(db, task).await
+    } else {
+        Ok(task)
+    }
 }
 
 pub async fn delete_task_db(
     db: &State<Pool>,
-    id: i32,
-    user_id: i32
-) -> Result<DeleteResult, TaskError> {
+    task_to_delete: task::Model,
+) -> Result<UpdateResult, AppError> {
     let conn = db.inner();
     let repo = TaskRepository::new(conn);
-    let result = repo.delete_task(user_id, id)
-        .await
-        .map_err(|e| TaskError::DatabaseError(e.to_string()))?;
+    let id = task_to_delete.id;
+    let result = repo.delete_task(task_to_delete).await?;
 
     if result.rows_affected == 0 {
-        Err(TaskError::TaskNotFound(format!("Task with id {} not found", id)))
+        Err(AppError::NotFound(format!("Task with id {} not found", id)))
     } else {
         Ok(result)
     }
-}
\ No newline at end of file
+}
+
+pub async fn status_history_db(db: &Pool, task: &task::Model) -> Result<Vec<task_status_history::Model>, AppError> {
+    TaskRepository::new(db).find_status_history(task.id).await
+}
+
+/// Move a tarefa atrasada para o primeiro horário livre do dia seguinte (no
+/// fuso do usuário), mantendo a duração. Retorna `None` se ela não couber.
+pub async fn roll_over_overdue(db: &Pool, task: task::Model) -> Result<Option<task::Model>, AppError> {
+    let repo = TaskRepository::new(db);
+    let timezone = profile_service::user_timezone(&auth_service::find_user(db, task.user_id).await?);
+    let tomorrow = Utc::now().with_timezone(&timezone).date_naive() + Duration::days(1);
+    let Some(period) = place_on_day(&repo, &task, tomorrow, timezone, None).await? else {
+        return Ok(None);
+    };
+    let task = repo.update_task(task, &TaskUpdateDto::default(), None, Some(period)).await?;
+    Ok(Some(task))
+}
+
+/// Move as tarefas pendentes do dia `from` para o dia `to`, na mesma
+/// ordem e com as mesmas durações. Cada uma vai para o primeiro horário livre
+/// depois da anterior, de preferência no horário local original; as que não
+/// couberem ficam onde estão. Tarefas repetidas e adiadas não são movidas.
+pub async fn rollover_tasks_db(
+    db: &Pool,
+    user_id: i32,
+    rollover: &TaskRolloverDto,
+) -> Result<TaskRolloverResultDto, AppError> {
+    let from = parse_date(&rollover.from)?;
+    let to = parse_date(&rollover.to)?;
+    if from == to {
+        return Err(AppError::field("to", "Escolha um dia diferente do de origem."));
+    }
+    let timezone = profile_service::user_timezone(&auth_service::find_user(db, user_id).await?);
+
+    let txn = db.begin().await?;
+    let repo = TaskRepository::new(&txn);
+    let tasks = repo
+        .find_pending_starting_between(
+            user_id,
+            local_midnight(timezone, from),
+            local_midnight(timezone, from + Duration::days(1)),
+        )
+        .await?;
+
+    let mut moved = Vec::new();
+    let mut unplaced = Vec::new();
+    let mut not_before = None;
+    for task in tasks {
+        match place_on_day(&repo, &task, to, timezone, not_before).await? {
+            Some(period) => {
+                not_before = Some(period.1);
+                moved.push(repo.update_task(task, &TaskUpdateDto::default(), None, Some(period)).await?);
+            }
+            None => unplaced.push(TaskRolloverSkipDto {
+                id: task.id,
+                title: task.title,
+                reason: "Não há horário livre para a tarefa no dia de destino.".to_string(),
+            }),
+        }
+    }
+    txn.commit().await?;
+
+    Ok(TaskRolloverResultDto { moved: to_response_dtos(db, moved).await?, unplaced })
+}
+
+/// Confere que a tarefa está na lixeira e que o horário dela (ou da série)
+/// continua livre para restaurá-la.
+pub async fn ensure_restorable(db: &Pool, user_id: i32, task_id: i32) -> Result<(), AppError> {
+    let repo = TaskRepository::new(db);
+    let task = repo
+        .find_deleted(user_id, task_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Item with id {} not found in trash", task_id)))?;
+    let rule = repo
+        .find_recurrence(task.id)
+        .await?
+        .map(|recurrence| RecurrenceRule::from_model(&recurrence));
+    let timezone = profile_service::user_timezone(&auth_service::find_user(db, user_id).await?);
+    let periods = scheduled_periods(rule.as_ref(), timezone, task.begin_date, task.complete_date);
+    ensure_no_overlap(&repo, user_id, timezone, &periods, Some(task.id)).await
+}
+
+/// Ocorrências do usuário entre as datas `from` e `to` (inclusive, `YYYY-MM-DD`
+/// no fuso do usuário), com as séries expandidas e as exceções aplicadas.
+pub async fn list_occurrences_db(
+    db: &State<Pool>,
+    user_id: i32,
+    from: &str,
+    to: &str,
+) -> Result<Vec<TaskOccurrenceDto>, AppError> {
+    let conn = db.inner();
+    let repo = TaskRepository::new(conn);
+    let timezone = profile_service::user_timezone(&auth_service::find_user(conn, user_id).await?);
+
+    let from = parse_date(from)?;
+    let to = parse_date(to)?;
+    if to < from || (to - from).num_days() > MAX_RANGE_DAYS {
+        return Err(AppError::BadRequest(format!(
+            "Invalid range: 'to' must be after 'from' and at most {} days apart",
+            MAX_RANGE_DAYS
+        )));
+    }
+
+    let range_start = local_midnight(timezone, from);
+    let range_end = local_midnight(timezone, to + Duration::days(1));
+    let mut occurrences = expand_occurrences(&repo, user_id, timezone, range_start, range_end).await?;
+    fill_occurrence_details(conn, &mut occurrences).await?;
+    Ok(occurrences)
+}
+
+/// Uma página das tarefas do usuário. Tarefas repetidas aparecem uma vez, pela
+/// primeira ocorrência; as demais estão em `GET /api/tasks/occurrences`.
+pub async fn list_tasks_db(
+    db: &Pool,
+    user_id: i32,
+    query: &ListQuery,
+) -> Result<PageDto<TaskResponseDto>, AppError> {
+    let filter = list_service::parse_filter(db, user_id, query).await?;
+    let page = list_service::parse_page(query, &SORTS)?;
+    let limit = page.limit;
+    let tasks = TaskRepository::new(db).find_page(user_id, &filter, page).await?;
+    Ok(PageDto {
+        items: to_response_dtos(db, tasks.items).await?,
+        next_cursor: tasks.next_cursor,
+        limit,
+    })
+}
+
+/// Tarefa com o progresso do checklist e os bloqueadores pendentes.
+pub async fn to_response_dto(db: &Pool, task: task::Model) -> Result<TaskResponseDto, AppError> {
+    let mut tasks = to_response_dtos(db, vec![task]).await?;
+    Ok(tasks.remove(0))
+}
+
+pub async fn to_response_dtos(db: &Pool, tasks: Vec<task::Model>) -> Result<Vec<TaskResponseDto>, AppError> {
+    let task_ids: Vec<i32> = tasks.iter().map(|task| task.id).collect();
+    let progress = checklist_service::progress_by_task_ids(db, task_ids.clone()).await?;
+    let mut blockers = dependency_service::pending_blockers_by_task_ids(db, task_ids.clone()).await?;
+    let tracked = time_entry_service::tracked_seconds_by_task_ids(db, task_ids).await?;
+    Ok(tasks
+        .into_iter()
+        .map(|task| TaskResponseDto {
+            checklist: progress.get(&task.id).copied().unwrap_or_default(),
+            blocked_by: blockers.remove(&task.id).unwrap_or_default(),
+            tracked_seconds: tracked.get(&task.id).copied().unwrap_or_default(),
+            task,
+        })
+        .collect())
+}
+
+/// Edita apenas uma ocorrência de uma série (título, descrição, categoria ou status).
+pub async fn update_occurrence_db(
+    db: &State<Pool>,
+    task: task::Model,
+    occurrence_start: i64,
+    task_info: &TaskUpdateDto,
+) -> Result<TaskOccurrenceDto, AppError> {
+    if task_info.reschedules() {
+        return Err(AppError::BadRequest(
+            "Occurrences cannot be rescheduled individually; reschedule the task instead".to_string(),
+        ));
+    }
+
+    let conn = db.inner();
+    let repo = TaskRepository::new(conn);
+    let start = find_occurrence_start(&repo, conn, &task, occurrence_start).await?;
+    if completes(task_info) {
+        dependency_service::ensure_not_blocked(conn, task.id, &[]).await?;
+    }
+    let category = if task_info.category.is_some() || task_info.category_id.is_some() {
+        category_service::resolve_category(conn, task.user_id, task_info.category_id, task_info.category.as_deref())
+            .await?
+    } else {
+        None
+    };
+    let exception = repo.upsert_exception(&task, start, task_info, category.as_ref(), false).await?;
+    let mut occurrences = vec![convert_to_occurrence_dto(&task, start, true, Some(&exception))];
+    fill_occurrence_details(conn, &mut occurrences).await?;
+    Ok(occurrences.remove(0))
+}
+
+/// Remove apenas uma ocorrência de uma série; as demais continuam.
+pub async fn skip_occurrence_db(
+    db: &State<Pool>,
+    task: task::Model,
+    occurrence_start: i64,
+) -> Result<(), AppError> {
+    let conn = db.inner();
+    let repo = TaskRepository::new(conn);
+    let start = find_occurrence_start(&repo, conn, &task, occurrence_start).await?;
+    repo.upsert_exception(&task, start, &TaskUpdateDto::default(), None, true).await?;
+    Ok(())
+}
+
+/// Início, fim e tipo da tarefa. Em ordem de prioridade: dia inteiro, fim
+/// explícito, duração em minutos e, por último, os atalhos de `type`. O dia
+/// inteiro e os turnos seguem o dia do usuário, no fuso dele.
+fn task_period(
+    begin: DateTime<Utc>,
+    task_type: &str,
+    complete_date: Option<DateTime<Utc>>,
+    duration_minutes: Option<i32>,
+    all_day: bool,
+    timezone: Tz,
+) -> Result<(DateTime<Utc>, DateTime<Utc>, String), AppError> {
+    // Hora cheia do dia local; 24 é a meia-noite do dia seguinte.
+    let at = |date: DateTime<Utc>, hour: i64| {
+        let day = date.with_timezone(&timezone).date_naive();
+        local_to_utc(timezone, day.and_time(NaiveTime::MIN) + Duration::hours(hour))
+            .unwrap_or_else(|| local_midnight(timezone, day) + Duration::hours(hour))
+    };
+    let second = Duration::seconds(1);
+
+    if complete_date.is_some() && duration_minutes.is_some() {
+        return Err(AppError::field(
+            "duration_minutes",
+            "Informe o fim ou a duração, não os dois.",
+        ));
+    }
+
+    let (begin_date, complete_date, task_type) = if all_day {
+        let last_day = complete_date.unwrap_or(begin);
+        (at(begin, 0), at(last_day, 24) - second, ALL_DAY_TYPE)
+    } else if let Some(complete_date) = complete_date {
+        (begin, complete_date, CUSTOM_TYPE)
+    } else if let Some(minutes) = duration_minutes {
+        (begin, begin + Duration::minutes(minutes.into()), CUSTOM_TYPE)
+    } else {
+        let (begin_date, complete_date) = match task_type {
+            "MeiaHora" => (begin, begin + Duration::minutes(30)),
+            "UmaHora" => (begin, begin + Duration::hours(1)),
+            "DuasHoras" => (begin, begin + Duration::hours(2)),
+            "Manha" => (at(begin, 6), at(begin, 12) - second),
+            "Tarde" => (at(begin, 12), at(begin, 18) - second),
+            "Noite" => (at(begin, 18), at(begin, 24) - second),
+            "Madrugada" => (at(begin, 0), at(begin, 6) - second),
+            _ => {
+                return Err(AppError::field(
+                    "type",
+                    format!("Invalid task type: {}", task_type),
+                ))
+            }
+        };
+        (begin_date, complete_date, task_type)
+    };
+
+    if complete_date <= begin_date {
+        return Err(AppError::field(
+            "complete_date",
+            "O fim da tarefa deve ser posterior ao início.",
+        ));
+    }
+    Ok((begin_date, complete_date, task_type.to_string()))
+}
+
+/// Novo período de uma tarefa remarcada. Se só o início mudar, a tarefa
+/// mantém o tipo e a duração (ou o número de dias, se for de dia inteiro).
+fn reschedule_period(
+    task: &task::Model,
+    task_info: &TaskUpdateDto,
+    timezone: Tz,
+) -> Result<(DateTime<Utc>, DateTime<Utc>, String), AppError> {
+    let begin = task_info.begin_date.unwrap_or(task.begin_date);
+    let keeps_duration = task_info.r#type.is_none()
+        && task_info.complete_date.is_none()
+        && task_info.duration_minutes.is_none()
+        && task_info.all_day.is_none();
+
+    if !keeps_duration {
+        return task_period(
+            begin,
+            task_info.r#type.as_deref().unwrap_or(&task.r#type),
+            task_info.complete_date,
+            task_info.duration_minutes,
+            task_info.all_day.unwrap_or(false),
+            timezone,
+        );
+    }
+
+    match task.r#type.as_str() {
+        ALL_DAY_TYPE => {
+            let local_day = |date: DateTime<Utc>| date.with_timezone(&timezone).date_naive();
+            let days = (local_day(task.complete_date) - local_day(task.begin_date)).num_days();
+            task_period(begin, ALL_DAY_TYPE, Some(begin + Duration::days(days)), None, true, timezone)
+        }
+        CUSTOM_TYPE => task_period(
+            begin,
+            CUSTOM_TYPE,
+            Some(begin + (task.complete_date - task.begin_date)),
+            None,
+            false,
+            timezone,
+        ),
+        preset => task_period(begin, preset, None, None, false, timezone),
+    }
+}
+
+/// Períodos que a tarefa vai ocupar: um só, ou as ocorrências da série dentro
+/// de [`CONFLICT_HORIZON_DAYS`].
+fn scheduled_periods(
+    rule: Option<&RecurrenceRule>,
+    timezone: Tz,
+    begin_date: DateTime<Utc>,
+    complete_date: DateTime<Utc>,
+) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+    let Some(rule) = rule else {
+        return vec![(begin_date, complete_date)];
+    };
+    let duration = complete_date - begin_date;
+    rule.starts_between(
+        begin_date,
+        timezone,
+        begin_date,
+        begin_date + Duration::days(CONFLICT_HORIZON_DAYS),
+    )
+    .into_iter()
+    .map(|start| (start, start + duration))
+    .collect()
+}
+
+/// Recusa os períodos que se sobrepõem a alguma ocorrência já agendada,
+/// ignorando as da tarefa `ignore_task_id`. O `409` traz a ocorrência em
+/// conflito em `conflict`.
+async fn ensure_no_overlap<C: ConnectionTrait>(
+    repo: &TaskRepository<'_, C>,
+    user_id: i32,
+    timezone: Tz,
+    periods: &[(DateTime<Utc>, DateTime<Utc>)],
+    ignore_task_id: Option<i32>,
+) -> Result<(), AppError> {
+    let (Some(from), Some(to)) = (
+        periods.iter().map(|(start, _)| *start).min(),
+        periods.iter().map(|(_, end)| *end).max(),
+    ) else {
+        return Ok(());
+    };
+
+    let scheduled = expand_occurrences(repo, user_id, timezone, from, to).await?;
+    let conflict = periods.iter().find_map(|(start, end)| {
+        scheduled.iter().find(|occurrence| {
+            Some(occurrence.id) != ignore_task_id
+                && occurrence.begin_date < *end
+                && occurrence.complete_date > *start
+        })
+    });
+
+    match conflict {
+        Some(conflict) => Err(AppError::ConflictWith {
+            message: "Task time overlaps with an existing task.".to_string(),
+            conflict: serde_json::to_value(conflict).map_err(|err| AppError::Internal(err.to_string()))?,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Novo período da tarefa no dia `day`: o primeiro horário livre a partir do
+/// horário local em que ela começava (e não antes de `not_before`) ou, se não
+/// houver, a partir do início do dia. Retorna `None` se a duração não couber.
+async fn place_on_day<C: ConnectionTrait>(
+    repo: &TaskRepository<'_, C>,
+    task: &task::Model,
+    day: NaiveDate,
+    timezone: Tz,
+    not_before: Option<DateTime<Utc>>,
+) -> Result<Option<(DateTime<Utc>, DateTime<Utc>, String)>, AppError> {
+    let (day_start, day_end) = day_bounds(task, day, timezone);
+    let occupied: Vec<(DateTime<Utc>, DateTime<Utc>)> = expand_occurrences(repo, task.user_id, timezone, day_start, day_end)
+        .await?
+        .into_iter()
+        .filter(|occurrence| occurrence.id != task.id)
+        .map(|occurrence| (occurrence.begin_date, occurrence.complete_date))
+        .collect();
+    Ok(free_period_on_day(task, day, timezone, not_before, &occupied))
+}
+
+/// Início e fim do dia local `day`; o fim se estende se a tarefa for mais
+/// longa que o dia.
+fn day_bounds(task: &task::Model, day: NaiveDate, timezone: Tz) -> (DateTime<Utc>, DateTime<Utc>) {
+    let day_start = local_midnight(timezone, day);
+    let day_end = local_midnight(timezone, day + Duration::days(1)).max(day_start + (task.complete_date - task.begin_date));
+    (day_start, day_end)
+}
+
+/// Parte de [`place_on_day`] que não consulta o banco: `occupied` são os
+/// períodos das outras tarefas no dia, ordenados pelo início.
+fn free_period_on_day(
+    task: &task::Model,
+    day: NaiveDate,
+    timezone: Tz,
+    not_before: Option<DateTime<Utc>>,
+    occupied: &[(DateTime<Utc>, DateTime<Utc>)],
+) -> Option<(DateTime<Utc>, DateTime<Utc>, String)> {
+    let duration = task.complete_date - task.begin_date;
+    let (day_start, day_end) = day_bounds(task, day, timezone);
+    let local_begin = task.begin_date.with_timezone(&timezone);
+    let preferred = day_start + (task.begin_date - local_midnight(timezone, local_begin.date_naive()));
+    let earliest = not_before.map_or(day_start, |not_before| not_before.max(day_start));
+
+    let begin = first_free_start(occupied, preferred.max(earliest), day_end, duration)
+        .or_else(|| first_free_start(occupied, earliest, day_end, duration));
+    begin.map(|begin| (begin, begin + duration, moved_type(task, begin, timezone)))
+}
+
+/// Primeiro início, a partir de `earliest`, em que `duration` cabe até
+/// `latest_end` sem se sobrepor aos períodos ocupados, ordenados pelo início.
+fn first_free_start(
+    occupied: &[(DateTime<Utc>, DateTime<Utc>)],
+    earliest: DateTime<Utc>,
+    latest_end: DateTime<Utc>,
+    duration: Duration,
+) -> Option<DateTime<Utc>> {
+    let mut start = earliest;
+    for (busy_start, busy_end) in occupied {
+        if *busy_end <= start {
+            continue;
+        }
+        if start + duration <= *busy_start {
+            break;
+        }
+        start = *busy_end;
+    }
+    (start + duration <= latest_end).then_some(start)
+}
+
+/// Tipo da tarefa movida para `begin`. Os turnos e o dia inteiro só valem no
+/// mesmo horário local; em outro horário, a tarefa passa a ter duração livre.
+fn moved_type(task: &task::Model, begin: DateTime<Utc>, timezone: Tz) -> String {
+    let same_time = task.begin_date.with_timezone(&timezone).time() == begin.with_timezone(&timezone).time();
+    match task.r#type.as_str() {
+        "MeiaHora" | "UmaHora" | "DuasHoras" | CUSTOM_TYPE => task.r#type.clone(),
+        _ if same_time => task.r#type.clone(),
+        _ => CUSTOM_TYPE.to_string(),
+    }
+}
+
+/// Ocorrências que se sobrepõem a `[from, to)`, ordenadas pelo início. As
+/// ocorrências puladas ficam de fora.
+async fn expand_occurrences<C: ConnectionTrait>(
+    repo: &TaskRepository<'_, C>,
+    user_id: i32,
+    timezone: Tz,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Vec<TaskOccurrenceDto>, AppError> {
+    let mut occurrences: Vec<TaskOccurrenceDto> = repo
+        .find_single_in_range(user_id, from, to)
+        .await?
+        .iter()
+        .map(|task| convert_to_occurrence_dto(task, task.begin_date, false, None))
+        .collect();
+
+    let series = repo.find_series(user_id, to).await?;
+    let task_ids = series.iter().map(|(task, _)| task.id).collect();
+    let exceptions: HashMap<(i32, DateTime<Utc>), task_occurrence::Model> = repo
+        .find_exceptions(task_ids, to)
+        .await?
+        .into_iter()
+        .map(|exception| ((exception.task_id, exception.occurrence_start), exception))
+        .collect();
+
+    for (task, recurrence) in &series {
+        let rule = RecurrenceRule::from_model(recurrence);
+        let duration = task.complete_date - task.begin_date;
+        let is_skipped = |start| exceptions.get(&(task.id, start)).is_some_and(|exception| exception.skipped);
+        for start in rule.overlapping_starts(task.begin_date, duration, timezone, from, to, is_skipped) {
+            let exception = exceptions.get(&(task.id, start));
+            occurrences.push(convert_to_occurrence_dto(task, start, true, exception));
+        }
+    }
+
+    occurrences.sort_by_key(|occurrence| (occurrence.begin_date, occurrence.id));
+    Ok(occurrences)
+}
+
+/// Confere que a tarefa se repete e que `timestamp` (segundos Unix) é o
+/// início de uma das suas ocorrências.
+async fn find_occurrence_start(
+    repo: &TaskRepository<'_>,
+    db: &Pool,
+    task: &task::Model,
+    timestamp: i64,
+) -> Result<DateTime<Utc>, AppError> {
+    let recurrence = repo
+        .find_recurrence(task.id)
+        .await?
+        .ok_or_else(|| AppError::BadRequest(format!("Task with id {} is not recurring", task.id)))?;
+
+    let timezone = profile_service::user_timezone(&auth_service::find_user(db, task.user_id).await?);
+    let rule = RecurrenceRule::from_model(&recurrence);
+    DateTime::from_timestamp(timestamp, 0)
+        .filter(|start| rule.has_start(task.begin_date, timezone, *start))
+        .ok_or_else(|| AppError::NotFound(format!("Occurrence {} of task {} not found", timestamp, task.id)))
+}
+
+fn convert_to_occurrence_dto(
+    task: &task::Model,
+    start: DateTime<Utc>,
+    recurring: bool,
+    exception: Option<&task_occurrence::Model>,
+) -> TaskOccurrenceDto {
+    let field = |value: Option<&Option<String>>| value.and_then(|value| value.clone());
+    let (category, category_id) = exception
+        .and_then(|e| e.category.clone().map(|category| (category, e.category_id)))
+        .unwrap_or_else(|| (task.category.clone(), task.category_id));
+    TaskOccurrenceDto {
+        id: task.id,
+        user_id: task.user_id,
+        title: field(exception.map(|e| &e.title)).unwrap_or_else(|| task.title.clone()),
+        description: field(exception.map(|e| &e.description)).or_else(|| task.description.clone()),
+        status: exception.and_then(|e| e.status).unwrap_or(task.status),
+        begin_date: start,
+        complete_date: start + (task.complete_date - task.begin_date),
+        category,
+        category_id,
+        r#type: task.r#type.clone(),
+        occurrence_start: start,
+        recurring,
+        modified: exception.is_some(),
+        checklist_auto_complete: task.checklist_auto_complete,
+        checklist: ChecklistProgressDto::default(),
+        blocked_by: Vec::new(),
+        tracked_seconds: 0,
+    }
+}
+
+/// Preenche o progresso do checklist, os bloqueadores e o tempo registrado das ocorrências.
+async fn fill_occurrence_details(db: &Pool, occurrences: &mut [TaskOccurrenceDto]) -> Result<(), AppError> {
+    let mut task_ids: Vec<i32> = occurrences.iter().map(|occurrence| occurrence.id).collect();
+    task_ids.sort_unstable();
+    task_ids.dedup();
+    let progress = checklist_service::progress_by_task_ids(db, task_ids.clone()).await?;
+    let blockers = dependency_service::pending_blockers_by_task_ids(db, task_ids.clone()).await?;
+    let tracked = time_entry_service::tracked_seconds_by_task_ids(db, task_ids).await?;
+    for occurrence in occurrences.iter_mut() {
+        occurrence.checklist = progress.get(&occurrence.id).copied().unwrap_or_default();
+        occurrence.blocked_by = blockers.get(&occurrence.id).cloned().unwrap_or_default();
+        occurrence.tracked_seconds = tracked.get(&occurrence.id).copied().unwrap_or_default();
+    }
+    Ok(())
+}
+
+/// Se a atualização marca a tarefa como concluída.
+fn completes(task_info: &TaskUpdateDto) -> bool {
+    task_info.status.as_deref().and_then(TaskStatus::parse) == Some(TaskStatus::Concluida)
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate, AppError> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| AppError::BadRequest(format!("Invalid date '{}', expected YYYY-MM-DD", value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn utc(day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 1, day, hour, 0, 0).unwrap()
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 1, day).unwrap()
+    }
+
+    fn task(begin: DateTime<Utc>, hours: i64, task_type: &str) -> task::Model {
+        task::Model {
+            id: 1,
+            title: "Tarefa".to_string(),
+            user_id: 1,
+            description: None,
+            status: TaskStatus::Pendente,
+            begin_date: begin,
+            complete_date: begin + Duration::hours(hours),
+            category: "Geral".to_string(),
+            r#type: task_type.to_string(),
+            checklist_auto_complete: false,
+            category_id: None,
+            deleted_at: None,
+            completed_at: None,
+            postponed_at: None,
+            status_changed_at: None,
+            overdue_at: None,
+        }
+    }
+
+    #[test]
+    fn first_free_start_uses_earliest_when_free() {
+        let start = first_free_start(&[], utc(2, 10), utc(3, 0), Duration::hours(1));
+        assert_eq!(start, Some(utc(2, 10)));
+    }
+
+    #[test]
+    fn first_free_start_fits_in_a_gap() {
+        let occupied = [(utc(2, 8), utc(2, 9)), (utc(2, 10), utc(2, 12)), (utc(2, 13), utc(2, 15))];
+        assert_eq!(first_free_start(&occupied, utc(2, 8), utc(3, 0), Duration::hours(1)), Some(utc(2, 9)));
+        assert_eq!(first_free_start(&occupied, utc(2, 10), utc(3, 0), Duration::hours(1)), Some(utc(2, 12)));
+        assert_eq!(first_free_start(&occupied, utc(2, 10), utc(3, 0), Duration::hours(2)), Some(utc(2, 15)));
+    }
+
+    #[test]
+    fn first_free_start_ignores_periods_that_ended_before() {
+        let occupied = [(utc(2, 6), utc(2, 7))];
+        assert_eq!(first_free_start(&occupied, utc(2, 9), utc(3, 0), Duration::hours(1)), Some(utc(2, 9)));
+    }
+
+    #[test]
+    fn first_free_start_needs_the_whole_duration_before_the_limit() {
+        let occupied = [(utc(2, 9), utc(2, 23))];
+        assert_eq!(first_free_start(&occupied, utc(2, 9), utc(3, 0), Duration::hours(1)), Some(utc(2, 23)));
+        assert_eq!(first_free_start(&occupied, utc(2, 9), utc(3, 0), Duration::hours(2)), None);
+    }
+
+    #[test]
+    fn place_keeps_the_local_time_on_the_new_day() {
+        let timezone: Tz = "America/Sao_Paulo".parse().unwrap();
+        // 10:00 em São Paulo (UTC-3).
+        let task = task(utc(1, 13), 1, "UmaHora");
+        let period = free_period_on_day(&task, date(2), timezone, None, &[]);
+        assert_eq!(period, Some((utc(2, 13), utc(2, 14), "UmaHora".to_string())));
+    }
+
+    #[test]
+    fn place_moves_past_busy_periods_and_not_before() {
+        let task = task(utc(1, 10), 1, "UmaHora");
+        let occupied = [(utc(2, 10), utc(2, 12))];
+        let period = free_period_on_day(&task, date(2), Tz::UTC, None, &occupied);
+        assert_eq!(period.map(|(begin, _, _)| begin), Some(utc(2, 12)));
+
+        let period = free_period_on_day(&task, date(2), Tz::UTC, Some(utc(2, 15)), &[]);
+        assert_eq!(period.map(|(begin, _, _)| begin), Some(utc(2, 15)));
+    }
+
+    #[test]
+    fn place_falls_back_to_the_start_of_the_day() {
+        let task = task(utc(1, 10), 1, "UmaHora");
+        let occupied = [(utc(2, 9), utc(3, 0))];
+        let period = free_period_on_day(&task, date(2), Tz::UTC, None, &occupied);
+        assert_eq!(period.map(|(begin, _, _)| begin), Some(utc(2, 0)));
+    }
+
+    #[test]
+    fn place_gives_up_when_the_day_is_full() {
+        let task = task(utc(1, 10), 1, "UmaHora");
+        let occupied = [(utc(2, 0), utc(3, 0))];
+        assert_eq!(free_period_on_day(&task, date(2), Tz::UTC, None, &occupied), None);
+    }
+
+    #[test]
+    fn presets_moved_to_another_time_become_custom() {
+        let task = task(utc(1, 6), 6, "Manha");
+        let occupied = [(utc(2, 6), utc(2, 8))];
+        let period = free_period_on_day(&task, date(2), Tz::UTC, None, &occupied);
+        assert_eq!(period, Some((utc(2, 8), utc(2, 14), CUSTOM_TYPE.to_string())));
+
+        let period = free_period_on_day(&task, date(2), Tz::UTC, None, &[]);
+        assert_eq!(period.map(|(_, _, task_type)| task_type), Some("Manha".to_string()));
+    }
+}
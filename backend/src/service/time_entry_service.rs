@@ -0,0 +1,120 @@
+//! Registro do tempo gasto nas tarefas.
+//!
+//! O cronômetro cria um registro em andamento (`stopped_at` vazio) e o
+//! encerra ao parar; cada usuário tem no máximo um cronômetro ligado. Os
+//! registros também podem ser lançados e corrigidos à mão. O total de cada
+//! tarefa vai em `tracked_seconds` e as horas entram nos relatórios.
+
+use chrono::Utc;
+use sea_orm::prelude::DateTimeUtc;
+use crate::db::Pool;
+use crate::dto::time_entry_dto::{TimeEntryDto, TimeEntryUpdateDto, TimerStartDto};
+use crate::entity::{task, time_entry};
+use crate::errors::AppError;
+use crate::repository::time_entry_repository::TimeEntryRepository;
+use std::collections::HashMap;
+use validator::Validate;
+
+/// Cronômetro ligado do usuário, se houver.
+pub async fn running_timer_db(db: &Pool, user_id: i32) -> Result<Option<time_entry::Model>, AppError> {
+    TimeEntryRepository::new(db).find_running(user_id).await
+}
+
+/// Liga o cronômetro da tarefa. Com outro já ligado, responde `409` com ele em `conflict`.
+pub async fn start_timer_db(db: &Pool, task: &task::Model, timer_info: &TimerStartDto) -> Result<time_entry::Model, AppError> {
+    timer_info.validate()?;
+    let repo = TimeEntryRepository::new(db);
+    if let Some(running) = repo.find_running(task.user_id).await? {
+        return Err(AppError::ConflictWith {
+            message: "A timer is already running; stop it first.".to_string(),
+            conflict: serde_json::to_value(running).map_err(|err| AppError::Internal(err.to_string()))?,
+        });
+    }
+    repo.create_entry(task.user_id, task.id, Utc::now(), None, clean_note(timer_info.note.as_deref()))
+        .await
+}
+
+/// Para o cronômetro ligado do usuário.
+pub async fn stop_timer_db(db: &Pool, user_id: i32) -> Result<time_entry::Model, AppError> {
+    let repo = TimeEntryRepository::new(db);
+    let running = repo
+        .find_running(user_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("No timer is running".to_string()))?;
+    let started_at = running.started_at;
+    repo.update_entry(running, started_at, Some(Utc::now()), None).await
+}
+
+pub async fn list_entries_db(db: &Pool, task: &task::Model) -> Result<Vec<time_entry::Model>, AppError> {
+    TimeEntryRepository::new(db).find_by_task_id(task.id).await
+}
+
+pub async fn create_entry_db(db: &Pool, task: &task::Model, entry_info: &TimeEntryDto) -> Result<time_entry::Model, AppError> {
+    entry_info.validate()?;
+    check_period(entry_info.started_at, Some(entry_info.stopped_at))?;
+    TimeEntryRepository::new(db)
+        .create_entry(
+            task.user_id,
+            task.id,
+            entry_info.started_at,
+            Some(entry_info.stopped_at),
+            clean_note(entry_info.note.as_deref()),
+        )
+        .await
+}
+
+/// Corrige o início, o fim ou a observação de um registro.
+pub async fn update_entry_db(
+    db: &Pool,
+    task: &task::Model,
+    entry_id: i32,
+    entry_info: &TimeEntryUpdateDto,
+) -> Result<time_entry::Model, AppError> {
+    entry_info.validate()?;
+    let repo = TimeEntryRepository::new(db);
+    let entry = find_entry(&repo, task, entry_id).await?;
+    let started_at = entry_info.started_at.unwrap_or(entry.started_at);
+    let stopped_at = entry_info.stopped_at.or(entry.stopped_at);
+    check_period(started_at, stopped_at)?;
+    let note = entry_info.note.as_deref().map(|note| clean_note(Some(note)));
+    repo.update_entry(entry, started_at, stopped_at, note).await
+}
+
+pub async fn delete_entry_db(db: &Pool, task: &task::Model, entry_id: i32) -> Result<(), AppError> {
+    let repo = TimeEntryRepository::new(db);
+    let entry = find_entry(&repo, task, entry_id).await?;
+    repo.delete_entry(entry).await
+}
+
+/// Segundos registrados em cada tarefa; tarefas sem registros ficam de fora.
+pub async fn tracked_seconds_by_task_ids(db: &Pool, task_ids: Vec<i32>) -> Result<HashMap<i32, i64>, AppError> {
+    TimeEntryRepository::new(db).total_by_task_ids(task_ids).await
+}
+
+async fn find_entry(
+    repo: &TimeEntryRepository<'_>,
+    task: &task::Model,
+    entry_id: i32,
+) -> Result<time_entry::Model, AppError> {
+    repo.find_entry(task.id, entry_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Time entry {} of task {} not found", entry_id, task.id)))
+}
+
+fn check_period(started_at: DateTimeUtc, stopped_at: Option<DateTimeUtc>) -> Result<(), AppError> {
+    let now = Utc::now();
+    if started_at > now {
+        return Err(AppError::field("started_at", "O início não pode estar no futuro."));
+    }
+    match stopped_at {
+        Some(stopped_at) if stopped_at <= started_at => {
+            Err(AppError::field("stopped_at", "O fim deve ser depois do início."))
+        }
+        Some(stopped_at) if stopped_at > now => Err(AppError::field("stopped_at", "O fim não pode estar no futuro.")),
+        _ => Ok(()),
+    }
+}
+
+fn clean_note(note: Option<&str>) -> Option<String> {
+    note.map(str::trim).filter(|note| !note.is_empty()).map(str::to_string)
+}
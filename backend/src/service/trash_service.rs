@@ -0,0 +1,65 @@
+//! Lixeira: tarefas, metas, lembretes e notas excluídos ficam marcados com
+//! `deleted_at` e podem ser restaurados até a limpeza automática, que os
+//! remove de vez depois de `TRASH_RETENTION_DAYS` dias (padrão 30).
+
+use chrono::{Duration, Utc};
+use std::env::var;
+use crate::db::Pool;
+use crate::dto::trash_dto::TrashItemDto;
+use crate::errors::AppError;
+use crate::repository::trash_repository::{TrashKind, TrashRepository};
+use crate::service::task_service;
+
+const DEFAULT_RETENTION_DAYS: i32 = 30;
+
+/// Dias que um item passa na lixeira antes de ser removido de vez.
+pub fn retention_days() -> i32 {
+    var("TRASH_RETENTION_DAYS")
+        .ok()
+        .and_then(|days| days.parse().ok())
+        .filter(|days| *days > 0)
+        .unwrap_or(DEFAULT_RETENTION_DAYS)
+}
+
+pub async fn list_trash_db(db: &Pool, user_id: i32) -> Result<Vec<TrashItemDto>, AppError> {
+    TrashRepository::new(db).find_by_user_id(user_id, retention_days()).await
+}
+
+/// Restaura o item. Uma tarefa só volta se o horário dela continuar livre.
+pub async fn restore_db(db: &Pool, user_id: i32, kind: &str, id: i32) -> Result<(), AppError> {
+    let kind = parse_kind(kind)?;
+    if kind == TrashKind::Task {
+        task_service::ensure_restorable(db, user_id, id).await?;
+    }
+    match TrashRepository::new(db).restore(kind, user_id, id).await? {
+        0 => Err(not_in_trash(id)),
+        _ => Ok(()),
+    }
+}
+
+/// Remove o item de vez, sem esperar a limpeza automática.
+pub async fn purge_db(db: &Pool, user_id: i32, kind: &str, id: i32) -> Result<(), AppError> {
+    let kind = parse_kind(kind)?;
+    match TrashRepository::new(db).purge(kind, user_id, id).await? {
+        0 => Err(not_in_trash(id)),
+        _ => Ok(()),
+    }
+}
+
+pub async fn empty_trash_db(db: &Pool, user_id: i32) -> Result<u64, AppError> {
+    TrashRepository::new(db).purge_by_user_id(user_id).await
+}
+
+/// Remove de vez os itens que passaram do prazo na lixeira; chamada pela tarefa periódica.
+pub async fn purge_expired(db: &Pool) -> Result<u64, AppError> {
+    let cutoff = Utc::now() - Duration::days(retention_days().into());
+    TrashRepository::new(db).purge_deleted_before(cutoff).await
+}
+
+fn parse_kind(kind: &str) -> Result<TrashKind, AppError> {
+    TrashKind::parse(kind).ok_or_else(|| AppError::NotFound(format!("Unknown trash kind '{}'", kind)))
+}
+
+fn not_in_trash(id: i32) -> AppError {
+    AppError::NotFound(format!("Item with id {} not found in trash", id))
+}
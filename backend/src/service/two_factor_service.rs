@@ -0,0 +1,315 @@
+//! Autenticação em dois fatores com TOTP (RFC 6238).
+//!
+//! O fluxo de inscrição é: `enroll` gera e salva o segredo, `confirm` valida
+//! o primeiro código, ativa o 2FA e devolve os códigos de recuperação (que só
+//! são persistidos como hash). A partir daí o login passa a exigir um código
+//! TOTP ou um código de recuperação não utilizado.
+//!
+//! Os códigos de recuperação são gravados com Argon2, como as senhas. O
+//! segredo TOTP fica em texto (hex) por escolha: o servidor precisa dele para
+//! calcular os códigos, então um hash não serve, e cifrá-lo exigiria uma chave
+//! guardada fora do banco, que o projeto ainda não tem.
+
+use crate::db::Pool;
+use crate::entity::user;
+use crate::errors::AppError;
+use crate::repository::{auth_repository, recovery_code_repository};
+use crate::service::auth_service;
+use argon2::password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use rocket::http::RawStr;
+use sha1::Sha1;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const ISSUER: &str = "Agenda";
+const SECRET_BYTES: usize = 20;
+const STEP_SECS: u64 = 30;
+const DIGITS: u32 = 6;
+/// Passos aceitos antes e depois do atual, para tolerar relógios dessincronizados.
+const ALLOWED_SKEW: i64 = 1;
+const RECOVERY_CODE_COUNT: usize = 10;
+
+#[derive(serde::Serialize)]
+pub struct Enrollment {
+    pub secret: String,
+    pub otpauth_uri: String,
+}
+
+/// Gera um novo segredo para o usuário. O 2FA só passa a valer após `confirm`.
+pub async fn enroll(db: &Pool, user_id: i32) -> Result<Enrollment, AppError> {
+    let user = auth_service::find_user(db, user_id).await?;
+    if user.totp_enabled {
+        return Err(AppError::Conflict("Two-factor authentication is already enabled".to_string()));
+    }
+
+    let mut secret = [0u8; SECRET_BYTES];
+    rand::thread_rng().fill_bytes(&mut secret);
+    let encoded_secret = base32_encode(&secret);
+
+    let label = format!("{}:{}", ISSUER, user.username);
+    let otpauth_uri = format!(
+        "otpauth://totp/{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+        RawStr::new(&label).percent_encode(),
+        encoded_secret,
+        ISSUER,
+        DIGITS,
+        STEP_SECS
+    );
+
+    auth_repository::update_two_factor(db, user, Some(hex::encode(secret)), false).await?;
+    Ok(Enrollment {
+        secret: encoded_secret,
+        otpauth_uri,
+    })
+}
+
+/// Ativa o 2FA se o código corresponder ao segredo da inscrição, devolvendo
+/// os códigos de recuperação em texto puro (exibidos uma única vez).
+pub async fn confirm(db: &Pool, user_id: i32, code: &str) -> Result<Vec<String>, AppError> {
+    let user = auth_service::find_user(db, user_id).await?;
+    if user.totp_enabled {
+        return Err(AppError::Conflict("Two-factor authentication is already enabled".to_string()));
+    }
+    let secret = user
+        .totp_secret
+        .clone()
+        .ok_or_else(|| AppError::BadRequest("Two-factor enrollment not started".to_string()))?;
+
+    if verify_totp(&secret, code, None)?.is_none() {
+        return Err(AppError::field("code", "Invalid code"));
+    }
+
+    auth_repository::update_two_factor(db, user, Some(secret), true).await?;
+
+    let codes: Vec<String> = (0..RECOVERY_CODE_COUNT).map(|_| generate_recovery_code()).collect();
+    let hashes = codes.iter().map(|code| hash_recovery_code(code)).collect::<Result<_, _>>()?;
+    recovery_code_repository::replace_codes(db, user_id, hashes).await?;
+    Ok(codes)
+}
+
+pub async fn disable(db: &Pool, user_id: i32, password: &str) -> Result<(), AppError> {
+    let user = auth_service::find_user(db, user_id).await?;
+    auth_service::confirm_password(&user, password, "password")?;
+    auth_repository::update_two_factor(db, user, None, false).await?;
+    recovery_code_repository::delete_by_user_id(db, user_id).await
+}
+
+/// Segundo passo do login: aceita um código TOTP ainda não utilizado ou um
+/// código de recuperação, que é marcado como usado.
+pub async fn verify_login_code(db: &Pool, user: user::Model, code: &str) -> Result<user::Model, AppError> {
+    let invalid = || AppError::Unauthorized("Invalid two-factor code".to_string());
+    let secret = match (&user.totp_secret, user.totp_enabled) {
+        (Some(secret), true) => secret.clone(),
+        _ => return Err(invalid()),
+    };
+
+    if let Some(step) = verify_totp(&secret, code, user.totp_last_step)? {
+        return auth_repository::update_totp_last_step(db, user, step).await;
+    }
+
+    let code = normalize_recovery_code(code);
+    let unused = recovery_code_repository::find_unused(db, user.id).await?;
+    match unused.into_iter().find(|recovery_code| recovery_code_matches(&recovery_code.code_hash, &code)) {
+        Some(recovery_code) => {
+            recovery_code_repository::mark_used(db, recovery_code).await?;
+            Ok(user)
+        }
+        None => Err(invalid()),
+    }
+}
+
+/// Retorna o passo em que o código é válido, ignorando passos até `last_step`.
+fn verify_totp(hex_secret: &str, code: &str, last_step: Option<i64>) -> Result<Option<i64>, AppError> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    verify_totp_at(hex_secret, code, last_step, now)
+}
+
+/// [`verify_totp`] no instante `now` (segundos Unix).
+fn verify_totp_at(hex_secret: &str, code: &str, last_step: Option<i64>, now: u64) -> Result<Option<i64>, AppError> {
+    let code = code.trim();
+    if code.len() != DIGITS as usize || !code.chars().all(|c| c.is_ascii_digit()) {
+        return Ok(None);
+    }
+    let code: u32 = code.parse().map_err(|_| AppError::Internal("Invalid code".to_string()))?;
+    let secret = hex::decode(hex_secret).map_err(|e| AppError::Internal(e.to_string()))?;
+
+    let current_step = (now / STEP_SECS) as i64;
+
+    let step = (-ALLOWED_SKEW..=ALLOWED_SKEW)
+        .map(|offset| current_step + offset)
+        .filter(|step| last_step.is_none_or(|last| *step > last))
+        .find(|step| hotp(&secret, *step as u64) == code);
+    Ok(step)
+}
+
+/// HOTP (RFC 4226) com HMAC-SHA1 e truncamento dinâmico.
+fn hotp(secret: &[u8], counter: u64) -> u32 {
+    let mut mac = Hmac::<Sha1>::new_from_slice(secret).expect("HMAC accepts keys of any size");
+    mac.update(&counter.to_be_bytes());
+    let hash = mac.finalize().into_bytes();
+
+    let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+    let binary = u32::from_be_bytes([
+        hash[offset] & 0x7f,
+        hash[offset + 1],
+        hash[offset + 2],
+        hash[offset + 3],
+    ]);
+    binary % 10u32.pow(DIGITS)
+}
+
+/// Base32 (RFC 4648) sem padding, formato esperado pelos aplicativos autenticadores.
+fn base32_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+    let mut encoded = String::new();
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for &byte in bytes {
+        buffer = ((buffer << 8) | u32::from(byte)) & 0xffff;
+        bits += 8;
+        while bits >= 5 {
+            encoded.push(ALPHABET[((buffer >> (bits - 5)) & 31) as usize] as char);
+            bits -= 5;
+        }
+    }
+    if bits > 0 {
+        encoded.push(ALPHABET[((buffer << (5 - bits)) & 31) as usize] as char);
+    }
+    encoded
+}
+
+fn generate_recovery_code() -> String {
+    let mut bytes = [0u8; 5];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    let code = hex::encode(bytes);
+    format!("{}-{}", &code[..5], &code[5..])
+}
+
+/// Minúsculas e sem hífen, como os códigos são gravados.
+fn normalize_recovery_code(code: &str) -> String {
+    code.trim()
+        .chars()
+        .filter(|c| *c != '-')
+        .collect::<String>()
+        .to_lowercase()
+}
+
+fn hash_recovery_code(code: &str) -> Result<String, AppError> {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(normalize_recovery_code(code).as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| AppError::Internal(e.to_string()))
+}
+
+/// Confere o código normalizado contra o hash Argon2 salvo.
+fn recovery_code_matches(code_hash: &str, code: &str) -> bool {
+    PasswordHash::new(code_hash)
+        .is_ok_and(|parsed| Argon2::default().verify_password(code.as_bytes(), &parsed).is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Segredo dos vetores de teste das RFCs 4226 e 6238 (SHA-1).
+    const RFC_SECRET: &[u8] = b"12345678901234567890";
+
+    fn code_at(step: u64) -> String {
+        format!("{:06}", hotp(RFC_SECRET, step))
+    }
+
+    /// Decodificação usada só para conferir a ida e volta do base32.
+    fn base32_decode(encoded: &str) -> Vec<u8> {
+        const ALPHABET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+        let mut bytes = Vec::new();
+        let mut buffer: u32 = 0;
+        let mut bits = 0;
+        for c in encoded.chars() {
+            buffer = (buffer << 5) | ALPHABET.find(c).unwrap() as u32;
+            bits += 5;
+            if bits >= 8 {
+                bytes.push((buffer >> (bits - 8)) as u8);
+                bits -= 8;
+            }
+        }
+        bytes
+    }
+
+    #[test]
+    fn hotp_matches_rfc_4226() {
+        let expected = [
+            755224, 287082, 359152, 969429, 338314, 254676, 287922, 162583, 399871, 520489,
+        ];
+        for (counter, code) in expected.iter().enumerate() {
+            assert_eq!(hotp(RFC_SECRET, counter as u64), *code);
+        }
+    }
+
+    #[test]
+    fn totp_matches_rfc_6238() {
+        let secret = hex::encode(RFC_SECRET);
+        // Últimos seis dígitos dos códigos de oito dígitos da RFC.
+        let vectors = [(59, "287082"), (1111111109, "081804"), (1234567890, "005924"), (2000000000, "279037")];
+        for (time, code) in vectors {
+            let step = (time / STEP_SECS) as i64;
+            assert_eq!(verify_totp_at(&secret, code, None, time).unwrap(), Some(step));
+        }
+    }
+
+    #[test]
+    fn base32_matches_rfc_4648() {
+        assert_eq!(base32_encode(b""), "");
+        assert_eq!(base32_encode(b"f"), "MY");
+        assert_eq!(base32_encode(b"foo"), "MZXW6");
+        assert_eq!(base32_encode(b"foobar"), "MZXW6YTBOI");
+    }
+
+    #[test]
+    fn base32_round_trips() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        for length in [1, 5, 19, SECRET_BYTES, 256] {
+            assert_eq!(base32_decode(&base32_encode(&bytes[..length])), &bytes[..length]);
+        }
+    }
+
+    #[test]
+    fn accepts_one_step_of_clock_skew() {
+        let secret = hex::encode(RFC_SECRET);
+        let now = 1_000 * STEP_SECS;
+        assert_eq!(verify_totp_at(&secret, &code_at(999), None, now).unwrap(), Some(999));
+        assert_eq!(verify_totp_at(&secret, &code_at(1_001), None, now).unwrap(), Some(1_001));
+        assert_eq!(verify_totp_at(&secret, &code_at(998), None, now).unwrap(), None);
+        assert_eq!(verify_totp_at(&secret, &code_at(1_002), None, now).unwrap(), None);
+    }
+
+    #[test]
+    fn rejects_reused_steps() {
+        let secret = hex::encode(RFC_SECRET);
+        let now = 1_000 * STEP_SECS;
+        assert_eq!(verify_totp_at(&secret, &code_at(1_000), Some(1_000), now).unwrap(), None);
+        assert_eq!(verify_totp_at(&secret, &code_at(999), Some(999), now).unwrap(), None);
+        assert_eq!(verify_totp_at(&secret, &code_at(1_000), Some(999), now).unwrap(), Some(1_000));
+    }
+
+    #[test]
+    fn rejects_malformed_codes() {
+        let secret = hex::encode(RFC_SECRET);
+        assert_eq!(verify_totp_at(&secret, "28708", None, 59).unwrap(), None);
+        assert_eq!(verify_totp_at(&secret, "28708a", None, 59).unwrap(), None);
+    }
+
+    #[test]
+    fn recovery_codes_are_normalized_and_salted() {
+        let first = hash_recovery_code("ABCDE-12345").unwrap();
+        let second = hash_recovery_code("abcde12345").unwrap();
+        assert_ne!(first, second);
+        assert!(recovery_code_matches(&first, &normalize_recovery_code(" abcde-12345 ")));
+        assert!(recovery_code_matches(&second, &normalize_recovery_code("ABCDE-12345")));
+        assert!(!recovery_code_matches(&first, "abcde12346"));
+        assert!(!recovery_code_matches(&auth_service::hash_token("abcde12345"), "abcde12345"));
+    }
+}
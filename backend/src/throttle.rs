@@ -0,0 +1,454 @@
+//! Proteção contra força bruta em `/api/login`, `/api/login/2fa` e `/api/register`.
+//!
+//! As tentativas são contadas por usuário e por IP dentro de uma janela de
+//! tempo. Passado o limite de falhas, cada nova falha dobra o tempo de
+//! bloqueio (até `max_lockout_secs`) e as requisições recebem `429` com o
+//! cabeçalho `Retry-After`. Um login bem-sucedido zera o contador do usuário;
+//! o do IP só expira com a janela.
+//!
+//! Configuração por variáveis de ambiente:
+//!
+//! - `AUTH_THROTTLE_STORE`: `memory` (padrão) ou `database`, que também
+//!   mantém o histórico de tentativas na tabela `login_attempt`;
+//! - `AUTH_THROTTLE_MAX_USER_FAILURES` (padrão 5);
+//! - `AUTH_THROTTLE_MAX_IP_FAILURES` (padrão 20);
+//! - `AUTH_THROTTLE_BASE_DELAY_SECS` (padrão 30);
+//! - `AUTH_THROTTLE_MAX_LOCKOUT_SECS` (padrão 900);
+//! - `AUTH_THROTTLE_WINDOW_SECS` (padrão 3600).
+
+use crate::db::Pool;
+use crate::errors::AppError;
+use crate::repository::login_attempt_repository;
+use chrono::{DateTime, Duration, Utc};
+use rocket::request::{FromRequest, Outcome, Request};
+use rocket::State;
+use std::collections::HashMap;
+use std::env::var;
+use std::net::IpAddr;
+use std::sync::Mutex;
+
+#[derive(Clone, Copy, Debug)]
+pub enum AuthAction {
+    Login,
+    Register,
+    SecondFactor,
+}
+
+impl AuthAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuthAction::Login => "login",
+            AuthAction::Register => "register",
+            AuthAction::SecondFactor => "2fa",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ThrottleConfig {
+    pub max_user_failures: u32,
+    pub max_ip_failures: u32,
+    pub base_delay_secs: u64,
+    pub max_lockout_secs: u64,
+    pub window_secs: u64,
+}
+
+impl Default for ThrottleConfig {
+    fn default() -> Self {
+        ThrottleConfig {
+            max_user_failures: 5,
+            max_ip_failures: 20,
+            base_delay_secs: 30,
+            max_lockout_secs: 900,
+            window_secs: 3600,
+        }
+    }
+}
+
+impl ThrottleConfig {
+    pub fn from_env() -> Self {
+        let default = ThrottleConfig::default();
+        ThrottleConfig {
+            max_user_failures: env_or("AUTH_THROTTLE_MAX_USER_FAILURES", default.max_user_failures),
+            max_ip_failures: env_or("AUTH_THROTTLE_MAX_IP_FAILURES", default.max_ip_failures),
+            base_delay_secs: env_or("AUTH_THROTTLE_BASE_DELAY_SECS", default.base_delay_secs),
+            max_lockout_secs: env_or("AUTH_THROTTLE_MAX_LOCKOUT_SECS", default.max_lockout_secs),
+            window_secs: env_or("AUTH_THROTTLE_WINDOW_SECS", default.window_secs),
+        }
+    }
+
+    /// Tempo de bloqueio após `failures` falhas, ou `None` se ainda abaixo do limite.
+    fn lockout(&self, failures: u64, max_failures: u32) -> Option<Duration> {
+        let over = failures.checked_sub(u64::from(max_failures))?;
+        let factor = 1u64.checked_shl(over.min(32) as u32).unwrap_or(u64::MAX);
+        let secs = self.base_delay_secs.saturating_mul(factor).min(self.max_lockout_secs);
+        Some(Duration::seconds(secs as i64))
+    }
+
+    /// Segundos de bloqueio que ainda restam para a chave, se houver.
+    fn retry_after(&self, status: &AttemptStatus, max_failures: u32, now: DateTime<Utc>) -> Option<u64> {
+        let locked_until = status.last_failure? + self.lockout(status.failures, max_failures)?;
+        (locked_until > now).then(|| (locked_until - now).num_seconds().max(1) as u64)
+    }
+}
+
+fn env_or<T: std::str::FromStr>(key: &str, default: T) -> T {
+    var(key).ok().and_then(|v| v.parse().ok()).unwrap_or(default)
+}
+
+/// Chave pela qual as falhas são contadas.
+pub enum AttemptKey<'a> {
+    Username(&'a str),
+    Ip(IpAddr),
+}
+
+/// Falhas contadas para uma chave e a data da mais recente.
+pub struct AttemptStatus {
+    pub failures: u64,
+    pub last_failure: Option<DateTime<Utc>>,
+}
+
+pub struct Attempt<'a> {
+    pub action: AuthAction,
+    pub username: &'a str,
+    pub ip: Option<IpAddr>,
+    pub succeeded: bool,
+}
+
+/// Armazenamento das tentativas de autenticação.
+#[rocket::async_trait]
+pub trait AttemptStore: Send + Sync {
+    /// Falhas da chave desde `since`; para usuários, apenas as posteriores ao último sucesso.
+    async fn status(
+        &self,
+        db: &Pool,
+        action: AuthAction,
+        key: &AttemptKey<'_>,
+        since: DateTime<Utc>,
+    ) -> Result<AttemptStatus, AppError>;
+
+    async fn record(&self, db: &Pool, attempt: &Attempt<'_>) -> Result<(), AppError>;
+}
+
+/// Guarda as falhas em memória; o histórico se perde ao reiniciar o servidor.
+pub struct MemoryAttemptStore {
+    failures: Mutex<HashMap<String, Vec<DateTime<Utc>>>>,
+    window: Duration,
+}
+
+impl MemoryAttemptStore {
+    pub fn new(window_secs: u64) -> Self {
+        MemoryAttemptStore {
+            failures: Mutex::new(HashMap::new()),
+            window: Duration::seconds(window_secs as i64),
+        }
+    }
+
+    fn key(action: AuthAction, key: &AttemptKey<'_>) -> String {
+        match key {
+            AttemptKey::Username(username) => format!("{}:user:{}", action.as_str(), username),
+            AttemptKey::Ip(ip) => format!("{}:ip:{}", action.as_str(), ip),
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl AttemptStore for MemoryAttemptStore {
+    async fn status(
+        &self,
+        _db: &Pool,
+        action: AuthAction,
+        key: &AttemptKey<'_>,
+        since: DateTime<Utc>,
+    ) -> Result<AttemptStatus, AppError> {
+        let failures = self.failures.lock().unwrap();
+        let recent: Vec<&DateTime<Utc>> = failures
+            .get(&Self::key(action, key))
+            .map(|times| times.iter().filter(|t| **t >= since).collect())
+            .unwrap_or_default();
+
+        Ok(AttemptStatus {
+            failures: recent.len() as u64,
+            last_failure: recent.into_iter().max().copied(),
+        })
+    }
+
+    async fn record(&self, _db: &Pool, attempt: &Attempt<'_>) -> Result<(), AppError> {
+        let now = Utc::now();
+        let cutoff = now - self.window;
+        let user_key = Self::key(attempt.action, &AttemptKey::Username(attempt.username));
+        let mut failures = self.failures.lock().unwrap();
+
+        if attempt.succeeded {
+            failures.remove(&user_key);
+            return Ok(());
+        }
+
+        let mut keys = vec![user_key];
+        if let Some(ip) = attempt.ip {
+            keys.push(Self::key(attempt.action, &AttemptKey::Ip(ip)));
+        }
+        for key in keys {
+            failures.entry(key).or_default().push(now);
+        }
+        failures.retain(|_, times| {
+            times.retain(|t| *t >= cutoff);
+            !times.is_empty()
+        });
+        Ok(())
+    }
+}
+
+/// Guarda todas as tentativas na tabela `login_attempt`, que serve também de auditoria.
+pub struct DatabaseAttemptStore;
+
+#[rocket::async_trait]
+impl AttemptStore for DatabaseAttemptStore {
+    async fn status(
+        &self,
+        db: &Pool,
+        action: AuthAction,
+        key: &AttemptKey<'_>,
+        since: DateTime<Utc>,
+    ) -> Result<AttemptStatus, AppError> {
+        let (failures, last_failure) = match key {
+            AttemptKey::Username(username) => {
+                let last_success =
+                    login_attempt_repository::last_success_by_username(db, action.as_str(), username).await?;
+                let since = last_success.map_or(since, |s| s.max(since));
+                login_attempt_repository::failures_by_username(db, action.as_str(), username, since).await?
+            }
+            AttemptKey::Ip(ip) => {
+                login_attempt_repository::failures_by_ip(db, action.as_str(), &ip.to_string(), since).await?
+            }
+        };
+        Ok(AttemptStatus { failures, last_failure })
+    }
+
+    async fn record(&self, db: &Pool, attempt: &Attempt<'_>) -> Result<(), AppError> {
+        login_attempt_repository::create_attempt(
+            db,
+            attempt.action.as_str(),
+            attempt.username,
+            attempt.ip.map(|ip| ip.to_string()),
+            attempt.succeeded,
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+/// Estado gerenciado pelo Rocket com a configuração e o armazenamento escolhidos.
+pub struct AuthThrottleState {
+    config: ThrottleConfig,
+    store: Box<dyn AttemptStore>,
+}
+
+impl AuthThrottleState {
+    pub fn from_env() -> Self {
+        let config = ThrottleConfig::from_env();
+        let store: Box<dyn AttemptStore> = match var("AUTH_THROTTLE_STORE").as_deref() {
+            Ok("database") => Box::new(DatabaseAttemptStore),
+            _ => Box::new(MemoryAttemptStore::new(config.window_secs)),
+        };
+        AuthThrottleState { config, store }
+    }
+}
+
+/// Request guard que dá às rotas de autenticação acesso ao controle de tentativas
+/// do IP da requisição.
+pub struct AuthThrottle<'r> {
+    state: &'r AuthThrottleState,
+    db: &'r Pool,
+    ip: Option<IpAddr>,
+}
+
+impl AuthThrottle<'_> {
+    /// Recusa a tentativa com `429` se o usuário ou o IP estiverem bloqueados.
+    pub async fn check(&self, action: AuthAction, username: &str) -> Result<(), AppError> {
+        let config = &self.state.config;
+        let now = Utc::now();
+        let since = now - Duration::seconds(config.window_secs as i64);
+
+        let mut keys = vec![(AttemptKey::Username(username), config.max_user_failures)];
+        if let Some(ip) = self.ip {
+            keys.push((AttemptKey::Ip(ip), config.max_ip_failures));
+        }
+
+        let mut retry_after = 0;
+        for (key, max_failures) in keys {
+            let status = self.state.store.status(self.db, action, &key, since).await?;
+            if let Some(remaining) = config.retry_after(&status, max_failures, now) {
+                retry_after = retry_after.max(remaining);
+            }
+        }
+
+        if retry_after > 0 {
+            log::warn!(
+                "auth: {} blocked for username '{}' from {} ({}s remaining)",
+                action.as_str(),
+                username,
+                self.ip_label(),
+                retry_after
+            );
+            return Err(AppError::TooManyRequests {
+                message: "Too many attempts. Try again later.".to_string(),
+                retry_after,
+            });
+        }
+        Ok(())
+    }
+
+    /// Registra o resultado da tentativa. Apenas erros causados pelas credenciais
+    /// enviadas contam como falha; erros internos são ignorados.
+    pub async fn record<T>(
+        &self,
+        action: AuthAction,
+        username: &str,
+        result: &Result<T, AppError>,
+    ) -> Result<(), AppError> {
+        let succeeded = match result {
+            Ok(_) => true,
+            Err(AppError::Unauthorized(_) | AppError::Conflict(_) | AppError::Validation { .. }) => false,
+            Err(_) => return Ok(()),
+        };
+
+        if !succeeded {
+            log::warn!(
+                "auth: failed {} for username '{}' from {}",
+                action.as_str(),
+                username,
+                self.ip_label()
+            );
+        }
+
+        let attempt = Attempt {
+            action,
+            username,
+            ip: self.ip,
+            succeeded,
+        };
+        self.state.store.record(self.db, &attempt).await
+    }
+
+    fn ip_label(&self) -> String {
+        self.ip.map_or_else(|| "unknown ip".to_string(), |ip| ip.to_string())
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for AuthThrottle<'r> {
+    type Error = AppError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let state = match request.guard::<&State<AuthThrottleState>>().await {
+            Outcome::Success(state) => state.inner(),
+            _ => return AppError::Internal("Auth throttle not configured".to_string()).fail(request),
+        };
+
+        let db = match request.guard::<&State<Pool>>().await {
+            Outcome::Success(db) => db.inner(),
+            _ => return AppError::Internal("Database unavailable".to_string()).fail(request),
+        };
+
+        Outcome::Success(AuthThrottle {
+            state,
+            db,
+            ip: request.client_ip(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> ThrottleConfig {
+        ThrottleConfig {
+            max_user_failures: 3,
+            max_ip_failures: 10,
+            base_delay_secs: 30,
+            max_lockout_secs: 300,
+            window_secs: 3600,
+        }
+    }
+
+    fn failure(username: &str, ip: Option<IpAddr>) -> Attempt<'_> {
+        Attempt { action: AuthAction::Login, username, ip, succeeded: false }
+    }
+
+    #[test]
+    fn lockout_starts_at_the_limit_and_doubles() {
+        let config = config();
+        assert_eq!(config.lockout(2, 3), None);
+        assert_eq!(config.lockout(3, 3), Some(Duration::seconds(30)));
+        assert_eq!(config.lockout(4, 3), Some(Duration::seconds(60)));
+        assert_eq!(config.lockout(5, 3), Some(Duration::seconds(120)));
+    }
+
+    #[test]
+    fn lockout_is_capped() {
+        let config = config();
+        assert_eq!(config.lockout(7, 3), Some(Duration::seconds(300)));
+        assert_eq!(config.lockout(u64::MAX, 3), Some(Duration::seconds(300)));
+    }
+
+    #[test]
+    fn retry_after_counts_from_the_last_failure() {
+        let config = config();
+        let now = Utc::now();
+        let status = AttemptStatus { failures: 4, last_failure: Some(now - Duration::seconds(20)) };
+        assert_eq!(config.retry_after(&status, 3, now), Some(40));
+
+        let expired = AttemptStatus { failures: 4, last_failure: Some(now - Duration::seconds(60)) };
+        assert_eq!(config.retry_after(&expired, 3, now), None);
+
+        let below = AttemptStatus { failures: 2, last_failure: Some(now) };
+        assert_eq!(config.retry_after(&below, 3, now), None);
+    }
+
+    #[test]
+    fn retry_after_is_at_least_one_second() {
+        let config = config();
+        let now = Utc::now();
+        let status = AttemptStatus { failures: 3, last_failure: Some(now - Duration::milliseconds(29_500)) };
+        assert_eq!(config.retry_after(&status, 3, now), Some(1));
+    }
+
+    #[tokio::test]
+    async fn memory_store_counts_by_username_and_ip() {
+        let db = Pool::Disconnected;
+        let store = MemoryAttemptStore::new(3600);
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let since = Utc::now() - Duration::hours(1);
+
+        store.record(&db, &failure("ana", Some(ip))).await.unwrap();
+        store.record(&db, &failure("bia", Some(ip))).await.unwrap();
+
+        let ana = store.status(&db, AuthAction::Login, &AttemptKey::Username("ana"), since).await.unwrap();
+        let by_ip = store.status(&db, AuthAction::Login, &AttemptKey::Ip(ip), since).await.unwrap();
+        let register = store.status(&db, AuthAction::Register, &AttemptKey::Ip(ip), since).await.unwrap();
+        assert_eq!(ana.failures, 1);
+        assert_eq!(by_ip.failures, 2);
+        assert_eq!(register.failures, 0);
+    }
+
+    #[tokio::test]
+    async fn memory_store_success_resets_only_the_username() {
+        let db = Pool::Disconnected;
+        let store = MemoryAttemptStore::new(3600);
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let since = Utc::now() - Duration::hours(1);
+
+        store.record(&db, &failure("ana", Some(ip))).await.unwrap();
+        store.record(&db, &failure("ana", Some(ip))).await.unwrap();
+        let success = Attempt { succeeded: true, ..failure("ana", Some(ip)) };
+        store.record(&db, &success).await.unwrap();
+
+        let ana = store.status(&db, AuthAction::Login, &AttemptKey::Username("ana"), since).await.unwrap();
+        let by_ip = store.status(&db, AuthAction::Login, &AttemptKey::Ip(ip), since).await.unwrap();
+        assert_eq!(ana.failures, 0);
+        assert_eq!(ana.last_failure, None);
+        assert_eq!(by_ip.failures, 2);
+    }
+}
@@ -1,15 +1,22 @@
 use wasm_bindgen_futures::spawn_local;
-use yew::{function_component, html, use_effect, use_state, Callback, Html, MouseEvent, Properties};
-use chrono::{Local, NaiveDate, Datelike};
+use yew::{function_component, html, use_effect, use_effect_with, use_state, Callback, Html, MouseEvent, Properties, UseStateHandle};
+use chrono::{Local, Months, NaiveDate, Datelike};
 use chrono::TimeZone;
 use crate::components::{task_card::TaskCard, task_form::TaskForm};
 use crate::components::{reminder_form::ReminderForm, reminder_card::ReminderCard};
 use crate::components::{goal_form::GoalForm, goal_card::GoalCard};
+use crate::components::task_bulk_bar::TaskBulkBar;
+use crate::components::undo_toast::{DeletedItem, UndoToast};
 use crate::types::{TaskDuration, Task};
-use crate::services::tasks::{TaskUpdateDto};
+use crate::services::tasks::{get_overdue_tasks, get_task_occurrences, rollover_tasks, skip_occurrence, OverdueTasks, update_occurrence, TaskUpdateDto};
 use crate::types::reminder::Reminder;
 use crate::types::goal::Goal;
+use crate::types::category::Category;
+use crate::types::search::SearchResult;
+use crate::services::category_service::get_categories;
 use crate::services::goal_service::{get_all_goals, update_goal, GoalDto};
+use crate::services::trash_service;
+use crate::services::timer_service::{self, TimeEntry};
 use web_sys::HtmlAudioElement;
 
 
@@ -25,6 +32,9 @@ pub enum ViewType {
 pub struct CalendarAppProps {
     pub visible: bool,
     pub on_close: Callback<()>,
+    /// Item escolhido na busca: a agenda abre no dia e na aba dele e o destaca.
+    #[prop_or_default]
+    pub focus: Option<SearchResult>,
 }
 
 #[function_component(CalendarApp)]
@@ -38,6 +48,185 @@ pub fn calendar_app(props: &CalendarAppProps) -> Html {
     let show_goal_form = use_state(|| false);
     let goal_to_edit = use_state(|| None::<Goal>);
     let error_message = use_state(String::new);
+    // Último item excluído, que o aviso "Desfazer" ainda pode restaurar.
+    let last_deleted = use_state(|| None::<DeletedItem>);
+    let overdue = use_state(OverdueTasks::default);
+    let running_timer = use_state(|| None::<TimeEntry>);
+
+    let current_date = Local::now().date_naive();
+
+    let current_month = use_state(|| current_date.month());
+    let current_year = use_state(|| current_date.year());
+    let selected_day = use_state(|| current_date.day());
+    let selected_date = NaiveDate::from_ymd_opt(*current_year, *current_month, *selected_day)
+        .unwrap_or(current_date);
+
+    {
+        let current_year = current_year.clone();
+        let current_month = current_month.clone();
+        let selected_day = selected_day.clone();
+        let current_view = current_view.clone();
+        use_effect_with(props.focus.clone(), move |focus| {
+            if let Some(focus) = focus {
+                let date = focus.date.with_timezone(&Local).date_naive();
+                current_year.set(date.year());
+                current_month.set(date.month());
+                selected_day.set(date.day());
+                current_view.set(match focus.kind.as_str() {
+                    "goal" => ViewType::Goals,
+                    "reminder" => ViewType::Reminders,
+                    _ => ViewType::Tasks,
+                });
+            }
+        });
+    }
+    let is_focused = |kind: &str, id: i32| props.focus.as_ref().is_some_and(|focus| focus.kind == kind && focus.id == id);
+
+    // As tarefas exibidas são as ocorrências do mês, com as repetidas expandidas.
+    {
+        let tasks = tasks.clone();
+        let error_message = error_message.clone();
+        use_effect_with((*current_year, *current_month), move |(year, month)| {
+            load_month_tasks(tasks, error_message, *year, *month);
+        });
+    }
+
+    // O contador de atrasadas e o cronômetro ligado acompanham cada recarga das tarefas.
+    {
+        let overdue = overdue.clone();
+        let running_timer = running_timer.clone();
+        use_effect_with((*tasks).clone(), move |_| {
+            spawn_local(async move {
+                if let Ok(result) = get_overdue_tasks().await {
+                    overdue.set(result);
+                }
+                if let Ok(timer) = timer_service::get_running_timer().await {
+                    running_timer.set(timer);
+                }
+            });
+        });
+    }
+
+    let on_overdue_click = {
+        let overdue = overdue.clone();
+        let current_year = current_year.clone();
+        let current_month = current_month.clone();
+        let selected_day = selected_day.clone();
+        let current_view = current_view.clone();
+        Callback::from(move |_: MouseEvent| {
+            if let Some(first) = overdue.tasks.first() {
+                let date = first.complete_date.with_timezone(&Local).date_naive();
+                current_year.set(date.year());
+                current_month.set(date.month());
+                selected_day.set(date.day());
+                current_view.set(ViewType::Tasks);
+            }
+        })
+    };
+
+    let reload_tasks = {
+        let tasks = tasks.clone();
+        let error_message = error_message.clone();
+        let (year, month) = (*current_year, *current_month);
+        Callback::from(move |_: ()| {
+            load_month_tasks(tasks.clone(), error_message.clone(), year, month);
+        })
+    };
+
+    // Liga o cronômetro da tarefa ou, se ele já estiver ligado nela, para.
+    let on_timer_toggle = {
+        let running_timer = running_timer.clone();
+        let reload_tasks = reload_tasks.clone();
+        Callback::from(move |task_id: u32| {
+            let running = running_timer.as_ref().is_some_and(|timer| timer.task_id == task_id);
+            let reload_tasks = reload_tasks.clone();
+            spawn_local(async move {
+                let result = if running {
+                    timer_service::stop_timer().await.map(|_| ())
+                } else {
+                    timer_service::start_timer(task_id).await.map(|_| ())
+                };
+                match result {
+                    Ok(()) => reload_tasks.emit(()),
+                    Err(error) => {
+                        let _ = web_sys::window().unwrap().alert_with_message(&error);
+                    }
+                }
+            });
+        })
+    };
+
+    // Tarefas marcadas para as ações em lote; a seleção vale entre dias diferentes.
+    let selected_tasks = use_state(Vec::<u32>::new);
+
+    let on_task_select = {
+        let selected_tasks = selected_tasks.clone();
+        Callback::from(move |task_id: u32| {
+            let mut selected = (*selected_tasks).clone();
+            match selected.iter().position(|id| *id == task_id) {
+                Some(index) => {
+                    selected.remove(index);
+                }
+                None => selected.push(task_id),
+            }
+            selected_tasks.set(selected);
+        })
+    };
+
+    let on_bulk_applied = {
+        let selected_tasks = selected_tasks.clone();
+        let reload_tasks = reload_tasks.clone();
+        Callback::from(move |_: ()| {
+            selected_tasks.set(Vec::new());
+            reload_tasks.emit(());
+        })
+    };
+
+    // Leva as tarefas pendentes do dia selecionado para o dia seguinte.
+    let on_rollover = {
+        let reload_tasks = reload_tasks.clone();
+        Callback::from(move |_: MouseEvent| {
+            let reload_tasks = reload_tasks.clone();
+            spawn_local(async move {
+                match rollover_tasks(selected_date, selected_date + chrono::Duration::days(1)).await {
+                    Ok(result) => {
+                        reload_tasks.emit(());
+                        if !result.unplaced.is_empty() {
+                            let titles: Vec<String> = result.unplaced.iter().map(|task| format!("\"{}\"", task.title)).collect();
+                            let message = format!(
+                                "{} tarefa(s) movida(s). Sem horário livre amanhã: {}.",
+                                result.moved.len(),
+                                titles.join(", ")
+                            );
+                            let _ = web_sys::window().unwrap().alert_with_message(&message);
+                        }
+                    }
+                    Err(error) => {
+                        let _ = web_sys::window().unwrap().alert_with_message(&error);
+                    }
+                }
+            });
+        })
+    };
+
+    let on_bulk_clear = {
+        let selected_tasks = selected_tasks.clone();
+        Callback::from(move |_: ()| selected_tasks.set(Vec::new()))
+    };
+
+    let on_bulk_select_day = {
+        let selected_tasks = selected_tasks.clone();
+        let tasks = tasks.clone();
+        Callback::from(move |_: ()| {
+            let mut selected = (*selected_tasks).clone();
+            for task in tasks.iter().filter(|task| is_on_date(task, selected_date)) {
+                if !selected.contains(&task.id) {
+                    selected.push(task.id);
+                }
+            }
+            selected_tasks.set(selected);
+        })
+    };
 
     let switch_to_tasks = {
         let current_view = current_view.clone();
@@ -122,6 +311,25 @@ pub fn calendar_app(props: &CalendarAppProps) -> Html {
     let show_reminder_form = use_state(|| false);
     let show_goal_form = use_state(|| false);
 
+    // Categorias recarregadas ao abrir ou fechar os formulários, onde podem ser criadas.
+    let categories = use_state(Vec::<Category>::new);
+    {
+        let categories = categories.clone();
+        use_effect_with(
+            (*show_task_form, *show_reminder_form, *show_goal_form, (*current_view).clone()),
+            move |_| {
+                spawn_local(async move {
+                    if let Ok(fetched) = get_categories().await {
+                        categories.set(fetched);
+                    }
+                });
+            },
+        );
+    }
+    let category_color = |category_id: Option<u32>| {
+        category_id.and_then(|id| categories.iter().find(|c| c.id == id).map(|c| c.color.clone()))
+    };
+
     let toggle_reminder_form = {
         let show_reminder_form = show_reminder_form.clone();
         Callback::from(move |_: MouseEvent| {
@@ -146,12 +354,17 @@ pub fn calendar_app(props: &CalendarAppProps) -> Html {
     };
 
     let on_delete_goal = {
+        let goals = goals.clone();
         let reload_goals = reload_goals.clone();
+        let last_deleted = last_deleted.clone();
         Callback::from(move |goal_id: i32| {
             let reload_goals = reload_goals.clone();
+            let last_deleted = last_deleted.clone();
+            let title = goals.iter().find(|goal| goal.id == goal_id).map(|goal| goal.name.clone()).unwrap_or_default();
             spawn_local(async move {
                 if crate::services::goal_service::delete_goal(goal_id).await.is_ok() {
                     reload_goals.emit(());
+                    last_deleted.set(Some(DeletedItem { kind: "goal", id: goal_id, title }));
                 }
             });
         })
@@ -173,8 +386,44 @@ pub fn calendar_app(props: &CalendarAppProps) -> Html {
 
     let on_task_delete = {
         let tasks = tasks.clone();
+        let last_deleted = last_deleted.clone();
         Callback::from(move |task_id: u32| {
             let tasks = tasks.clone();
+            let last_deleted = last_deleted.clone();
+            let Some(current_task) = find_task(&tasks, task_id, selected_date) else {
+                web_sys::console::log_1(&"Task not found for delete".into());
+                return;
+            };
+            if current_task.recurring {
+                let window = web_sys::window().unwrap();
+                if window
+                    .confirm_with_message("Excluir apenas esta ocorrência? (Cancelar para excluir a série inteira)")
+                    .unwrap_or(false)
+                {
+                    spawn_local(async move {
+                        match skip_occurrence(&current_task).await {
+                            Ok(_) => {
+                                let updated_tasks: Vec<Task> = (*tasks)
+                                    .iter()
+                                    .filter(|task| !same_occurrence(task, &current_task))
+                                    .cloned()
+                                    .collect();
+                                tasks.set(updated_tasks);
+                            }
+                            Err(error) => {
+                                web_sys::console::log_1(&format!("Failed to skip occurrence: {}", error).into());
+                            }
+                        }
+                    });
+                    return;
+                }
+                if !window
+                    .confirm_with_message("Excluir todas as ocorrências desta tarefa?")
+                    .unwrap_or(false)
+                {
+                    return;
+                }
+            }
             spawn_local(async move {
                 match crate::services::tasks::delete_task(task_id).await {
                     Ok(_) => {
@@ -184,6 +433,7 @@ pub fn calendar_app(props: &CalendarAppProps) -> Html {
                             .filter(|task| task.id != task_id)
                             .collect();
                         tasks.set(updated_tasks);
+                        last_deleted.set(Some(DeletedItem { kind: "task", id: task_id as i32, title: current_task.title }));
                     }
                     Err(error) => {
                         web_sys::console::log_1(&format!("Failed to delete task: {}", error).into());
@@ -195,23 +445,53 @@ pub fn calendar_app(props: &CalendarAppProps) -> Html {
 
     let on_task_update = {
         let tasks = tasks.clone();
-        Callback::from(move |(task_id, new_title, new_description): (u32, String, String)| {
+        let reload_tasks = reload_tasks.clone();
+        Callback::from(move |(task_id, new_title, new_description, new_begin): (u32, String, String, String)| {
             let tasks = tasks.clone();
-            let tasks_for_find = tasks.clone();
+            let reload_tasks = reload_tasks.clone();
+            let current_task = find_task(&tasks, task_id, selected_date);
+            // Só tarefas sem repetição podem ser remarcadas pelo cartão.
+            let new_begin_date = chrono::NaiveDateTime::parse_from_str(&new_begin, "%Y-%m-%dT%H:%M")
+                .ok()
+                .map(|naive| naive.and_utc())
+                .filter(|begin| current_task.as_ref().is_some_and(|task| *begin != task.begin_date));
+            // Em tarefas repetidas, pergunta se a edição vale para a série inteira.
+            let whole_series = current_task.as_ref().is_some_and(|task| task.recurring)
+                && web_sys::window()
+                    .unwrap()
+                    .confirm_with_message("Aplicar a alteração a todas as ocorrências? (Cancelar para editar só esta)")
+                    .unwrap_or(false);
             spawn_local(async move {
-                if let Some(current_task) = (*tasks_for_find).iter().find(|t| t.id == task_id) {
-                    let task_dto = TaskUpdateDto {
-                        title: new_title.clone(),
-                        description: new_description.clone(),
-                        status: current_task.status.clone(),
+                if let Some(current_task) = current_task {
+                    let result = if whole_series {
+                        let task_dto = TaskUpdateDto {
+                            title: new_title.clone(),
+                            description: new_description.clone(),
+                            status: None,
+                            begin_date: None,
+                        };
+                        crate::services::tasks::update_task_with_dto(task_id, task_dto).await
+                    } else {
+                        let task_dto = TaskUpdateDto {
+                            title: new_title.clone(),
+                            description: new_description.clone(),
+                            status: Some(current_task.status.clone()),
+                            begin_date: new_begin_date,
+                        };
+                        if current_task.recurring {
+                            update_occurrence(&current_task, task_dto).await
+                        } else {
+                            crate::services::tasks::update_task_with_dto(task_id, task_dto).await
+                        }
                     };
-                    
-                    match crate::services::tasks::update_task_with_dto(task_id, task_dto).await {
+
+                    match result {
+                        Ok(_) if whole_series || new_begin_date.is_some() => reload_tasks.emit(()),
                         Ok(_) => {
                             let updated_tasks: Vec<Task> = (*tasks)
                                 .iter()
                                 .map(|task| {
-                                    if task.id == task_id {
+                                    if same_occurrence(task, &current_task) {
                                         let mut updated_task = task.clone();
                                         updated_task.title = new_title.clone();
                                         updated_task.description = new_description.clone();
@@ -223,6 +503,10 @@ pub fn calendar_app(props: &CalendarAppProps) -> Html {
                                 .collect();
                             tasks.set(updated_tasks);
                         }
+                        Err(error) if new_begin_date.is_some() => {
+                            // Ao remarcar, explica o motivo (ex.: conflito de horário).
+                            let _ = web_sys::window().unwrap().alert_with_message(&error);
+                        }
                         Err(error) => {
                             web_sys::console::log_1(&format!("Failed to update task: {}", error).into());
                         }
@@ -274,17 +558,21 @@ pub fn calendar_app(props: &CalendarAppProps) -> Html {
 
     let on_reminder_delete = {
         let reminders = reminders.clone();
+        let last_deleted = last_deleted.clone();
         Callback::from(move |reminder_id: i32| {
             let reminders = reminders.clone();
+            let last_deleted = last_deleted.clone();
             spawn_local(async move {
                 match crate::services::reminder_service::delete_reminder(reminder_id as u32).await {
                     Ok(_) => {
+                        let title = reminders.iter().find(|reminder| reminder.id == reminder_id).map(|reminder| reminder.name.clone()).unwrap_or_default();
                         let updated_reminders: Vec<Reminder> = (*reminders)
                             .iter()
                             .cloned()
                             .filter(|reminder| reminder.id != reminder_id)
                             .collect();
                         reminders.set(updated_reminders);
+                        last_deleted.set(Some(DeletedItem { kind: "reminder", id: reminder_id, title }));
                     }
                     Err(error) => {
                         web_sys::console::log_1(&format!("Failed to delete reminder: {}", error).into());
@@ -294,12 +582,44 @@ pub fn calendar_app(props: &CalendarAppProps) -> Html {
         })
     };
 
+    let on_undo_delete = {
+        let last_deleted = last_deleted.clone();
+        let reload_tasks = reload_tasks.clone();
+        let reload_goals = reload_goals.clone();
+        let reminders = reminders.clone();
+        Callback::from(move |item: DeletedItem| {
+            let last_deleted = last_deleted.clone();
+            let reload_tasks = reload_tasks.clone();
+            let reload_goals = reload_goals.clone();
+            let reminders = reminders.clone();
+            last_deleted.set(None);
+            spawn_local(async move {
+                if let Err(error) = trash_service::restore(item.kind, item.id).await {
+                    let _ = web_sys::window().unwrap().alert_with_message(&error);
+                    return;
+                }
+                match item.kind {
+                    "task" => reload_tasks.emit(()),
+                    "goal" => reload_goals.emit(()),
+                    _ => {
+                        if let Ok(fetched_reminders) = crate::services::reminder_service::get_all_reminders().await {
+                            reminders.set(fetched_reminders);
+                        }
+                    }
+                }
+            });
+        })
+    };
+
+    let on_undo_dismiss = {
+        let last_deleted = last_deleted.clone();
+        Callback::from(move |_: ()| last_deleted.set(None))
+    };
+
     let on_task_created = {
-        let tasks = tasks.clone();
-        Callback::from(move |new_task: Task| {
-            let mut current_tasks = (*tasks).clone();
-            current_tasks.push(new_task);
-            tasks.set(current_tasks);
+        let reload_tasks = reload_tasks.clone();
+        Callback::from(move |_new_task: Task| {
+            reload_tasks.emit(());
         })
     };
 
@@ -317,7 +637,10 @@ pub fn calendar_app(props: &CalendarAppProps) -> Html {
         Callback::from(move |(task_id, new_status): (u32, String)| {
             let tasks = tasks.clone();
             spawn_local(async move {
-                if let Some(task_index) = (*tasks).iter().position(|t| t.id == task_id) {
+                if let Some(task_index) = (*tasks)
+                    .iter()
+                    .position(|t| t.id == task_id && is_on_date(t, selected_date))
+                {
                     let mut task = (*tasks)[task_index].clone();
                     task.status = new_status.clone();
                     
@@ -325,10 +648,17 @@ pub fn calendar_app(props: &CalendarAppProps) -> Html {
 
                         title: task.title.clone(),
                         description: task.description.clone(),
-                        status: new_status,
+                        status: Some(new_status),
+                        begin_date: None,
                     };
 
-                    match crate::services::tasks::update_task_with_dto(task_id, task_dto).await {
+                    // Em tarefas repetidas, o status é de cada ocorrência.
+                    let result = if task.recurring {
+                        update_occurrence(&task, task_dto).await
+                    } else {
+                        crate::services::tasks::update_task_with_dto(task_id, task_dto).await
+                    };
+                    match result {
                         Ok(_) => {
                             let mut updated_tasks = (*tasks).clone();
                             updated_tasks[task_index] = task;
@@ -336,6 +666,8 @@ pub fn calendar_app(props: &CalendarAppProps) -> Html {
                         }
                         Err(error) => {
                             web_sys::console::log_1(&format!("Failed to update task status: {}", error).into());
+                            // Ex.: a tarefa ainda está bloqueada por outras.
+                            let _ = web_sys::window().unwrap().alert_with_message(&error);
                         }
                     }
                 } else {
@@ -346,7 +678,6 @@ pub fn calendar_app(props: &CalendarAppProps) -> Html {
     };
 
     {
-        let tasks = tasks.clone();
         let reminders = reminders.clone();
         let goals = goals.clone();
         let first_render = first_render.clone();
@@ -364,10 +695,6 @@ pub fn calendar_app(props: &CalendarAppProps) -> Html {
 
 
             spawn_local(async move {
-                match crate::services::tasks::get_all_tasks().await {
-                    Ok(fetched_tasks) => tasks.set(fetched_tasks),
-                    Err(err) => error_message.set(format!("Erro ao buscar tarefas: {}", err)),
-                }
                 match crate::services::reminder_service::get_all_reminders().await {
                     Ok(fetched_reminders) => reminders.set(fetched_reminders),
                     Err(err) => error_message.set(format!("Erro ao buscar lembretes: {}", err)),
@@ -380,12 +707,6 @@ pub fn calendar_app(props: &CalendarAppProps) -> Html {
         });
     }
 
-    let current_date = Local::now().date_naive();
-
-    let current_month = use_state(|| current_date.month());
-    let current_year = use_state(|| current_date.year());
-    let selected_day = use_state(|| current_date.day());
-
     let on_day_click = {
         let selected_day = selected_day.clone();
         Callback::from(move |day: u32| {
@@ -451,7 +772,7 @@ pub fn calendar_app(props: &CalendarAppProps) -> Html {
             .unwrap_or_else(|| Local::now().date_naive());
         
         tasks.iter().filter(|task| {
-            task.begin_date.date_naive() == target_date
+            is_on_date(task, target_date)
         }).count()
     };
     
@@ -484,6 +805,16 @@ pub fn calendar_app(props: &CalendarAppProps) -> Html {
             <div class="calendar-content">
                 <div class="calendar">
                     <h2 class="calendar-heading">{ "Agenda" }</h2>
+                    if overdue.count > 0 {
+                        <button
+                            class="overdue-badge"
+                            type="button"
+                            title={overdue.tasks.iter().map(|task| task.title.clone()).collect::<Vec<_>>().join("\n")}
+                            onclick={on_overdue_click}
+                        >
+                            { format!("{} atrasada{}", overdue.count, if overdue.count == 1 { "" } else { "s" }) }
+                        </button>
+                    }
                 <div class="navigate-date">
                     <h2 class="month"> { months_of_year[*current_month as usize - 1] } </h2>
                     <h2 class="year"> { *current_year } </h2>
@@ -576,7 +907,10 @@ pub fn calendar_app(props: &CalendarAppProps) -> Html {
                         <div class="action-buttons">
                             { match &*current_view {
                                 ViewType::Tasks => html! {
-                                    <button class="add-btn" onclick={toggle_task_form}>{ "Nova Tarefa" }</button>
+                                    <>
+                                        <button class="add-btn" onclick={toggle_task_form}>{ "Nova Tarefa" }</button>
+                                        <button class="add-btn" title="Levar as tarefas pendentes deste dia para o dia seguinte" onclick={on_rollover}>{ "Passar para amanhã" }</button>
+                                    </>
                                 },
                                 ViewType::Reminders => html! {
                                     <button class="add-btn" onclick={toggle_reminder_form}>{ "Novo Lembrete" }</button>
@@ -592,17 +926,15 @@ pub fn calendar_app(props: &CalendarAppProps) -> Html {
                     { match &*current_view {
                         ViewType::Tasks => {
                             // Filter tasks by selected date
-                            let selected_date = NaiveDate::from_ymd_opt(*current_year, *current_month, *selected_day)
-                                .unwrap_or_else(|| Local::now().date_naive());
-                            
                             let filtered_tasks: Vec<&Task> = tasks.iter()
-                                .filter(|task| task.begin_date.date_naive() == selected_date)
+                                .filter(|task| is_on_date(task, selected_date))
                                 .collect();
                             
                             let task_cards: Vec<Html> = filtered_tasks.iter().enumerate().map(|(index, task)| {
                                 let duration = TaskDuration::from_value(&task.task_type).unwrap_or_default();
                                 let date_formatted = task.begin_date.format("%B %d, %Y").to_string();
                                 let time_formatted = task.begin_date.format("%H:%M").to_string();
+                                let end_time = task_end_label(task, duration);
                                 
                                 html! {
                                     <TaskCard 
@@ -615,17 +947,39 @@ pub fn calendar_app(props: &CalendarAppProps) -> Html {
                                         on_task_update={Some(on_task_update.clone())}
                                         on_status_update={Some(on_status_update.clone())}
                                         status={task.status.clone()}
+                                        recurring={task.recurring}
                                         date={date_formatted}
                                         time={time_formatted}
+                                        end_time={end_time}
+                                        begin={if task.recurring { String::new() } else { task.begin_date.format("%Y-%m-%dT%H:%M").to_string() }}
                                         duration={duration}
+                                        checklist={task.checklist}
+                                        checklist_auto_complete={task.checklist_auto_complete}
+                                        on_checklist_change={Some(reload_tasks.clone())}
+                                        blocked={!task.blocked_by.is_empty()}
+                                        color={category_color(task.category_id)}
+                                        highlighted={is_focused("task", task.id as i32)}
+                                        on_select={Some(on_task_select.clone())}
+                                        selected={selected_tasks.contains(&task.id)}
+                                        tracked_seconds={task.tracked_seconds}
+                                        timer_running={running_timer.as_ref().is_some_and(|timer| timer.task_id == task.id)}
+                                        on_timer_toggle={Some(on_timer_toggle.clone())}
                                     />
                                 }
                             }).collect();
                             
-                            if task_cards.is_empty() {
-                                html! { <></> }
-                            } else {
-                                html! { <>{task_cards}</> }
+                            html! {
+                                <>
+                                    if !selected_tasks.is_empty() {
+                                        <TaskBulkBar
+                                            task_ids={(*selected_tasks).clone()}
+                                            on_applied={on_bulk_applied.clone()}
+                                            on_select_day={on_bulk_select_day.clone()}
+                                            on_clear={on_bulk_clear.clone()}
+                                        />
+                                    }
+                                    {task_cards}
+                                </>
                             }
                         },
                         ViewType::Reminders => {
@@ -655,6 +1009,8 @@ pub fn calendar_app(props: &CalendarAppProps) -> Html {
                                         date_end={reminder.date_end}
                                         on_reminder_delete={on_reminder_delete.clone()}
                                         on_reminder_update={Some(on_reminder_update.clone())}
+                                        color={category_color(reminder.category_id.map(|id| id as u32))}
+                                        highlighted={is_focused("reminder", reminder.id)}
                                     />
                                 }
                             }).collect();
@@ -684,6 +1040,7 @@ pub fn calendar_app(props: &CalendarAppProps) -> Html {
                                             name={goal.name.clone()}
                                             description={goal.description.clone()}
                                             category={goal.category.clone()}
+                                            category_id={goal.category_id}
                                             status={goal.status.clone()}
                                             goal_type={goal.goal_type.clone()}
                                             date_start={goal.date_start.clone()}
@@ -693,6 +1050,8 @@ pub fn calendar_app(props: &CalendarAppProps) -> Html {
                                             on_goal_delete={on_delete_goal.clone()}
                                             on_edit={Callback::from(move |_| on_edit_goal.emit(goal_clone_for_edit.clone()))}
                                             on_status_change={on_goal_status_update.clone()}
+                                            color={category_color(goal.category_id.map(|id| id as u32))}
+                                            highlighted={is_focused("goal", goal.id)}
                                         />
                                     }
                                 }).collect();
@@ -723,7 +1082,59 @@ pub fn calendar_app(props: &CalendarAppProps) -> Html {
                 on_close={on_close_goal_form}
                 on_save={on_save_goal}
             />
+
+            if let Some(item) = (*last_deleted).clone() {
+                <UndoToast item={item} on_undo={on_undo_delete} on_dismiss={on_undo_dismiss} />
+            }
         </div>
     }
 }
-}
\ No newline at end of file
+}
+
+/// Carrega as ocorrências do mês exibido.
+fn load_month_tasks(tasks: UseStateHandle<Vec<Task>>, error_message: UseStateHandle<String>, year: i32, month: u32) {
+    let Some(first_day) = NaiveDate::from_ymd_opt(year, month, 1) else {
+        return;
+    };
+    let last_day = first_day
+        .checked_add_months(Months::new(1))
+        .and_then(|date| date.pred_opt())
+        .unwrap_or(first_day);
+
+    spawn_local(async move {
+        match get_task_occurrences(first_day, last_day).await {
+            Ok(fetched_tasks) => tasks.set(fetched_tasks),
+            Err(err) => error_message.set(format!("Erro ao buscar tarefas: {}", err)),
+        }
+    });
+}
+
+/// Se a tarefa ocupa algum horário do dia; tarefas de vários dias aparecem em todos eles.
+fn is_on_date(task: &Task, date: NaiveDate) -> bool {
+    let last_day = (task.complete_date - chrono::Duration::seconds(1)).date_naive();
+    task.begin_date.date_naive() <= date && date <= last_day.max(task.begin_date.date_naive())
+}
+
+/// Fim mostrado no cartão: só o horário, ou também a data quando a tarefa
+/// termina em outro dia.
+fn task_end_label(task: &Task, duration: TaskDuration) -> String {
+    let same_day = task.complete_date.date_naive() == task.begin_date.date_naive();
+    match duration {
+        TaskDuration::DiaInteiro if same_day => String::new(),
+        TaskDuration::DiaInteiro => task.complete_date.format("%d/%m").to_string(),
+        _ if same_day => task.complete_date.format("%H:%M").to_string(),
+        _ => task.complete_date.format("%d/%m %H:%M").to_string(),
+    }
+}
+
+/// Tarefa exibida no dia escolhido; nas repetidas, o id se repete em vários dias.
+fn find_task(tasks: &[Task], task_id: u32, date: NaiveDate) -> Option<Task> {
+    tasks
+        .iter()
+        .find(|task| task.id == task_id && is_on_date(task, date))
+        .cloned()
+}
+
+fn same_occurrence(a: &Task, b: &Task) -> bool {
+    a.id == b.id && a.occurrence_start == b.occurrence_start
+}
@@ -0,0 +1,158 @@
+use yew::{function_component, html, use_effect_with, use_state, Callback, Event, Html, InputEvent, MouseEvent, Properties, TargetCast};
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use wasm_bindgen_futures::spawn_local;
+use crate::types::category::Category;
+use crate::services::category_service::{CategoryDto, create_category, get_categories};
+
+/// Valor da opção que abre o formulário de nova categoria.
+const NEW_CATEGORY: &str = "__new__";
+const DEFAULT_COLOR: &str = "#000080";
+
+/// Nomes de categoria são únicos sem diferenciar maiúsculas de minúsculas.
+fn same_name(a: &str, b: &str) -> bool {
+    a.to_lowercase() == b.to_lowercase()
+}
+
+#[derive(Properties, PartialEq)]
+pub struct CategoryPickerProps {
+    /// Nome da categoria selecionada.
+    pub value: String,
+    pub on_change: Callback<String>,
+    #[prop_or_default]
+    pub required: bool,
+}
+
+/// Seleção entre as categorias do usuário, com a opção de criar uma nova na hora.
+#[function_component(CategoryPicker)]
+pub fn category_picker(props: &CategoryPickerProps) -> Html {
+    let categories = use_state(Vec::<Category>::new);
+    let creating = use_state(|| false);
+    let new_name = use_state(String::new);
+    let new_color = use_state(|| DEFAULT_COLOR.to_string());
+    let error = use_state(String::new);
+
+    {
+        let categories = categories.clone();
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                if let Ok(fetched) = get_categories().await {
+                    categories.set(fetched);
+                }
+            });
+        });
+    }
+
+    let on_select = {
+        let creating = creating.clone();
+        let on_change = props.on_change.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            let value = select.value();
+            if value == NEW_CATEGORY {
+                creating.set(true);
+            } else {
+                creating.set(false);
+                on_change.emit(value);
+            }
+        })
+    };
+
+    let on_name_change = {
+        let new_name = new_name.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            new_name.set(input.value());
+        })
+    };
+
+    let on_color_change = {
+        let new_color = new_color.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            new_color.set(input.value());
+        })
+    };
+
+    let on_create = {
+        let categories = categories.clone();
+        let creating = creating.clone();
+        let new_name = new_name.clone();
+        let new_color = new_color.clone();
+        let error = error.clone();
+        let on_change = props.on_change.clone();
+        Callback::from(move |_: MouseEvent| {
+            let name = new_name.trim().to_string();
+            if name.chars().count() < 2 {
+                error.set("O nome deve ter pelo menos 2 caracteres.".to_string());
+                return;
+            }
+            let dto = CategoryDto { name, color: (*new_color).clone() };
+            let categories = categories.clone();
+            let creating = creating.clone();
+            let new_name = new_name.clone();
+            let error = error.clone();
+            let on_change = on_change.clone();
+            spawn_local(async move {
+                match create_category(dto).await {
+                    Ok(category) => {
+                        let mut updated = (*categories).clone();
+                        on_change.emit(category.name.clone());
+                        updated.push(category);
+                        categories.set(updated);
+                        creating.set(false);
+                        new_name.set(String::new());
+                        error.set(String::new());
+                    }
+                    Err(e) => error.set(e),
+                }
+            });
+        })
+    };
+
+    // Um nome que ainda não virou categoria continua aparecendo como opção.
+    let known = props.value.is_empty()
+        || categories.iter().any(|c| same_name(&c.name, &props.value));
+
+    html! {
+        <div class="category-picker">
+            <select id="category" class="win98-select" required={props.required} onchange={on_select}>
+                <option value="" selected={props.value.is_empty() && !*creating}>{ "Selecione a categoria" }</option>
+                { for categories.iter().map(|category| {
+                    let label = match &category.icon {
+                        Some(icon) => format!("{} {}", icon, category.name),
+                        None => category.name.clone(),
+                    };
+                    html! {
+                        <option
+                            value={category.name.clone()}
+                            selected={!*creating && same_name(&category.name, &props.value)}
+                            style={format!("color: {}", category.color)}
+                        >
+                            { label }
+                        </option>
+                    }
+                })}
+                if !known {
+                    <option value={props.value.clone()} selected={!*creating}>{ props.value.clone() }</option>
+                }
+                <option value={NEW_CATEGORY} selected={*creating}>{ "Nova categoria…" }</option>
+            </select>
+            if *creating {
+                <div class="category-picker-new">
+                    <input
+                        type="text"
+                        placeholder="Nome da categoria"
+                        maxlength="50"
+                        value={(*new_name).clone()}
+                        oninput={on_name_change}
+                    />
+                    <input type="color" value={(*new_color).clone()} oninput={on_color_change} />
+                    <button type="button" onclick={on_create}>{ "Criar" }</button>
+                </div>
+                if !error.is_empty() {
+                    <p class="category-picker-error">{ (*error).clone() }</p>
+                }
+            }
+        </div>
+    }
+}
@@ -7,6 +7,8 @@ pub struct GoalCardProps {
     pub name: String,
     pub description: Option<String>,
     pub category: Option<String>,
+    #[prop_or_default]
+    pub category_id: Option<i32>,
     pub status: String,
     pub goal_type: String,
     pub date_start: String,
@@ -16,6 +18,12 @@ pub struct GoalCardProps {
     pub on_goal_delete: Callback<i32>,
     pub on_edit: Callback<Goal>,
     pub on_status_change: Callback<(i32, String)>,
+    /// Cor da categoria, usada na borda do cartão.
+    #[prop_or_default]
+    pub color: Option<String>,
+    /// Destaca o cartão aberto pela busca.
+    #[prop_or_default]
+    pub highlighted: bool,
 }
 
 
@@ -41,6 +49,7 @@ pub struct GoalCardProps {
                 name: props.name.clone(),
                 description: props.description.clone(),
                 category: props.category.clone(),
+                category_id: props.category_id,
                 status: props.status.clone(),
                 goal_type: props.goal_type.clone(),
                 date_start: props.date_start.clone(),
@@ -86,7 +95,7 @@ pub struct GoalCardProps {
         };
 
     html! {
-        <div class="goal-card">
+        <div class={classes!("goal-card", props.highlighted.then_some("search-highlight"))} style={props.color.as_ref().map(|color| format!("border-left: 4px solid {}", color))}>
             <div class="goal-header">
                 <h3 class="goal-title">{ &props.name }</h3>
                 <div class="goal-actions">
@@ -7,6 +7,7 @@ use wasm_bindgen_futures::spawn_local;
 use crate::types::goal::Goal;
 // Importe tanto create_goal quanto update_goal do seu serviço
 use crate::services::goal_service::{GoalDto, create_goal, update_goal};
+use crate::components::category_picker::CategoryPicker;
 
 // Seu componente Windows98Select está ótimo, ele será mantido como está.
 #[derive(Properties, PartialEq, Clone)]
@@ -221,7 +222,13 @@ pub fn goal_form(props: &GoalFormProps) -> Html {
                         // Coluna da Direita
                         <div>
                             <label for="category">{ "Categoria:" }</label>
-                            <input type="text" id="category" value={(*goal_category).clone()} oninput={on_input_change(goal_category.clone())} />
+                            <CategoryPicker
+                                value={(*goal_category).clone()}
+                                on_change={
+                                    let goal_category = goal_category.clone();
+                                    Callback::from(move |category: String| goal_category.set(category))
+                                }
+                            />
 
                             <label for="goal_type">{ "Tipo de Meta:" }</label>
                             <Windows98Select<String>
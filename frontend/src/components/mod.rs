@@ -9,4 +9,7 @@ pub mod taskbar;
 pub mod report_app;
 pub mod goal_card;
 pub mod goal_form;
-pub mod bar_chart;
\ No newline at end of file
+pub mod bar_chart;pub mod category_picker;
+pub mod search_box;
+pub mod task_bulk_bar;
+pub mod undo_toast;
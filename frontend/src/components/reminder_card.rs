@@ -1,6 +1,7 @@
-use yew::{function_component, html, Html, Properties, Callback, MouseEvent, use_state, InputEvent, TargetCast};
+use yew::{function_component, html, Html, Properties, Callback, MouseEvent, use_state, InputEvent, TargetCast, classes};
 use chrono::{DateTime, Utc, Local};
 use crate::services::reminder_service::{ReminderUpdateDto, update_reminder};
+use crate::components::category_picker::CategoryPicker;
 
 fn format_reminder_date(date_end: &DateTime<Utc>) -> String {
     date_end.format("%Y-%m-%d").to_string()
@@ -20,6 +21,12 @@ pub struct ReminderCardProps {
     pub on_reminder_delete: Callback<i32>,
     #[prop_or_default]
     pub on_reminder_update: Option<Callback<(i32, String, String, String)>>,
+    /// Cor da categoria, usada na borda do cartão.
+    #[prop_or_default]
+    pub color: Option<String>,
+    /// Destaca o cartão aberto pela busca.
+    #[prop_or_default]
+    pub highlighted: bool,
 }
 
 #[function_component(ReminderCard)]
@@ -67,10 +74,7 @@ pub fn reminder_card(props: &ReminderCardProps) -> Html {
     };
     let on_category_input = {
         let edit_category = edit_category.clone();
-        Callback::from(move |e: InputEvent| {
-            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
-            edit_category.set(input.value());
-        })
+        Callback::from(move |category: String| edit_category.set(category))
     };
     let on_date_input = {
         let edit_date_end = edit_date_end.clone();
@@ -138,7 +142,7 @@ pub fn reminder_card(props: &ReminderCardProps) -> Html {
     };
 
     html! {
-        <div class="reminder-card">
+        <div class={classes!("reminder-card", props.highlighted.then_some("search-highlight"))} style={props.color.as_ref().map(|color| format!("border-left: 4px solid {}", color))}>
             <div class="reminder-header">
                 if *is_editing {
                     <input 
@@ -162,11 +166,9 @@ pub fn reminder_card(props: &ReminderCardProps) -> Html {
             </div>
             <div class="reminder-body">
                 if *is_editing {
-                    <input 
-                        class="reminder-category-input"
-                        type="text"
+                    <CategoryPicker
                         value={(*edit_category).clone()}
-                        oninput={on_category_input}
+                        on_change={on_category_input}
                     />
                     <input 
                         class="reminder-date-input"
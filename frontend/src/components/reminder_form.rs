@@ -5,6 +5,7 @@ use wasm_bindgen_futures::spawn_local;
 
 use crate::services::reminder_service::{ReminderDto, create_reminder, ReminderResult};
 use crate::types::reminder::Reminder;
+use crate::components::category_picker::CategoryPicker;
 
 #[derive(Properties, PartialEq)]
 pub struct ReminderFormProps {
@@ -34,10 +35,7 @@ pub fn reminder_form(props: &ReminderFormProps) -> Html {
 
     let on_category_change = {
         let reminder_category = reminder_category.clone();
-        Callback::from(move |e: InputEvent| {
-            let input: HtmlInputElement = e.target_unchecked_into();
-            reminder_category.set(input.value());
-        })
+        Callback::from(move |category: String| reminder_category.set(category))
     };
 
     let on_date_change = {
@@ -172,14 +170,10 @@ pub fn reminder_form(props: &ReminderFormProps) -> Html {
 
                         <div>
                             <label for="category">{ "Categoria:" }</label>
-                            <input
-                                type="text"
-                                id="category"
-                                name="category"
-                                required=true
-                                placeholder="Digite a categoria"
+                            <CategoryPicker
                                 value={(*reminder_category).clone()}
-                                oninput={on_category_change}
+                                on_change={on_category_change}
+                                required=true
                             />
                         </div>
 
@@ -4,6 +4,19 @@ use web_sys::{HtmlSelectElement, HtmlInputElement, console};
 use crate::components::bar_chart::BarChart;
 use crate::services::report_service::{ReportService};
 use crate::types::report::{StatsYearResponse, StatsMonthResponse, StatsWeekResponse};
+use std::collections::BTreeMap;
+
+/// Horas registradas por grupo, como em `Trabalho: 3.5h, Estudo: 1.0h`.
+fn format_hours(hours: &BTreeMap<String, f64>) -> String {
+    if hours.is_empty() {
+        return "N/A".to_string();
+    }
+    hours
+        .iter()
+        .map(|(name, hours)| format!("{}: {:.1}h", name, hours))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
 
 #[derive(Properties, PartialEq)]
 pub struct ReportAppProps {
@@ -215,6 +228,22 @@ pub fn report_app(props: &ReportAppProps) -> Html {
                             <span class="summary-label">{ "Resumo geral:" }</span>
                             <span class="summary-value">{ &stats.classification_tasks }</span>
                         </div>
+                        <div class="summary-item">
+                            <span class="summary-label">{ "Horas registradas:" }</span>
+                            <span class="summary-value">{ format!("{:.1}h", stats.tracked_hours_tasks) }</span>
+                        </div>
+                        <div class="summary-item">
+                            <span class="summary-label">{ "Pomodoros concluídos:" }</span>
+                            <span class="summary-value">{ stats.completed_pomodoros }</span>
+                        </div>
+                        <div class="summary-item">
+                            <span class="summary-label">{ "Horas por categoria:" }</span>
+                            <span class="summary-value">{ format_hours(&stats.tracked_hours_by_category_tasks) }</span>
+                        </div>
+                        <div class="summary-item">
+                            <span class="summary-label">{ "Horas por turno:" }</span>
+                            <span class="summary-value">{ format_hours(&stats.tracked_hours_by_shift_tasks) }</span>
+                        </div>
                     </div>
                     <h3>{ "Resumo Anual de Metas" }</h3>
                     <div class="summary-data">
@@ -282,6 +311,22 @@ pub fn report_app(props: &ReportAppProps) -> Html {
                             <span class="summary-label">{ "Resumo geral:" }</span>
                             <span class="summary-value">{ &stats.classification_tasks }</span>
                         </div>
+                        <div class="summary-item">
+                            <span class="summary-label">{ "Horas registradas:" }</span>
+                            <span class="summary-value">{ format!("{:.1}h", stats.tracked_hours_tasks) }</span>
+                        </div>
+                        <div class="summary-item">
+                            <span class="summary-label">{ "Pomodoros concluídos:" }</span>
+                            <span class="summary-value">{ stats.completed_pomodoros }</span>
+                        </div>
+                        <div class="summary-item">
+                            <span class="summary-label">{ "Horas por categoria:" }</span>
+                            <span class="summary-value">{ format_hours(&stats.tracked_hours_by_category_tasks) }</span>
+                        </div>
+                        <div class="summary-item">
+                            <span class="summary-label">{ "Horas por turno:" }</span>
+                            <span class="summary-value">{ format_hours(&stats.tracked_hours_by_shift_tasks) }</span>
+                        </div>
                     </div>
                     <h3>{ "Resumo Mensal de Metas" }</h3>
                     <div class="summary-data">
@@ -345,6 +390,22 @@ pub fn report_app(props: &ReportAppProps) -> Html {
                             <span class="summary-label">{ "Resumo geral:" }</span>
                             <span class="summary-value">{ &stats.classification_tasks }</span>
                         </div>
+                        <div class="summary-item">
+                            <span class="summary-label">{ "Horas registradas:" }</span>
+                            <span class="summary-value">{ format!("{:.1}h", stats.tracked_hours_tasks) }</span>
+                        </div>
+                        <div class="summary-item">
+                            <span class="summary-label">{ "Pomodoros concluídos:" }</span>
+                            <span class="summary-value">{ stats.completed_pomodoros }</span>
+                        </div>
+                        <div class="summary-item">
+                            <span class="summary-label">{ "Horas por categoria:" }</span>
+                            <span class="summary-value">{ format_hours(&stats.tracked_hours_by_category_tasks) }</span>
+                        </div>
+                        <div class="summary-item">
+                            <span class="summary-label">{ "Horas por turno:" }</span>
+                            <span class="summary-value">{ format_hours(&stats.tracked_hours_by_shift_tasks) }</span>
+                        </div>
                     </div>
                      <h3>{ "Resumo Mensal de Metas" }</h3>
                     <div class="summary-data">
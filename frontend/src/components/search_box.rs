@@ -0,0 +1,125 @@
+use yew::{function_component, html, use_state, Callback, Html, InputEvent, KeyboardEvent, MouseEvent, Properties, TargetCast};
+use web_sys::HtmlInputElement;
+use wasm_bindgen_futures::spawn_local;
+use crate::services::search_service::search;
+use crate::types::search::SearchResult;
+
+#[derive(Properties, PartialEq)]
+pub struct SearchBoxProps {
+    /// Chamado ao escolher um resultado.
+    pub on_select: Callback<SearchResult>,
+}
+
+fn kind_label(kind: &str) -> &'static str {
+    match kind {
+        "task" => "Tarefa",
+        "goal" => "Meta",
+        "reminder" => "Lembrete",
+        _ => "Nota",
+    }
+}
+
+/// Monta o trecho destacado sem interpretar o texto como HTML.
+fn render_snippet(snippet: &str) -> Html {
+    let mut parts = Vec::new();
+    for (index, piece) in snippet.split("<mark>").enumerate() {
+        match piece.split_once("</mark>") {
+            Some((marked, rest)) if index > 0 => {
+                parts.push(html! { <mark>{ marked.to_string() }</mark> });
+                parts.push(html! { { rest.to_string() } });
+            }
+            _ => parts.push(html! { { piece.to_string() } }),
+        }
+    }
+    html! { <>{ for parts }</> }
+}
+
+/// Campo de busca da barra de tarefas, com os resultados em uma lista acima dele.
+#[function_component(SearchBox)]
+pub fn search_box(props: &SearchBoxProps) -> Html {
+    let query = use_state(String::new);
+    let results = use_state(|| None::<Result<Vec<SearchResult>, String>>);
+
+    let on_input = {
+        let query = query.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            query.set(input.value());
+        })
+    };
+
+    let on_keydown = {
+        let query = query.clone();
+        let results = results.clone();
+        Callback::from(move |e: KeyboardEvent| match e.key().as_str() {
+            "Enter" => {
+                let text = query.trim().to_string();
+                if text.is_empty() {
+                    return;
+                }
+                let results = results.clone();
+                spawn_local(async move {
+                    results.set(Some(search(&text).await));
+                });
+            }
+            "Escape" => results.set(None),
+            _ => {}
+        })
+    };
+
+    let close = {
+        let results = results.clone();
+        Callback::from(move |_: MouseEvent| results.set(None))
+    };
+
+    let result_list = match &*results {
+        None => html! {},
+        Some(Err(error)) => html! {
+            <div class="search-results">
+                <p class="search-empty">{ error.clone() }</p>
+            </div>
+        },
+        Some(Ok(found)) => html! {
+            <div class="search-results">
+                <div class="search-results-header">
+                    <span>{ format!("{} resultado(s)", found.len()) }</span>
+                    <button type="button" onclick={close}>{ "✕" }</button>
+                </div>
+                if found.is_empty() {
+                    <p class="search-empty">{ "Nada encontrado." }</p>
+                }
+                { for found.iter().map(|result| {
+                    let onclick = {
+                        let on_select = props.on_select.clone();
+                        let results = results.clone();
+                        let result = result.clone();
+                        Callback::from(move |_: MouseEvent| {
+                            on_select.emit(result.clone());
+                            results.set(None);
+                        })
+                    };
+                    html! {
+                        <button type="button" class="search-result" {onclick}>
+                            <span class="search-result-kind">{ kind_label(&result.kind) }</span>
+                            <span class="search-result-title">{ result.title.clone() }</span>
+                            <span class="search-result-snippet">{ render_snippet(&result.snippet) }</span>
+                        </button>
+                    }
+                })}
+            </div>
+        },
+    };
+
+    html! {
+        <div class="taskbar-search">
+            { result_list }
+            <input
+                type="search"
+                placeholder="Pesquisar..."
+                value={(*query).clone()}
+                oninput={on_input}
+                onkeydown={on_keydown}
+            />
+        </div>
+    }
+}
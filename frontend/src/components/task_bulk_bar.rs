@@ -0,0 +1,139 @@
+use yew::{function_component, html, use_state, Callback, Event, Html, InputEvent, MouseEvent, Properties, TargetCast, UseStateHandle};
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use wasm_bindgen_futures::spawn_local;
+use crate::components::category_picker::CategoryPicker;
+use crate::services::tasks::{bulk_update, BulkAction};
+
+#[derive(Properties, PartialEq)]
+pub struct TaskBulkBarProps {
+    /// Tarefas selecionadas; em tarefas repetidas, a ação vale para a série.
+    pub task_ids: Vec<u32>,
+    /// Chamado depois que a ação foi gravada.
+    pub on_applied: Callback<()>,
+    pub on_select_day: Callback<()>,
+    pub on_clear: Callback<()>,
+}
+
+fn alert(message: &str) {
+    let _ = web_sys::window().unwrap().alert_with_message(message);
+}
+
+/// Envia a ação às tarefas selecionadas, ignorando novos cliques até a resposta.
+fn run(task_ids: Vec<u32>, action: BulkAction, busy: UseStateHandle<bool>, on_applied: Callback<()>) {
+    if *busy {
+        return;
+    }
+    busy.set(true);
+    spawn_local(async move {
+        match bulk_update(&task_ids, &action).await {
+            Ok(()) => on_applied.emit(()),
+            Err(error) => alert(&error),
+        }
+        busy.set(false);
+    });
+}
+
+/// Ações aplicadas de uma vez às tarefas selecionadas na lista do calendário.
+#[function_component(TaskBulkBar)]
+pub fn task_bulk_bar(props: &TaskBulkBarProps) -> Html {
+    let category = use_state(String::new);
+    let days = use_state(|| "1".to_string());
+    let busy = use_state(|| false);
+
+    let on_status = {
+        let task_ids = props.task_ids.clone();
+        let busy = busy.clone();
+        let on_applied = props.on_applied.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            let status = select.value();
+            select.set_value("");
+            if !status.is_empty() {
+                run(task_ids.clone(), BulkAction::SetStatus { status }, busy.clone(), on_applied.clone());
+            }
+        })
+    };
+
+    let on_category_change = {
+        let category = category.clone();
+        Callback::from(move |value: String| category.set(value))
+    };
+
+    let on_apply_category = {
+        let task_ids = props.task_ids.clone();
+        let category = category.clone();
+        let busy = busy.clone();
+        let on_applied = props.on_applied.clone();
+        Callback::from(move |_: MouseEvent| {
+            if category.is_empty() {
+                alert("Escolha uma categoria.");
+                return;
+            }
+            let action = BulkAction::SetCategory { category: (*category).clone() };
+            run(task_ids.clone(), action, busy.clone(), on_applied.clone());
+        })
+    };
+
+    let on_days_input = {
+        let days = days.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            days.set(input.value());
+        })
+    };
+
+    let on_shift = {
+        let task_ids = props.task_ids.clone();
+        let days = days.clone();
+        let busy = busy.clone();
+        let on_applied = props.on_applied.clone();
+        Callback::from(move |_: MouseEvent| match days.trim().parse::<i32>() {
+            Ok(days) if days != 0 => run(task_ids.clone(), BulkAction::Shift { days }, busy.clone(), on_applied.clone()),
+            _ => alert("Informe um número de dias diferente de zero."),
+        })
+    };
+
+    let on_delete = {
+        let task_ids = props.task_ids.clone();
+        let busy = busy.clone();
+        let on_applied = props.on_applied.clone();
+        Callback::from(move |_: MouseEvent| {
+            let confirmed = web_sys::window()
+                .unwrap()
+                .confirm_with_message(&format!("Excluir {} tarefa(s)? Tarefas repetidas são excluídas por inteiro.", task_ids.len()))
+                .unwrap_or(false);
+            if confirmed {
+                run(task_ids.clone(), BulkAction::Delete, busy.clone(), on_applied.clone());
+            }
+        })
+    };
+
+    html! {
+        <div class="task-bulk-bar">
+            <div class="task-bulk-header">
+                <span>{ format!("{} selecionada(s)", props.task_ids.len()) }</span>
+                <button type="button" onclick={props.on_select_day.reform(|_: MouseEvent| ())}>{ "Selecionar o dia" }</button>
+                <button type="button" onclick={props.on_clear.reform(|_: MouseEvent| ())}>{ "Limpar" }</button>
+            </div>
+            <div class="task-bulk-actions">
+                <select class="win98-select" disabled={*busy} onchange={on_status}>
+                    <option value="" selected=true>{ "Mudar status…" }</option>
+                    <option value="Pendente">{ "Pendente" }</option>
+                    <option value="Adiada">{ "Adiada" }</option>
+                    <option value="Concluída">{ "Concluída" }</option>
+                </select>
+                <CategoryPicker value={(*category).clone()} on_change={on_category_change} />
+                <button type="button" disabled={*busy} onclick={on_apply_category}>{ "Aplicar categoria" }</button>
+                <input
+                    type="number"
+                    class="task-bulk-days"
+                    title="Dias (negativo para antecipar)"
+                    value={(*days).clone()}
+                    oninput={on_days_input}
+                />
+                <button type="button" disabled={*busy} onclick={on_shift}>{ "Mover dias" }</button>
+                <button type="button" class="delete-button" disabled={*busy} onclick={on_delete}>{ "Excluir" }</button>
+            </div>
+        </div>
+    }
+}
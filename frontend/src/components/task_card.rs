@@ -1,9 +1,35 @@
-use yew::{function_component, html, Html, Properties, use_state, Callback, MouseEvent, InputEvent, TargetCast};
+use yew::{function_component, html, use_effect_with, use_state, Callback, Event, Html, InputEvent, MouseEvent, Properties, TargetCast, UseStateHandle, classes};
+use wasm_bindgen_futures::spawn_local;
 use web_sys::HtmlInputElement;
+use crate::services::checklist_service::{add_item, delete_item, get_checklist, set_auto_complete, update_item, ChecklistItemDto, ChecklistItemUpdateDto};
+use crate::types::checklist::{ChecklistItem, ChecklistProgress};
 use crate::types::TaskDuration;
 
-fn format_time_display(time: &str, duration: &TaskDuration) -> String {
+/// Recarrega os itens do checklist e avisa o calendário, que atualiza o
+/// progresso e o status da tarefa (que pode ter sido concluída).
+async fn reload_checklist(task_id: u32, items: UseStateHandle<Vec<ChecklistItem>>, on_change: Option<Callback<()>>) {
+    match get_checklist(task_id).await {
+        Ok(loaded) => items.set(loaded),
+        Err(e) => web_sys::console::log_1(&e.into()),
+    }
+    if let Some(callback) = on_change {
+        callback.emit(());
+    }
+}
+
+fn alert(message: &str) {
+    let _ = web_sys::window().unwrap().alert_with_message(message);
+}
+
+fn stop_click(e: MouseEvent) {
+    e.stop_propagation();
+}
+
+fn format_time_display(time: &str, end_time: &str, duration: &TaskDuration) -> String {
     match duration {
+        TaskDuration::DiaInteiro if end_time.is_empty() => "Dia inteiro".to_string(),
+        TaskDuration::DiaInteiro => format!("Dia inteiro, até {}", end_time),
+        TaskDuration::Personalizado => format!("{} - {}", time, end_time),
         TaskDuration::Manha => "Manhã".to_string(),
         TaskDuration::Tarde => "Tarde".to_string(),
         TaskDuration::Noite => "Noite".to_string(),
@@ -36,6 +62,16 @@ fn format_time_display(time: &str, duration: &TaskDuration) -> String {
     }
 }
 
+/// Tempo registrado no formato `1h 05min` ou `12min`.
+fn format_tracked(seconds: i64) -> String {
+    let minutes = seconds / 60;
+    if minutes >= 60 {
+        format!("{}h {:02}min", minutes / 60, minutes % 60)
+    } else {
+        format!("{}min", minutes)
+    }
+}
+
 #[derive(Properties, PartialEq)]
 pub struct TaskCardProps {
     pub id: u32,
@@ -45,10 +81,48 @@ pub struct TaskCardProps {
     pub date: String,
     pub time: String,
     pub duration: TaskDuration,
+    /// Fim exibido nas tarefas `Personalizado` e nas de vários dias.
+    #[prop_or_default]
+    pub end_time: String,
     pub status: String,
+    #[prop_or_default]
+    pub recurring: bool,
     pub on_task_delete: Callback<u32>,
-    pub on_task_update: Option<Callback<(u32, String, String)>>, // (id, title, description)
+    /// Início no formato de `datetime-local`; quando preenchido, a edição permite remarcar a tarefa.
+    #[prop_or_default]
+    pub begin: String,
+    pub on_task_update: Option<Callback<(u32, String, String, String)>>, // (id, title, description, begin)
     pub on_status_update: Option<Callback<(u32, String)>>, // (id, new_status)
+    #[prop_or_default]
+    pub checklist: ChecklistProgress,
+    #[prop_or_default]
+    pub checklist_auto_complete: bool,
+    /// Chamado depois de qualquer alteração no checklist.
+    #[prop_or_default]
+    pub on_checklist_change: Option<Callback<()>>,
+    /// Se a tarefa tem bloqueadores pendentes e ainda não pode ser concluída.
+    #[prop_or_default]
+    pub blocked: bool,
+    /// Cor da categoria, usada na borda do cartão.
+    #[prop_or_default]
+    pub color: Option<String>,
+    /// Destaca o cartão aberto pela busca.
+    #[prop_or_default]
+    pub highlighted: bool,
+    /// Com o callback, o cartão mostra uma caixa para selecioná-lo para as ações em lote.
+    #[prop_or_default]
+    pub on_select: Option<Callback<u32>>,
+    #[prop_or_default]
+    pub selected: bool,
+    /// Tempo registrado na tarefa, em segundos.
+    #[prop_or_default]
+    pub tracked_seconds: i64,
+    /// Se o cronômetro ligado é o desta tarefa.
+    #[prop_or_default]
+    pub timer_running: bool,
+    /// Com o callback, o cartão mostra o botão que liga ou para o cronômetro.
+    #[prop_or_default]
+    pub on_timer_toggle: Option<Callback<u32>>,
 }
 
 #[function_component(TaskCard)]
@@ -57,6 +131,23 @@ pub fn task_card(props: &TaskCardProps) -> Html {
     let is_editing = use_state(|| false);
     let edit_title = use_state(|| props.title.clone());
     let edit_description = use_state(|| props.description.clone());
+    let edit_begin = use_state(|| props.begin.clone());
+    let checklist_items = use_state(Vec::<ChecklistItem>::new);
+    let new_item_title = use_state(String::new);
+
+    // Os itens só são carregados quando o cartão é aberto.
+    {
+        let checklist_items = checklist_items.clone();
+        use_effect_with((*show_info, props.id), move |(open, task_id)| {
+            if *open {
+                let task_id = *task_id;
+                spawn_local(async move {
+                    reload_checklist(task_id, checklist_items, None).await;
+                });
+            }
+            || ()
+        });
+    }
 
     let toggle_info = {
         let show_info = show_info.clone();
@@ -72,14 +163,17 @@ pub fn task_card(props: &TaskCardProps) -> Html {
         let is_editing = is_editing.clone();
         let edit_title = edit_title.clone();
         let edit_description = edit_description.clone();
+        let edit_begin = edit_begin.clone();
         let props_title = props.title.clone();
         let props_description = props.description.clone();
+        let props_begin = props.begin.clone();
         Callback::from(move |e: MouseEvent| {
             e.prevent_default();
             e.stop_propagation();
             is_editing.set(true);
             edit_title.set(props_title.clone());
             edit_description.set(props_description.clone());
+            edit_begin.set(props_begin.clone());
         })
     };
 
@@ -87,6 +181,7 @@ pub fn task_card(props: &TaskCardProps) -> Html {
         let is_editing = is_editing.clone();
         let edit_title = edit_title.clone();
         let edit_description = edit_description.clone();
+        let edit_begin = edit_begin.clone();
         let on_task_update = props.on_task_update.clone();
         let task_id = props.id;
         Callback::from(move |e: MouseEvent| {
@@ -94,7 +189,7 @@ pub fn task_card(props: &TaskCardProps) -> Html {
             e.stop_propagation();
             
             if let Some(callback) = &on_task_update {
-                callback.emit((task_id, (*edit_title).clone(), (*edit_description).clone()));
+                callback.emit((task_id, (*edit_title).clone(), (*edit_description).clone(), (*edit_begin).clone()));
             }
             is_editing.set(false);
         })
@@ -131,6 +226,14 @@ pub fn task_card(props: &TaskCardProps) -> Html {
         })
     };
 
+    let on_begin_input = {
+        let edit_begin = edit_begin.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            edit_begin.set(input.value());
+        })
+    };
+
     let on_status_toggle = {
         let on_status_update = props.on_status_update.clone();
         let task_id = props.id;
@@ -162,9 +265,194 @@ pub fn task_card(props: &TaskCardProps) -> Html {
         })
     };
 
+    let on_timer_click = {
+        let on_timer_toggle = props.on_timer_toggle.clone();
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            e.stop_propagation();
+            if let Some(callback) = &on_timer_toggle {
+                callback.emit(task_id);
+            }
+        })
+    };
+
+    let on_item_toggle = {
+        let checklist_items = checklist_items.clone();
+        let on_change = props.on_checklist_change.clone();
+        Callback::from(move |(item_id, done): (u32, bool)| {
+            let checklist_items = checklist_items.clone();
+            let on_change = on_change.clone();
+            spawn_local(async move {
+                let dto = ChecklistItemUpdateDto { done: Some(done), ..Default::default() };
+                if let Err(e) = update_item(task_id, item_id, dto).await {
+                    alert(&e);
+                }
+                reload_checklist(task_id, checklist_items, on_change).await;
+            });
+        })
+    };
+
+    let on_item_move = {
+        let checklist_items = checklist_items.clone();
+        let on_change = props.on_checklist_change.clone();
+        Callback::from(move |(item_id, position): (u32, u32)| {
+            let checklist_items = checklist_items.clone();
+            let on_change = on_change.clone();
+            spawn_local(async move {
+                let dto = ChecklistItemUpdateDto { position: Some(position), ..Default::default() };
+                if let Err(e) = update_item(task_id, item_id, dto).await {
+                    alert(&e);
+                }
+                reload_checklist(task_id, checklist_items, on_change).await;
+            });
+        })
+    };
+
+    let on_item_delete = {
+        let checklist_items = checklist_items.clone();
+        let on_change = props.on_checklist_change.clone();
+        Callback::from(move |item_id: u32| {
+            let checklist_items = checklist_items.clone();
+            let on_change = on_change.clone();
+            spawn_local(async move {
+                if let Err(e) = delete_item(task_id, item_id).await {
+                    alert(&e);
+                }
+                reload_checklist(task_id, checklist_items, on_change).await;
+            });
+        })
+    };
+
+    let on_new_item_input = {
+        let new_item_title = new_item_title.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            new_item_title.set(input.value());
+        })
+    };
+
+    let on_add_item = {
+        let checklist_items = checklist_items.clone();
+        let new_item_title = new_item_title.clone();
+        let on_change = props.on_checklist_change.clone();
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            e.stop_propagation();
+            let title = new_item_title.trim().to_string();
+            if title.is_empty() {
+                return;
+            }
+            let checklist_items = checklist_items.clone();
+            let new_item_title = new_item_title.clone();
+            let on_change = on_change.clone();
+            spawn_local(async move {
+                match add_item(task_id, ChecklistItemDto { title }).await {
+                    Ok(_) => new_item_title.set(String::new()),
+                    Err(e) => alert(&e),
+                }
+                reload_checklist(task_id, checklist_items, on_change).await;
+            });
+        })
+    };
+
+    let on_auto_complete_change = {
+        let on_change = props.on_checklist_change.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let enabled = input.checked();
+            let on_change = on_change.clone();
+            spawn_local(async move {
+                if let Err(e) = set_auto_complete(task_id, enabled).await {
+                    alert(&e);
+                }
+                if let Some(callback) = on_change {
+                    callback.emit(());
+                }
+            });
+        })
+    };
+
+    let item_count = checklist_items.len() as u32;
+    let checklist_section = html! {
+        <div class="task-checklist" onclick={Callback::from(stop_click)}>
+            <div class="task-checklist-header">
+                <span class="status-label">{ "Checklist" }</span>
+                if props.checklist.total > 0 {
+                    <span class="task-checklist-count">
+                        { format!("{}/{}", props.checklist.done, props.checklist.total) }
+                    </span>
+                }
+            </div>
+            if props.checklist.total > 0 {
+                <div class="task-checklist-progress">
+                    <div
+                        class="task-checklist-progress-fill"
+                        style={format!("width: {:.0}%", props.checklist.ratio * 100.0)}
+                    />
+                </div>
+            }
+            <ul class="task-checklist-items">
+                { for checklist_items.iter().enumerate().map(|(index, item)| {
+                    let index = index as u32;
+                    let item_id = item.id;
+                    let done = item.done;
+                    let on_toggle = on_item_toggle.reform(move |_: Event| (item_id, !done));
+                    let on_up = on_item_move.reform(move |_: MouseEvent| (item_id, index.saturating_sub(1)));
+                    let on_down = on_item_move.reform(move |_: MouseEvent| (item_id, index + 1));
+                    let on_remove = on_item_delete.reform(move |_: MouseEvent| item_id);
+                    html! {
+                        <li class={if done { "task-checklist-item done" } else { "task-checklist-item" }} key={item_id}>
+                            <label>
+                                <input type="checkbox" checked={done} onchange={on_toggle} />
+                                { &item.title }
+                            </label>
+                            <span class="task-checklist-item-actions">
+                                <button disabled={index == 0} onclick={on_up} title="Subir">{ "↑" }</button>
+                                <button disabled={index + 1 == item_count} onclick={on_down} title="Descer">{ "↓" }</button>
+                                <button onclick={on_remove} title="Remover">{ "✕" }</button>
+                            </span>
+                        </li>
+                    }
+                }) }
+            </ul>
+            <div class="task-checklist-new">
+                <input
+                    type="text"
+                    placeholder="Novo item"
+                    value={(*new_item_title).clone()}
+                    oninput={on_new_item_input}
+                />
+                <button onclick={on_add_item}>{ "Adicionar" }</button>
+            </div>
+            if !props.recurring {
+                <label class="task-checklist-auto">
+                    <input type="checkbox" checked={props.checklist_auto_complete} onchange={on_auto_complete_change} />
+                    { "Concluir a tarefa ao marcar todos os itens" }
+                </label>
+            }
+        </div>
+    };
+
     html! {
-        <div class="task-card" onclick={toggle_info}>
+        <div
+            class={classes!("task-card", props.highlighted.then_some("search-highlight"))}
+            style={props.color.as_ref().map(|color| format!("border-left: 4px solid {}", color))}
+            onclick={toggle_info}
+        >
             <div class="task-header">
+                if let (Some(on_select), false) = (props.on_select.clone(), *is_editing) {
+                    <input
+                        class="task-select"
+                        type="checkbox"
+                        title="Selecionar para ações em lote"
+                        checked={props.selected}
+                        onclick={Callback::from(stop_click)}
+                        onchange={
+                            let id = props.id;
+                            Callback::from(move |_: Event| on_select.emit(id))
+                        }
+                    />
+                }
                 if *is_editing {
                     <input 
                         class="task-title-input"
@@ -179,6 +467,9 @@ pub fn task_card(props: &TaskCardProps) -> Html {
                 } else {
                     <h3 class="task-title">{ &props.title }</h3>
                 }
+                if props.blocked && !*is_editing {
+                    <span class="task-blocked-badge" title="Bloqueada por tarefas pendentes">{ "Bloqueada" }</span>
+                }
                 if *show_info {
                     <div class="task-actions">
                         if *is_editing {
@@ -203,6 +494,18 @@ pub fn task_card(props: &TaskCardProps) -> Html {
                                 e.stop_propagation();
                             })}
                         />
+                        if !props.begin.is_empty() {
+                            <input
+                                class="task-title-input"
+                                type="datetime-local"
+                                value={(*edit_begin).clone()}
+                                oninput={on_begin_input}
+                                onclick={Callback::from(|e: MouseEvent| {
+                                    e.prevent_default();
+                                    e.stop_propagation();
+                                })}
+                            />
+                        }
                     } else {
                         <p class="task-description">{ &props.description }</p>
                     }
@@ -213,16 +516,39 @@ pub fn task_card(props: &TaskCardProps) -> Html {
                 }
                 <div class="task-datetime">
                     <span class="task-date">{ format!("Data: {}", &props.date) }</span>
-                    <span class="task-time">{ format_time_display(&props.time, &props.duration) }</span>
+                    <span class="task-time">{ format_time_display(&props.time, &props.end_time, &props.duration) }</span>
+                    if props.recurring {
+                        <span class="task-recurring" title="Tarefa repetida">{ "↻" }</span>
+                    }
+                    if props.timer_running {
+                        <span class="task-timer running" title="Cronômetro ligado">{ "⏱ em andamento" }</span>
+                    } else if props.tracked_seconds >= 60 {
+                        <span class="task-timer" title="Tempo registrado">{ format!("⏱ {}", format_tracked(props.tracked_seconds)) }</span>
+                    }
+                    if !*show_info && props.checklist.total > 0 {
+                        <span class="task-checklist-count" title="Itens do checklist">
+                            { format!("☑ {}/{}", props.checklist.done, props.checklist.total) }
+                        </span>
+                    }
                 </div>
+                if *show_info && !*is_editing {
+                    { checklist_section }
+                }
                 if *show_info {
                     <div class="task-status-actions">
                         <button 
                             class={if props.status.to_lowercase() == "pendente" { "complete-button" } else { "incomplete-button" }}
+                            disabled={props.blocked && props.status.to_lowercase() == "pendente"}
+                            title={if props.blocked { "Conclua primeiro as tarefas que bloqueiam esta" } else { "" }}
                             onclick={on_status_toggle}
                         >
                             { if props.status.to_lowercase() == "pendente" { "Completar" } else { "Descompletar" } }
                         </button>
+                        if props.on_timer_toggle.is_some() {
+                            <button class="timer-button" onclick={on_timer_click}>
+                                { if props.timer_running { "Parar cronômetro" } else { "Iniciar cronômetro" } }
+                            </button>
+                        }
                     </div>
                 }
             </div>
@@ -1,8 +1,20 @@
-use yew::{function_component, html, use_state, Callback, Html, InputEvent, MouseEvent, Properties, TargetCast, classes};
-use web_sys::{HtmlInputElement, HtmlTextAreaElement};
+use yew::{function_component, html, use_state, Callback, Event, Html, InputEvent, MouseEvent, Properties, TargetCast, classes};
+use web_sys::{HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement};
 use chrono::NaiveDate;
 use crate::types::{Task, TaskDuration};
-use crate::services::tasks::{TaskDto, create_task};
+use crate::services::tasks::{RecurrenceDto, TaskDto, create_task};
+use crate::components::category_picker::CategoryPicker;
+
+/// Dias da semana para a repetição semanal: (código do RRULE, rótulo).
+const WEEKDAYS: [(&str, &str); 7] = [
+    ("MO", "Seg"),
+    ("TU", "Ter"),
+    ("WE", "Qua"),
+    ("TH", "Qui"),
+    ("FR", "Sex"),
+    ("SA", "Sáb"),
+    ("SU", "Dom"),
+];
 use wasm_bindgen_futures::spawn_local;
 
 #[derive(Properties, PartialEq)]
@@ -25,6 +37,14 @@ pub fn task_form(props: &TaskFormProps) -> Html {
     let task_minute = use_state(|| 0u32);
     let task_type = use_state(|| TaskDuration::default());
     let form_status = use_state(|| String::new()); // "success", "error", or ""
+    let repeat_frequency = use_state(String::new); // "", "daily", "weekly" ou "monthly"
+    let repeat_interval = use_state(|| 1u32);
+    let repeat_weekdays = use_state(Vec::<String>::new);
+    let repeat_end = use_state(|| "never".to_string()); // "never", "until" ou "count"
+    let repeat_until = use_state(String::new);
+    let repeat_count = use_state(|| 10u32);
+    let end_date = use_state(String::new); // vazio: mesmo dia do início
+    let end_time = use_state(|| "10:00".to_string());
 
     let begin_date = format!("{}T{:02}:{:02}", 
         props.selected_date.format("%Y-%m-%d"), 
@@ -89,6 +109,16 @@ pub fn task_form(props: &TaskFormProps) -> Html {
         let on_close = props.on_close.clone();
         let on_task_created = props.on_task_created.clone();
         let begin_date = begin_date.clone();
+        let repeat_frequency = repeat_frequency.clone();
+        let complete_date = complete_date(*task_type, props.selected_date, &end_date, &end_time);
+        let recurrence = recurrence_dto(
+            &repeat_frequency,
+            *repeat_interval,
+            &repeat_weekdays,
+            &repeat_end,
+            &repeat_until,
+            *repeat_count,
+        );
 
         Callback::from(move |e: MouseEvent| {
             e.prevent_default();
@@ -100,6 +130,9 @@ pub fn task_form(props: &TaskFormProps) -> Html {
             let on_close = on_close.clone();
             let on_task_created = on_task_created.clone();
             let begin_date = begin_date.clone();
+            let recurrence = recurrence.clone();
+            let repeat_frequency = repeat_frequency.clone();
+            let complete_date = complete_date;
             
             spawn_local(async move {
                 let begin_date_parsed = chrono::NaiveDateTime::parse_from_str(&begin_date, "%Y-%m-%dT%H:%M")
@@ -113,6 +146,10 @@ pub fn task_form(props: &TaskFormProps) -> Html {
                     description: (*task_description).clone(),
                     begin_date: begin_date_parsed,
                     task_type: task_type.value().to_string(),
+                    complete_date,
+                    duration_minutes: None,
+                    all_day: *task_type == TaskDuration::DiaInteiro,
+                    recurrence,
                 };
                 
                 let result = create_task(&task_info).await;
@@ -131,6 +168,7 @@ pub fn task_form(props: &TaskFormProps) -> Html {
                         let task_type = task_type.clone();
                         let form_status = form_status.clone();
                         let on_close = on_close.clone();
+                        let repeat_frequency = repeat_frequency.clone();
                         
                         wasm_bindgen_futures::spawn_local(async move {
                             gloo_timers::future::TimeoutFuture::new(500).await;
@@ -138,6 +176,7 @@ pub fn task_form(props: &TaskFormProps) -> Html {
                             task_category.set(String::new());
                             task_description.set(String::new());
                             task_type.set(TaskDuration::default());
+                            repeat_frequency.set(String::new());
                             form_status.set(String::new());
                             
                             if let Some(callback) = &on_close {
@@ -145,6 +184,16 @@ pub fn task_form(props: &TaskFormProps) -> Html {
                             }
                         });
                     },
+                    crate::services::tasks::TaskResult::Conflict(message) => {
+                        form_status.set("error".to_string());
+                        let _ = web_sys::window().unwrap().alert_with_message(&message);
+
+                        let form_status = form_status.clone();
+                        wasm_bindgen_futures::spawn_local(async move {
+                            gloo_timers::future::TimeoutFuture::new(3000).await;
+                            form_status.set(String::new());
+                        });
+                    },
                     crate::services::tasks::TaskResult::InvalidFields => {
                         web_sys::console::log_1(&"Falha ao criar tarefa: Campos inválidos".into());
                         form_status.set("error".to_string());
@@ -181,10 +230,7 @@ pub fn task_form(props: &TaskFormProps) -> Html {
 
     let on_category_change = {
         let task_category = task_category.clone();
-        Callback::from(move |e: InputEvent| {
-            let input: HtmlInputElement = e.target_unchecked_into();
-            task_category.set(input.value());
-        })
+        Callback::from(move |category: String| task_category.set(category))
     };
 
     let on_description_change = {
@@ -202,6 +248,68 @@ pub fn task_form(props: &TaskFormProps) -> Html {
         })
     };
 
+    let on_frequency_change = {
+        let repeat_frequency = repeat_frequency.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            repeat_frequency.set(select.value());
+        })
+    };
+
+    let on_interval_change = {
+        let repeat_interval = repeat_interval.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            repeat_interval.set(input.value().parse::<u32>().unwrap_or(1).clamp(1, 365));
+        })
+    };
+
+    let on_end_change = {
+        let repeat_end = repeat_end.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            repeat_end.set(select.value());
+        })
+    };
+
+    let on_until_change = {
+        let repeat_until = repeat_until.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            repeat_until.set(input.value());
+        })
+    };
+
+    let on_count_change = {
+        let repeat_count = repeat_count.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            repeat_count.set(input.value().parse::<u32>().unwrap_or(1).clamp(1, 1000));
+        })
+    };
+
+    let on_end_date_change = {
+        let end_date = end_date.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            end_date.set(input.value());
+        })
+    };
+
+    let on_end_time_change = {
+        let end_time = end_time.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            end_time.set(input.value());
+        })
+    };
+
+    let interval_unit = match repeat_frequency.as_str() {
+        "weekly" => "semana(s)",
+        "monthly" => "mês(es)",
+        _ => "dia(s)",
+    };
+
     let on_close = {
         let on_close = props.on_close.clone();
         Callback::from(move |_: MouseEvent| {
@@ -239,34 +347,47 @@ pub fn task_form(props: &TaskFormProps) -> Html {
                         }
 
                         // Time input - left column
-                        <div>
-                            <label>{ "Hora:" }</label>
-                            <div class="time-input">
-                                <div class="event-popup-time">
-                                    <input 
-                                        type="number" 
-                                        name="hours" 
-                                        min="0" 
-                                        max="23" 
-                                        class="hour-input" 
-                                        placeholder="HH" 
-                                        value={format!("{:02}", *task_hour)}
-                                        oninput={on_hour_change}
-                                    />
-                                    { ":" }
-                                    <input 
-                                        type="number" 
-                                        name="minutes" 
-                                        min="0" 
-                                        max="59" 
-                                        class="minute-input" 
-                                        placeholder="MM" 
-                                        value={format!("{:02}", *task_minute)}
-                                        oninput={on_minute_change}
-                                    />
+                        if *task_type == TaskDuration::DiaInteiro {
+                            <div>
+                                <label for="end_date">{ "Até (opcional):" }</label>
+                                <input
+                                    type="date"
+                                    id="end_date"
+                                    min={props.selected_date.format("%Y-%m-%d").to_string()}
+                                    value={(*end_date).clone()}
+                                    oninput={on_end_date_change.clone()}
+                                />
+                            </div>
+                        } else {
+                            <div>
+                                <label>{ "Hora:" }</label>
+                                <div class="time-input">
+                                    <div class="event-popup-time">
+                                        <input 
+                                            type="number" 
+                                            name="hours" 
+                                            min="0" 
+                                            max="23" 
+                                            class="hour-input" 
+                                            placeholder="HH" 
+                                            value={format!("{:02}", *task_hour)}
+                                            oninput={on_hour_change}
+                                        />
+                                        { ":" }
+                                        <input 
+                                            type="number" 
+                                            name="minutes" 
+                                            min="0" 
+                                            max="59" 
+                                            class="minute-input" 
+                                            placeholder="MM" 
+                                            value={format!("{:02}", *task_minute)}
+                                            oninput={on_minute_change}
+                                        />
+                                    </div>
                                 </div>
                             </div>
-                        </div>
+                        }
 
                         <div>
                             <label for="title">{ "Título:" }</label>
@@ -285,15 +406,10 @@ pub fn task_form(props: &TaskFormProps) -> Html {
                         // Category - left column
                         <div>
                             <label for="category">{ "Categoria:" }</label>
-                            <input 
-                                type="text" 
-                                id="category" 
-                                name="category" 
-                                minlength="5" 
-                                required=true 
-                                placeholder="Digite a categoria"
+                            <CategoryPicker
                                 value={(*task_category).clone()}
-                                oninput={on_category_change}
+                                on_change={on_category_change}
+                                required=true
                             />
                         </div>
 
@@ -307,6 +423,29 @@ pub fn task_form(props: &TaskFormProps) -> Html {
                             />
                         </div>
 
+                        // Fim livre - full width
+                        if *task_type == TaskDuration::Personalizado {
+                            <div class="full-width task-end">
+                                <label for="end_date">{ "Fim:" }</label>
+                                <div class="task-recurrence-row">
+                                    <input
+                                        type="date"
+                                        id="end_date"
+                                        class="task-recurrence-date"
+                                        min={props.selected_date.format("%Y-%m-%d").to_string()}
+                                        value={if end_date.is_empty() { props.selected_date.format("%Y-%m-%d").to_string() } else { (*end_date).clone() }}
+                                        oninput={on_end_date_change}
+                                    />
+                                    <input
+                                        type="time"
+                                        class="task-recurrence-date"
+                                        value={(*end_time).clone()}
+                                        oninput={on_end_time_change}
+                                    />
+                                </div>
+                            </div>
+                        }
+
                         <div class="full-width">
                             <label for="description">{ "Descrição:" }</label>
                             <textarea 
@@ -320,6 +459,83 @@ pub fn task_form(props: &TaskFormProps) -> Html {
                             ></textarea>
                         </div>
 
+                        // Repetição - full width
+                        <div class="full-width task-recurrence">
+                            <label for="frequency">{ "Repetir:" }</label>
+                            <select id="frequency" onchange={on_frequency_change}>
+                                <option value="" selected={repeat_frequency.is_empty()}>{ "Não repete" }</option>
+                                <option value="daily" selected={*repeat_frequency == "daily"}>{ "Diariamente" }</option>
+                                <option value="weekly" selected={*repeat_frequency == "weekly"}>{ "Semanalmente" }</option>
+                                <option value="monthly" selected={*repeat_frequency == "monthly"}>{ "Mensalmente" }</option>
+                            </select>
+                            if !repeat_frequency.is_empty() {
+                                <div class="task-recurrence-row">
+                                    <span>{ "A cada" }</span>
+                                    <input
+                                        type="number"
+                                        min="1"
+                                        max="365"
+                                        class="task-recurrence-number"
+                                        value={repeat_interval.to_string()}
+                                        oninput={on_interval_change}
+                                    />
+                                    <span>{ interval_unit }</span>
+                                </div>
+                                if *repeat_frequency == "weekly" {
+                                    <div class="task-recurrence-row">
+                                        { for WEEKDAYS.iter().map(|(code, label)| {
+                                            let checked = repeat_weekdays.iter().any(|day| day == code);
+                                            let onchange = {
+                                                let repeat_weekdays = repeat_weekdays.clone();
+                                                let code = code.to_string();
+                                                Callback::from(move |_: Event| {
+                                                    let mut days = (*repeat_weekdays).clone();
+                                                    if let Some(index) = days.iter().position(|day| *day == code) {
+                                                        days.remove(index);
+                                                    } else {
+                                                        days.push(code.clone());
+                                                    }
+                                                    repeat_weekdays.set(days);
+                                                })
+                                            };
+                                            html! {
+                                                <label class="task-recurrence-day" key={*code}>
+                                                    <input type="checkbox" {checked} {onchange} />
+                                                    { *label }
+                                                </label>
+                                            }
+                                        }) }
+                                    </div>
+                                }
+                                <div class="task-recurrence-row">
+                                    <span>{ "Termina:" }</span>
+                                    <select onchange={on_end_change}>
+                                        <option value="never" selected={*repeat_end == "never"}>{ "Nunca" }</option>
+                                        <option value="until" selected={*repeat_end == "until"}>{ "Em" }</option>
+                                        <option value="count" selected={*repeat_end == "count"}>{ "Após" }</option>
+                                    </select>
+                                    if *repeat_end == "until" {
+                                        <input
+                                            type="date"
+                                            class="task-recurrence-date"
+                                            value={(*repeat_until).clone()}
+                                            oninput={on_until_change}
+                                        />
+                                    } else if *repeat_end == "count" {
+                                        <input
+                                            type="number"
+                                            min="1"
+                                            max="1000"
+                                            class="task-recurrence-number"
+                                            value={repeat_count.to_string()}
+                                            oninput={on_count_change}
+                                        />
+                                        <span>{ "ocorrências" }</span>
+                                    }
+                                </div>
+                            }
+                        </div>
+
                         // Buttons - full width
                         <div class="button-container">
                             <button type="submit" onclick={on_create}>{"Criar"}</button>
@@ -339,6 +555,56 @@ pub fn task_form(props: &TaskFormProps) -> Html {
     }
 }
 
+/// Fim da tarefa para os tipos sem duração fixa: data e hora escolhidas em
+/// `Personalizado` e o último dia em `DiaInteiro`. Sem data, vale o dia do início.
+fn complete_date(
+    task_type: TaskDuration,
+    selected_date: NaiveDate,
+    end_date: &str,
+    end_time: &str,
+) -> Option<chrono::DateTime<chrono::Utc>> {
+    let date = NaiveDate::parse_from_str(end_date, "%Y-%m-%d").ok();
+    match task_type {
+        TaskDuration::Personalizado => {
+            let time = chrono::NaiveTime::parse_from_str(end_time, "%H:%M").ok()?;
+            Some(date.unwrap_or(selected_date).and_time(time).and_utc())
+        }
+        TaskDuration::DiaInteiro => date.and_then(|date| date.and_hms_opt(0, 0, 0)).map(|naive| naive.and_utc()),
+        _ => None,
+    }
+}
+
+/// Monta a regra de repetição a partir dos campos do formulário; `None` se a
+/// tarefa não se repete. A data final vale até o fim do dia escolhido.
+fn recurrence_dto(
+    frequency: &str,
+    interval: u32,
+    weekdays: &[String],
+    end: &str,
+    until: &str,
+    count: u32,
+) -> Option<RecurrenceDto> {
+    if frequency.is_empty() {
+        return None;
+    }
+
+    let until = match end {
+        "until" => NaiveDate::parse_from_str(until, "%Y-%m-%d")
+            .ok()
+            .and_then(|date| date.and_hms_opt(23, 59, 59))
+            .map(|naive| naive.and_utc()),
+        _ => None,
+    };
+
+    Some(RecurrenceDto {
+        frequency: frequency.to_string(),
+        interval,
+        weekdays: if frequency == "weekly" { weekdays.to_vec() } else { Vec::new() },
+        until,
+        count: (end == "count").then_some(count),
+    })
+}
+
 // Custom Windows 98 style dropdown component
 #[derive(Properties, PartialEq)]
 pub struct Windows98SelectProps {
@@ -1,6 +1,15 @@
+use chrono::{Local, Utc};
 use yew::{prelude::*, Properties, Callback};
+use yew_router::hooks::use_navigator;
 use gloo_timers::callback::Interval;
-use chrono::Local;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{HtmlAudioElement, HtmlSelectElement};
+use crate::services::{auth, pomodoro_service, profile_service, tasks};
+use crate::services::pomodoro_service::PomodoroState;
+use crate::types::task::Task;
+use crate::components::search_box::SearchBox;
+use crate::types::search::SearchResult;
+use crate::utils::routes::Route;
 
 #[derive(Properties, PartialEq)]
 pub struct TaskbarProps {
@@ -8,17 +17,32 @@ pub struct TaskbarProps {
     pub calendar_visible: bool,
     pub on_report_toggle: Callback<()>,
     pub report_visible: bool,
+    /// Chamado ao escolher um resultado da busca.
+    pub on_search_select: Callback<SearchResult>,
 }
 
 #[function_component(Taskbar)]
 pub fn taskbar(props: &TaskbarProps) -> Html {
-    let time = use_state(|| Local::now().format("%I:%M %p").to_string());
+    let profile = use_state(profile_service::cached_profile);
+    let time = use_state(|| profile_service::format_clock(profile.as_ref()));
+
+    {
+        let profile = profile.clone();
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                if let Ok(latest) = profile_service::get_profile().await {
+                    profile.set(Some(latest));
+                }
+            });
+        });
+    }
 
     {
         let time = time.clone();
+        let profile = (*profile).clone();
         use_effect(move || {
             let interval = Interval::new(1000, move || {
-                time.set(Local::now().format("%I:%M %p").to_string());
+                time.set(profile_service::format_clock(profile.as_ref()));
             });
 
             let a = || drop(interval);
@@ -26,9 +50,49 @@ pub fn taskbar(props: &TaskbarProps) -> Html {
         });
     }
 
+    let navigator = use_navigator().unwrap();
+    let start_menu_open = use_state(|| false);
+
+    let toggle_start_menu = {
+        let start_menu_open = start_menu_open.clone();
+        Callback::from(move |_: MouseEvent| {
+            start_menu_open.set(!*start_menu_open);
+        })
+    };
+
+    let on_account = {
+        let navigator = navigator.clone();
+        Callback::from(move |_: MouseEvent| {
+            navigator.push(&Route::Account);
+        })
+    };
+
+    let on_logout = {
+        let navigator = navigator.clone();
+        Callback::from(move |_: MouseEvent| {
+            let navigator = navigator.clone();
+            spawn_local(async move {
+                auth::logout().await;
+                navigator.push(&Route::Login);
+            });
+        })
+    };
+
     html! {
         <div class="taskbar">
-            <button class="start-button"></button>
+            {
+                if *start_menu_open {
+                    html! {
+                        <div class="start-menu">
+                            <button class="start-menu-item" onclick={on_account}>{ "Minha conta" }</button>
+                            <button class="start-menu-item" onclick={on_logout}>{ "Fazer logoff" }</button>
+                        </div>
+                    }
+                } else {
+                    html! {}
+                }
+            }
+            <button class="start-button" onclick={toggle_start_menu}></button>
             <div class="taskbar-divider"></div>
             <div class="quick-launch">
                 // Ícones de acesso rápido podem ser adicionados aqui
@@ -59,7 +123,9 @@ pub fn taskbar(props: &TaskbarProps) -> Html {
                     <span class="app-name">{ "Relatório" }</span>
                 </button>
             </div>
+            <SearchBox on_select={props.on_search_select.clone()} />
             <div class="system-tray">
+                <PomodoroTray />
                 <icon class ="tray-icon network-icon"></icon>
                 <icon class ="tray-icon sound-icon"></icon>
                 <span class="time">{ (*time).clone() }</span>
@@ -67,3 +133,197 @@ pub fn taskbar(props: &TaskbarProps) -> Html {
         </div>
     }
 }
+
+fn show_error(message: &str) {
+    let _ = web_sys::window().unwrap().alert_with_message(message);
+}
+
+fn kind_label(kind: &str) -> &'static str {
+    match kind {
+        "short_break" => "Pausa curta",
+        "long_break" => "Pausa longa",
+        _ => "Foco",
+    }
+}
+
+/// Pomodoro na bandeja: mostra a contagem regressiva da sessão atual e toca
+/// um som quando ela termina.
+#[function_component(PomodoroTray)]
+fn pomodoro_tray() -> Html {
+    let state = use_state(PomodoroState::default);
+    let now = use_state(Utc::now);
+    let menu_open = use_state(|| false);
+    let pending = use_state(Vec::<Task>::new);
+    let selected_task = use_state(|| None::<u32>);
+
+    let refresh = {
+        let state = state.clone();
+        Callback::from(move |_: ()| {
+            let state = state.clone();
+            spawn_local(async move {
+                if let Ok(latest) = pomodoro_service::get_state().await {
+                    state.set(latest);
+                }
+            });
+        })
+    };
+
+    {
+        let refresh = refresh.clone();
+        use_effect_with((), move |_| refresh.emit(()));
+    }
+
+    {
+        let now = now.clone();
+        use_effect_with((), move |_| {
+            let interval = Interval::new(1000, move || now.set(Utc::now()));
+            move || drop(interval)
+        });
+    }
+
+    // Ao fim do tempo, encerra a sessão (o backend a marca como concluída).
+    let expired = state
+        .active
+        .as_ref()
+        .filter(|session| session.ends_at <= *now)
+        .map(|session| session.id);
+    {
+        let refresh = refresh.clone();
+        use_effect_with(expired, move |expired| {
+            if expired.is_some() {
+                if let Ok(audio) = HtmlAudioElement::new_with_src("/Windows_XP_Startup.wav") {
+                    let _ = audio.play();
+                }
+                spawn_local(async move {
+                    let _ = pomodoro_service::stop_session().await;
+                    refresh.emit(());
+                });
+            }
+        });
+    }
+
+    let toggle_menu = {
+        let menu_open = menu_open.clone();
+        let pending = pending.clone();
+        let selected_task = selected_task.clone();
+        Callback::from(move |_: MouseEvent| {
+            if !*menu_open {
+                let pending = pending.clone();
+                let selected_task = selected_task.clone();
+                spawn_local(async move {
+                    let today = Local::now().date_naive();
+                    if let Ok(list) = tasks::get_task_occurrences(today, today).await {
+                        let list: Vec<Task> = list
+                            .into_iter()
+                            .filter(|task| task.status != "Concluída")
+                            .collect();
+                        selected_task.set(list.first().map(|task| task.id));
+                        pending.set(list);
+                    }
+                });
+            }
+            menu_open.set(!*menu_open);
+        })
+    };
+
+    let on_task_change = {
+        let selected_task = selected_task.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            selected_task.set(select.value().parse().ok());
+        })
+    };
+
+    let start = |kind: &'static str| {
+        let refresh = refresh.clone();
+        let menu_open = menu_open.clone();
+        let selected_task = selected_task.clone();
+        Callback::from(move |_: MouseEvent| {
+            let task_id = if kind == "work" { *selected_task } else { None };
+            let refresh = refresh.clone();
+            menu_open.set(false);
+            spawn_local(async move {
+                if let Err(error) = pomodoro_service::start_session(kind, task_id).await {
+                    show_error(&error);
+                }
+                refresh.emit(());
+            });
+        })
+    };
+
+    let on_stop = {
+        let refresh = refresh.clone();
+        let menu_open = menu_open.clone();
+        Callback::from(move |_: MouseEvent| {
+            let refresh = refresh.clone();
+            menu_open.set(false);
+            spawn_local(async move {
+                if let Err(error) = pomodoro_service::stop_session().await {
+                    show_error(&error);
+                }
+                refresh.emit(());
+            });
+        })
+    };
+
+    let label = match &state.active {
+        Some(session) => {
+            let remaining = (session.ends_at - *now).num_seconds().max(0);
+            format!("{} {:02}:{:02}", kind_label(&session.kind), remaining / 60, remaining % 60)
+        }
+        None => format!("🍅 {}", state.completed_today),
+    };
+
+    html! {
+        <div class="pomodoro-tray">
+            {
+                if *menu_open {
+                    html! {
+                        <div class="pomodoro-menu">
+                            {
+                                if state.active.is_some() {
+                                    html! {
+                                        <button class="start-menu-item" onclick={on_stop}>{ "Parar sessão" }</button>
+                                    }
+                                } else {
+                                    html! {
+                                        <>
+                                            <select class="pomodoro-task" onchange={on_task_change}>
+                                                { for pending.iter().map(|task| html! {
+                                                    <option
+                                                        value={task.id.to_string()}
+                                                        selected={*selected_task == Some(task.id)}
+                                                    >{ &task.title }</option>
+                                                }) }
+                                            </select>
+                                            <button
+                                                class={if state.next_kind == "work" { "start-menu-item suggested" } else { "start-menu-item" }}
+                                                disabled={selected_task.is_none()}
+                                                onclick={start("work")}
+                                            >{ format!("Foco ({} min)", state.settings.work_minutes) }</button>
+                                            <button
+                                                class={if state.next_kind == "short_break" { "start-menu-item suggested" } else { "start-menu-item" }}
+                                                onclick={start("short_break")}
+                                            >{ format!("Pausa curta ({} min)", state.settings.short_break_minutes) }</button>
+                                            <button
+                                                class={if state.next_kind == "long_break" { "start-menu-item suggested" } else { "start-menu-item" }}
+                                                onclick={start("long_break")}
+                                            >{ format!("Pausa longa ({} min)", state.settings.long_break_minutes) }</button>
+                                        </>
+                                    }
+                                }
+                            }
+                        </div>
+                    }
+                } else {
+                    html! {}
+                }
+            }
+            <button
+                class={if state.active.is_some() { "pomodoro-button running" } else { "pomodoro-button" }}
+                title="Pomodoro"
+                onclick={toggle_menu}
+            >{ label }</button>
+        </div>
+    }
+}
@@ -0,0 +1,47 @@
+use gloo_timers::callback::Timeout;
+use yew::{function_component, html, use_effect_with, Callback, Html, MouseEvent, Properties};
+
+/// Tempo que o aviso fica na tela antes de sumir sozinho.
+const UNDO_TIMEOUT_MS: u32 = 8000;
+
+/// Item que acabou de ir para a lixeira.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DeletedItem {
+    /// `task`, `goal`, `reminder` ou `note`, como na lixeira do backend.
+    pub kind: &'static str,
+    pub id: i32,
+    pub title: String,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct UndoToastProps {
+    pub item: DeletedItem,
+    pub on_undo: Callback<DeletedItem>,
+    pub on_dismiss: Callback<()>,
+}
+
+/// Aviso exibido depois de uma exclusão, com o botão que restaura o item.
+#[function_component(UndoToast)]
+pub fn undo_toast(props: &UndoToastProps) -> Html {
+    // O prazo recomeça a cada nova exclusão.
+    {
+        let on_dismiss = props.on_dismiss.clone();
+        use_effect_with(props.item.clone(), move |_| {
+            let timeout = Timeout::new(UNDO_TIMEOUT_MS, move || on_dismiss.emit(()));
+            move || drop(timeout)
+        });
+    }
+
+    let on_undo = {
+        let item = props.item.clone();
+        props.on_undo.reform(move |_: MouseEvent| item.clone())
+    };
+
+    html! {
+        <div class="undo-toast" role="status">
+            <span>{ format!("\"{}\" foi para a lixeira.", props.item.title) }</span>
+            <button type="button" onclick={on_undo}>{ "Desfazer" }</button>
+            <button type="button" class="undo-toast-close" title="Fechar" onclick={props.on_dismiss.reform(|_: MouseEvent| ())}>{ "✕" }</button>
+        </div>
+    }
+}
@@ -0,0 +1,445 @@
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+use yew_router::hooks::use_navigator;
+
+use crate::services::auth::{self, AccountResult, TwoFactorEnrollment};
+use crate::services::profile_service;
+use crate::types::profile::{Profile, ProfileUpdate};
+use crate::utils::{routes::Route, validation};
+
+/// Mensagem exibida abaixo de cada formulário: (texto, é erro?).
+pub type Feedback = Option<(String, bool)>;
+
+pub fn input_callback(state: &UseStateHandle<String>) -> Callback<InputEvent> {
+    let state = state.clone();
+    Callback::from(move |e: InputEvent| {
+        let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+        state.set(input.value());
+    })
+}
+
+fn select_callback(state: &UseStateHandle<String>) -> Callback<Event> {
+    let state = state.clone();
+    Callback::from(move |e: Event| {
+        let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
+        state.set(select.value());
+    })
+}
+
+/// Campo opcional do perfil: vazio significa "não informado".
+fn optional_field(value: &str) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+const WEEK_DAYS: [&str; 7] = [
+    "Domingo",
+    "Segunda-feira",
+    "Terça-feira",
+    "Quarta-feira",
+    "Quinta-feira",
+    "Sexta-feira",
+    "Sábado",
+];
+
+pub fn feedback_html(feedback: &Feedback) -> Html {
+    match feedback {
+        Some((message, true)) => html! {
+            <div class="error-messages"><div>{ format!("- {}", message) }</div></div>
+        },
+        Some((message, false)) => html! {
+            <div class="account-success">{ message.clone() }</div>
+        },
+        None => html! {},
+    }
+}
+
+fn result_feedback(result: AccountResult, success: &str) -> Feedback {
+    result_feedback_with_conflict(result, success, "Nome de usuário já está em uso")
+}
+
+fn result_feedback_with_conflict(result: AccountResult, success: &str, conflict: &str) -> Feedback {
+    let message = match result {
+        AccountResult::Success => return Some((success.to_string(), false)),
+        AccountResult::InvalidFields => "Dados inválidos ou senha incorreta",
+        AccountResult::Conflict => conflict,
+        AccountResult::SessionExpired => "Sessão expirada, faça login novamente",
+        AccountResult::NetworkError => "Backend off do line",
+    };
+    Some((message.to_string(), true))
+}
+
+fn two_factor_result_feedback(result: AccountResult, success: &str) -> Feedback {
+    result_feedback_with_conflict(result, success, "O 2FA já está ativado")
+}
+
+#[function_component(Account)]
+pub fn account() -> Html {
+    let navigator = use_navigator().unwrap();
+
+    let display_name = use_state(String::new);
+    let email = use_state(String::new);
+    let timezone = use_state(|| "UTC".to_string());
+    let locale = use_state(|| "pt-BR".to_string());
+    let week_start_day = use_state(|| "1".to_string());
+    let use_24h_clock = use_state(|| false);
+    let profile_feedback = use_state(|| None::<(String, bool)>);
+
+    let load_profile = {
+        let display_name = display_name.clone();
+        let email = email.clone();
+        let timezone = timezone.clone();
+        let locale = locale.clone();
+        let week_start_day = week_start_day.clone();
+        let use_24h_clock = use_24h_clock.clone();
+        move |profile: Profile| {
+            display_name.set(profile.display_name.unwrap_or_default());
+            email.set(profile.email.unwrap_or_default());
+            timezone.set(profile.timezone);
+            locale.set(profile.locale);
+            week_start_day.set(profile.week_start_day.to_string());
+            use_24h_clock.set(profile.use_24h_clock);
+        }
+    };
+
+    {
+        let load_profile = load_profile.clone();
+        let profile_feedback = profile_feedback.clone();
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                match profile_service::get_profile().await {
+                    Ok(profile) => load_profile(profile),
+                    Err(result) => profile_feedback.set(result_feedback(result, "")),
+                }
+            });
+        });
+    }
+
+    let on_toggle_24h_clock = {
+        let use_24h_clock = use_24h_clock.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            use_24h_clock.set(input.checked());
+        })
+    };
+
+    let on_save_profile = {
+        let display_name = display_name.clone();
+        let email = email.clone();
+        let timezone = timezone.clone();
+        let locale = locale.clone();
+        let week_start_day = week_start_day.clone();
+        let use_24h_clock = use_24h_clock.clone();
+        let profile_feedback = profile_feedback.clone();
+        Callback::from(move |_: MouseEvent| {
+            let update = ProfileUpdate {
+                display_name: optional_field(&display_name),
+                email: optional_field(&email),
+                timezone: timezone.trim().to_string(),
+                locale: locale.trim().to_string(),
+                week_start_day: week_start_day.parse().unwrap_or(1),
+                use_24h_clock: *use_24h_clock,
+            };
+            let load_profile = load_profile.clone();
+            let profile_feedback = profile_feedback.clone();
+            spawn_local(async move {
+                match profile_service::update_profile(&update).await {
+                    Ok(profile) => {
+                        load_profile(profile);
+                        profile_feedback.set(Some(("Perfil salvo".to_string(), false)));
+                    }
+                    Err(result) => profile_feedback.set(result_feedback_with_conflict(
+                        result,
+                        "",
+                        "E-mail já está em uso",
+                    )),
+                }
+            });
+        })
+    };
+
+    let new_username = use_state(String::new);
+    let username_feedback = use_state(|| None::<(String, bool)>);
+
+    let current_password = use_state(String::new);
+    let new_password = use_state(String::new);
+    let confirm_password = use_state(String::new);
+    let password_feedback = use_state(|| None::<(String, bool)>);
+
+    let delete_password = use_state(String::new);
+    let delete_feedback = use_state(|| None::<(String, bool)>);
+
+    let enrollment = use_state(|| None::<TwoFactorEnrollment>);
+    let two_factor_code = use_state(String::new);
+    let recovery_codes = use_state(Vec::<String>::new);
+    let two_factor_password = use_state(String::new);
+    let two_factor_feedback = use_state(|| None::<(String, bool)>);
+
+    let on_enroll_two_factor = {
+        let enrollment = enrollment.clone();
+        let recovery_codes = recovery_codes.clone();
+        let two_factor_feedback = two_factor_feedback.clone();
+        Callback::from(move |_: MouseEvent| {
+            let enrollment = enrollment.clone();
+            let recovery_codes = recovery_codes.clone();
+            let two_factor_feedback = two_factor_feedback.clone();
+            spawn_local(async move {
+                match auth::enroll_two_factor().await {
+                    Ok(new_enrollment) => {
+                        recovery_codes.set(Vec::new());
+                        two_factor_feedback.set(None);
+                        enrollment.set(Some(new_enrollment));
+                    }
+                    Err(result) => two_factor_feedback.set(two_factor_result_feedback(result, "")),
+                }
+            });
+        })
+    };
+
+    let on_confirm_two_factor = {
+        let enrollment = enrollment.clone();
+        let two_factor_code = two_factor_code.clone();
+        let recovery_codes = recovery_codes.clone();
+        let two_factor_feedback = two_factor_feedback.clone();
+        Callback::from(move |_: MouseEvent| {
+            let code = (*two_factor_code).clone();
+            let enrollment = enrollment.clone();
+            let two_factor_code = two_factor_code.clone();
+            let recovery_codes = recovery_codes.clone();
+            let two_factor_feedback = two_factor_feedback.clone();
+            spawn_local(async move {
+                match auth::confirm_two_factor(&code).await {
+                    Ok(codes) => {
+                        enrollment.set(None);
+                        two_factor_code.set(String::new());
+                        recovery_codes.set(codes);
+                        two_factor_feedback.set(Some((
+                            "2FA ativado. Guarde os códigos de recuperação abaixo; eles não serão exibidos novamente".to_string(),
+                            false,
+                        )));
+                    }
+                    Err(result) => two_factor_feedback.set(two_factor_result_feedback(result, "")),
+                }
+            });
+        })
+    };
+
+    let on_disable_two_factor = {
+        let two_factor_password = two_factor_password.clone();
+        let recovery_codes = recovery_codes.clone();
+        let two_factor_feedback = two_factor_feedback.clone();
+        Callback::from(move |_: MouseEvent| {
+            let password = (*two_factor_password).clone();
+            let two_factor_password = two_factor_password.clone();
+            let recovery_codes = recovery_codes.clone();
+            let two_factor_feedback = two_factor_feedback.clone();
+            spawn_local(async move {
+                let result = auth::disable_two_factor(&password).await;
+                if let AccountResult::Success = result {
+                    two_factor_password.set(String::new());
+                    recovery_codes.set(Vec::new());
+                }
+                two_factor_feedback.set(two_factor_result_feedback(result, "2FA desativado"));
+            });
+        })
+    };
+
+    let on_change_username = {
+        let new_username = new_username.clone();
+        let username_feedback = username_feedback.clone();
+        Callback::from(move |_: MouseEvent| {
+            let errors = validation::validate_username(&new_username);
+            if let Some(error) = errors.first() {
+                username_feedback.set(Some((error.clone(), true)));
+                return;
+            }
+
+            let username = (*new_username).clone();
+            let username_feedback = username_feedback.clone();
+            spawn_local(async move {
+                let result = auth::change_username(&username).await;
+                username_feedback.set(result_feedback(result, "Nome de usuário alterado"));
+            });
+        })
+    };
+
+    let on_change_password = {
+        let current_password = current_password.clone();
+        let new_password = new_password.clone();
+        let confirm_password = confirm_password.clone();
+        let password_feedback = password_feedback.clone();
+        Callback::from(move |_: MouseEvent| {
+            let errors = validation::validate_password(&new_password);
+            if let Some(error) = errors.first() {
+                password_feedback.set(Some((error.clone(), true)));
+                return;
+            }
+            if *new_password != *confirm_password {
+                password_feedback.set(Some(("As senhas não coincidem".to_string(), true)));
+                return;
+            }
+
+            let current = (*current_password).clone();
+            let new = (*new_password).clone();
+            let current_password = current_password.clone();
+            let new_password = new_password.clone();
+            let confirm_password = confirm_password.clone();
+            let password_feedback = password_feedback.clone();
+            spawn_local(async move {
+                let result = auth::change_password(&current, &new).await;
+                if let AccountResult::Success = result {
+                    current_password.set(String::new());
+                    new_password.set(String::new());
+                    confirm_password.set(String::new());
+                }
+                password_feedback.set(result_feedback(result, "Senha alterada; as outras sessões foram encerradas"));
+            });
+        })
+    };
+
+    let on_delete_account = {
+        let delete_password = delete_password.clone();
+        let delete_feedback = delete_feedback.clone();
+        let navigator = navigator.clone();
+        Callback::from(move |_: MouseEvent| {
+            let confirmed = web_sys::window()
+                .unwrap()
+                .confirm_with_message("Apagar a conta remove todas as suas tarefas, metas, lembretes e notas. Continuar?")
+                .unwrap_or(false);
+            if !confirmed {
+                return;
+            }
+
+            let password = (*delete_password).clone();
+            let delete_feedback = delete_feedback.clone();
+            let navigator = navigator.clone();
+            spawn_local(async move {
+                match auth::delete_account(&password).await {
+                    AccountResult::Success => navigator.push(&Route::Register),
+                    result => delete_feedback.set(result_feedback(result, "")),
+                }
+            });
+        })
+    };
+
+    let on_back = {
+        let navigator = navigator.clone();
+        Callback::from(move |_: MouseEvent| {
+            navigator.push(&Route::Home);
+        })
+    };
+
+    html! {
+        <div class="login-page-wrapper">
+            <div class="login-father-container account-container">
+                <div class="login-header">
+                    <span class="login-header-title">{"Minha conta"}</span>
+                    <div class="login-header-controls">
+                        <button class="login-control-button close" type="button" onclick={on_back.clone()}></button>
+                    </div>
+                </div>
+
+                <div class="account-content">
+                    <fieldset class="account-section">
+                        <legend>{"Perfil"}</legend>
+                        <label class="login-form-label">{"Nome de exibição"}</label>
+                        <input class="login-input" type="text"
+                            value={(*display_name).clone()}
+                            oninput={input_callback(&display_name)} />
+                        <label class="login-form-label">{"E-mail"}</label>
+                        <input class="login-input" type="email"
+                            value={(*email).clone()}
+                            oninput={input_callback(&email)} />
+                        <label class="login-form-label">{"Fuso horário (ex.: America/Sao_Paulo)"}</label>
+                        <input class="login-input" type="text"
+                            value={(*timezone).clone()}
+                            oninput={input_callback(&timezone)} />
+                        <label class="login-form-label">{"Idioma (ex.: pt-BR)"}</label>
+                        <input class="login-input" type="text"
+                            value={(*locale).clone()}
+                            oninput={input_callback(&locale)} />
+                        <label class="login-form-label">{"Primeiro dia da semana"}</label>
+                        <select class="login-input" onchange={select_callback(&week_start_day)}>
+                            { for WEEK_DAYS.iter().enumerate().map(|(day, name)| html! {
+                                <option value={day.to_string()} selected={*week_start_day == day.to_string()}>{ *name }</option>
+                            }) }
+                        </select>
+                        <label class="account-checkbox">
+                            <input type="checkbox" checked={*use_24h_clock} onchange={on_toggle_24h_clock} />
+                            {"Relógio de 24 horas"}
+                        </label>
+                        { feedback_html(&profile_feedback) }
+                        <button class="login-button" type="button" onclick={on_save_profile}>{"Salvar perfil"}</button>
+                    </fieldset>
+
+                    <fieldset class="account-section">
+                        <legend>{"Nome de usuário"}</legend>
+                        <label class="login-form-label">{"Novo nome de usuário"}</label>
+                        <input class="login-input" type="text"
+                            value={(*new_username).clone()}
+                            oninput={input_callback(&new_username)} />
+                        { feedback_html(&username_feedback) }
+                        <button class="login-button" type="button" onclick={on_change_username}>{"Alterar nome"}</button>
+                    </fieldset>
+
+                    <fieldset class="account-section">
+                        <legend>{"Senha"}</legend>
+                        <label class="login-form-label">{"Senha atual"}</label>
+                        <input class="login-input-password" type="password"
+                            value={(*current_password).clone()}
+                            oninput={input_callback(&current_password)} />
+                        <label class="login-form-label">{"Nova senha"}</label>
+                        <input class="login-input-password" type="password"
+                            value={(*new_password).clone()}
+                            oninput={input_callback(&new_password)} />
+                        <label class="login-form-label">{"Confirme a nova senha"}</label>
+                        <input class="login-input-password" type="password"
+                            value={(*confirm_password).clone()}
+                            oninput={input_callback(&confirm_password)} />
+                        { feedback_html(&password_feedback) }
+                        <button class="login-button" type="button" onclick={on_change_password}>{"Alterar senha"}</button>
+                    </fieldset>
+
+                    <fieldset class="account-section">
+                        <legend>{"Verificação em duas etapas"}</legend>
+                        if let Some(enrollment) = &*enrollment {
+                            <label class="login-form-label">{"Adicione a conta no seu aplicativo autenticador com a chave:"}</label>
+                            <code class="account-secret">{ enrollment.secret.clone() }</code>
+                            <a class="account-secret" href={enrollment.otpauth_uri.clone()}>{ enrollment.otpauth_uri.clone() }</a>
+                            <label class="login-form-label">{"Código gerado pelo aplicativo"}</label>
+                            <input class="login-input" type="text" autocomplete="one-time-code"
+                                value={(*two_factor_code).clone()}
+                                oninput={input_callback(&two_factor_code)} />
+                            <button class="login-button" type="button" onclick={on_confirm_two_factor}>{"Confirmar"}</button>
+                        } else {
+                            <button class="login-button" type="button" onclick={on_enroll_two_factor}>{"Ativar 2FA"}</button>
+                        }
+                        if !recovery_codes.is_empty() {
+                            <ul class="account-recovery-codes">
+                                { for recovery_codes.iter().map(|code| html! { <li><code>{ code.clone() }</code></li> }) }
+                            </ul>
+                        }
+                        { feedback_html(&two_factor_feedback) }
+                        <label class="login-form-label">{"Para desativar, confirme sua senha"}</label>
+                        <input class="login-input-password" type="password"
+                            value={(*two_factor_password).clone()}
+                            oninput={input_callback(&two_factor_password)} />
+                        <button class="login-button" type="button" onclick={on_disable_two_factor}>{"Desativar 2FA"}</button>
+                    </fieldset>
+
+                    <fieldset class="account-section account-danger">
+                        <legend>{"Apagar conta"}</legend>
+                        <label class="login-form-label">{"Confirme sua senha"}</label>
+                        <input class="login-input-password" type="password"
+                            value={(*delete_password).clone()}
+                            oninput={input_callback(&delete_password)} />
+                        { feedback_html(&delete_feedback) }
+                        <button class="login-button" type="button" onclick={on_delete_account}>{"Apagar conta"}</button>
+                    </fieldset>
+
+                    <a class="login-register-link" onclick={on_back}>{"Voltar para a agenda"}</a>
+                </div>
+            </div>
+        </div>
+    }
+}
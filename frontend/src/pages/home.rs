@@ -1,10 +1,12 @@
 use crate::components::{calendar_app::CalendarApp, report_app::ReportApp, taskbar::Taskbar};
+use crate::types::search::SearchResult;
 use yew::{prelude::*, Callback};
 
 #[function_component(Home)]
 pub fn home_component() -> Html {
     let calendar_visible = use_state(|| true);
     let report_visible = use_state(|| false);
+    let search_focus = use_state(|| None::<SearchResult>);
 
     let toggle_calendar = {
         let calendar_visible = calendar_visible.clone();
@@ -42,15 +44,28 @@ pub fn home_component() -> Html {
         })
     };
 
+    // Abre a agenda no item escolhido na busca.
+    let open_search_result = {
+        let calendar_visible = calendar_visible.clone();
+        let report_visible = report_visible.clone();
+        let search_focus = search_focus.clone();
+        Callback::from(move |result: SearchResult| {
+            report_visible.set(false);
+            calendar_visible.set(true);
+            search_focus.set(Some(result));
+        })
+    };
+
     html! {
         <div class="app-container">
-            <CalendarApp visible={*calendar_visible} on_close={close_calendar} />
+            <CalendarApp visible={*calendar_visible} on_close={close_calendar} focus={(*search_focus).clone()} />
             <ReportApp visible={*report_visible} on_close={close_report} />
             <Taskbar
                 on_calendar_toggle={toggle_calendar}
                 calendar_visible={*calendar_visible}
                 on_report_toggle={toggle_report}
                 report_visible={*report_visible}
+                on_search_select={open_search_result}
             />
         </div>
     }
@@ -1,6 +1,7 @@
 use wasm_bindgen_futures::spawn_local;
 use yew::prelude::*;
 use yew_router::hooks::use_navigator;
+use yew_router::navigator::Navigator;
 
 use crate::utils::{routes::Route, validation};
 
@@ -14,12 +15,16 @@ pub fn login() -> Html {
     let password = use_state(String::new);
     let login_pressed = use_state(|| false);
     let show_invalid_credentials = use_state(|| false);
+    // Preenchido quando a senha confere mas a conta exige o segundo fator.
+    let challenge = use_state(|| None::<String>);
+    let code = use_state(String::new);
 
     let onclick = {
         let username = (*username).clone();
         let password = (*password).clone();
         let navigator = navigator.clone();
         let show_invalid_credentials = show_invalid_credentials.clone();
+        let challenge = challenge.clone();
         Callback::from(move |_: MouseEvent| {
             let (user_errs, pass_errs) = validation::validate_credentials(&username, &password);
 
@@ -35,6 +40,7 @@ pub fn login() -> Html {
             let navigator = navigator.clone();
             let login_pressed = login_pressed.clone();
             let show_invalid_credentials = show_invalid_credentials.clone();
+            let challenge = challenge.clone();
             if *login_pressed {
                 return;
             }
@@ -43,24 +49,55 @@ pub fn login() -> Html {
                 let login_info: AuthStruct = AuthStruct::new(username, password);
                 let login_response = auth::login(&login_info).await;
                 match login_response {
-                    LoginResult::Success => {
-                        navigator.push(&Route::Home);
-                    }
-                    LoginResult::IncorrectCredentials => {
-                        show_invalid_credentials.set(true);
-                    }
-                    LoginResult::NetworkError => {
-                        web_sys::window()
-                            .unwrap()
-                            .alert_with_message("Backend off do line")
-                            .unwrap();
+                    LoginResult::TwoFactorRequired(challenge_token) => {
+                        challenge.set(Some(challenge_token));
                     }
+                    result => handle_login_result(result, &navigator, &show_invalid_credentials),
                 }
                 login_pressed.set(false);
             });
         })
     };
 
+    let on_code_submit = {
+        let challenge = challenge.clone();
+        let code = code.clone();
+        let navigator = navigator.clone();
+        let show_invalid_credentials = show_invalid_credentials.clone();
+        Callback::from(move |_: MouseEvent| {
+            let Some(challenge_token) = (*challenge).clone() else {
+                return;
+            };
+            let code_value = (*code).clone();
+            let navigator = navigator.clone();
+            let show_invalid_credentials = show_invalid_credentials.clone();
+            spawn_local(async move {
+                let result = auth::login_two_factor(&challenge_token, &code_value).await;
+                handle_login_result(result, &navigator, &show_invalid_credentials);
+            });
+        })
+    };
+
+    // o token de desafio expira em poucos minutos; permite recomeçar pela senha
+    let on_restart_login = {
+        let challenge = challenge.clone();
+        let code = code.clone();
+        Callback::from(move |_: MouseEvent| {
+            challenge.set(None);
+            code.set(String::new());
+        })
+    };
+
+    let on_code_input_change = {
+        let code = code.clone();
+        let show_invalid_credentials = show_invalid_credentials.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            code.set(input.value());
+            show_invalid_credentials.set(false);
+        })
+    };
+
     let onclick_register: Callback<MouseEvent> = {
         let navigator = navigator.clone();
         Callback::from(move |_: MouseEvent| {
@@ -68,6 +105,13 @@ pub fn login() -> Html {
         })
     };
 
+    let onclick_forgot_password: Callback<MouseEvent> = {
+        let navigator = navigator.clone();
+        Callback::from(move |_: MouseEvent| {
+            navigator.push(&Route::PasswordReset);
+        })
+    };
+
     let on_username_input_change = {
         let username = username.clone();
         let show_invalid_credentials = show_invalid_credentials.clone();
@@ -122,6 +166,21 @@ pub fn login() -> Html {
                         <div class="right-login-container">
                             <h1>{ "Bem vindo de volta!" } </h1>
                             <form class="login-form">
+                                if (*challenge).is_some() {
+                                    <div class="login-form-container">
+                                        <label class="login-form-label"> {"Insira o código do aplicativo autenticador ou um código de recuperação"} </label>
+                                        <input value={(*code).clone()}
+                                            oninput={on_code_input_change}
+                                            class="login-input" type="text" autocomplete="one-time-code" />
+                                        if *show_invalid_credentials {
+                                            <div class="error-messages">
+                                                <div>{ "- Código inválido" }</div>
+                                            </div>
+                                        }
+                                        <button onclick={on_code_submit} class="login-button" type="button"> {"Verificar"} </button>
+                                        <a class="login-register-link" onclick={on_restart_login}> {"Voltar para o login"} </a>
+                                    </div>
+                                } else {
                                 <div class="login-form-container">
                                     <label class="login-form-label"> {"Insira seu nome de usuário"} </label>
                                     <input value={(*username).clone()}
@@ -144,7 +203,9 @@ pub fn login() -> Html {
                                     }
                                     <button {onclick} class="login-button" type="button"> {"Entrar"} </button>
                                     <a class="login-register-link" onclick={onclick_register}> {"Ainda não possui uma conta? Clique aqui."} </a>
+                                    <a class="login-register-link" onclick={onclick_forgot_password}> {"Esqueceu a senha?"} </a>
                                 </div>
+                                }
                             </form>
                         </div>
                         <div class="left-login-container">
@@ -155,3 +216,33 @@ pub fn login() -> Html {
         </div>
     }
 }
+
+fn handle_login_result(
+    result: LoginResult,
+    navigator: &Navigator,
+    show_invalid_credentials: &UseStateHandle<bool>,
+) {
+    match result {
+        LoginResult::Success => {
+            navigator.push(&Route::Home);
+        }
+        LoginResult::IncorrectCredentials => {
+            show_invalid_credentials.set(true);
+        }
+        LoginResult::TooManyAttempts(seconds) => {
+            web_sys::window()
+                .unwrap()
+                .alert_with_message(&format!(
+                    "Muitas tentativas de login. Tente novamente em {} segundos.",
+                    seconds
+                ))
+                .unwrap();
+        }
+        LoginResult::TwoFactorRequired(_) | LoginResult::NetworkError => {
+            web_sys::window()
+                .unwrap()
+                .alert_with_message("Backend off do line")
+                .unwrap();
+        }
+    }
+}
@@ -1,4 +1,6 @@
+pub mod account;
 pub mod home;
 pub mod login;
+pub mod password_reset;
 pub mod register;
 
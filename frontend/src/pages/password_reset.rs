@@ -0,0 +1,134 @@
+use serde::Deserialize;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+use yew_router::hooks::{use_location, use_navigator};
+
+use crate::pages::account::{feedback_html, input_callback};
+use crate::services::auth::{self, AccountResult};
+use crate::utils::{routes::Route, validation};
+
+#[derive(Deserialize)]
+struct ResetQuery {
+    token: Option<String>,
+}
+
+/// Sem `token` na URL, pede o e-mail de redefinição; com o token (link do
+/// e-mail), define a nova senha.
+#[function_component(PasswordReset)]
+pub fn password_reset() -> Html {
+    let navigator = use_navigator().unwrap();
+    let token = use_location()
+        .and_then(|location| location.query::<ResetQuery>().ok())
+        .and_then(|query| query.token);
+
+    let email = use_state(String::new);
+    let new_password = use_state(String::new);
+    let confirm_password = use_state(String::new);
+    let feedback = use_state(|| None::<(String, bool)>);
+
+    let on_request = {
+        let email = email.clone();
+        let feedback = feedback.clone();
+        Callback::from(move |_: MouseEvent| {
+            if email.trim().is_empty() {
+                feedback.set(Some(("Informe o e-mail da sua conta".to_string(), true)));
+                return;
+            }
+
+            let email = (*email).clone();
+            let feedback = feedback.clone();
+            spawn_local(async move {
+                let message = match auth::request_password_reset(&email).await {
+                    AccountResult::NetworkError => ("Backend off do line".to_string(), true),
+                    _ => (
+                        "Se houver uma conta com esse e-mail, enviaremos um link para redefinir a senha".to_string(),
+                        false,
+                    ),
+                };
+                feedback.set(Some(message));
+            });
+        })
+    };
+
+    let on_confirm = {
+        let token = token.clone().unwrap_or_default();
+        let new_password = new_password.clone();
+        let confirm_password = confirm_password.clone();
+        let feedback = feedback.clone();
+        let navigator = navigator.clone();
+        Callback::from(move |_: MouseEvent| {
+            let errors = validation::validate_password(&new_password);
+            if let Some(error) = errors.first() {
+                feedback.set(Some((error.clone(), true)));
+                return;
+            }
+            if *new_password != *confirm_password {
+                feedback.set(Some(("As senhas não coincidem".to_string(), true)));
+                return;
+            }
+
+            let token = token.clone();
+            let password = (*new_password).clone();
+            let feedback = feedback.clone();
+            let navigator = navigator.clone();
+            spawn_local(async move {
+                match auth::confirm_password_reset(&token, &password).await {
+                    AccountResult::Success => {
+                        auth::clear_token();
+                        navigator.push(&Route::Login);
+                    }
+                    AccountResult::NetworkError => feedback.set(Some(("Backend off do line".to_string(), true))),
+                    _ => feedback.set(Some(("Link inválido ou expirado, peça um novo e-mail".to_string(), true))),
+                }
+            });
+        })
+    };
+
+    let on_back = {
+        let navigator = navigator.clone();
+        Callback::from(move |_: MouseEvent| {
+            navigator.push(&Route::Login);
+        })
+    };
+
+    html! {
+        <div class="login-page-wrapper">
+            <div class="login-father-container account-container">
+                <div class="login-header">
+                    <span class="login-header-title">{"Redefinir senha"}</span>
+                    <div class="login-header-controls">
+                        <button class="login-control-button close" type="button" onclick={on_back.clone()}></button>
+                    </div>
+                </div>
+
+                <div class="account-content">
+                    <fieldset class="account-section">
+                        if token.is_some() {
+                            <legend>{"Nova senha"}</legend>
+                            <label class="login-form-label">{"Nova senha"}</label>
+                            <input class="login-input-password" type="password"
+                                value={(*new_password).clone()}
+                                oninput={input_callback(&new_password)} />
+                            <label class="login-form-label">{"Confirme a nova senha"}</label>
+                            <input class="login-input-password" type="password"
+                                value={(*confirm_password).clone()}
+                                oninput={input_callback(&confirm_password)} />
+                            { feedback_html(&feedback) }
+                            <button class="login-button" type="button" onclick={on_confirm}>{"Salvar senha"}</button>
+                        } else {
+                            <legend>{"Esqueci minha senha"}</legend>
+                            <label class="login-form-label">{"E-mail cadastrado no seu perfil"}</label>
+                            <input class="login-input" type="email"
+                                value={(*email).clone()}
+                                oninput={input_callback(&email)} />
+                            { feedback_html(&feedback) }
+                            <button class="login-button" type="button" onclick={on_request}>{"Enviar link"}</button>
+                        }
+                    </fieldset>
+
+                    <a class="login-register-link" onclick={on_back}>{"Voltar para o login"}</a>
+                </div>
+            </div>
+        </div>
+    }
+}
@@ -92,6 +92,8 @@ pub fn register() -> Html {
             let navigator = navigator.clone();
             let button_pressed = button_pressed.clone();
             let show_username_exists = show_username_exists.clone();
+            let username_errors = username_errors.clone();
+            let password_errors = password_errors.clone();
             if (*button_pressed) == false {
                 spawn_local(async move {
                     button_pressed.set(true);
@@ -100,15 +102,26 @@ pub fn register() -> Html {
                             web_sys::console::log_1(&"Registro realizado com sucesso".into());
                             navigator.push(&Route::Home);
                         }
-                        RegisterResult::InvalidFields => {
+                        RegisterResult::InvalidFields(field_errors) => {
                             web_sys::console::log_1(
                                 &format!("Falha no registro, campos inválidos").into(),
                             );
+                            username_errors.set(field_errors.get("username").cloned().unwrap_or_default());
+                            password_errors.set(field_errors.get("password").cloned().unwrap_or_default());
                         }
                         RegisterResult::UsernameAlreadyExists => {
                             web_sys::console::log_1(&"Nome de usuário já utilizado".into());
                             show_username_exists.set(true);
                         }
+                        RegisterResult::TooManyAttempts(seconds) => {
+                            web_sys::window()
+                                .unwrap()
+                                .alert_with_message(&format!(
+                                    "Muitas tentativas. Tente novamente em {} segundos.",
+                                    seconds
+                                ))
+                                .unwrap();
+                        }
                         RegisterResult::NetworkError => {
                             web_sys::console::log_1(&format!("Backend fora do ar").into());
                         }
@@ -3,6 +3,7 @@ use gloo_storage::{LocalStorage, Storage};
 use js_sys::Date;
 use jsonwebtoken::{decode, Algorithm, DecodingKey, Validation};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use crate::config::get_api_url;
 
 #[derive(Serialize, Deserialize)]
@@ -14,6 +15,43 @@ pub struct AuthStruct {
 #[derive(Serialize, Deserialize)]
 pub struct Token {
     pub token: String,
+    #[serde(default)]
+    pub refresh_token: String,
+}
+
+/// Resposta do `/login`: tokens ou, com 2FA ativo, um desafio.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum LoginResponse {
+    Challenge { challenge_token: String },
+    Tokens(Token),
+}
+
+#[derive(Serialize)]
+struct TwoFactorLoginStruct<'a> {
+    challenge_token: &'a str,
+    code: &'a str,
+}
+
+#[derive(Serialize)]
+struct TwoFactorCodeStruct<'a> {
+    code: &'a str,
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+pub struct TwoFactorEnrollment {
+    pub secret: String,
+    pub otpauth_uri: String,
+}
+
+#[derive(Deserialize)]
+struct RecoveryCodesResponse {
+    recovery_codes: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct RefreshStruct<'a> {
+    refresh_token: &'a str,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -38,23 +76,77 @@ impl Default for Token {
     fn default() -> Self {
         Token {
             token: "".to_string(),
+            refresh_token: "".to_string(),
         }
     }
 }
 
 pub enum LoginResult {
     Success,
+    /// Senha correta, mas a conta tem 2FA; contém o token de desafio.
+    TwoFactorRequired(String),
     IncorrectCredentials,
+    /// Bloqueio temporário por excesso de tentativas; segundos até poder tentar de novo.
+    TooManyAttempts(u64),
     NetworkError,
 }
 
+/// Erros por campo devolvidos pelo servidor (`field_errors` do corpo de erro).
+pub type FieldErrors = HashMap<String, Vec<String>>;
+
+#[derive(Deserialize, Default)]
+struct ErrorBody {
+    #[serde(default)]
+    field_errors: FieldErrors,
+}
+
 pub enum RegisterResult {
     Success,
-    InvalidFields,
+    /// Credenciais recusadas pela política do servidor, com as mensagens de cada campo.
+    InvalidFields(FieldErrors),
     UsernameAlreadyExists,
+    TooManyAttempts(u64),
+    NetworkError,
+}
+
+/// Resultado das operações da tela "Minha conta".
+pub enum AccountResult {
+    Success,
+    InvalidFields,
+    /// 409: nome de usuário já em uso ou 2FA já ativado.
+    Conflict,
+    SessionExpired,
     NetworkError,
 }
 
+#[derive(Serialize)]
+struct ChangePasswordStruct<'a> {
+    current_password: &'a str,
+    new_password: &'a str,
+}
+
+#[derive(Serialize)]
+struct ChangeUsernameStruct<'a> {
+    username: &'a str,
+}
+
+#[derive(Serialize)]
+struct PasswordResetRequestStruct<'a> {
+    email: &'a str,
+}
+
+#[derive(Serialize)]
+struct PasswordResetConfirmStruct<'a> {
+    token: &'a str,
+    new_password: &'a str,
+}
+
+/// Corpo com a senha de confirmação, usado para apagar a conta e desativar o 2FA.
+#[derive(Serialize)]
+struct DeleteAccountStruct<'a> {
+    password: &'a str,
+}
+
 
 pub async fn login(login_info: &AuthStruct) -> LoginResult {
     let login_url = format!("{}/login", get_api_url());
@@ -65,12 +157,40 @@ pub async fn login(login_info: &AuthStruct) -> LoginResult {
         .await
     {
         Ok(response) => match response.status() {
-            200 | 201 => {
+            200 | 201 => match response.json::<LoginResponse>().await.unwrap() {
+                LoginResponse::Challenge { challenge_token } => {
+                    LoginResult::TwoFactorRequired(challenge_token)
+                }
+                LoginResponse::Tokens(token_value) => {
+                    save_token(token_value);
+                    LoginResult::Success
+                }
+            },
+            400 | 401 | 422 => LoginResult::IncorrectCredentials,
+            429 => LoginResult::TooManyAttempts(retry_after(&response)),
+            _ => LoginResult::NetworkError,
+        },
+        Err(_) => LoginResult::NetworkError,
+    }
+}
+
+/// Segundo passo do login: troca o desafio e o código (TOTP ou de recuperação) pelos tokens.
+pub async fn login_two_factor(challenge_token: &str, code: &str) -> LoginResult {
+    let login_url = format!("{}/login/2fa", get_api_url());
+    match Request::post(&login_url)
+        .json(&TwoFactorLoginStruct { challenge_token, code })
+        .unwrap()
+        .send()
+        .await
+    {
+        Ok(response) => match response.status() {
+            200 => {
                 let token_value = response.json::<Token>().await.unwrap();
-                save_token(token_value.token);
+                save_token(token_value);
                 LoginResult::Success
             }
-            400 => LoginResult::IncorrectCredentials,
+            400 | 401 | 422 => LoginResult::IncorrectCredentials,
+            429 => LoginResult::TooManyAttempts(retry_after(&response)),
             _ => LoginResult::NetworkError,
         },
         Err(_) => LoginResult::NetworkError,
@@ -88,23 +208,210 @@ pub async fn register(register_info: &AuthStruct) -> RegisterResult {
         Ok(response) => match response.status() {
             200 | 201 => {
                 let token_value = response.json::<Token>().await.unwrap();
-                save_token(token_value.token);
+                save_token(token_value);
                 RegisterResult::Success
             }
-            400 => RegisterResult::InvalidFields,
+            400 | 422 => {
+                let body = response.json::<ErrorBody>().await.unwrap_or_default();
+                RegisterResult::InvalidFields(body.field_errors)
+            }
             409 => RegisterResult::UsernameAlreadyExists,
+            429 => RegisterResult::TooManyAttempts(retry_after(&response)),
             _ => RegisterResult::NetworkError,
         },
         Err(_) => RegisterResult::NetworkError,
     }
 }
 
-pub fn save_token(token_value: String) {
-    let token = Token { token: token_value };
+/// Lê o cabeçalho `Retry-After` de uma resposta 429.
+fn retry_after(response: &gloo::net::http::Response) -> u64 {
+    response
+        .headers()
+        .get("Retry-After")
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(0)
+}
+
+pub fn save_token(token: Token) {
     web_sys::console::log_1(&format!("saving token {}", token.token).into());
     LocalStorage::set("token", token).unwrap();
 }
 
+pub fn clear_token() {
+    LocalStorage::delete("token");
+    LocalStorage::delete("profile");
+}
+
+/// Troca o refresh token salvo por um novo par de tokens.
+/// Retorna false se a sessão não puder ser renovada (revogada ou expirada).
+pub async fn refresh_token() -> bool {
+    let token = get_token();
+    if token.refresh_token.is_empty() {
+        return false;
+    }
+
+    let refresh_url = format!("{}/refresh", get_api_url());
+    match Request::post(&refresh_url)
+        .json(&RefreshStruct { refresh_token: &token.refresh_token })
+        .unwrap()
+        .send()
+        .await
+    {
+        Ok(response) if response.status() == 200 => match response.json::<Token>().await {
+            Ok(token_value) => {
+                save_token(token_value);
+                true
+            }
+            Err(_) => false,
+        },
+        Ok(_) => {
+            clear_token();
+            false
+        }
+        Err(_) => false,
+    }
+}
+
+/// Retorna o token salvo, renovando-o antes caso o access token tenha expirado.
+pub async fn get_fresh_token() -> Token {
+    let token = get_token();
+    if token.token.is_empty() || verify_token(&token) {
+        return token;
+    }
+    if refresh_token().await {
+        get_token()
+    } else {
+        token
+    }
+}
+
+/// Encerra a sessão no servidor e remove os tokens locais.
+pub async fn logout() {
+    let token = get_token();
+    if !token.token.is_empty() {
+        let logout_url = format!("{}/logout", get_api_url());
+        let _ = Request::post(&logout_url)
+            .header("Authorization", &format!("Bearer {}", token.token))
+            .send()
+            .await;
+    }
+    clear_token();
+}
+
+pub async fn change_password(current_password: &str, new_password: &str) -> AccountResult {
+    let token = get_fresh_token().await;
+    let url = format!("{}/account/password", get_api_url());
+    let request = Request::put(&url)
+        .header("Authorization", &format!("Bearer {}", token.token))
+        .json(&ChangePasswordStruct { current_password, new_password })
+        .unwrap();
+    account_result(request.send().await)
+}
+
+pub async fn change_username(username: &str) -> AccountResult {
+    let token = get_fresh_token().await;
+    let url = format!("{}/account/username", get_api_url());
+    let request = Request::put(&url)
+        .header("Authorization", &format!("Bearer {}", token.token))
+        .json(&ChangeUsernameStruct { username })
+        .unwrap();
+    account_result(request.send().await)
+}
+
+/// Apaga a conta no servidor; em caso de sucesso também remove os tokens locais.
+pub async fn delete_account(password: &str) -> AccountResult {
+    let token = get_fresh_token().await;
+    let url = format!("{}/account", get_api_url());
+    let request = Request::delete(&url)
+        .header("Authorization", &format!("Bearer {}", token.token))
+        .json(&DeleteAccountStruct { password })
+        .unwrap();
+    let result = account_result(request.send().await);
+    if let AccountResult::Success = result {
+        clear_token();
+    }
+    result
+}
+
+/// Inicia a inscrição no 2FA, retornando o segredo e a URI `otpauth://`.
+pub async fn enroll_two_factor() -> Result<TwoFactorEnrollment, AccountResult> {
+    let token = get_fresh_token().await;
+    let url = format!("{}/2fa/enroll", get_api_url());
+    let response = Request::post(&url)
+        .header("Authorization", &format!("Bearer {}", token.token))
+        .send()
+        .await;
+    match response {
+        Ok(response) if response.status() == 200 => response
+            .json::<TwoFactorEnrollment>()
+            .await
+            .map_err(|_| AccountResult::NetworkError),
+        other => Err(account_result(other)),
+    }
+}
+
+/// Confirma o 2FA com o primeiro código; retorna os códigos de recuperação.
+pub async fn confirm_two_factor(code: &str) -> Result<Vec<String>, AccountResult> {
+    let token = get_fresh_token().await;
+    let url = format!("{}/2fa/confirm", get_api_url());
+    let response = Request::post(&url)
+        .header("Authorization", &format!("Bearer {}", token.token))
+        .json(&TwoFactorCodeStruct { code })
+        .unwrap()
+        .send()
+        .await;
+    match response {
+        Ok(response) if response.status() == 200 => response
+            .json::<RecoveryCodesResponse>()
+            .await
+            .map(|body| body.recovery_codes)
+            .map_err(|_| AccountResult::NetworkError),
+        other => Err(account_result(other)),
+    }
+}
+
+pub async fn disable_two_factor(password: &str) -> AccountResult {
+    let token = get_fresh_token().await;
+    let url = format!("{}/2fa/disable", get_api_url());
+    let request = Request::post(&url)
+        .header("Authorization", &format!("Bearer {}", token.token))
+        .json(&DeleteAccountStruct { password })
+        .unwrap();
+    account_result(request.send().await)
+}
+
+/// Pede o e-mail de redefinição de senha. O servidor responde igual para
+/// e-mails cadastrados ou não.
+pub async fn request_password_reset(email: &str) -> AccountResult {
+    let url = format!("{}/password-reset/request", get_api_url());
+    let request = Request::post(&url)
+        .json(&PasswordResetRequestStruct { email })
+        .unwrap();
+    account_result(request.send().await)
+}
+
+/// Define a nova senha com o token recebido por e-mail; todas as sessões são encerradas.
+pub async fn confirm_password_reset(token: &str, new_password: &str) -> AccountResult {
+    let url = format!("{}/password-reset/confirm", get_api_url());
+    let request = Request::post(&url)
+        .json(&PasswordResetConfirmStruct { token, new_password })
+        .unwrap();
+    account_result(request.send().await)
+}
+
+pub fn account_result(response: Result<gloo::net::http::Response, gloo::net::Error>) -> AccountResult {
+    match response {
+        Ok(response) => match response.status() {
+            200 | 202 | 204 => AccountResult::Success,
+            400 | 422 => AccountResult::InvalidFields,
+            401 => AccountResult::SessionExpired,
+            409 => AccountResult::Conflict,
+            _ => AccountResult::NetworkError,
+        },
+        Err(_) => AccountResult::NetworkError,
+    }
+}
+
 pub fn get_token() -> Token {
     LocalStorage::get::<Token>("token").unwrap_or_default()
 }
@@ -0,0 +1,60 @@
+use gloo::net::http::Request;
+use serde::Serialize;
+use crate::types::category::Category;
+use crate::config::get_api_url;
+use super::auth::get_fresh_token;
+
+#[derive(Serialize, Debug, Clone)]
+pub struct CategoryDto {
+    pub name: String,
+    pub color: String,
+}
+
+pub async fn get_categories() -> Result<Vec<Category>, String> {
+    let url = format!("{}/categories", get_api_url());
+    let token = get_fresh_token().await;
+    if token.token.is_empty() {
+        return Err("No authentication token found".to_string());
+    }
+
+    match Request::get(&url)
+        .header("Authorization", &format!("Bearer {}", token.token))
+        .send()
+        .await
+    {
+        Ok(response) if response.status() == 200 => response
+            .json::<Vec<Category>>()
+            .await
+            .map_err(|e| format!("Failed to parse categories JSON: {}", e)),
+        Ok(response) => Err(format!("Failed to fetch categories: HTTP {}", response.status())),
+        Err(e) => Err(format!("Network error: {}", e)),
+    }
+}
+
+pub async fn create_category(category_dto: CategoryDto) -> Result<Category, String> {
+    let url = format!("{}/categories", get_api_url());
+    let token = get_fresh_token().await;
+    if token.token.is_empty() {
+        return Err("No authentication token found".to_string());
+    }
+
+    match Request::post(&url)
+        .header("Authorization", &format!("Bearer {}", token.token))
+        .header("Content-Type", "application/json")
+        .json(&category_dto)
+        .unwrap()
+        .send()
+        .await
+    {
+        Ok(response) if response.status() == 200 => response
+            .json::<Category>()
+            .await
+            .map_err(|e| format!("Failed to parse category: {}", e)),
+        Ok(response) if response.status() == 409 => Err("Já existe uma categoria com esse nome.".to_string()),
+        Ok(response) => {
+            let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+            Err(format!("Failed to create category: HTTP {} - {}", response.status(), error_text))
+        }
+        Err(e) => Err(format!("Network error: {}", e)),
+    }
+}
@@ -0,0 +1,142 @@
+use gloo::net::http::Request;
+use serde::Serialize;
+use crate::types::checklist::ChecklistItem;
+use crate::config::get_api_url;
+use super::auth::get_fresh_token;
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ChecklistItemDto {
+    pub title: String,
+}
+
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct ChecklistItemUpdateDto {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub done: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<u32>,
+}
+
+#[derive(Serialize)]
+struct AutoCompleteDto {
+    checklist_auto_complete: bool,
+}
+
+fn checklist_url(task_id: u32) -> String {
+    format!("{}/tasks/{}/checklist", get_api_url(), task_id)
+}
+
+pub async fn get_checklist(task_id: u32) -> Result<Vec<ChecklistItem>, String> {
+    let token = get_fresh_token().await;
+    if token.token.is_empty() {
+        return Err("No authentication token found".to_string());
+    }
+
+    match Request::get(&checklist_url(task_id))
+        .header("Authorization", &format!("Bearer {}", token.token))
+        .send()
+        .await
+    {
+        Ok(response) if response.status() == 200 => response
+            .json::<Vec<ChecklistItem>>()
+            .await
+            .map_err(|e| format!("Failed to parse checklist JSON: {}", e)),
+        Ok(response) => Err(format!("Failed to fetch checklist: HTTP {}", response.status())),
+        Err(e) => Err(format!("Network error: {}", e)),
+    }
+}
+
+pub async fn add_item(task_id: u32, item_dto: ChecklistItemDto) -> Result<ChecklistItem, String> {
+    let token = get_fresh_token().await;
+    if token.token.is_empty() {
+        return Err("No authentication token found".to_string());
+    }
+
+    match Request::post(&checklist_url(task_id))
+        .header("Authorization", &format!("Bearer {}", token.token))
+        .header("Content-Type", "application/json")
+        .json(&item_dto)
+        .unwrap()
+        .send()
+        .await
+    {
+        Ok(response) if response.status() == 200 => response
+            .json::<ChecklistItem>()
+            .await
+            .map_err(|e| format!("Failed to parse checklist item: {}", e)),
+        Ok(response) => {
+            let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+            Err(format!("Failed to add checklist item: HTTP {} - {}", response.status(), error_text))
+        }
+        Err(e) => Err(format!("Network error: {}", e)),
+    }
+}
+
+pub async fn update_item(task_id: u32, item_id: u32, item_dto: ChecklistItemUpdateDto) -> Result<ChecklistItem, String> {
+    let url = format!("{}/{}", checklist_url(task_id), item_id);
+    let token = get_fresh_token().await;
+    if token.token.is_empty() {
+        return Err("No authentication token found".to_string());
+    }
+
+    match Request::put(&url)
+        .header("Authorization", &format!("Bearer {}", token.token))
+        .header("Content-Type", "application/json")
+        .json(&item_dto)
+        .unwrap()
+        .send()
+        .await
+    {
+        Ok(response) if response.status() == 200 => response
+            .json::<ChecklistItem>()
+            .await
+            .map_err(|e| format!("Failed to parse checklist item: {}", e)),
+        Ok(response) => {
+            let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+            Err(format!("Failed to update checklist item: HTTP {} - {}", response.status(), error_text))
+        }
+        Err(e) => Err(format!("Network error: {}", e)),
+    }
+}
+
+pub async fn delete_item(task_id: u32, item_id: u32) -> Result<(), String> {
+    let url = format!("{}/{}", checklist_url(task_id), item_id);
+    let token = get_fresh_token().await;
+    if token.token.is_empty() {
+        return Err("No authentication token found".to_string());
+    }
+
+    match Request::delete(&url)
+        .header("Authorization", &format!("Bearer {}", token.token))
+        .send()
+        .await
+    {
+        Ok(response) if response.status() == 204 => Ok(()),
+        Ok(response) => Err(format!("Failed to delete checklist item: HTTP {}", response.status())),
+        Err(e) => Err(format!("Network error: {}", e)),
+    }
+}
+
+/// Liga ou desliga a conclusão automática da tarefa pelo checklist.
+pub async fn set_auto_complete(task_id: u32, enabled: bool) -> Result<(), String> {
+    let url = format!("{}/tasks/{}", get_api_url(), task_id);
+    let token = get_fresh_token().await;
+    if token.token.is_empty() {
+        return Err("No authentication token found".to_string());
+    }
+
+    match Request::put(&url)
+        .header("Authorization", &format!("Bearer {}", token.token))
+        .header("Content-Type", "application/json")
+        .json(&AutoCompleteDto { checklist_auto_complete: enabled })
+        .unwrap()
+        .send()
+        .await
+    {
+        Ok(response) if response.status() == 200 => Ok(()),
+        Ok(response) => Err(format!("Failed to update task: HTTP {}", response.status())),
+        Err(e) => Err(format!("Network error: {}", e)),
+    }
+}
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use gloo::net::http::Request;
 use crate::types::goal::Goal;
-use super::auth::get_token;
+use super::auth::get_fresh_token;
 use crate::config::get_api_url;
 // use super::API_URL;
 
@@ -24,7 +24,7 @@ pub struct GoalUpdateDto {
 }
 pub async fn get_all_goals() -> Result<Vec<Goal>, String> {
     let url = format!("{}/goals/user", get_api_url());
-    let token = get_token();
+    let token = get_fresh_token().await;
 
     if token.token.is_empty() {
         return Err("No authentication token found".to_string());
@@ -65,7 +65,7 @@ pub async fn get_all_goals() -> Result<Vec<Goal>, String> {
 
 pub async fn create_goal(goal_dto: GoalDto) -> Result<Goal, String> {
     let url = format!("{}/goals/", get_api_url());
-    let token = get_token();
+    let token = get_fresh_token().await;
     
     if token.token.is_empty() {
         return Err("No authentication token found".to_string());
@@ -112,7 +112,7 @@ pub async fn create_goal(goal_dto: GoalDto) -> Result<Goal, String> {
 
 pub async fn update_goal(goal_id: i32, goal_dto: GoalDto) -> Result<Goal, String> {
     let url = format!("{}/goals/{}", get_api_url(), goal_id);
-    let token = get_token();
+    let token = get_fresh_token().await;
     
     if token.token.is_empty() {
         return Err("No authentication token found".to_string());
@@ -170,7 +170,7 @@ pub async fn update_goal(goal_id: i32, goal_dto: GoalDto) -> Result<Goal, String
 
 pub async fn delete_goal(id: i32) -> Result<(), String> {
     let url = format!("{}/goals/{}", get_api_url(), id);
-    let token = get_token();
+    let token = get_fresh_token().await;
     
     if token.token.is_empty() {
         return Err("No authentication token found".to_string());
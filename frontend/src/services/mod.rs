@@ -4,4 +4,11 @@ pub mod tasks;
 pub mod reminder_service;
 pub mod goal_service;
 pub mod report_service;
+pub mod profile_service;
 // pub const API_URL: &str = "http://127.0.0.1:8000";
+pub mod checklist_service;
+pub mod category_service;
+pub mod search_service;
+pub mod trash_service;
+pub mod timer_service;
+pub mod pomodoro_service;
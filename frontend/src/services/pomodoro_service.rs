@@ -0,0 +1,107 @@
+use chrono::{DateTime, Utc};
+use gloo::net::http::Request;
+use serde::{Deserialize, Serialize};
+use crate::config::get_api_url;
+use super::auth::get_fresh_token;
+
+/// Duração, em minutos, de cada tipo de sessão.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Default)]
+pub struct PomodoroSettings {
+    pub work_minutes: i32,
+    pub short_break_minutes: i32,
+    pub long_break_minutes: i32,
+}
+
+/// Sessão Pomodoro; `kind` é `work`, `short_break` ou `long_break`.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct PomodoroSession {
+    pub id: u32,
+    pub task_id: Option<u32>,
+    pub kind: String,
+    pub status: String,
+    pub started_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct PomodoroState {
+    pub settings: PomodoroSettings,
+    pub active: Option<PomodoroSession>,
+    pub next_kind: String,
+    pub completed_today: u32,
+}
+
+#[derive(Serialize)]
+struct StartDto<'a> {
+    kind: &'a str,
+    task_id: Option<u32>,
+}
+
+pub async fn get_state() -> Result<PomodoroState, String> {
+    let url = format!("{}/pomodoro", get_api_url());
+    let token = get_fresh_token().await;
+    if token.token.is_empty() {
+        return Err("No authentication token found".to_string());
+    }
+
+    match Request::get(&url)
+        .header("Authorization", &format!("Bearer {}", token.token))
+        .send()
+        .await
+    {
+        Ok(response) if response.status() == 200 => response
+            .json::<PomodoroState>()
+            .await
+            .map_err(|e| format!("Failed to parse Pomodoro state: {}", e)),
+        Ok(response) => Err(format!("Failed to fetch Pomodoro state: HTTP {}", response.status())),
+        Err(e) => Err(format!("Network error: {}", e)),
+    }
+}
+
+/// Inicia uma sessão; as de foco precisam de `task_id`.
+pub async fn start_session(kind: &str, task_id: Option<u32>) -> Result<PomodoroSession, String> {
+    let url = format!("{}/pomodoro/sessions", get_api_url());
+    let token = get_fresh_token().await;
+    if token.token.is_empty() {
+        return Err("No authentication token found".to_string());
+    }
+
+    match Request::post(&url)
+        .header("Authorization", &format!("Bearer {}", token.token))
+        .header("Content-Type", "application/json")
+        .json(&StartDto { kind, task_id })
+        .unwrap()
+        .send()
+        .await
+    {
+        Ok(response) if response.status() == 201 => response
+            .json::<PomodoroSession>()
+            .await
+            .map_err(|e| format!("Failed to parse Pomodoro session: {}", e)),
+        Ok(response) if response.status() == 409 => Err("Já há uma sessão em andamento.".to_string()),
+        Ok(response) if response.status() == 422 => Err("Escolha a tarefa da sessão de foco.".to_string()),
+        Ok(response) => Err(format!("Failed to start Pomodoro session: HTTP {}", response.status())),
+        Err(e) => Err(format!("Network error: {}", e)),
+    }
+}
+
+pub async fn stop_session() -> Result<PomodoroSession, String> {
+    let url = format!("{}/pomodoro/sessions/stop", get_api_url());
+    let token = get_fresh_token().await;
+    if token.token.is_empty() {
+        return Err("No authentication token found".to_string());
+    }
+
+    match Request::post(&url)
+        .header("Authorization", &format!("Bearer {}", token.token))
+        .send()
+        .await
+    {
+        Ok(response) if response.status() == 200 => response
+            .json::<PomodoroSession>()
+            .await
+            .map_err(|e| format!("Failed to parse Pomodoro session: {}", e)),
+        Ok(response) => Err(format!("Failed to stop Pomodoro session: HTTP {}", response.status())),
+        Err(e) => Err(format!("Network error: {}", e)),
+    }
+}
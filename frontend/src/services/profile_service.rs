@@ -0,0 +1,75 @@
+use gloo::net::http::Request;
+use gloo_storage::{LocalStorage, Storage};
+use js_sys::{Date, Object, Reflect};
+use wasm_bindgen::JsValue;
+use crate::types::profile::{Profile, ProfileUpdate};
+use super::auth::{account_result, get_fresh_token, AccountResult};
+use crate::config::get_api_url;
+
+/// Chave do LocalStorage onde fica a última versão conhecida do perfil.
+const PROFILE_KEY: &str = "profile";
+
+/// Perfil salvo localmente, usado enquanto o servidor não responde.
+pub fn cached_profile() -> Option<Profile> {
+    LocalStorage::get::<Profile>(PROFILE_KEY).ok()
+}
+
+pub async fn get_profile() -> Result<Profile, AccountResult> {
+    let token = get_fresh_token().await;
+    let url = format!("{}/me", get_api_url());
+    let response = Request::get(&url)
+        .header("Authorization", &format!("Bearer {}", token.token))
+        .send()
+        .await;
+    profile_result(response).await
+}
+
+pub async fn update_profile(profile: &ProfileUpdate) -> Result<Profile, AccountResult> {
+    let token = get_fresh_token().await;
+    let url = format!("{}/me", get_api_url());
+    let response = Request::put(&url)
+        .header("Authorization", &format!("Bearer {}", token.token))
+        .json(profile)
+        .unwrap()
+        .send()
+        .await;
+    profile_result(response).await
+}
+
+async fn profile_result(
+    response: Result<gloo::net::http::Response, gloo::net::Error>,
+) -> Result<Profile, AccountResult> {
+    match response {
+        Ok(response) if response.status() == 200 => {
+            let profile = response
+                .json::<Profile>()
+                .await
+                .map_err(|_| AccountResult::NetworkError)?;
+            let _ = LocalStorage::set(PROFILE_KEY, &profile);
+            Ok(profile)
+        }
+        other => Err(account_result(other)),
+    }
+}
+
+/// Hora atual (HH:MM) no fuso e no formato de relógio do perfil; sem perfil,
+/// usa o fuso do navegador e o relógio de 12 horas.
+pub fn format_clock(profile: Option<&Profile>) -> String {
+    let options = Object::new();
+    let _ = Reflect::set(&options, &"hour".into(), &"2-digit".into());
+    let _ = Reflect::set(&options, &"minute".into(), &"2-digit".into());
+    let hour12 = profile.is_none_or(|profile| !profile.use_24h_clock);
+    let _ = Reflect::set(&options, &"hour12".into(), &JsValue::from_bool(hour12));
+
+    let locale = match profile {
+        Some(profile) => {
+            let _ = Reflect::set(&options, &"timeZone".into(), &profile.timezone.as_str().into());
+            profile.locale.clone()
+        }
+        None => "en-US".to_string(),
+    };
+
+    Date::new_0()
+        .to_locale_time_string_with_options(&locale, &options)
+        .into()
+}
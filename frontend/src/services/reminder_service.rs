@@ -3,7 +3,7 @@ use gloo::net::http::Request;
 use serde::{Serialize, Deserialize};
 use crate::types::reminder::Reminder;
 use crate::config::get_api_url;
-use super::auth::get_token;
+use super::auth::get_fresh_token;
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct ReminderDto {
@@ -27,7 +27,7 @@ pub struct ReminderUpdateDto {
 
 pub async fn get_all_reminders() -> Result<Vec<Reminder>, String> {
     let url = format!("{}/reminders/user", get_api_url());
-    let token = get_token();
+    let token = get_fresh_token().await;
 
     if token.token.is_empty() {
         return Err("No authentication token found".to_string());
@@ -54,7 +54,7 @@ pub async fn get_all_reminders() -> Result<Vec<Reminder>, String> {
 
 pub async fn create_reminder(reminder_info: &ReminderDto) -> ReminderResult {
     let url = format!("{}/reminders", get_api_url());
-    let token = get_token();
+    let token = get_fresh_token().await;
     
     if token.token.is_empty() {
         return ReminderResult::NetworkError("No authentication token found".into());
@@ -91,7 +91,7 @@ pub async fn create_reminder(reminder_info: &ReminderDto) -> ReminderResult {
 
 pub async fn delete_reminder(reminder_id: u32) -> Result<(), String> {
     let url = format!("{}/reminders/{}", get_api_url(), reminder_id);
-    let token = get_token();
+    let token = get_fresh_token().await;
 
     if token.token.is_empty() {
         return Err("No authentication token found".into());
@@ -117,7 +117,7 @@ pub async fn delete_reminder(reminder_id: u32) -> Result<(), String> {
 
 pub async fn update_reminder(reminder_id: u32, dto: ReminderUpdateDto) -> Result<(), String> {
     let url = format!("{}/reminders/{}", get_api_url(), reminder_id);
-    let token = get_token();
+    let token = get_fresh_token().await;
     if token.token.is_empty() {
         return Err("No authentication token found".to_string());
     }
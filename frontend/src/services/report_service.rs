@@ -1,6 +1,6 @@
 use gloo_net::http::Request;
 use web_sys::console;
-use super::auth::get_token;
+use super::auth::get_fresh_token;
 use crate::config::get_api_url;
 use crate::types::report::{StatsYearResponse, StatsMonthResponse, StatsWeekResponse};
 pub struct ReportService;
@@ -15,7 +15,7 @@ impl ReportService {
         let url = format!("{}/reports/stats/year/{}", get_api_url(), year);
 
         console::log_1(&format!("Buscando estatísticas do ano: {}", year).into());
-        let token = get_token();
+        let token = get_fresh_token().await;
         if token.token.is_empty() {
             return Err("No authentication token found".to_string());
         }
@@ -49,7 +49,7 @@ impl ReportService {
 
     /// Busca estatísticas mensais
     pub async fn fetch_month_stats(year: i32, month: i32) -> Result<StatsMonthResponse, String> {
-        let token = get_token();
+        let token = get_fresh_token().await;
         if token.token.is_empty() {
             return Err("No authentication token found".to_string());
         }
@@ -88,7 +88,7 @@ impl ReportService {
 
     /// Busca estatísticas semanais
     pub async fn fetch_week_stats(year: i32, week: i32) -> Result<StatsWeekResponse, String> {
-        let token = get_token();
+        let token = get_fresh_token().await;
         if token.token.is_empty() {
             return Err("No authentication token found".to_string());
         }
@@ -0,0 +1,26 @@
+use gloo::net::http::Request;
+use crate::types::search::SearchResult;
+use crate::config::get_api_url;
+use super::auth::get_fresh_token;
+
+pub async fn search(query: &str) -> Result<Vec<SearchResult>, String> {
+    let url = format!("{}/search", get_api_url());
+    let token = get_fresh_token().await;
+    if token.token.is_empty() {
+        return Err("No authentication token found".to_string());
+    }
+
+    match Request::get(&url)
+        .query([("q", query)])
+        .header("Authorization", &format!("Bearer {}", token.token))
+        .send()
+        .await
+    {
+        Ok(response) if response.status() == 200 => response
+            .json::<Vec<SearchResult>>()
+            .await
+            .map_err(|e| format!("Failed to parse search results: {}", e)),
+        Ok(response) => Err(format!("Failed to search: HTTP {}", response.status())),
+        Err(e) => Err(format!("Network error: {}", e)),
+    }
+}
@@ -1,9 +1,9 @@
-use chrono::DateTime;
+use chrono::{DateTime, NaiveDate};
 use gloo::net::http::Request;
 use serde::{Serialize, Deserialize};
 use crate::types::Task;
 use crate::config::get_api_url;
-use super::auth::get_token;
+use super::auth::get_fresh_token;
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct TaskDto {
@@ -13,6 +13,29 @@ pub struct TaskDto {
     pub begin_date: DateTime<chrono::Utc>,
     #[serde(rename = "type")]
     pub task_type: String,
+    /// Fim explícito, usado pelos tipos `Personalizado` e `DiaInteiro` (último dia).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub complete_date: Option<DateTime<chrono::Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_minutes: Option<u32>,
+    #[serde(default)]
+    pub all_day: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recurrence: Option<RecurrenceDto>,
+}
+
+/// Regra de repetição enviada na criação da tarefa.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct RecurrenceDto {
+    /// `daily`, `weekly` ou `monthly`.
+    pub frequency: String,
+    pub interval: u32,
+    /// `MO`, `TU`, `WE`, `TH`, `FR`, `SA`, `SU`; apenas para `weekly`.
+    pub weekdays: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub until: Option<DateTime<chrono::Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,
 }
 
 
@@ -20,18 +43,63 @@ pub struct TaskDto {
 pub struct TaskUpdateDto {
     pub title: String,
     pub description: String,
-    pub status: String,
+    /// Ausente ao editar uma série inteira, para não sobrescrever o status das ocorrências.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    /// Novo início; a tarefa mantém a duração atual.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub begin_date: Option<DateTime<chrono::Utc>>,
+}
+
+/// Corpo do `409`: a tarefa em conflito de horário ou os bloqueadores pendentes.
+#[derive(Deserialize)]
+struct ConflictBody {
+    message: String,
+    conflict: Option<Conflict>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Conflict {
+    // A ocorrência também traz `blocked_by` (só com ids), então é testada primeiro.
+    Task(Box<Task>),
+    Blocked { blocked_by: Vec<Blocker> },
+}
+
+#[derive(Deserialize)]
+struct Blocker {
+    title: String,
+}
+
+/// Mensagem explicando o conflito a partir do corpo do `409`.
+fn conflict_message(body: &str) -> String {
+    match serde_json::from_str::<ConflictBody>(body) {
+        Ok(ConflictBody { conflict: Some(Conflict::Task(task)), .. }) => format!(
+            "O horário conflita com a tarefa \"{}\" ({} - {}).",
+            task.title,
+            task.begin_date.format("%d/%m %H:%M"),
+            task.complete_date.format("%d/%m %H:%M")
+        ),
+        Ok(ConflictBody { conflict: Some(Conflict::Blocked { blocked_by }), .. }) => {
+            let titles: Vec<String> = blocked_by.into_iter().map(|blocker| format!("\"{}\"", blocker.title)).collect();
+            format!("A tarefa está bloqueada por: {}. Conclua-as primeiro.", titles.join(", "))
+        }
+        Ok(ConflictBody { message, .. }) => message,
+        Err(_) => "O horário conflita com outra tarefa.".to_string(),
+    }
 }
 
 pub enum TaskResult {
     Success(Task),
     InvalidFields,
+    /// Horário ocupado; traz a explicação do conflito.
+    Conflict(String),
     NetworkError(String),
 }
 
 pub async fn delete_task(_task_id: u32) -> Result<(), String> {
     let url = format!("{}/tasks/{}", get_api_url(), _task_id as i32);
-    let token = get_token();
+    let token = get_fresh_token().await;
     if token.token.is_empty() {
         return Err("No authentication token found".to_string());
     }
@@ -55,50 +123,9 @@ pub async fn delete_task(_task_id: u32) -> Result<(), String> {
     }
 }
 
-pub async fn get_all_tasks() -> Result<Vec<Task>, String> {
-    let url = format!("{}/tasks", get_api_url());
-    let token = get_token();
-
-    if token.token.is_empty() {
-        return Err("No authentication token found".to_string());
-    }
-
-    match Request::get(&url)
-        .header("Authorization", &format!("Bearer {}", token.token))
-        .send()
-        .await
-    {
-        Ok(response) => {
-            if response.status() == 200 {
-                match response.json::<Vec<Task>>().await {
-                    Ok(tasks) => {
-                        web_sys::console::log_1(&format!("Fetched {} tasks", tasks.len()).into());
-                        Ok(tasks)
-                    }
-                    Err(e) => {
-                        let error_msg = format!("Failed to parse tasks JSON: {}", e);
-                        web_sys::console::log_1(&error_msg.clone().into());
-                        Err(error_msg)
-                    }
-                }
-            } else {
-                let error_msg = format!("Failed to fetch tasks: HTTP {}", response.status());
-                web_sys::console::log_1(&error_msg.clone().into());
-                Err(error_msg)
-            }
-        }
-        Err(e) => {
-            let error_msg = format!("Network error: {}", e);
-            web_sys::console::log_1(&error_msg.clone().into());
-            Err(error_msg)
-        }
-    }
-}
-
-
 pub async fn create_task(task_info: &TaskDto) -> TaskResult {
     let url = format!("{}/tasks", get_api_url());
-    let token = get_token();
+    let token = get_fresh_token().await;
     
     // Check if we have a valid token
     if token.token.is_empty() {
@@ -120,6 +147,10 @@ pub async fn create_task(task_info: &TaskDto) -> TaskResult {
             }
             400 => TaskResult::InvalidFields,
             401 => TaskResult::NetworkError("Unauthorized - please login again".into()),
+            409 => {
+                let body = response.text().await.unwrap_or_default();
+                TaskResult::Conflict(conflict_message(&body))
+            }
             422 => {
                 // Try to get the response body for more details
                 let error_text = response.text().await.unwrap_or_else(|_| "Unknown validation error".to_string());
@@ -134,9 +165,140 @@ pub async fn create_task(task_info: &TaskDto) -> TaskResult {
     }
 }
 
+/// Tarefa atrasada, como vem em `GET /tasks/overdue`.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct OverdueTask {
+    pub id: u32,
+    pub title: String,
+    pub complete_date: DateTime<chrono::Utc>,
+}
+
+/// Tarefas atrasadas ainda não concluídas, mostradas no contador da agenda.
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct OverdueTasks {
+    pub count: u32,
+    pub tasks: Vec<OverdueTask>,
+}
+
+pub async fn get_overdue_tasks() -> Result<OverdueTasks, String> {
+    let url = format!("{}/tasks/overdue", get_api_url());
+    let token = get_fresh_token().await;
+    if token.token.is_empty() {
+        return Err("No authentication token found".to_string());
+    }
+
+    match Request::get(&url)
+        .header("Authorization", &format!("Bearer {}", token.token))
+        .send()
+        .await
+    {
+        Ok(response) if response.status() == 200 => response
+            .json::<OverdueTasks>()
+            .await
+            .map_err(|e| format!("Failed to parse overdue tasks: {}", e)),
+        Ok(response) => Err(format!("Failed to fetch overdue tasks: HTTP {}", response.status())),
+        Err(e) => Err(format!("Network error: {}", e)),
+    }
+}
+
+/// Ocorrências entre `from` e `to` (inclusive), com as tarefas repetidas expandidas.
+pub async fn get_task_occurrences(from: NaiveDate, to: NaiveDate) -> Result<Vec<Task>, String> {
+    let url = format!(
+        "{}/tasks/occurrences?from={}&to={}",
+        get_api_url(),
+        from.format("%Y-%m-%d"),
+        to.format("%Y-%m-%d")
+    );
+    let token = get_fresh_token().await;
+    if token.token.is_empty() {
+        return Err("No authentication token found".to_string());
+    }
+
+    match Request::get(&url)
+        .header("Authorization", &format!("Bearer {}", token.token))
+        .send()
+        .await
+    {
+        Ok(response) => {
+            if response.status() == 200 {
+                response
+                    .json::<Vec<Task>>()
+                    .await
+                    .map_err(|e| format!("Failed to parse occurrences JSON: {}", e))
+            } else {
+                let error_msg = format!("Failed to fetch occurrences: HTTP {}", response.status());
+                web_sys::console::log_1(&error_msg.clone().into());
+                Err(error_msg)
+            }
+        }
+        Err(e) => Err(format!("Network error: {}", e)),
+    }
+}
+
+/// Edita só uma ocorrência de uma tarefa repetida.
+pub async fn update_occurrence(task: &Task, task_dto: TaskUpdateDto) -> Result<(), String> {
+    let url = occurrence_url(task)?;
+    let token = get_fresh_token().await;
+    if token.token.is_empty() {
+        return Err("No authentication token found".to_string());
+    }
+
+    match Request::put(&url)
+        .header("Authorization", &format!("Bearer {}", token.token))
+        .header("Content-Type", "application/json")
+        .json(&task_dto)
+        .unwrap()
+        .send()
+        .await
+    {
+        Ok(response) => {
+            if response.status() == 200 {
+                Ok(())
+            } else if response.status() == 409 {
+                let body = response.text().await.unwrap_or_default();
+                Err(conflict_message(&body))
+            } else {
+                let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+                Err(format!("Failed to update occurrence: HTTP {} - {}", response.status(), error_text))
+            }
+        }
+        Err(e) => Err(format!("Network error: {}", e)),
+    }
+}
+
+/// Pula só uma ocorrência de uma tarefa repetida; as demais continuam.
+pub async fn skip_occurrence(task: &Task) -> Result<(), String> {
+    let url = occurrence_url(task)?;
+    let token = get_fresh_token().await;
+    if token.token.is_empty() {
+        return Err("No authentication token found".to_string());
+    }
+
+    match Request::delete(&url)
+        .header("Authorization", &format!("Bearer {}", token.token))
+        .send()
+        .await
+    {
+        Ok(response) => {
+            if response.status() == 204 {
+                Ok(())
+            } else {
+                let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+                Err(format!("Failed to skip occurrence: HTTP {} - {}", response.status(), error_text))
+            }
+        }
+        Err(e) => Err(format!("Network error: {}", e)),
+    }
+}
+
+fn occurrence_url(task: &Task) -> Result<String, String> {
+    let start = task.occurrence_start.ok_or_else(|| "Task is not an occurrence".to_string())?;
+    Ok(format!("{}/tasks/{}/occurrences/{}", get_api_url(), task.id, start.timestamp()))
+}
+
 pub async fn update_task_with_dto(task_id: u32, task_dto: TaskUpdateDto) -> Result<(), String> {
     let url = format!("{}/tasks/{}", get_api_url(), task_id);
-    let token = get_token();
+    let token = get_fresh_token().await;
     if token.token.is_empty() {
         return Err("No authentication token found".to_string());
     }
@@ -152,6 +314,9 @@ pub async fn update_task_with_dto(task_id: u32, task_dto: TaskUpdateDto) -> Resu
         Ok(response) => {
             if response.status() == 200 || response.status() == 204 {
                 Ok(())
+            } else if response.status() == 409 {
+                let body = response.text().await.unwrap_or_default();
+                Err(conflict_message(&body))
             } else {
                 let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
                 let error_msg = format!("Failed to update task with DTO: HTTP {} - {}", response.status(), error_text);
@@ -161,4 +326,126 @@ pub async fn update_task_with_dto(task_id: u32, task_dto: TaskUpdateDto) -> Resu
         }
         Err(e) => Err(format!("Network error: {}", e)),
     }
-}
\ No newline at end of file
+}
+/// Ação aplicada às tarefas selecionadas em `POST /tasks/bulk`.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum BulkAction {
+    SetStatus { status: String },
+    SetCategory { category: String },
+    /// Desloca as tarefas em dias, mantendo a duração.
+    Shift { days: i32 },
+    Delete,
+}
+
+#[derive(Serialize)]
+struct BulkDto<'a> {
+    task_ids: &'a [u32],
+    #[serde(flatten)]
+    action: &'a BulkAction,
+}
+
+#[derive(Deserialize)]
+struct BulkResult {
+    results: Vec<BulkItem>,
+}
+
+#[derive(Deserialize)]
+struct BulkItem {
+    id: u32,
+    /// Mesmo corpo de erro da operação avulsa.
+    error: Option<serde_json::Value>,
+}
+
+/// Aplica a ação a todas as tarefas de uma vez. Se alguma for recusada, nada
+/// é gravado e o erro lista o motivo de cada uma.
+pub async fn bulk_update(task_ids: &[u32], action: &BulkAction) -> Result<(), String> {
+    let url = format!("{}/tasks/bulk", get_api_url());
+    let token = get_fresh_token().await;
+    if token.token.is_empty() {
+        return Err("No authentication token found".to_string());
+    }
+
+    let response = Request::post(&url)
+        .header("Authorization", &format!("Bearer {}", token.token))
+        .header("Content-Type", "application/json")
+        .json(&BulkDto { task_ids, action })
+        .unwrap()
+        .send()
+        .await
+        .map_err(|e| format!("Network error: {}", e))?;
+
+    if response.status() == 200 {
+        return Ok(());
+    }
+    let body = response.text().await.unwrap_or_default();
+    match serde_json::from_str::<BulkResult>(&body) {
+        Ok(result) => {
+            let reasons: Vec<String> = result
+                .results
+                .into_iter()
+                .filter_map(|item| {
+                    let error = item.error?;
+                    let message = if error["code"] == "conflict" {
+                        conflict_message(&error.to_string())
+                    } else {
+                        error["message"].as_str().unwrap_or("Erro desconhecido").to_string()
+                    };
+                    Some(format!("Tarefa {}: {}", item.id, message))
+                })
+                .collect();
+            Err(format!("Nenhuma tarefa foi alterada.\n{}", reasons.join("\n")))
+        }
+        Err(_) => Err(format!("Failed to update tasks: HTTP {} - {}", response.status(), body)),
+    }
+}
+
+#[derive(Serialize)]
+struct RolloverDto {
+    from: String,
+    to: String,
+}
+
+/// Tarefa que não coube no dia de destino e ficou no dia de origem.
+#[derive(Deserialize, Debug, Clone)]
+pub struct UnplacedTask {
+    pub title: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct RolloverResult {
+    pub moved: Vec<Task>,
+    pub unplaced: Vec<UnplacedTask>,
+}
+
+/// Leva as tarefas pendentes do dia `from` para o dia `to`.
+pub async fn rollover_tasks(from: NaiveDate, to: NaiveDate) -> Result<RolloverResult, String> {
+    let url = format!("{}/tasks/rollover", get_api_url());
+    let token = get_fresh_token().await;
+    if token.token.is_empty() {
+        return Err("No authentication token found".to_string());
+    }
+
+    let body = RolloverDto {
+        from: from.format("%Y-%m-%d").to_string(),
+        to: to.format("%Y-%m-%d").to_string(),
+    };
+    let response = Request::post(&url)
+        .header("Authorization", &format!("Bearer {}", token.token))
+        .header("Content-Type", "application/json")
+        .json(&body)
+        .unwrap()
+        .send()
+        .await
+        .map_err(|e| format!("Network error: {}", e))?;
+
+    if response.status() == 200 {
+        response
+            .json::<RolloverResult>()
+            .await
+            .map_err(|e| format!("Failed to parse rollover result: {}", e))
+    } else {
+        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+        Err(format!("Failed to roll over tasks: HTTP {} - {}", response.status(), error_text))
+    }
+}
@@ -0,0 +1,89 @@
+use chrono::{DateTime, Utc};
+use gloo::net::http::Request;
+use serde::{Deserialize, Serialize};
+use crate::config::get_api_url;
+use super::auth::get_fresh_token;
+
+/// Registro de tempo de uma tarefa; sem `stopped_at`, o cronômetro está ligado.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct TimeEntry {
+    pub id: u32,
+    pub task_id: u32,
+    pub started_at: DateTime<Utc>,
+    pub stopped_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize)]
+struct TimerStartDto {
+    note: Option<String>,
+}
+
+/// Cronômetro ligado do usuário, se houver.
+pub async fn get_running_timer() -> Result<Option<TimeEntry>, String> {
+    let url = format!("{}/tasks/timer", get_api_url());
+    let token = get_fresh_token().await;
+    if token.token.is_empty() {
+        return Err("No authentication token found".to_string());
+    }
+
+    match Request::get(&url)
+        .header("Authorization", &format!("Bearer {}", token.token))
+        .send()
+        .await
+    {
+        Ok(response) if response.status() == 200 => response
+            .json::<Option<TimeEntry>>()
+            .await
+            .map_err(|e| format!("Failed to parse timer: {}", e)),
+        Ok(response) => Err(format!("Failed to fetch timer: HTTP {}", response.status())),
+        Err(e) => Err(format!("Network error: {}", e)),
+    }
+}
+
+pub async fn start_timer(task_id: u32) -> Result<TimeEntry, String> {
+    let url = format!("{}/tasks/{}/timer/start", get_api_url(), task_id);
+    let token = get_fresh_token().await;
+    if token.token.is_empty() {
+        return Err("No authentication token found".to_string());
+    }
+
+    match Request::post(&url)
+        .header("Authorization", &format!("Bearer {}", token.token))
+        .header("Content-Type", "application/json")
+        .json(&TimerStartDto { note: None })
+        .unwrap()
+        .send()
+        .await
+    {
+        Ok(response) if response.status() == 201 => response
+            .json::<TimeEntry>()
+            .await
+            .map_err(|e| format!("Failed to parse timer: {}", e)),
+        Ok(response) if response.status() == 409 => {
+            Err("Já há um cronômetro ligado em outra tarefa. Pare-o primeiro.".to_string())
+        }
+        Ok(response) => Err(format!("Failed to start timer: HTTP {}", response.status())),
+        Err(e) => Err(format!("Network error: {}", e)),
+    }
+}
+
+pub async fn stop_timer() -> Result<TimeEntry, String> {
+    let url = format!("{}/tasks/timer/stop", get_api_url());
+    let token = get_fresh_token().await;
+    if token.token.is_empty() {
+        return Err("No authentication token found".to_string());
+    }
+
+    match Request::post(&url)
+        .header("Authorization", &format!("Bearer {}", token.token))
+        .send()
+        .await
+    {
+        Ok(response) if response.status() == 200 => response
+            .json::<TimeEntry>()
+            .await
+            .map_err(|e| format!("Failed to parse timer: {}", e)),
+        Ok(response) => Err(format!("Failed to stop timer: HTTP {}", response.status())),
+        Err(e) => Err(format!("Network error: {}", e)),
+    }
+}
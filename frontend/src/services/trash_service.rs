@@ -0,0 +1,25 @@
+use gloo::net::http::Request;
+use crate::config::get_api_url;
+use super::auth::get_fresh_token;
+
+/// Tira da lixeira um item excluído; `kind` é `task`, `goal`, `reminder` ou `note`.
+pub async fn restore(kind: &str, id: i32) -> Result<(), String> {
+    let url = format!("{}/trash/{}/{}/restore", get_api_url(), kind, id);
+    let token = get_fresh_token().await;
+    if token.token.is_empty() {
+        return Err("No authentication token found".to_string());
+    }
+
+    match Request::post(&url)
+        .header("Authorization", &format!("Bearer {}", token.token))
+        .send()
+        .await
+    {
+        Ok(response) if response.status() == 204 => Ok(()),
+        Ok(response) if response.status() == 409 => {
+            Err("O horário da tarefa já está ocupado por outra tarefa.".to_string())
+        }
+        Ok(response) => Err(format!("Failed to restore item: HTTP {}", response.status())),
+        Err(e) => Err(format!("Network error: {}", e)),
+    }
+}
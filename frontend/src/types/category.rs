@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Category {
+    pub id: u32,
+    pub name: String,
+    pub color: String,
+    pub icon: Option<String>,
+}
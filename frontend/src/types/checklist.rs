@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ChecklistItem {
+    pub id: u32,
+    pub task_id: u32,
+    pub title: String,
+    pub position: u32,
+    pub done: bool,
+}
+
+/// Progresso do checklist enviado junto com a tarefa.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChecklistProgress {
+    pub total: u32,
+    pub done: u32,
+    pub ratio: f64,
+}
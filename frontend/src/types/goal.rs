@@ -4,6 +4,8 @@ pub struct Goal {
     pub name: String,
     pub description: Option<String>,
     pub category: Option<String>,
+    #[serde(default)]
+    pub category_id: Option<i32>,
     pub status: String,
     pub goal_type: String,
     pub date_start: String,
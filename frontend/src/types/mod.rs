@@ -4,6 +4,10 @@ pub mod task;
 pub mod reminder;
 pub mod goal;
 pub mod report;
+pub mod profile;
+pub mod checklist;
+pub mod category;
+pub mod search;
 // Re-export commonly used types
 pub use task::{Task, TaskDuration};
 pub use report::{StatsYearResponse, StatsMonthResponse, StatsWeekResponse};
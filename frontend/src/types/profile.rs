@@ -0,0 +1,22 @@
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Profile {
+    pub id: i32,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub email: Option<String>,
+    pub timezone: String,
+    pub locale: String,
+    pub week_start_day: i16,
+    pub use_24h_clock: bool,
+    pub two_factor_enabled: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ProfileUpdate {
+    pub display_name: Option<String>,
+    pub email: Option<String>,
+    pub timezone: String,
+    pub locale: String,
+    pub week_start_day: i16,
+    pub use_24h_clock: bool,
+}
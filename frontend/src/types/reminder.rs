@@ -7,5 +7,7 @@ pub struct Reminder {
     pub id: i32,
     pub name: String,
     pub category: String,
+    #[serde(default)]
+    pub category_id: Option<i32>,
     pub date_end: DateTime<chrono::Utc>,
 }
\ No newline at end of file
@@ -1,4 +1,5 @@
 use serde::{Serialize, Deserialize};
+use std::collections::BTreeMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct StatsYearResponse {
@@ -13,6 +14,14 @@ pub struct StatsYearResponse {
     pub most_productive_month_tasks: String,
     pub most_productive_week_tasks: String,
     pub classification_tasks: String,
+    #[serde(default)]
+    pub tracked_hours_tasks: f64,
+    #[serde(default)]
+    pub tracked_hours_by_category_tasks: BTreeMap<String, f64>,
+    #[serde(default)]
+    pub tracked_hours_by_shift_tasks: BTreeMap<String, f64>,
+    #[serde(default)]
+    pub completed_pomodoros: u32,
     pub total_goals: i64,
     pub pendent_goals: i64,
     pub executed_goals: i64,
@@ -38,6 +47,14 @@ pub struct StatsMonthResponse {
     pub most_used_category_tasks: String,
     pub most_productive_week_tasks: String,
     pub classification_tasks: String,
+    #[serde(default)]
+    pub tracked_hours_tasks: f64,
+    #[serde(default)]
+    pub tracked_hours_by_category_tasks: BTreeMap<String, f64>,
+    #[serde(default)]
+    pub tracked_hours_by_shift_tasks: BTreeMap<String, f64>,
+    #[serde(default)]
+    pub completed_pomodoros: u32,
     pub total_goals: i64,
     pub pendent_goals: i64,
     pub executed_goals: i64,
@@ -62,6 +79,14 @@ pub struct StatsWeekResponse {
     pub most_used_category_tasks: String,
     pub most_productive_day_tasks: String,
     pub classification_tasks: String,
+    #[serde(default)]
+    pub tracked_hours_tasks: f64,
+    #[serde(default)]
+    pub tracked_hours_by_category_tasks: BTreeMap<String, f64>,
+    #[serde(default)]
+    pub tracked_hours_by_shift_tasks: BTreeMap<String, f64>,
+    #[serde(default)]
+    pub completed_pomodoros: u32,
     pub total_goals: i64,
     pub pendent_goals: i64,
     pub executed_goals: i64,
@@ -88,6 +113,10 @@ impl Default for StatsYearResponse {
             most_productive_month_tasks: "N/A".to_string(),
             most_productive_week_tasks: "N/A".to_string(),
             classification_tasks: "N/A".to_string(),
+            tracked_hours_tasks: 0.0,
+            tracked_hours_by_category_tasks: BTreeMap::new(),
+            tracked_hours_by_shift_tasks: BTreeMap::new(),
+            completed_pomodoros: 0,
             total_goals: 0,
             executed_goals: 0,
             pendent_goals: 0,
@@ -116,6 +145,10 @@ impl Default for StatsMonthResponse {
             most_used_category_tasks: "N/A".to_string(),
             most_productive_week_tasks: "N/A".to_string(),
             classification_tasks: "N/A".to_string(),
+            tracked_hours_tasks: 0.0,
+            tracked_hours_by_category_tasks: BTreeMap::new(),
+            tracked_hours_by_shift_tasks: BTreeMap::new(),
+            completed_pomodoros: 0,
             total_goals: 0,
             executed_goals: 0,
             pendent_goals: 0,
@@ -143,6 +176,10 @@ impl Default for StatsWeekResponse {
             most_used_category_tasks: "N/A".to_string(),
             most_productive_day_tasks: "N/A".to_string(),
             classification_tasks: "N/A".to_string(),
+            tracked_hours_tasks: 0.0,
+            tracked_hours_by_category_tasks: BTreeMap::new(),
+            tracked_hours_by_shift_tasks: BTreeMap::new(),
+            completed_pomodoros: 0,
             total_goals: 0,
             executed_goals: 0,
             pendent_goals: 0,
@@ -0,0 +1,14 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Item encontrado pela busca: `task`, `goal`, `reminder` ou `note`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SearchResult {
+    pub kind: String,
+    pub id: i32,
+    pub title: String,
+    /// Trecho com os termos encontrados entre `<mark>` e `</mark>`.
+    pub snippet: String,
+    pub date: DateTime<Utc>,
+    pub rank: f32,
+}
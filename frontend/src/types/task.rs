@@ -1,6 +1,7 @@
 use std::fmt;
 use chrono::DateTime;
 use serde::{Deserialize, Serialize};
+use super::checklist::ChecklistProgress;
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum TaskDuration {
@@ -11,6 +12,10 @@ pub enum TaskDuration {
     Tarde,
     Noite,
     Madrugada,
+    /// Fim escolhido livremente, inclusive em outro dia.
+    Personalizado,
+    /// Ocupa o dia inteiro (ou vários dias).
+    DiaInteiro,
 }
 
 impl TaskDuration {
@@ -23,6 +28,8 @@ impl TaskDuration {
             TaskDuration::Tarde,
             TaskDuration::Noite,
             TaskDuration::Madrugada,
+            TaskDuration::Personalizado,
+            TaskDuration::DiaInteiro,
         ]
     }
 
@@ -35,6 +42,8 @@ impl TaskDuration {
             TaskDuration::Tarde => "Tarde",
             TaskDuration::Noite => "Noite",
             TaskDuration::Madrugada => "Madrugada",
+            TaskDuration::Personalizado => "Personalizado",
+            TaskDuration::DiaInteiro => "Dia Inteiro",
         }
     }
 
@@ -47,6 +56,8 @@ impl TaskDuration {
             TaskDuration::Tarde => "Tarde",
             TaskDuration::Noite => "Noite",
             TaskDuration::Madrugada => "Madrugada",
+            TaskDuration::Personalizado => "Personalizado",
+            TaskDuration::DiaInteiro => "DiaInteiro",
         }
     }
 
@@ -59,6 +70,8 @@ impl TaskDuration {
             "Tarde" => Some(TaskDuration::Tarde),
             "Noite" => Some(TaskDuration::Noite),
             "Madrugada" => Some(TaskDuration::Madrugada),
+            "Personalizado" => Some(TaskDuration::Personalizado),
+            "DiaInteiro" => Some(TaskDuration::DiaInteiro),
             _ => None,
         }
     }
@@ -86,8 +99,26 @@ pub struct Task {
     pub begin_date: DateTime<chrono::Utc>,
     pub complete_date: DateTime<chrono::Utc>,
     pub category: String,
+    #[serde(default)]
+    pub category_id: Option<u32>,
     #[serde(rename = "type")]
     pub task_type: String,
+    /// Início original da ocorrência, presente nas respostas de `/tasks/occurrences`.
+    #[serde(default)]
+    pub occurrence_start: Option<DateTime<chrono::Utc>>,
+    #[serde(default)]
+    pub recurring: bool,
+    #[serde(default)]
+    pub checklist: ChecklistProgress,
+    /// Conclui a tarefa quando todos os itens do checklist forem marcados.
+    #[serde(default)]
+    pub checklist_auto_complete: bool,
+    /// Tarefas pendentes que impedem a conclusão desta.
+    #[serde(default)]
+    pub blocked_by: Vec<u32>,
+    /// Tempo registrado na tarefa, em segundos.
+    #[serde(default)]
+    pub tracked_seconds: i64,
 }
 
 #[allow(dead_code)]
@@ -112,7 +143,14 @@ impl Task {
             begin_date,
             complete_date,
             category,
+            category_id: None,
             task_type,
+            occurrence_start: None,
+            recurring: false,
+            checklist: ChecklistProgress::default(),
+            checklist_auto_complete: false,
+            blocked_by: Vec::new(),
+            tracked_seconds: 0,
         }
     }
 }
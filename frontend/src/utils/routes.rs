@@ -1,7 +1,10 @@
+use crate::pages::account::Account;
 use crate::pages::home::Home;
 use crate::pages::login::Login;
+use crate::pages::password_reset::PasswordReset;
 use crate::pages::register::Register;
 use crate::services::auth::{self, verify_token, Token};
+use wasm_bindgen_futures::spawn_local;
 use yew::prelude::*;
 use yew_router::prelude::*;
 
@@ -13,6 +16,10 @@ pub enum Route {
     Login,
     #[at("/register")]
     Register,
+    #[at("/account")]
+    Account,
+    #[at("/reset-password")]
+    PasswordReset,
 
 }
 
@@ -29,8 +36,13 @@ fn switch(route: Route) -> Html {
                     }
                 };
             }
-            // possui token mas está invalido, entao vai para login
+            // possui token mas está expirado, tenta renovar com o refresh token
             if !verify_token(&token) {
+                if !token.refresh_token.is_empty() {
+                    return html! {
+                        <RestoreSession/>
+                    };
+                }
                 return {
                     html! {
                         <Login/>
@@ -47,6 +59,42 @@ fn switch(route: Route) -> Html {
         Route::Register => html! {
             <Register/>
         },
+        Route::PasswordReset => html! {
+            <PasswordReset/>
+        },
+        Route::Account => {
+            if auth::get_token().token.is_empty() {
+                return html! {
+                    <Login/>
+                };
+            }
+            html! {
+                <Account/>
+            }
+        }
+    }
+}
+
+/// Renova a sessão em segundo plano; vai para o login se o refresh token
+/// também tiver sido revogado ou expirado.
+#[function_component(RestoreSession)]
+fn restore_session() -> Html {
+    let restored = use_state(|| None::<bool>);
+
+    {
+        let restored = restored.clone();
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                restored.set(Some(auth::refresh_token().await));
+            });
+            || ()
+        });
+    }
+
+    match *restored {
+        Some(true) => html! { <Home/> },
+        Some(false) => html! { <Login/> },
+        None => html! {},
     }
 }
 
@@ -1,13 +1,25 @@
 /// Validation utilities for user input
 
+// Mirrors the default credential policy of the backend (`credential_policy.rs`),
+// which also rejects common passwords and has the final word.
+const USERNAME_MAX_LENGTH: usize = 32;
+
 /// Validates username
 /// Returns empty Vec if valid, Vec of error messages if invalid
 pub fn validate_username(username: &str) -> Vec<String> {
     let mut errors = Vec::new();
     
-    if username.len() < 6 {
+    if username.chars().count() < 6 {
         errors.push("O nome de usuário deve ter pelo menos 6 caracteres".to_string());
     }
+
+    if username.chars().count() > USERNAME_MAX_LENGTH {
+        errors.push(format!("O nome de usuário deve ter no máximo {} caracteres", USERNAME_MAX_LENGTH));
+    }
+
+    if !username.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-')) {
+        errors.push("Use apenas letras sem acento, números, '.', '_' ou '-'".to_string());
+    }
     
     errors
 }
@@ -17,7 +29,7 @@ pub fn validate_username(username: &str) -> Vec<String> {
 pub fn validate_password(password: &str) -> Vec<String> {
     let mut errors = Vec::new();
     
-    if password.len() < 8 {
+    if password.chars().count() < 8 {
         errors.push("Senha curta, no mínimo 8 dígitos".to_string());
     }
     